@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::state::{Dataset, Registry};
+
+// Shared authorization checks, usable both in account constraints and inside handlers
+
+pub fn is_owner(dataset: &Dataset, signer: &Pubkey) -> bool {
+    dataset.contributor == *signer
+}
+
+// The contributor or one of the editors they designated
+pub fn is_editor(dataset: &Dataset, signer: &Pubkey) -> bool {
+    is_owner(dataset, signer) || dataset.editors.contains(signer)
+}
+
+pub fn is_admin(registry: &Registry, signer: &Pubkey) -> bool {
+    registry.admin == *signer
+}
+
+// A registry without a configured oracle accepts no reports
+pub fn is_oracle(registry: &Registry, signer: &Pubkey) -> bool {
+    registry.citation_oracle != Pubkey::default() && registry.citation_oracle == *signer
+}
+
+pub fn is_download_oracle(registry: &Registry, signer: &Pubkey) -> bool {
+    registry.download_oracle != Pubkey::default() && registry.download_oracle == *signer
+}
+
+pub fn is_institution_oracle(registry: &Registry, signer: &Pubkey) -> bool {
+    registry.institution_oracle != Pubkey::default() && registry.institution_oracle == *signer
+}
+
+pub fn require_owner(dataset: &Dataset, signer: &Pubkey) -> Result<()> {
+    require!(is_owner(dataset, signer), ErrorCode::UnauthorizedUpdate);
+    Ok(())
+}
+
+pub fn require_admin(registry: &Registry, signer: &Pubkey) -> Result<()> {
+    require!(is_admin(registry, signer), ErrorCode::UnauthorizedAdmin);
+    Ok(())
+}
+
+// remaining_accounts bypass Anchor's account validation; check ownership before reading or mutating one
+pub fn require_program_owned(info: &AccountInfo) -> Result<()> {
+    require_keys_eq!(*info.owner, crate::ID, ErrorCode::UnauthorizedUpdate);
+    Ok(())
+}
+
+// Closed accounts are handed back to the system program; a tx sending lamports to the address
+// afterwards does not bring the program account back
+pub fn is_closed(info: &AccountInfo) -> bool {
+    *info.owner != crate::ID || info.data_is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn owner_passes_owner_check() {
+        let dataset = Dataset::sample();
+        require_owner(&dataset, &dataset.contributor).unwrap();
+    }
+
+    #[test]
+    fn stranger_fails_owner_check() {
+        let dataset = Dataset::sample();
+        assert_eq!(
+            require_owner(&dataset, &Pubkey::new_unique()).unwrap_err(),
+            ErrorCode::UnauthorizedUpdate.into()
+        );
+    }
+
+    #[test]
+    fn contributor_and_editors_pass_editor_check() {
+        let editor = Pubkey::new_unique();
+        let dataset = Dataset { editors: vec![editor], ..Dataset::sample() };
+
+        assert!(is_editor(&dataset, &dataset.contributor));
+        assert!(is_editor(&dataset, &editor));
+        assert!(!is_editor(&dataset, &Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn admin_passes_admin_check() {
+        let registry = Registry::sample();
+        require_admin(&registry, &registry.admin).unwrap();
+    }
+
+    #[test]
+    fn only_configured_oracle_passes_oracle_check() {
+        let oracle = Pubkey::new_unique();
+        let registry = Registry { citation_oracle: oracle, ..Registry::sample() };
+
+        assert!(is_oracle(&registry, &oracle));
+        assert!(!is_oracle(&registry, &registry.admin));
+        assert!(!is_oracle(&Registry::sample(), &Pubkey::default()));
+        assert!(!is_download_oracle(&registry, &oracle));
+    }
+
+    #[test]
+    fn creator_is_not_admin_after_transfer() {
+        let registry = Registry::sample();
+        assert_eq!(
+            require_admin(&registry, &registry.creator).unwrap_err(),
+            ErrorCode::UnauthorizedAdmin.into()
+        );
+    }
+
+    #[test]
+    fn only_emptied_or_reassigned_accounts_count_as_closed() {
+        let key = Pubkey::new_unique();
+        let system = Pubkey::default();
+        let (mut lamports, mut data) = (1_000, vec![1u8; 16]);
+        let open = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &crate::ID, false, 0);
+        assert!(!is_closed(&open));
+
+        let (mut lamports, mut data) = (5, Vec::new());
+        let closed = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &system, false, 0);
+        assert!(is_closed(&closed));
+
+        let (mut lamports, mut data) = (0, Vec::new());
+        let drained = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &crate::ID, false, 0);
+        assert!(is_closed(&drained));
+    }
+}
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+
+// Structured form of a dataset's AI-derived metadata, stored Borsh-encoded in `ai_metadata` when the
+// registry sets `structured_ai_metadata`, so indexers can decode and filter on its fields
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AiMetadata {
+    pub model_id: [u8; 32], // Hash or zero-padded name of the model that produced the metadata
+    pub confidence: u8, // 0-100
+    pub tags: Vec<[u8; 16]>, // Zero-padded labels; MAX_AI_METADATA_LEN bounds how many fit
+    // Optional trailing ISO 639-1 code, e.g. b"sw", lowercased by `parse`. Outside the derived Borsh
+    // layout so blobs written before it existed still decode
+    #[borsh_skip]
+    pub detected_language: Option<[u8; 2]>,
+}
+
+impl AiMetadata {
+    // The whole blob must decode, with nothing after the tags but an optional two-letter language
+    // code, and carry a confidence in range
+    pub fn parse(bytes: &[u8]) -> Result<AiMetadata> {
+        let mut rest = bytes;
+        let mut metadata = AiMetadata::deserialize(&mut rest).map_err(|_| error!(ErrorCode::MalformedAiMetadata))?;
+        require!(metadata.confidence <= 100, ErrorCode::MalformedAiMetadata);
+        metadata.detected_language = match rest {
+            [] => None,
+            [a, b] if a.is_ascii_alphabetic() && b.is_ascii_alphabetic() => {
+                Some([a.to_ascii_lowercase(), b.to_ascii_lowercase()])
+            }
+            _ => return err!(ErrorCode::MalformedAiMetadata),
+        };
+        Ok(metadata)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata() -> AiMetadata {
+        let mut tag = [0u8; 16];
+        tag[..6].copy_from_slice(b"health");
+        AiMetadata { model_id: [7u8; 32], confidence: 92, tags: vec![tag], detected_language: None }
+    }
+
+    #[test]
+    fn well_formed_metadata_round_trips() {
+        let bytes = metadata().try_to_vec().unwrap();
+
+        assert_eq!(bytes.len(), 32 + 1 + 4 + 16);
+        assert_eq!(AiMetadata::parse(&bytes).unwrap(), metadata());
+    }
+
+    #[test]
+    fn malformed_metadata_is_rejected() {
+        let bytes = metadata().try_to_vec().unwrap();
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        let overconfident = AiMetadata { confidence: 101, ..metadata() }.try_to_vec().unwrap();
+
+        let bad_language = [&bytes[..], b"s1"].concat();
+        let long_language = [&bytes[..], b"swa"].concat();
+
+        for blob in [&br#"{"model": "gpt"}"#[..], &bytes[..bytes.len() - 1], &trailing, &overconfident, b"", &bad_language, &long_language] {
+            assert_eq!(AiMetadata::parse(blob).unwrap_err(), ErrorCode::MalformedAiMetadata.into());
+        }
+    }
+
+    #[test]
+    fn trailing_language_code_is_read_and_lowercased() {
+        let bytes = [&metadata().try_to_vec().unwrap()[..], b"SW"].concat();
+
+        let parsed = AiMetadata::parse(&bytes).unwrap();
+        assert_eq!(parsed.detected_language, Some(*b"sw"));
+        assert_eq!(parsed.tags, metadata().tags);
+    }
+}
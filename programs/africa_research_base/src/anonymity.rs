@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+const PSEUDONYM_TAG: &[u8] = b"anonymous";
+
+// One-way stand-in for an anonymous contributor's key. Stable, so an anonymous contributor's events
+// still link to each other, but the key can't be recovered from it without already knowing it.
+pub fn pseudonym(contributor: &Pubkey) -> Pubkey {
+    Pubkey::new_from_array(hashv(&[PSEUDONYM_TAG, contributor.as_ref()]).to_bytes())
+}
+
+// The key events and return data carry for a contributor: the real one unless they opted out
+pub fn published_key(contributor: &Pubkey, anonymous: bool) -> Pubkey {
+    if anonymous {
+        pseudonym(contributor)
+    } else {
+        *contributor
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn anonymous_contributors_publish_a_stable_pseudonym() {
+        let contributor = Pubkey::new_unique();
+
+        assert_eq!(published_key(&contributor, false), contributor);
+        assert_ne!(published_key(&contributor, true), contributor);
+        assert_eq!(published_key(&contributor, true), pseudonym(&contributor));
+        assert_ne!(pseudonym(&contributor), pseudonym(&Pubkey::new_unique()));
+    }
+}
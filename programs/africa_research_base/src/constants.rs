@@ -6,4 +6,217 @@ pub const SEED: &str = "anchor";
 pub const UPLOAD_WEIGHT: u32 = 10;
 pub const DOWNLOAD_WEIGHT: u32 = 2;
 pub const CITATION_WEIGHT: u32 = 15;
-pub const QUALITY_MULTIPLIER: u32 = 1;
\ No newline at end of file
+pub const FORK_WEIGHT: u32 = 5;
+pub const QUALITY_MULTIPLIER: u32 = 1;
+
+// Admin config changes (fees, quality floor, admin handover) wait this long before they can be executed
+pub const CONFIG_TIMELOCK_DELAY: i64 = 2 * 24 * 60 * 60;
+// Window Registry::treasury_withdrawal_cap applies to; a new one starts with the first withdrawal after it ends
+pub const TREASURY_WITHDRAWAL_PERIOD: i64 = 7 * 24 * 60 * 60;
+pub const MAX_PLATFORM_FEE_BPS: u16 = 1_000;
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+// Receipts are keyed by a client-supplied slot that must be this recent
+pub const RECEIPT_SLOT_WINDOW: u64 = 150;
+pub const NATIVE_MINT: Pubkey = pubkey!("So11111111111111111111111111111111111111112");
+
+// Display metadata for fee currencies: SOL defaults, and the token programs a fee mint may belong to
+pub const NATIVE_DECIMALS: u8 = 9;
+pub const NATIVE_SYMBOL: [u8; 8] = *b"SOL\0\0\0\0\0";
+pub const TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+pub const DEFAULT_REPUTATION_GRACE_PERIOD: i64 = 7 * 24 * 60 * 60;
+pub const MAX_REPUTATION_GRACE_PERIOD: i64 = 90 * 24 * 60 * 60;
+
+pub const MAX_FILE_SIZE: u64 = 104_857_600;
+
+// `preview_hash` covers this many leading bytes, so downloaders can check a file's start cheaply
+pub const PREVIEW_BYTES: u64 = 1_048_576;
+
+// Bounds on variable-length account fields, used for both validation and account sizing
+pub const MAX_AI_METADATA_LEN: usize = 1_500;
+// expand_metadata may grow ai_metadata past MAX_AI_METADATA_LEN, by at most MAX_AI_METADATA_GROWTH
+// bytes per call and MAX_AI_METADATA bytes in total
+pub const MAX_AI_METADATA: usize = 10_240;
+pub const MAX_AI_METADATA_GROWTH: usize = 512;
+pub const MAX_FILE_NAME_LEN: usize = 100;
+pub const MAX_TITLE_LEN: usize = 120;
+pub const MAX_COLUMNS: usize = 100;
+pub const DATA_URI_LEN: usize = 256;
+pub const MAX_PUBLISHED_INFORMATION_LEN: usize = 1_000;
+
+// Citations between the same two contributors beyond this count earn no reputation
+pub const DEFAULT_CITATION_PAIR_CAP: u8 = 3;
+
+pub const THUMBNAIL_URI_LEN: usize = 128;
+
+// Storage URIs must start with one of these schemes
+pub const ALLOWED_URI_SCHEMES: [&[u8]; 3] = [b"ipfs://", b"ar://", b"https://"];
+
+// Contributors must score above this to create featured datasets
+pub const DEFAULT_FEATURED_REPUTATION_THRESHOLD: u32 = 200;
+
+// Reputation deducted from a contributor whose dataset is frozen for a policy violation
+pub const DEFAULT_FREEZE_PENALTY: u32 = 50;
+
+// Dead-URI reports from distinct accounts before a dataset is taken offline
+pub const DEFAULT_UNAVAILABLE_REPORT_THRESHOLD: u8 = 3;
+pub const MAX_UNAVAILABLE_REPORTERS: usize = 16;
+
+// Community ratings run 1..=MAX_RATING; quality is only derived from them once enough have been given
+pub const MAX_RATING: u8 = 5;
+pub const MIN_RATINGS_FOR_QUALITY: u32 = 3;
+// Default quality_dispute_delta: how far a self-reported score may sit above the community's
+pub const DEFAULT_QUALITY_DISPUTE_DELTA: u8 = 25;
+
+// Registry dataset totals announced with a RegistryMilestone event
+pub const DATASET_MILESTONES: [u64; 3] = [100, 1_000, 10_000];
+
+// Lamports a funder pays into the fee vault per day of pinning incentive
+pub const PIN_FEE_PER_DAY: u64 = 100_000;
+pub const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+// File-name search index: words of at least MIN_TOKEN_LEN characters, at most MAX_TITLE_TOKENS per
+// name, and the most recent MAX_TOKEN_INDEX_ENTRIES datasets per word
+pub const MIN_TOKEN_LEN: usize = 2;
+pub const MAX_TITLE_TOKENS: usize = 4;
+pub const MAX_TOKEN_INDEX_ENTRIES: usize = 32;
+
+// Reputation decay: each full period since the last decay removes DECAY_BPS of the current score,
+// compounded over at most MAX_DECAY_PERIODS periods per call; vacations pause it for a bounded time
+pub const DECAY_PERIOD: i64 = 30 * SECONDS_PER_DAY;
+pub const DECAY_BPS: u64 = 500;
+pub const MAX_DECAY_PERIODS: i64 = 64;
+pub const MAX_VACATION_DURATION: i64 = 180 * SECONDS_PER_DAY;
+
+// Off-chain download reports: proofs longer than this exceed any tree a CDN would realistically submit
+pub const MAX_MERKLE_PROOF_LEN: usize = 24;
+
+// Bits of the Dataset::diff mask returned by compare_datasets
+pub const DIFF_FILE_SIZE: u8 = 1 << 0;
+pub const DIFF_ROW_COUNT: u8 = 1 << 1;
+pub const DIFF_COLUMN_COUNT: u8 = 1 << 2;
+pub const DIFF_CONTENT_HASH: u8 = 1 << 3;
+pub const DIFF_RESEARCH_FIELDS: u8 = 1 << 4;
+
+// Registry::quality_scale values: the top of the scale quality scores are submitted and displayed in
+pub const QUALITY_SCALE_PERCENT: u8 = 100;
+pub const QUALITY_SCALE_STARS: u8 = 5;
+
+pub const MAX_ALLOWED_HOSTS: usize = 16;
+// Research fields a registry may weight differently from the default of 1, and the largest weight
+pub const MAX_FIELD_WEIGHTS: usize = 16;
+pub const MAX_FIELD_WEIGHT: u8 = 10;
+
+// Content-addressed schemes served by their own networks, exempt from the gateway host allow-list
+pub const NATIVE_URI_SCHEMES: [&[u8]; 2] = [b"ipfs://", b"ar://"];
+
+// Dataset::review_status values
+pub const REVIEW_SUBMITTED: u8 = 0;
+pub const REVIEW_UNDER_REVIEW: u8 = 1;
+pub const REVIEW_APPROVED: u8 = 2;
+pub const REVIEW_REJECTED: u8 = 3;
+// A curated registry's default wait before an unreviewed dataset may be auto-approved
+pub const DEFAULT_REVIEW_SLA: i64 = 14 * 24 * 60 * 60;
+
+// Version prefix of Dataset::to_canonical_bytes; bump whenever the canonical layout changes
+pub const CANONICAL_LAYOUT_VERSION: u8 = 2;
+
+// Version prefix of the CreatedDataset return data; bump whenever its fields change
+pub const CREATED_DATASET_VERSION: u8 = 1;
+
+// Version prefix of the RatingSummary return data; bump whenever its fields change
+pub const RATING_SUMMARY_VERSION: u8 = 1;
+
+// Version prefix of the ReputationBreakdown return data; bump whenever its fields change
+pub const REPUTATION_BREAKDOWN_VERSION: u8 = 2;
+
+// Version prefix of the HashResolution return data; bump whenever its fields change
+pub const HASH_RESOLUTION_VERSION: u8 = 1;
+
+// Version prefix of the ContributorOverview return data; bump whenever its fields change
+pub const CONTRIBUTOR_OVERVIEW_VERSION: u8 = 1;
+
+// Dataset account layout version, bumped with every layout change; earlier layouts are frozen in
+// state/legacy_dataset.rs and brought up to date by migrate_data_uri. 1 stored data_uri as a fixed
+// [u8; 256], 2 as a Vec, 3 appended endorsement_count through parent and 4 added co_credits
+pub const DATASET_SCHEMA_VERSION: u8 = 4;
+
+pub const MAX_CO_CONTRIBUTORS: usize = 8;
+pub const MAX_EDITORS: usize = 5;
+
+// Lifetime of an access nonce backing a signed off-chain download URL
+pub const ACCESS_NONCE_TTL: i64 = 5 * 60;
+
+// AccessRequest::status values
+pub const ACCESS_PENDING: u8 = 0;
+pub const ACCESS_APPROVED: u8 = 1;
+pub const ACCESS_DENIED: u8 = 2;
+
+// StorageBackend::backend values
+pub const BACKEND_IPFS: u8 = 0;
+pub const BACKEND_ARWEAVE: u8 = 1;
+pub const BACKEND_FILECOIN: u8 = 2;
+pub const BACKEND_HTTPS: u8 = 3;
+// Replicas a dataset may register on top of its data_uri, and the room for each CID or locator
+pub const MAX_REPLICAS: usize = 4;
+pub const REPLICA_CID_LEN: usize = 64;
+
+pub const MAX_RESEARCH_FIELDS: usize = 4;
+// Upper bound on the quality bond a registry may require per dataset (10 SOL)
+pub const MAX_QUALITY_BOND: u64 = 10_000_000_000;
+// Upper bound on the anti-spam deposit a registry may require per dataset (0.1 SOL)
+pub const MAX_SPAM_DEPOSIT: u64 = 100_000_000;
+// No download releases the spam deposit before the dataset is this old, paid or not
+pub const SPAM_DEPOSIT_HOLD_PERIOD: i64 = 30 * 24 * 60 * 60;
+
+// Fee mints other than SOL an Earnings account tracks
+pub const MAX_EARNINGS_MINTS: usize = 8;
+
+// Datasets one institution manifest may list
+pub const MAX_MANIFEST_DATASETS: usize = 16;
+
+// Member datasets a collection can hold
+pub const MAX_COLLECTION_DATASETS: usize = 32;
+
+// Datasets bulk_tag accepts per call
+pub const MAX_BULK_TAG_DATASETS: usize = 10;
+pub const MAX_FIELD_SCORES: usize = 16;
+// Categories, active or retired, a registry taxonomy can hold
+pub const MAX_CATEGORIES: usize = 64;
+
+pub const LEADERBOARD_SIZE: usize = 10;
+
+// Short subject line of a DataRequest; the full description lives off-chain under description_hash
+pub const MAX_REQUEST_TOPIC_LEN: usize = 64;
+
+// Upper bounds on reputation history imported by seed_reputation
+pub const MAX_SEEDED_UPLOADS: u32 = 10_000;
+pub const MAX_SEEDED_DOWNLOADS: u64 = 1_000_000;
+pub const MAX_SEEDED_CITATIONS: u32 = 10_000;
+
+// Newest datasets kept per registry for "latest uploads" views
+pub const RECENT_DATASETS_LEN: usize = 16;
+
+// Trending score, Hacker-News style: downloads * TRENDING_SCALE / (age in hours + 2)^TRENDING_GRAVITY.
+// TRENDING_LEN datasets are kept per registry.
+pub const TRENDING_SCALE: u64 = 1_000_000;
+pub const TRENDING_GRAVITY: u32 = 2;
+pub const TRENDING_LEN: usize = 10;
+
+// Minimum reputation_score for each tier above 0
+pub const REPUTATION_TIER_THRESHOLDS: [u32; 3] = [100, 500, 2_000];
+
+// Stored reputation scores never exceed MAX_REPUTATION_SCORE. Raw scores up to REPUTATION_SCORE_KNEE
+// are kept as they are; above it the excess is compressed toward the cap, see
+// normalize_reputation_score. Reputation is shared by every registry, so these are program-wide.
+pub const MAX_REPUTATION_SCORE: u32 = 10_000;
+pub const REPUTATION_SCORE_KNEE: u32 = 5_000;
+
+// Weight of a community rating, indexed by the rater's tier: a tier 0 rater (or one without a
+// reputation account) counts once, a tier 3 rater four times
+pub const RATING_TIER_WEIGHTS: [u8; 4] = [1, 2, 3, 4];
+
+// Reputations per view_reputations call; 25 entries of 40 bytes plus a length prefix fit the 1 KiB return-data cap
+pub const MAX_REPUTATION_VIEW: usize = 25;
@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::instruction::Instruction;
+
+use crate::error::ErrorCode;
+
+// Layout of the ed25519 precompile's instruction data: a count byte and a padding byte, then one
+// block of u16 LE offsets per signature, then the signatures, keys and messages they point into
+const HEADER_LEN: usize = 2;
+const OFFSETS_LEN: usize = 14;
+const PUBKEY_LEN: usize = 32;
+// Instruction index meaning "this same instruction's data"
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+// Checks that `ix` is an ed25519 precompile call verifying exactly one signature by `signer`
+// over `message`. The runtime rejects the transaction if the signature itself is invalid, so
+// only what was verified needs checking here
+pub fn verify_signature(ix: &Instruction, signer: &Pubkey, message: &[u8]) -> Result<()> {
+    require_keys_eq!(ix.program_id, ed25519_program::ID, ErrorCode::InvalidManifestSignature);
+    let data = &ix.data;
+    require!(data.len() >= HEADER_LEN + OFFSETS_LEN && data[0] == 1, ErrorCode::InvalidManifestSignature);
+
+    let offset = |i: usize| u16::from_le_bytes([data[HEADER_LEN + 2 * i], data[HEADER_LEN + 2 * i + 1]]);
+    // signature_offset, signature_ix, pubkey_offset, pubkey_ix, message_offset, message_size, message_ix
+    let (signature_ix, pubkey_offset, pubkey_ix) = (offset(1), offset(2) as usize, offset(3));
+    let (message_offset, message_size, message_ix) = (offset(4) as usize, offset(5) as usize, offset(6));
+    require!(
+        [signature_ix, pubkey_ix, message_ix].iter().all(|&index| index == CURRENT_INSTRUCTION),
+        ErrorCode::InvalidManifestSignature
+    );
+
+    let pubkey = data.get(pubkey_offset..pubkey_offset + PUBKEY_LEN);
+    let signed = data.get(message_offset..message_offset + message_size);
+    require!(
+        pubkey == Some(signer.as_ref()) && signed == Some(message),
+        ErrorCode::InvalidManifestSignature
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Mirrors what Ed25519Program.createInstructionWithPublicKey builds client-side
+    fn precompile_ix(signer: &Pubkey, message: &[u8], instruction_index: u16) -> Instruction {
+        let pubkey_offset = (HEADER_LEN + OFFSETS_LEN) as u16;
+        let signature_offset = pubkey_offset + PUBKEY_LEN as u16;
+        let message_offset = signature_offset + 64;
+        let mut data = vec![1u8, 0];
+        for value in [
+            signature_offset,
+            instruction_index,
+            pubkey_offset,
+            instruction_index,
+            message_offset,
+            message.len() as u16,
+            instruction_index,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(signer.as_ref());
+        data.extend_from_slice(&[0u8; 64]);
+        data.extend_from_slice(message);
+        Instruction { program_id: ed25519_program::ID, accounts: Vec::new(), data }
+    }
+
+    #[test]
+    fn signature_by_the_signer_over_the_message_is_accepted() {
+        let signer = Pubkey::new_unique();
+        verify_signature(&precompile_ix(&signer, &[7u8; 32], CURRENT_INSTRUCTION), &signer, &[7u8; 32]).unwrap();
+    }
+
+    #[test]
+    fn other_signers_messages_and_programs_are_rejected() {
+        let signer = Pubkey::new_unique();
+        let ix = precompile_ix(&signer, &[7u8; 32], CURRENT_INSTRUCTION);
+        let not_a_precompile = Instruction { program_id: Pubkey::new_unique(), ..ix.clone() };
+        let elsewhere = precompile_ix(&signer, &[7u8; 32], 0);
+        let truncated = Instruction { data: ix.data[..HEADER_LEN + OFFSETS_LEN + 8].to_vec(), ..ix.clone() };
+
+        for (ix, signer, message) in [
+            (&ix, Pubkey::new_unique(), [7u8; 32]),
+            (&ix, signer, [8u8; 32]),
+            (&not_a_precompile, signer, [7u8; 32]),
+            (&elsewhere, signer, [7u8; 32]),
+            (&truncated, signer, [7u8; 32]),
+        ] {
+            assert_eq!(
+                verify_signature(ix, &signer, &message).unwrap_err(),
+                ErrorCode::InvalidManifestSignature.into()
+            );
+        }
+    }
+}
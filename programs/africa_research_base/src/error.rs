@@ -1,9 +1,8 @@
 use anchor_lang::prelude::*;
 
-#[error_code]
+// Offset keeps the numeric codes assigned before the CustomError placeholder was removed
+#[error_code(offset = 6001)]
 pub enum ErrorCode {
-    #[msg("Custom error message")]
-    CustomError,
     #[msg("Content hash too long")]
     HashTooLong,
     #[msg("File name too long")]
@@ -32,4 +31,306 @@ pub enum ErrorCode {
     DuplicateDataset,
     #[msg("Invalid contributor status")]
     InvalidContributorStatus,
+    #[msg("Platform fee exceeds the maximum allowed")]
+    InvalidPlatformFee,
+    #[msg("Invalid admin key")]
+    InvalidAdmin,
+    #[msg("Config change is still timelocked")]
+    TimelockActive,
+    #[msg("Quality score is below the registry minimum")]
+    QualityBelowMinimum,
+    #[msg("Registry name must not be empty")]
+    InvalidRegistryName,
+    #[msg("Receipt was issued for a different mint")]
+    ReceiptMintMismatch,
+    #[msg("Paid downloads require a receipt and fee vault")]
+    ReceiptRequired,
+    #[msg("Receipt slot is in the future or too old")]
+    InvalidReceiptSlot,
+    #[msg("Upload reputation was already finalized for this dataset")]
+    ReputationAlreadyFinalized,
+    #[msg("Dataset is still inside the reputation grace period")]
+    GracePeriodActive,
+    #[msg("Invalid reputation grace period")]
+    InvalidGracePeriod,
+    #[msg("Datasets do not share the same content hash")]
+    HashMismatch,
+    #[msg("A dataset cannot be merged into itself")]
+    SelfMerge,
+    #[msg("File is smaller than the registry minimum")]
+    FileTooSmall,
+    #[msg("Datasets larger than the preview window need a preview hash")]
+    PreviewHashRequired,
+    #[msg("Dataset has expired")]
+    DatasetExpired,
+    #[msg("Dataset has not expired yet")]
+    DatasetNotExpired,
+    #[msg("Expiry must be in the future")]
+    InvalidExpiry,
+    #[msg("AI metadata too long")]
+    MetadataTooLong,
+    #[msg("Only the registry admin may perform this action")]
+    UnauthorizedAdmin,
+    #[msg("Contributors cannot cite their own datasets")]
+    SelfCitation,
+    #[msg("Published information too long")]
+    PublishedInformationTooLong,
+    #[msg("URI must use an ipfs://, ar:// or https:// scheme")]
+    InvalidUriScheme,
+    #[msg("Reputation too low to create a featured dataset")]
+    InsufficientReputation,
+    #[msg("Signer is not the registry's citation oracle")]
+    UnauthorizedOracle,
+    #[msg("Storage URI host is not on the registry allow-list")]
+    DisallowedHost,
+    #[msg("Host allow-list is full")]
+    AllowListFull,
+    #[msg("Dataset is not in a reviewable state")]
+    InvalidReviewStatus,
+    #[msg("Signer is not the dataset's assigned reviewer")]
+    UnauthorizedReviewer,
+    #[msg("Curated registries only serve approved datasets")]
+    DatasetNotApproved,
+    #[msg("Too many co-contributors")]
+    TooManyCoContributors,
+    #[msg("Co-contributors must be distinct from each other and the contributor")]
+    DuplicateCoContributor,
+    #[msg("Remaining accounts must be the co-contributors' reputation PDAs, in order")]
+    InvalidCoContributorAccounts,
+    #[msg("Only the citer or the registry admin may revoke a citation")]
+    UnauthorizedRevocation,
+    #[msg("Dataset submission failed several validation rules; see the program log")]
+    MultipleValidationErrors,
+    #[msg("An unused access nonce is still valid")]
+    NonceStillValid,
+    #[msg("Access nonce does not match")]
+    NonceMismatch,
+    #[msg("Access nonce was already used")]
+    NonceConsumed,
+    #[msg("Access nonce has expired")]
+    NonceExpired,
+    #[msg("Too many research fields")]
+    TooManyResearchFields,
+    #[msg("Research fields must be distinct")]
+    DuplicateResearchField,
+    #[msg("Dataset URI is locked")]
+    UriLocked,
+    #[msg("Too many reputation accounts for one view")]
+    TooManyReputations,
+    #[msg("Contributors cannot fork their own datasets")]
+    SelfFork,
+    #[msg("Dataset is frozen")]
+    DatasetFrozen,
+    #[msg("This account already reported the dataset URI as unavailable")]
+    DuplicateUnavailableReport,
+    #[msg("Report threshold must be between 1 and MAX_UNAVAILABLE_REPORTERS")]
+    InvalidReportThreshold,
+    #[msg("Contributors cannot delegate reputation to themselves")]
+    SelfDelegation,
+    #[msg("Reputation is already delegated")]
+    AlreadyDelegated,
+    #[msg("Institutions cannot delegate, and delegates cannot accept delegations")]
+    DelegationCycle,
+    #[msg("File name must be non-empty UTF-8 without control characters")]
+    InvalidFileName,
+    #[msg("Rating must be between 1 and MAX_RATING")]
+    InvalidRating,
+    #[msg("Contributors cannot rate their own datasets")]
+    SelfRating,
+    #[msg("Not enough ratings to derive a quality score")]
+    NotEnoughRatings,
+    #[msg("Dataset is not hidden for low quality")]
+    DatasetNotHidden,
+    #[msg("Download limit for this window reached")]
+    DownloadRateLimited,
+    #[msg("A capped dataset needs a positive download window")]
+    InvalidDownloadWindow,
+    #[msg("Capped datasets require the downloader's download record")]
+    DownloadRecordRequired,
+    #[msg("Pin extension must be at least one day")]
+    InvalidPinDuration,
+    #[msg("Remaining accounts must be the file name's token index PDAs, in order")]
+    InvalidTokenIndexAccounts,
+    #[msg("Fee mint must be an initialized SPL token mint")]
+    InvalidFeeMint,
+    #[msg("Fee symbol must be 1-8 ASCII letters or digits, zero-padded")]
+    InvalidFeeSymbol,
+    #[msg("Download fees can only be collected in SOL")]
+    UnsupportedFeeMint,
+    #[msg("Too many editors")]
+    TooManyEditors,
+    #[msg("Editors must be distinct from each other and the contributor")]
+    DuplicateEditor,
+    #[msg("Not an editor of this dataset")]
+    EditorNotFound,
+    #[msg("Storage URI exceeds 256 bytes")]
+    DataUriTooLong,
+    #[msg("Account is not a dataset in an earlier schema")]
+    NotLegacyDataset,
+    #[msg("Vacation must end within the maximum duration and cannot extend an active one")]
+    InvalidVacation,
+    #[msg("Only institutions accepting delegations can be put on vacation by an admin")]
+    NotAnInstitution,
+    #[msg("Merkle proof does not match the download report root")]
+    InvalidMerkleProof,
+    #[msg("Merkle proof is too long")]
+    MerkleProofTooLong,
+    #[msg("Datasets are not in the same version lineage")]
+    NotInLineage,
+    #[msg("Quality scale must be 0-100 or 0-5")]
+    InvalidQualityScale,
+    #[msg("Account data does not match the current layout")]
+    LayoutMismatch,
+    #[msg("Contributors cannot endorse their own datasets")]
+    SelfEndorsement,
+    #[msg("Registry has not been initialized; run initialize_registry first")]
+    RegistryNotInitialized,
+    #[msg("The vault's dataset still exists")]
+    DatasetStillExists,
+    #[msg("Title exceeds MAX_TITLE_LEN bytes")]
+    TitleTooLong,
+    #[msg("Title must be non-empty UTF-8 without control characters")]
+    InvalidTitle,
+    #[msg("Download reversal count must be positive")]
+    InvalidReversalCount,
+    #[msg("New contributor key must differ from the current one")]
+    SameContributorKey,
+    #[msg("Revoke delegations to and from this contributor before rotating their key")]
+    RotationWhileDelegated,
+    #[msg("Dataset license has expired")]
+    LicenseExpired,
+    #[msg("License expiry must be in the future and later than the current one")]
+    InvalidLicenseExpiry,
+    #[msg("A dataset cannot supersede itself")]
+    SelfSupersession,
+    #[msg("Dataset already supersedes another dataset")]
+    AlreadySupersedes,
+    #[msg("Request topic must be 1 to MAX_REQUEST_TOPIC_LEN bytes and match its topic hash")]
+    InvalidRequestTopic,
+    #[msg("Data request has already been fulfilled")]
+    RequestAlreadyFulfilled,
+    #[msg("Only a dataset created after the request can fulfill it")]
+    DatasetPredatesRequest,
+    #[msg("ai_metadata is not a valid Borsh-encoded AiMetadata")]
+    MalformedAiMetadata,
+    #[msg("Quality dispute delta must be at most 100")]
+    InvalidQualityDisputeDelta,
+    #[msg("Only a reputation with no recorded activity can be seeded")]
+    ReputationAlreadyActive,
+    #[msg("Seeded reputation values exceed the allowed maxima")]
+    InvalidSeedValues,
+    #[msg("ai_metadata growth exceeds the per-call or total cap")]
+    GrowthExceeded,
+    #[msg("Rent deposit does not match the incremental rent of the expanded account")]
+    IncorrectRentDeposit,
+    #[msg("bulk_tag needs between one and MAX_BULK_TAG_DATASETS writable datasets of this registry")]
+    InvalidBulkTagAccounts,
+    #[msg("Quality bond exceeds MAX_QUALITY_BOND")]
+    InvalidQualityBond,
+    #[msg("Dataset is locked by an instruction in progress")]
+    DatasetLocked,
+    #[msg("Data dictionary must define exactly one column per dataset column")]
+    ColumnCountMismatch,
+    #[msg("Research field is not an active category of the registry taxonomy")]
+    UnknownCategory,
+    #[msg("Category code is already defined in the taxonomy")]
+    DuplicateCategory,
+    #[msg("Taxonomy is full (max MAX_CATEGORIES categories)")]
+    TaxonomyFull,
+    #[msg("Review SLA must not be negative")]
+    InvalidReviewSla,
+    #[msg("Auto-approval is disabled for this registry")]
+    AutoApproveDisabled,
+    #[msg("Review deadline has not passed yet")]
+    ReviewDeadlineNotReached,
+    #[msg("Paid downloads need the contributor's earnings account")]
+    EarningsRequired,
+    #[msg("Withdrawal exceeds the available earnings or the vault's balance above rent")]
+    InsufficientEarnings,
+    #[msg("Quality score of 0 requires allow_zero_quality")]
+    ZeroQualityScore,
+    #[msg("Featured promotion must end in the future")]
+    InvalidFeaturedUntil,
+    #[msg("Dataset is not featured")]
+    NotFeatured,
+    #[msg("Featured promotion has not expired")]
+    FeatureNotExpired,
+    #[msg("Access request has already been answered")]
+    AccessRequestResolved,
+    #[msg("Approving an access request requires the grant account")]
+    GrantRequired,
+    #[msg("Manifest hash does not match its contents")]
+    ManifestHashMismatch,
+    #[msg("Manifest must list between 1 and MAX_MANIFEST_DATASETS datasets")]
+    InvalidManifest,
+    #[msg("Expected an ed25519 signature by the institution oracle over the manifest hash")]
+    InvalidManifestSignature,
+    #[msg("Collection is full")]
+    CollectionFull,
+    #[msg("Dataset is already in the collection")]
+    DuplicateCollectionMember,
+    #[msg("Dataset is not in the collection")]
+    NotACollectionMember,
+    #[msg("Only the collection's curator can change it")]
+    UnauthorizedCurator,
+    #[msg("Dataset belongs to a different registry than the collection")]
+    CollectionRegistryMismatch,
+    #[msg("Dataset is finalized and can no longer be changed")]
+    DatasetFinalized,
+    #[msg("Spam deposit exceeds MAX_SPAM_DEPOSIT")]
+    InvalidSpamDeposit,
+    #[msg("The contributor account must be passed to refund the spam deposit")]
+    DepositRefundRequired,
+    #[msg("Overview datasets must be distinct and belong to the contributor")]
+    InvalidOverviewDataset,
+    #[msg("Timestamp is earlier than the dataset's last update")]
+    NonMonotonicTimestamp,
+    #[msg("Field weight must be between 1 and MAX_FIELD_WEIGHT")]
+    InvalidFieldWeight,
+    #[msg("Field weight map is full")]
+    FieldWeightsFull,
+    #[msg("Registry is already initialized")]
+    AlreadyInitialized,
+    #[msg("Language must be a two-letter ISO 639-1 code")]
+    InvalidLanguageCode,
+    #[msg("Withdrawal amount must be greater than zero")]
+    InvalidWithdrawalAmount,
+    #[msg("Withdrawal exceeds the treasury's remaining cap for this period")]
+    WithdrawalCapExceeded,
+    #[msg("Treasury balance above rent exemption is too low")]
+    InsufficientTreasuryBalance,
+    #[msg("Unknown storage backend or empty CID")]
+    InvalidReplica,
+    #[msg("This replica is already registered")]
+    DuplicateReplica,
+    #[msg("Dataset already lists MAX_REPLICAS replicas")]
+    ReplicasFull,
+    #[msg("This contributor key has been rotated; use or re-point to its successor")]
+    ContributorKeyRotated,
+    #[msg("Dataset does not belong to the rotated-away key")]
+    InvalidRepoint,
+    #[msg("A parent version must be the same contributor's dataset in the same registry")]
+    InvalidParentVersion,
+    #[msg("Only the manifest's institution may claim its entries")]
+    UnauthorizedManifestClaim,
+    #[msg("Manifest entry does not list this content hash")]
+    InvalidManifestEntry,
+    #[msg("Manifest entry has already been claimed")]
+    ManifestEntryClaimed,
+    #[msg("A registered hash resolves only with its dataset account")]
+    HashDatasetRequired,
+    #[msg("Denying an access request must not create a grant account")]
+    GrantNotAllowed,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_codes_keep_their_numbers() {
+        assert_eq!(u32::from(ErrorCode::HashTooLong), 6001);
+        assert_eq!(u32::from(ErrorCode::NumericalOverflow), 6010);
+        assert_eq!(u32::from(ErrorCode::UnauthorizedAdmin), 6034);
+    }
 }
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::state::ConfigChange;
+
 #[event]
 pub struct DatasetCreated {
     pub id: Pubkey,
@@ -23,4 +25,423 @@ pub struct CitationRecorded {  // Bonus: For citation tracking
     pub citer: Pubkey,
     pub contributor: Pubkey,
     pub citing_time: i64,
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct ConfigChangeProposed {
+    pub registry: Pubkey,
+    pub change: ConfigChange,
+    pub unlock_at: i64,
+}
+
+#[event]
+pub struct ConfigChangeExecuted {
+    pub registry: Pubkey,
+    pub change: ConfigChange,
+    pub executed_at: i64,
+}
+
+#[event]
+pub struct DatasetDownloaded {
+    pub dataset_id: Pubkey,
+    pub downloader: Pubkey,
+    pub contributor: Pubkey,
+    pub amount_paid: u64,
+    pub download_time: i64,
+}
+
+#[event]
+pub struct DatasetClosed {
+    pub id: Pubkey,
+    pub contributor: Pubkey,
+    pub reputation_finalized: bool,
+    pub bond_refunded: u64,
+    pub spam_deposit_forfeited: u64,
+    pub closed_at: i64,
+}
+
+#[event]
+pub struct DatasetsMerged {
+    pub canonical: Pubkey,
+    pub duplicate: Pubkey,
+    pub content_hash: [u8; 32],
+    pub download_count: u32,
+}
+
+#[event]
+pub struct DatasetArchived {
+    pub id: Pubkey,
+    pub contributor: Pubkey,
+    pub archived_at: i64,
+}
+
+#[event]
+pub struct ExternalCitationReported {
+    pub dataset_id: Pubkey,
+    pub contributor: Pubkey,
+    pub oracle: Pubkey,
+    pub publication_hash: [u8; 32],
+    pub reported_at: i64,
+}
+
+#[event]
+pub struct ReviewerAssigned {
+    pub dataset_id: Pubkey,
+    pub reviewer: Pubkey,
+}
+
+#[event]
+pub struct DatasetReviewed {
+    pub dataset_id: Pubkey,
+    pub reviewer: Pubkey,
+    pub approved: bool,
+    pub note_hash: [u8; 32],
+    pub reviewed_at: i64,
+}
+
+#[event]
+pub struct CitationRevoked {
+    pub dataset_id: Pubkey,
+    pub citer: Pubkey,
+    pub revoked_by: Pubkey,
+    pub revoked_at: i64,
+}
+
+#[event]
+pub struct AccessNonceIssued {
+    pub dataset_id: Pubkey,
+    pub grantee: Pubkey,
+    pub nonce: [u8; 32],
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct AccessNonceConsumed {
+    pub dataset_id: Pubkey,
+    pub grantee: Pubkey,
+    pub consumed_at: i64,
+}
+
+#[event]
+pub struct DatasetForked {
+    pub source: Pubkey,
+    pub fork: Pubkey,
+    pub forker: Pubkey,
+    pub original_contributor: Pubkey,
+    pub source_fork_count: u32,
+}
+
+#[event]
+pub struct DatasetFrozen {
+    pub dataset_id: Pubkey,
+    pub contributor: Pubkey,
+    pub penalty: u32,
+    pub reason_hash: [u8; 32],
+    pub new_reputation_score: u32,
+    pub bond_forfeited: u64,
+    pub frozen_at: i64,
+}
+
+#[event]
+pub struct DatasetUnavailable {
+    pub dataset_id: Pubkey,
+    pub contributor: Pubkey,
+    pub report_count: u8,
+    pub flagged_at: i64,
+}
+
+#[event]
+pub struct ReputationDelegated {
+    pub delegator: Pubkey,
+    pub institution: Pubkey,
+    pub score: u32,
+    pub institution_aggregate_score: u32,
+}
+
+#[event]
+pub struct DelegationRevoked {
+    pub delegator: Pubkey,
+    pub institution: Pubkey,
+    pub score: u32,
+    pub institution_aggregate_score: u32,
+}
+
+#[event]
+pub struct QualityDisputed {
+    pub dataset_id: Pubkey,
+    pub contributor: Pubkey,
+    pub reported_quality: u8,
+    pub community_quality: u8,
+    pub disputed_at: i64,
+}
+
+#[event]
+pub struct DatasetQualityHidden {
+    pub dataset_id: Pubkey,
+    pub contributor: Pubkey,
+    pub quality_score: u8,
+    pub min_quality_score: u8,
+    pub hidden_at: i64,
+}
+
+#[event]
+pub struct RegistryMilestone {
+    pub registry: Pubkey,
+    pub milestone: u64,
+    pub total_datasets: u64,
+    pub reached_at: i64,
+}
+
+#[event]
+pub struct PinExtended {
+    pub dataset_id: Pubkey,
+    pub funder: Pubkey,
+    pub days: u32,
+    pub amount_paid: u64,
+    pub pinned_until: i64,
+}
+
+#[event]
+pub struct DatasetMigrated {
+    pub dataset_id: Pubkey,
+    pub schema_version: u8,
+}
+
+#[event]
+pub struct ReputationDecayed {
+    pub contributor: Pubkey,
+    pub removed: u32,
+    pub new_reputation_score: u32,
+}
+
+#[event]
+pub struct VacationSet {
+    pub contributor: Pubkey,
+    pub decay_paused_until: i64,
+    pub set_by: Pubkey,
+}
+
+#[event]
+pub struct DownloadReportSubmitted {
+    pub registry: Pubkey,
+    pub report: Pubkey,
+    pub report_id: u64,
+    pub root: [u8; 32],
+    pub submitted_at: i64,
+}
+
+#[event]
+pub struct DownloadDeltaClaimed {
+    pub report: Pubkey,
+    pub dataset_id: Pubkey,
+    pub contributor: Pubkey,
+    pub delta: u32,
+    pub download_count: u32,
+}
+
+#[event]
+pub struct DatasetEndorsed {
+    pub dataset_id: Pubkey,
+    pub endorser: Pubkey,
+    pub endorsement_count: u32,
+    pub endorsed_at: i64,
+}
+
+#[event]
+pub struct VaultRecovered {
+    pub vault: Pubkey,
+    pub dataset_id: Pubkey,
+    pub treasury: Pubkey,
+    pub contributor_refund: u64, // Unwithdrawn income returned to the contributor
+    pub lamports: u64, // Rent reserve swept into the treasury
+}
+
+#[event]
+pub struct DownloadsReverted {
+    pub dataset_id: Pubkey,
+    pub contributor: Pubkey,
+    pub admin: Pubkey,
+    pub reversal_id: u64,
+    pub removed: u32,
+    pub download_count: u32,
+    pub new_reputation_score: u32,
+}
+
+#[event]
+pub struct ContributorKeyRotated {
+    pub old_contributor: Pubkey,
+    pub new_contributor: Pubkey,
+    pub dataset_count: u32,
+    pub datasets_repointed: u32, // Datasets passed to the rotation; the rest follow via repoint_datasets
+    pub reputation_score: u32,
+    pub rotated_at: i64,
+}
+
+#[event]
+pub struct DatasetsRepointed {
+    pub old_contributor: Pubkey,
+    pub new_contributor: Pubkey,
+    pub count: u32,
+}
+
+#[event]
+pub struct LicenseRenewed {
+    pub dataset_id: Pubkey,
+    pub contributor: Pubkey,
+    pub license_expires_at: i64,
+}
+
+#[event]
+pub struct DatasetSuperseded {
+    pub old_dataset: Pubkey,
+    pub new_dataset: Pubkey,
+    pub old_contributor: Pubkey,
+    pub new_contributor: Pubkey,
+    pub superseded_at: i64,
+}
+
+#[event]
+pub struct DataRequested {
+    pub request: Pubkey,
+    pub requester: Pubkey,
+    pub registry: Pubkey,
+    pub topic_hash: [u8; 32],
+    pub bounty: u64,
+}
+
+#[event]
+pub struct DataRequestFulfilled {
+    pub request: Pubkey,
+    pub dataset_id: Pubkey,
+    pub contributor: Pubkey,
+    pub bounty: u64,
+    pub fulfilled_at: i64,
+}
+
+#[event]
+pub struct ReputationSeeded {
+    pub contributor: Pubkey,
+    pub authority: Pubkey,
+    pub total_uploads: u32,
+    pub reputation_score: u32,
+}
+
+#[event]
+pub struct MetadataExpanded {
+    pub dataset_id: Pubkey,
+    pub ai_metadata_len: u32,
+    pub rent_deposit: u64,
+}
+
+#[event]
+pub struct DatasetsTagged {
+    pub registry: Pubkey,
+    pub admin: Pubkey,
+    pub research_fields: Vec<u8>,
+    pub dataset_count: u8,
+}
+
+#[event]
+pub struct DataDictionarySet {
+    pub dataset_id: Pubkey,
+    pub dictionary: Pubkey,
+    pub column_count: u8,
+}
+
+#[event]
+pub struct DatasetAutoApproved {
+    pub dataset_id: Pubkey,
+    pub review_deadline: i64,
+    pub approved_at: i64,
+}
+
+#[event]
+pub struct EarningsWithdrawn {
+    pub contributor: Pubkey,
+    pub dataset_id: Pubkey,
+    pub lamports: u64,
+    pub lifetime_lamports: u64,
+    pub available_lamports: u64,
+}
+
+#[event]
+pub struct DatasetUnfeatured {
+    pub dataset_id: Pubkey,
+    pub registry: Pubkey,
+    pub featured_count: u64,
+    pub unfeatured_at: i64,
+}
+
+#[event]
+pub struct AccessRequested {
+    pub dataset_id: Pubkey,
+    pub requester: Pubkey,
+    pub justification_hash: [u8; 32],
+    pub requested_at: i64,
+}
+
+#[event]
+pub struct AccessRequestAnswered {
+    pub dataset_id: Pubkey,
+    pub requester: Pubkey,
+    pub approved: bool,
+    pub grant: Option<Pubkey>,
+    pub responded_at: i64,
+}
+
+#[event]
+pub struct ManifestImported {
+    pub registry: Pubkey,
+    pub institution: Pubkey,
+    pub manifest: Pubkey,
+    pub manifest_hash: [u8; 32],
+    pub dataset_count: u8,
+    pub imported_at: i64,
+}
+
+#[event]
+pub struct ManifestDatasetClaimed {
+    pub manifest: Pubkey,
+    pub dataset_id: Pubkey,
+    pub institution: Pubkey,
+    pub entry: u8,
+}
+
+#[event]
+pub struct CollectionUpdated {
+    pub collection: Pubkey,
+    pub dataset_id: Pubkey,
+    pub added: bool,
+    pub member_count: u8,
+    pub collection_root: [u8; 32],
+}
+
+#[event]
+pub struct DatasetFinalized {
+    pub dataset_id: Pubkey,
+    pub content_hash: [u8; 32],
+    pub finalized_at: i64,
+}
+
+#[event]
+pub struct SpamDepositRefunded {
+    pub dataset_id: Pubkey,
+    pub downloader: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct TreasuryWithdrawn {
+    pub registry: Pubkey,
+    pub destination: Pubkey,
+    pub lamports: u64,
+    pub period_withdrawn: u64,
+    pub withdrawn_at: i64,
+}
+
+#[event]
+pub struct ReplicaAdded {
+    pub dataset_id: Pubkey,
+    pub backend: u8,
+    pub replica_count: u8,
+}
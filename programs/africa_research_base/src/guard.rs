@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Dataset;
+
+// Instructions opt into the dataset lock by implementing this for their accounts struct; every
+// other instruction that mutates a dataset refuses it while `locked` is set
+pub trait GuardedDataset<'info> {
+    fn guarded_dataset(&mut self) -> &mut Account<'info, Dataset>;
+}
+
+// Runs `f` with the dataset locked. The lock is written to the account before `f` starts, so a CPI
+// made by `f` cannot re-enter the program and mutate the same dataset. It is released again
+// whatever `f` returns; a failed instruction rolls the persisted lock back along with everything else.
+pub fn with_dataset_lock<'info, A, T>(accounts: &mut A, f: impl FnOnce(&mut A) -> Result<T>) -> Result<T>
+where
+    A: GuardedDataset<'info>,
+{
+    let dataset = accounts.guarded_dataset();
+    dataset.lock()?;
+    dataset.exit(&crate::ID)?;
+
+    let result = f(accounts);
+    accounts.guarded_dataset().unlock();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorCode;
+
+    struct Accounts<'info> {
+        dataset: Account<'info, Dataset>,
+    }
+
+    impl<'info> GuardedDataset<'info> for Accounts<'info> {
+        fn guarded_dataset(&mut self) -> &mut Account<'info, Dataset> {
+            &mut self.dataset
+        }
+    }
+
+    fn stored(data: &[u8]) -> Dataset {
+        Dataset::try_deserialize(&mut &data[..]).unwrap()
+    }
+
+    #[test]
+    fn nested_mutation_is_rejected() {
+        let key = Pubkey::new_unique();
+        let mut lamports = 1_000_000;
+        let mut data = Vec::new();
+        Dataset::sample().try_serialize(&mut data).unwrap();
+        let info = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &crate::ID, false, 0);
+        let mut accounts = Accounts { dataset: Account::try_from(&info).unwrap() };
+
+        let nested = with_dataset_lock(&mut accounts, |outer| {
+            // What a re-entrant call would load from the account mid-instruction
+            assert!(stored(&outer.dataset.to_account_info().try_borrow_data().unwrap()).locked);
+            with_dataset_lock(outer, |inner| {
+                inner.dataset.download_count += 1;
+                Ok(())
+            })
+        });
+
+        assert_eq!(nested.unwrap_err(), ErrorCode::DatasetLocked.into());
+        assert_eq!(accounts.dataset.download_count, Dataset::sample().download_count);
+        assert!(!accounts.dataset.locked);
+    }
+
+    #[test]
+    fn lock_is_released_on_success_and_failure() {
+        let key = Pubkey::new_unique();
+        let mut lamports = 1_000_000;
+        let mut data = Vec::new();
+        Dataset::sample().try_serialize(&mut data).unwrap();
+        let info = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &crate::ID, false, 0);
+        let mut accounts = Accounts { dataset: Account::try_from(&info).unwrap() };
+
+        let failed: Result<()> = with_dataset_lock(&mut accounts, |_| err!(ErrorCode::DatasetInactive));
+        assert_eq!(failed.unwrap_err(), ErrorCode::DatasetInactive.into());
+        assert!(!accounts.dataset.locked);
+
+        with_dataset_lock(&mut accounts, |a| {
+            a.dataset.download_count += 1;
+            Ok(())
+        })
+        .unwrap();
+        assert!(!accounts.dataset.locked);
+        assert_eq!(accounts.dataset.download_count, Dataset::sample().download_count + 1);
+    }
+}
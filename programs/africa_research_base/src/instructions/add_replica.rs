@@ -0,0 +1,52 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{Dataset, DatasetReplicas};
+use crate::access::is_owner;
+use crate::error::ErrorCode;
+use crate::events::ReplicaAdded;
+use crate::seeds::DATASET_SEED;
+use crate::time::current_time;
+
+// Replicas hold copies of the existing content, so finalized datasets may still register them
+#[derive(Accounts)]
+pub struct AddReplica<'info> {
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+
+    #[account(
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        constraint = is_owner(&dataset, &contributor.key()) @ ErrorCode::UnauthorizedUpdate
+    )]
+    pub dataset: Account<'info, Dataset>,
+
+    // Created by the first replica
+    #[account(
+        init_if_needed,
+        payer = contributor,
+        space = 8 + DatasetReplicas::SPACE,
+        seeds = [b"replicas", dataset.key().as_ref()],
+        bump
+    )]
+    pub replicas: Account<'info, DatasetReplicas>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AddReplica<'info> {
+    pub fn add_replica(&mut self, backend: u8, cid: [u8; 64], bumps: &AddReplicaBumps) -> Result<()> {
+        if self.replicas.dataset == Pubkey::default() {
+            self.replicas.dataset = self.dataset.key();
+            self.replicas.bump = bumps.replicas;
+        }
+        self.replicas.add(backend, cid, current_time()?)?;
+
+        emit!(ReplicaAdded {
+            dataset_id: self.dataset.key(),
+            backend,
+            replica_count: self.replicas.replica_count(),
+        });
+        Ok(())
+    }
+}
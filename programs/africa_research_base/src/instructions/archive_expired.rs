@@ -0,0 +1,51 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{Dataset, Registry};
+use crate::error::ErrorCode;
+use crate::events::DatasetArchived;
+use crate::seeds::{DATASET_SEED, REGISTRY_SEED};
+use crate::time::current_time;
+
+#[derive(Accounts)]
+pub struct ArchiveExpired<'info> {
+    // Permissionless: anyone may archive a dataset once it has expired
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        mut,
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        has_one = registry,
+        constraint = !dataset.locked @ ErrorCode::DatasetLocked
+    )]
+    pub dataset: Account<'info, Dataset>,
+}
+
+impl<'info> ArchiveExpired<'info> {
+    pub fn archive_expired(&mut self) -> Result<()> {
+        let now = current_time()?;
+        require!(self.dataset.is_expired(now), ErrorCode::DatasetNotExpired);
+        require!(self.dataset.is_active, ErrorCode::DatasetInactive);
+
+        self.dataset.is_active = false;
+        self.dataset.touch(now)?;
+        self.registry.archived_count = self.registry.archived_count
+            .checked_add(1)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+
+        emit!(DatasetArchived {
+            id: self.dataset.key(),
+            contributor: self.dataset.published_contributor(),
+            archived_at: now,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,164 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+use crate::Reputation;
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::instructions::normalize_reputation_score;
+use crate::seeds::REPUTATION_SEED;
+
+// The parts of a reputation score, as computed by `calculate_reputation_score`. Deductions are the
+// amounts actually taken, so `score()`, the five credits minus the three deductions, is exactly the
+// stored score. Version 2 added `normalization`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReputationBreakdown {
+    pub version: u8, // REPUTATION_BREAKDOWN_VERSION
+    pub upload_score: u32,
+    pub quality_score: u32, // Average upload quality times QUALITY_MULTIPLIER
+    pub download_score: u32,
+    pub citation_score: u32,
+    pub fork_score: u32,
+    pub penalties: u32, // Up to total_penalties; a score never goes below zero
+    pub decay: u32, // Up to total_decay, taken after penalties
+    pub normalization: u32, // What normalize_reputation_score takes off the raw score above REPUTATION_SCORE_KNEE
+}
+
+impl ReputationBreakdown {
+    pub fn new(reputation: &Reputation) -> Result<Self> {
+        let upload_score = reputation.total_uploads
+            .checked_mul(UPLOAD_WEIGHT)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+
+        let quality_score = (reputation.average_quality().unwrap_or(0) as u32)
+            .checked_mul(QUALITY_MULTIPLIER)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+
+        let download_score = u32::try_from(reputation.total_downloads)
+            .map_err(|_| ErrorCode::NumericalOverflow)?
+            .checked_mul(DOWNLOAD_WEIGHT)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+
+        let citation_score = reputation.total_citations
+            .checked_mul(CITATION_WEIGHT)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+
+        let fork_score = reputation.total_forks
+            .checked_mul(FORK_WEIGHT)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+
+        let credits = upload_score
+            .checked_add(quality_score)
+            .ok_or(ErrorCode::NumericalOverflow)?
+            .checked_add(download_score)
+            .ok_or(ErrorCode::NumericalOverflow)?
+            .checked_add(citation_score)
+            .ok_or(ErrorCode::NumericalOverflow)?
+            .checked_add(fork_score)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        let penalties = reputation.total_penalties.min(credits);
+        let decay = reputation.total_decay.min(credits - penalties);
+        let raw = credits - penalties - decay;
+        let normalization = raw - normalize_reputation_score(raw);
+
+        Ok(ReputationBreakdown {
+            version: REPUTATION_BREAKDOWN_VERSION,
+            upload_score,
+            quality_score,
+            download_score,
+            citation_score,
+            fork_score,
+            penalties,
+            decay,
+            normalization,
+        })
+    }
+
+    pub fn score(&self) -> u32 {
+        // Cannot overflow or underflow: `new` checked the credits and capped the deductions
+        self.upload_score + self.quality_score + self.download_score + self.citation_score + self.fork_score
+            - self.penalties
+            - self.decay
+            - self.normalization
+    }
+}
+
+#[derive(Accounts)]
+pub struct AuditReputation<'info> {
+    #[account(
+        seeds = [REPUTATION_SEED, reputation.contributor.as_ref()],
+        bump = reputation.bump
+    )]
+    pub reputation: Account<'info, Reputation>,
+}
+
+impl<'info> AuditReputation<'info> {
+    // Read-only; meant to be simulated, with the breakdown read from the return data
+    pub fn audit_reputation(&self) -> Result<()> {
+        let breakdown = ReputationBreakdown::new(&self.reputation)?;
+        set_return_data(&breakdown.try_to_vec()?);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::calculate_reputation_score;
+
+    fn scored(reputation: Reputation) -> Reputation {
+        let mut reputation = reputation;
+        calculate_reputation_score(&mut reputation).unwrap();
+        reputation
+    }
+
+    #[test]
+    fn components_sum_to_the_stored_score() {
+        let reputation = scored(Reputation {
+            total_uploads: 3,
+            total_quality_score: 240,
+            total_downloads: 12,
+            total_citations: 2,
+            total_forks: 1,
+            total_penalties: 15,
+            total_decay: 4,
+            ..Reputation::sample()
+        });
+        let breakdown = ReputationBreakdown::new(&reputation).unwrap();
+
+        assert_eq!(breakdown.quality_score, 80 * QUALITY_MULTIPLIER);
+        assert_eq!(breakdown.download_score, 12 * DOWNLOAD_WEIGHT);
+        assert_eq!((breakdown.penalties, breakdown.decay, breakdown.normalization), (15, 4, 0));
+        assert_eq!(breakdown.score(), reputation.reputation_score);
+
+        // Far enough above the knee that normalization takes a share
+        let prolific = scored(Reputation {
+            total_downloads: (2 * REPUTATION_SCORE_KNEE / DOWNLOAD_WEIGHT) as u64,
+            total_penalties: 10,
+            ..reputation
+        });
+        let breakdown = ReputationBreakdown::new(&prolific).unwrap();
+        let raw = breakdown.score() + breakdown.normalization;
+
+        assert!(raw > REPUTATION_SCORE_KNEE);
+        assert!(breakdown.normalization > 0);
+        assert_eq!(breakdown.normalization, raw - normalize_reputation_score(raw));
+        assert_eq!(breakdown.score(), prolific.reputation_score);
+    }
+
+    #[test]
+    fn deductions_report_only_what_was_taken() {
+        let reputation = scored(Reputation {
+            total_citations: 1,
+            total_penalties: CITATION_WEIGHT - 1,
+            total_decay: 100,
+            ..Reputation::sample()
+        });
+        let breakdown = ReputationBreakdown::new(&reputation).unwrap();
+
+        assert_eq!(reputation.reputation_score, 0);
+        assert_eq!((breakdown.penalties, breakdown.decay), (CITATION_WEIGHT - 1, 1));
+        assert_eq!(breakdown.score(), 0);
+        assert_eq!(ReputationBreakdown::try_from_slice(&breakdown.try_to_vec().unwrap()).unwrap(), breakdown);
+    }
+}
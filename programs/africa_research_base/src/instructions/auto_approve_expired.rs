@@ -0,0 +1,43 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{Dataset, Registry};
+use crate::error::ErrorCode;
+use crate::events::DatasetAutoApproved;
+use crate::seeds::{DATASET_SEED, REGISTRY_SEED};
+use crate::time::current_time;
+
+// Permissionless: anyone may approve a dataset whose review deadline passed without a decision
+#[derive(Accounts)]
+pub struct AutoApproveExpired<'info> {
+    #[account(
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump,
+        constraint = registry.review_sla != 0 @ ErrorCode::AutoApproveDisabled
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        mut,
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        has_one = registry,
+        constraint = !dataset.locked @ ErrorCode::DatasetLocked
+    )]
+    pub dataset: Account<'info, Dataset>,
+}
+
+impl<'info> AutoApproveExpired<'info> {
+    pub fn auto_approve_expired(&mut self) -> Result<()> {
+        let now = current_time()?;
+        self.dataset.auto_approve(now)?;
+        self.dataset.touch(now)?;
+
+        emit!(DatasetAutoApproved {
+            dataset_id: self.dataset.key(),
+            review_deadline: self.dataset.review_deadline,
+            approved_at: now,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{Dataset, Registry};
+use crate::access::{is_admin, require_program_owned};
+use crate::constants::MAX_BULK_TAG_DATASETS;
+use crate::error::ErrorCode;
+use crate::events::DatasetsTagged;
+use crate::seeds::REGISTRY_SEED;
+use crate::time::current_time;
+
+#[derive(Accounts)]
+pub struct BulkTag<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump,
+        constraint = is_admin(&registry, &admin.key()) @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub registry: Account<'info, Registry>,
+    // remaining_accounts: the writable datasets to tag, all in this registry
+}
+
+impl<'info> BulkTag<'info> {
+    // Curation of legacy uploads: adds `research_fields` to every dataset passed. Reputation field
+    // scores already credited under a dataset's old fields stay where they are.
+    pub fn bulk_tag(
+        &mut self,
+        research_fields: Vec<u8>,
+        datasets: &'info [AccountInfo<'info>],
+        program_id: &Pubkey
+    ) -> Result<()> {
+        require!(
+            !datasets.is_empty() && datasets.len() <= MAX_BULK_TAG_DATASETS,
+            ErrorCode::InvalidBulkTagAccounts
+        );
+        let now = current_time()?;
+
+        // Every dataset is checked and tagged before any is written back, so a dataset over the field
+        // cap fails the call with none of them changed
+        let mut tagged = Vec::with_capacity(datasets.len());
+        for info in datasets {
+            require_program_owned(info)?;
+            require!(info.is_writable, ErrorCode::InvalidBulkTagAccounts);
+            let mut dataset: Account<'info, Dataset> = Account::try_from(info)?;
+            require_keys_eq!(dataset.registry, self.registry.key(), ErrorCode::InvalidBulkTagAccounts);
+            require!(!dataset.locked, ErrorCode::DatasetLocked);
+            require!(!dataset.finalized, ErrorCode::DatasetFinalized);
+
+            dataset.add_research_fields(&research_fields)?;
+            dataset.touch(now)?;
+            tagged.push(dataset);
+        }
+        for dataset in &tagged {
+            dataset.exit(program_id)?;
+        }
+
+        emit!(DatasetsTagged {
+            registry: self.registry.key(),
+            admin: self.admin.key(),
+            research_fields,
+            dataset_count: tagged.len() as u8,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,182 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{Dataset, HashRegistry, Registry, Reputation, TokenIndex, Treasury};
+use crate::access::{is_closed, is_owner, require_program_owned};
+use crate::error::ErrorCode;
+use crate::events::{DatasetClosed, ReputationUpdated};
+use crate::instructions::{calculate_reputation_score, token_index_address};
+use crate::seeds::{find_address, reputation_seeds, DATASET_SEED, REGISTRY_SEED, REPUTATION_SEED};
+use crate::time::current_time;
+
+#[derive(Accounts)]
+pub struct CloseDataset<'info> {
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+
+    // Gives back the featured slot of a featured dataset
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        mut,
+        close = contributor,
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        constraint = is_owner(&dataset, &contributor.key()) @ ErrorCode::UnauthorizedUpdate,
+        has_one = registry,
+        constraint = !dataset.locked @ ErrorCode::DatasetLocked,
+        constraint = !dataset.finalized @ ErrorCode::DatasetFinalized
+    )]
+    pub dataset: Account<'info, Dataset>,
+
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, contributor.key().as_ref()],
+        bump = reputation.bump,
+        constraint = reputation.is_current() @ ErrorCode::ContributorKeyRotated
+    )]
+    pub reputation: Account<'info, Reputation>,
+
+    // Receives the spam deposit of a dataset closed before any qualifying download
+    #[account(
+        mut,
+        seeds = [b"treasury", registry.key().as_ref()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// CHECK: the content hash's registry PDA, which datasets uploaded before it existed may lack;
+    /// released in close_dataset when it still names this dataset
+    #[account(mut, seeds = [b"content_hash", dataset.content_hash.as_ref()], bump)]
+    pub hash_registry: UncheckedAccount<'info>,
+    // remaining_accounts: the token index PDAs passed to create_dataset, in the same order, then one
+    // writable reputation PDA per co_credits entry not held by the contributor, in co_credits order
+}
+
+impl<'info> CloseDataset<'info> {
+    pub fn close_dataset(&mut self, remaining_accounts: &'info [AccountInfo<'info>], program_id: &Pubkey) -> Result<()> {
+        let (token_indexes, co_reputations) = split_remaining_accounts(&self.dataset.title, remaining_accounts);
+        unindex_title(&self.registry.key(), &self.dataset.key(), &self.dataset.title, token_indexes, program_id)?;
+
+        reverse_upload_credit(&mut self.reputation, &self.dataset, co_reputations, "close", program_id)?;
+
+        let info = self.hash_registry.to_account_info();
+        if !is_closed(&info) {
+            let mut entry = HashRegistry::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+            entry.release(&self.dataset.key());
+            entry.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+        }
+
+        if self.dataset.featured {
+            self.registry.release_featured();
+        }
+
+        let spam_deposit_forfeited = self.dataset.forfeit_spam_deposit();
+        if spam_deposit_forfeited > 0 {
+            self.dataset.sub_lamports(spam_deposit_forfeited)?;
+            self.treasury.add_lamports(spam_deposit_forfeited)?;
+        }
+
+        emit!(DatasetClosed {
+            id: self.dataset.key(),
+            contributor: self.dataset.published_contributor(),
+            reputation_finalized: self.dataset.reputation_finalized,
+            bond_refunded: self.dataset.bond,
+            spam_deposit_forfeited,
+            closed_at: current_time()?,
+        });
+        Ok(())
+    }
+}
+
+// A dataset removed before finalize_reputation never earned upload credit; otherwise every share is
+// taken back exactly as it was credited, the contributor's and each co-contributor's
+pub(crate) fn reverse_upload_credit<'info>(
+    reputation: &mut Reputation,
+    dataset: &Dataset,
+    co_reputations: &'info [AccountInfo<'info>],
+    action: &str,
+    program_id: &Pubkey
+) -> Result<()> {
+    if !dataset.reputation_finalized {
+        require!(co_reputations.is_empty(), ErrorCode::InvalidCoContributorAccounts);
+        return Ok(());
+    }
+    // A co-contributor the contributor rotated onto holds both shares in one reputation
+    let (own, others): (Vec<_>, Vec<_>) = dataset.co_credits
+        .iter()
+        .partition(|credit| credit.contributor == dataset.contributor);
+    require!(co_reputations.len() == others.len(), ErrorCode::InvalidCoContributorAccounts);
+
+    reputation.debit_upload_credit(dataset.credited_quality, &dataset.credited_field_points);
+    for credit in own {
+        reputation.debit_upload_credit(credit.quality, &credit.field_points);
+    }
+    calculate_reputation_score(reputation)?;
+    emit!(ReputationUpdated {
+        contributor: dataset.published_contributor(),
+        action: action.to_string(),
+        new_dataset_count: reputation.dataset_count,
+        new_reputation_score: reputation.reputation_score,
+    });
+
+    for (credit, info) in others.into_iter().zip(co_reputations) {
+        let (address, _) = find_address(&reputation_seeds(&credit.contributor), program_id);
+        require_keys_eq!(*info.key, address, ErrorCode::InvalidCoContributorAccounts);
+        require_program_owned(info)?;
+
+        // As in finalize_reputation, a rotated co-contributor is reached through the re-pointed dataset
+        let mut co_reputation = Account::<Reputation>::try_from(info)?;
+        require!(co_reputation.is_current(), ErrorCode::ContributorKeyRotated);
+        co_reputation.debit_upload_credit(credit.quality, &credit.field_points);
+        calculate_reputation_score(&mut co_reputation)?;
+        co_reputation.exit(program_id)?;
+
+        emit!(ReputationUpdated {
+            contributor: co_reputation.published_key(),
+            action: action.to_string(),
+            new_dataset_count: co_reputation.dataset_count,
+            new_reputation_score: co_reputation.reputation_score,
+        });
+    }
+    Ok(())
+}
+
+// The token indexes come first, one per title token; whatever follows is for reverse_upload_credit
+pub(crate) fn split_remaining_accounts<'info>(
+    title: &[u8],
+    accounts: &'info [AccountInfo<'info>]
+) -> (&'info [AccountInfo<'info>], &'info [AccountInfo<'info>]) {
+    accounts.split_at(TokenIndex::tokenize(title).len().min(accounts.len()))
+}
+
+// Indexes that were never created (e.g. for forks, which are not indexed) are skipped
+pub(crate) fn unindex_title<'info>(
+    registry: &Pubkey,
+    dataset: &Pubkey,
+    title: &[u8],
+    token_indexes: &'info [AccountInfo<'info>],
+    program_id: &Pubkey
+) -> Result<()> {
+    let tokens = TokenIndex::tokenize(title);
+    require!(token_indexes.len() == tokens.len(), ErrorCode::InvalidTokenIndexAccounts);
+
+    for (token, info) in tokens.iter().zip(token_indexes) {
+        let (address, _) = token_index_address(registry, token, program_id);
+        require_keys_eq!(*info.key, address, ErrorCode::InvalidTokenIndexAccounts);
+        if info.data_is_empty() {
+            continue;
+        }
+        require_program_owned(info)?;
+
+        let mut index = Account::<TokenIndex>::try_from(info)?;
+        index.remove(dataset);
+        index.exit(program_id)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,30 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+use crate::Dataset;
+use crate::seeds::DATASET_SEED;
+
+#[derive(Accounts)]
+pub struct CompareDatasets<'info> {
+    #[account(
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump
+    )]
+    pub dataset: Account<'info, Dataset>,
+
+    #[account(
+        seeds = [DATASET_SEED, other.creator.as_ref(), &other.dataset_index.to_le_bytes()],
+        bump = other.bump
+    )]
+    pub other: Account<'info, Dataset>,
+}
+
+impl<'info> CompareDatasets<'info> {
+    // Read-only; the return data is a single byte of DIFF_* bits
+    pub fn compare_datasets(&self) -> Result<()> {
+        let mask = self.dataset.diff(&self.other)?;
+        set_return_data(&[mask]);
+        Ok(())
+    }
+}
@@ -0,0 +1,107 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::{ConfigChange, PendingConfigChange, Registry};
+use crate::access::is_admin;
+use crate::constants::*;
+use crate::events::{ConfigChangeExecuted, ConfigChangeProposed};
+use crate::seeds::REGISTRY_SEED;
+use crate::time::current_time;
+
+#[derive(Accounts)]
+pub struct ProposeConfigChange<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump,
+        constraint = is_admin(&registry, &admin.key()) @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + PendingConfigChange::SPACE,
+        seeds = [b"config_change", registry.key().as_ref()],
+        bump
+    )]
+    pub pending_change: Account<'info, PendingConfigChange>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteConfigChange<'info> {
+    // Anyone may execute once the timelock has elapsed
+    pub executor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [b"config_change", registry.key().as_ref()],
+        bump = pending_change.bump,
+        has_one = registry,
+        has_one = proposer
+    )]
+    pub pending_change: Account<'info, PendingConfigChange>,
+
+    /// CHECK: receives the rent of the closed proposal, verified via `has_one`
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+}
+
+impl<'info> ProposeConfigChange<'info> {
+    pub fn propose_config_change(
+        &mut self,
+        change: ConfigChange,
+        bumps: &ProposeConfigChangeBumps
+    ) -> Result<()> {
+        change.validate()?;
+
+        let now = current_time()?;
+        let unlock_at = now + CONFIG_TIMELOCK_DELAY;
+
+        self.pending_change.set_inner(PendingConfigChange {
+            registry: self.registry.key(),
+            proposer: self.admin.key(),
+            change,
+            proposed_at: now,
+            unlock_at,
+            bump: bumps.pending_change
+        });
+
+        emit!(ConfigChangeProposed {
+            registry: self.registry.key(),
+            change,
+            unlock_at,
+        });
+        Ok(())
+    }
+}
+
+impl<'info> ExecuteConfigChange<'info> {
+    pub fn execute_config_change(&mut self) -> Result<()> {
+        let now = current_time()?;
+        self.pending_change.ensure_unlocked(now)?;
+
+        let change = self.pending_change.change;
+        self.registry.apply_config_change(&change)?;
+
+        emit!(ConfigChangeExecuted {
+            registry: self.registry.key(),
+            change,
+            executed_at: now,
+        });
+        Ok(())
+    }
+}
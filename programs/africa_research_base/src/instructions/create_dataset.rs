@@ -1,147 +1,451 @@
 #![allow(unexpected_cfgs)]
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+use anchor_lang::system_program::{create_account, transfer, CreateAccount, Transfer};
 
-use crate::{Dataset, Registry, Reputation};
-use crate::events::{DatasetCreated, ReputationUpdated};
+use crate::{Dataset, HashRegistry, ManifestImport, RecentDatasets, Registry, RegistryConfig, Reputation, Taxonomy, TokenIndex};
+use crate::access::{is_admin, require_program_owned};
+use crate::ai_metadata::AiMetadata;
+use crate::events::{DatasetCreated, ManifestDatasetClaimed, RegistryMilestone};
+use crate::constants::*;
 use crate::error::ErrorCode;
+use crate::seeds::{create_address, registry_seeds, DATASET_SEED, REPUTATION_SEED};
+use crate::uri::{uri_bytes, validate_uri_scheme};
+use crate::profiling::profile;
+use crate::validation::ValidationFlags;
+use crate::time::current_time;
 
+// Optional settings supplied at creation; new optional fields belong here rather than in more arguments
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct DatasetOptions {
+    pub expires_at: Option<i64>,
+    pub thumbnail_uri: Option<[u8; 128]>,
+    pub featured: bool,
+    pub co_contributors: Vec<Pubkey>,
+    pub research_fields: Vec<u8>,
+    pub report_all_errors: bool, // Report every failed rule instead of only the first
+    pub title: Option<Vec<u8>>, // Defaults to the file name
+    pub license_expires_at: Option<i64>, // None for a perpetual license
+    pub allow_zero_quality: bool, // A quality_score of 0 is rejected as a likely unset value unless this is set
+    pub featured_until: Option<i64>, // End of the featured promotion; None keeps it featured. Ignored unless featured
+    pub language: Option<[u8; 2]>, // ISO 639-1 code of the content, in either case
+    pub manifest_entry: Option<u8>, // Entry of the passed manifest this upload claims; set exactly when one is passed
+}
+
+// Return data of `create_dataset`, so clients need not re-derive the PDA
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CreatedDataset {
+    pub version: u8, // CREATED_DATASET_VERSION
+    pub dataset: Pubkey,
+    pub dataset_index: u32,
+}
+
+impl CreatedDataset {
+    pub fn new(dataset: Pubkey, dataset_index: u32) -> Self {
+        CreatedDataset { version: CREATED_DATASET_VERSION, dataset, dataset_index }
+    }
+}
+
+// Two signers: the registry admin approves the upload, and the contributor owns the dataset and
+// pays for every account it creates
 #[derive(Accounts)]
+#[instruction(content_hash: [u8; 32])]
 pub struct CreateDataset <'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
 
-    #[account(mut)]
-    pub user: Signer<'info>,
-
     #[account(mut)]
     pub contributor: Signer<'info>,
-    #[account(
-        mut,
-        seeds = [b"registry", admin.key().as_ref()],
-        bump = registry.bump
-    )]
-    pub registry: Account <'info, Registry>,
+    /// CHECK: loaded by load_registry, so a registry that was never initialized reports
+    /// RegistryNotInitialized rather than a generic deserialization failure
+    #[account(mut)]
+    pub registry: UncheckedAccount<'info>,
+
+    /// CHECK: loaded by load_registry; it does not exist either when the registry is uninitialized
+    pub registry_config: UncheckedAccount<'info>,
 
     
     #[account(
         init,
         payer = contributor,
-        space = 8 + Dataset::INIT_SPACE,
-        seeds = [b"dataset", contributor.key().as_ref(), &reputation.dataset_count.to_le_bytes()],
+        space = 8 + Dataset::SPACE,
+        seeds = [DATASET_SEED, contributor.key().as_ref(), &reputation.dataset_count.to_le_bytes()],
         bump
     )]
     pub dataset: Account <'info, Dataset>,
 
     #[account(
         mut,
-        seeds = [b"reputation", contributor.key().as_ref()],
-        bump = reputation.bump
+        seeds = [REPUTATION_SEED, contributor.key().as_ref()],
+        bump = reputation.bump,
+        constraint = reputation.is_current() @ ErrorCode::ContributorKeyRotated
     )]
     pub reputation: Account <'info, Reputation>,
 
+    #[account(
+        init_if_needed,
+        payer = contributor,
+        space = 8 + RecentDatasets::SPACE,
+        seeds = [b"recent_datasets", registry.key().as_ref()],
+        bump
+    )]
+    pub recent_datasets: Box<Account<'info, RecentDatasets>>,
+
+    // Created by the first upload of this content anywhere; later uploads leave it as it is
+    #[account(
+        init_if_needed,
+        payer = contributor,
+        space = 8 + HashRegistry::SPACE,
+        seeds = [b"content_hash", content_hash.as_ref()],
+        bump
+    )]
+    pub hash_registry: Box<Account<'info, HashRegistry>>,
+
+    // Earlier version this upload supersedes within the same lineage; see Dataset::parent
+    #[account(
+        seeds = [DATASET_SEED, parent.creator.as_ref(), &parent.dataset_index.to_le_bytes()],
+        bump = parent.bump,
+        constraint = parent.contributor == contributor.key() && parent.registry == registry.key()
+            @ ErrorCode::InvalidParentVersion
+    )]
+    pub parent: Option<Box<Account<'info, Dataset>>>,
+
+    // An institution's imported manifest listing this content; see DatasetOptions::manifest_entry
+    #[account(
+        mut,
+        seeds = [b"manifest", registry.key().as_ref(), manifest.manifest_hash.as_ref()],
+        bump = manifest.bump
+    )]
+    pub manifest: Option<Box<Account<'info, ManifestImport>>>,
+
+    // Required once the registry has a taxonomy, unless the dataset lists no research fields
+    #[account(
+        seeds = [b"taxonomy", registry.key().as_ref()],
+        bump = taxonomy.bump
+    )]
+    pub taxonomy: Option<Box<Account<'info, Taxonomy>>>,
+
     pub system_program: Program<'info, System>,
+    // remaining_accounts: one writable token index PDA per `TokenIndex::tokenize(title)` entry, in order
 }
 
-impl <'info> CreateDataset <'info> {
-    fn update_reputation(&mut self, quality_score: u8) -> Result<()> {
-        let reputation = &mut self.reputation;
-        
-        // Update basic stats
-        reputation.contributor = self.contributor.key();
-        reputation.total_uploads = reputation.total_uploads
-            .checked_add(1)
-            .ok_or(ErrorCode::NumericalOverflow)?;
-        reputation.total_quality_score = reputation.total_quality_score
-            .checked_add(quality_score as u64)
-            .ok_or(ErrorCode::NumericalOverflow)?;
-            
-        Ok(())
+pub(crate) fn token_index_address(registry: &Pubkey, token: &[u8; 8], program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"token_index", registry.as_ref(), token], program_id)
+}
+
+// Adds the dataset under each word of its title, creating indexes for new words at the payer's expense
+pub(crate) fn index_title<'info>(
+    registry: &Pubkey,
+    dataset: &Pubkey,
+    title: &[u8],
+    token_indexes: &'info [AccountInfo<'info>],
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    program_id: &Pubkey
+) -> Result<()> {
+    let tokens = TokenIndex::tokenize(title);
+    require!(token_indexes.len() == tokens.len(), ErrorCode::InvalidTokenIndexAccounts);
+
+    for (token, info) in tokens.iter().zip(token_indexes) {
+        let (address, bump) = token_index_address(registry, token, program_id);
+        require_keys_eq!(*info.key, address, ErrorCode::InvalidTokenIndexAccounts);
+
+        if info.data_is_empty() {
+            let space = 8 + TokenIndex::SPACE;
+            create_account(
+                CpiContext::new_with_signer(
+                    system_program.clone(),
+                    CreateAccount { from: payer.clone(), to: info.clone() },
+                    &[&[b"token_index", registry.as_ref(), token, &[bump]]],
+                ),
+                Rent::get()?.minimum_balance(space),
+                space as u64,
+                program_id,
+            )?;
+            TokenIndex { registry: *registry, token_hash: *token, datasets: Vec::new(), bump }
+                .try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+        }
+        require_program_owned(info)?;
+
+        let mut index = Account::<TokenIndex>::try_from(info)?;
+        index.insert(*dataset);
+        index.exit(program_id)?;
     }
+    Ok(())
+}
 
+impl <'info> CreateDataset <'info> {
+    #[allow(clippy::too_many_arguments)]
     pub fn create_dataset (
         &mut self,
         content_hash: [u8; 32],
         ai_metadata: Vec<u8>,
-        file_name: Vec<u8>,
+        mut file_name: Vec<u8>,
         file_size: u64,
-        data_uri: [u8; 256],
+        data_uri: Vec<u8>,
         column_count: u64,
         row_count: u64,
         quality_score: u8,
-        // upload_timestamp: i64,
-        // last_updated: Option<i64>,
-        // download_count: u32,
-        // is_active: bool,
+        preview_hash: [u8; 32],
+        options: DatasetOptions,
+        token_indexes: &'info [AccountInfo<'info>],
+        program_id: &Pubkey,
         bumps: &CreateDatasetBumps
     ) -> Result<()> {
-        require!(content_hash.len() == 32, ErrorCode::HashTooLong);
-        require!(content_hash == content_hash, ErrorCode::DuplicateDataset);
-        require!(file_name.len() <= 100, ErrorCode::FileNameTooLong);
-        require!(quality_score <= 100, ErrorCode::InvalidQualityScore);
-        require!(file_size <= 104_857_600, ErrorCode::FileTooLarge);
-        require!(file_size > 0, ErrorCode::InvalidFileSize);
-        require!(column_count <= 100, ErrorCode::TooManyColumns);
-        // require!(upload_timestamp <= 104_857_600, ErrorCode::FileTooLarge);
+        let now = current_time()?;
+        let (mut registry, registry_config) = self.load_registry(program_id)?;
+
+        Dataset::trim_file_name(&mut file_name);
+        let mut title = options.title.unwrap_or_else(|| file_name.clone());
+        Dataset::trim_file_name(&mut title);
+
+        profile!("create_dataset: validation");
+        let mut flags = ValidationFlags::default();
+        flags.require(file_name.len() <= MAX_FILE_NAME_LEN, ErrorCode::FileNameTooLong);
+        flags.require(ai_metadata.len() <= MAX_AI_METADATA_LEN, ErrorCode::MetadataTooLong);
+        let detected_language = if registry.structured_ai_metadata {
+            let parsed = AiMetadata::parse(&ai_metadata);
+            let detected_language = parsed.as_ref().ok().and_then(|metadata| metadata.detected_language);
+            flags.check(parsed.map(|_| ()))?;
+            detected_language
+        } else {
+            None
+        };
+        flags.require(quality_score <= registry.quality_scale, ErrorCode::InvalidQualityScore);
+        flags.check(Dataset::validate_quality_intent(quality_score, options.allow_zero_quality))?;
+        // Clamped so an out-of-scale score still flags the remaining rules
+        let quality_score = registry.normalize_quality(quality_score.min(registry.quality_scale))?;
+        flags.require(quality_score >= registry.min_quality_score, ErrorCode::QualityBelowMinimum);
+        flags.require(file_size <= MAX_FILE_SIZE, ErrorCode::FileTooLarge);
+        flags.require(file_size > 0, ErrorCode::InvalidFileSize);
+        flags.check(registry.ensure_min_file_size(file_size))?;
+        flags.check(Dataset::validate_preview_hash(file_size, &preview_hash))?;
+        flags.require(column_count <= MAX_COLUMNS as u64, ErrorCode::TooManyColumns);
+        flags.require(data_uri.len() <= DATA_URI_LEN, ErrorCode::DataUriTooLong);
+        flags.check(registry_config.ensure_host_allowed(&data_uri))?;
+        if let Some(expires_at) = options.expires_at {
+            flags.require(expires_at > now, ErrorCode::InvalidExpiry);
+        }
+        if let Some(license_expires_at) = options.license_expires_at {
+            flags.require(license_expires_at > now, ErrorCode::InvalidLicenseExpiry);
+        }
+        if let (true, Some(featured_until)) = (options.featured, options.featured_until) {
+            flags.require(featured_until > now, ErrorCode::InvalidFeaturedUntil);
+        }
+        if let Some(thumbnail_uri) = &options.thumbnail_uri {
+            flags.check(validate_uri_scheme(thumbnail_uri))?;
+        }
+        flags.check(Dataset::validate_co_contributors(&self.contributor.key(), &options.co_contributors))?;
+        flags.check(Dataset::validate_research_fields(&options.research_fields))?;
+        if registry.taxonomy_enabled && !options.research_fields.is_empty() {
+            match &self.taxonomy {
+                Some(taxonomy) => flags.check(taxonomy.validate_research_fields(&options.research_fields))?,
+                None => flags.require(false, ErrorCode::UnknownCategory),
+            }
+        }
+        flags.check(Dataset::validate_file_name(&file_name))?;
+        flags.check(Dataset::validate_title(&title))?;
+        if let Some(language) = &options.language {
+            flags.check(Dataset::validate_language(language))?;
+        }
+        flags.into_result(options.report_all_errors)?;
+        profile!("create_dataset: validated");
+
+        if options.featured {
+            registry.ensure_can_feature(self.reputation.reputation_score)?;
+        }
+        // The institution creates the claimed dataset itself, so finalize_reputation credits it
+        let claimed_entry = match (&mut self.manifest, options.manifest_entry) {
+            (Some(manifest), Some(entry)) => {
+                manifest.claim(entry, &content_hash, &self.contributor.key())?;
+                Some(entry)
+            }
+            (None, None) => None,
+            _ => return err!(ErrorCode::InvalidManifestEntry),
+        };
 
 
         {
         let dataset = &mut self.dataset;
-        let registry = &mut self.registry;
         //let reputation = &mut self.reputation;
-        let clock = Clock::get()?;
 
-        //bug fix here manualaly. IDKKKK
-        let current_index =self.reputation.dataset_count;
-        dataset.dataset_index  = current_index;
+        dataset.dataset_index = self.reputation.dataset_count;
 
         dataset.id = dataset.key();
+        dataset.registry = self.registry.key();
         dataset.contributor = self.contributor.key();
+        dataset.co_contributors = options.co_contributors;
+        dataset.editors = Vec::new();
         dataset.content_hash = content_hash;
+        dataset.preview_hash = preview_hash;
         dataset.ai_metadata = ai_metadata;
         dataset.file_name = file_name;
+        dataset.research_fields = options.research_fields;
         dataset.file_size = file_size;
-        dataset.data_uri = data_uri;
+        dataset.data_uri = uri_bytes(&data_uri).to_vec();
+        dataset.uri_locked = false;
+        dataset.thumbnail_uri = options.thumbnail_uri;
         dataset.column_count = column_count;
         dataset.row_count = row_count;
         dataset.quality_score = quality_score;
-        dataset.upload_timestamp = clock.unix_timestamp;
+        dataset.upload_timestamp = now;
         dataset.last_updated = None;
         dataset.download_count = 0;
+        dataset.download_fee = 0;
+        dataset.download_cap = 0;
+        dataset.download_window = 0;
+        dataset.rating_sum = 0;
+        dataset.rating_count = 0;
+        dataset.citation_count = 0;
         dataset.is_active = true;
+        dataset.featured = options.featured;
+        dataset.expires_at = options.expires_at;
+        dataset.reputation_finalized = false;
+        dataset.review_status = REVIEW_SUBMITTED;
+        dataset.reviewer = Pubkey::default();
+        dataset.review_note_hash = [0u8; 32];
+        dataset.forked_from = Pubkey::default();
+        dataset.fork_count = 0;
+        dataset.frozen = false;
+        dataset.unavailable = false;
+        dataset.quality_hidden = false;
+        dataset.pinned_until = 0;
+        dataset.pin_seconds_funded = 0;
+        dataset.endorsement_count = 0;
+        dataset.rating_weight = 0;
+        dataset.title = title;
+        dataset.license_expires_at = options.license_expires_at;
+        dataset.supersedes = None;
+        dataset.superseded_by = None;
+        dataset.quality_disputed = false;
+        dataset.bond = registry.quality_bond;
+        dataset.locked = false;
+        dataset.anonymous = self.reputation.anonymous;
+        dataset.review_deadline = Dataset::review_deadline_for(now, registry.review_sla);
+        dataset.featured_until = if options.featured { options.featured_until.unwrap_or(0) } else { 0 };
+        dataset.finalized = false;
+        dataset.spam_deposit = registry.spam_deposit;
+        dataset.language = options.language.map(|code| code.map(|c| c.to_ascii_lowercase()));
+        dataset.language_mismatch = Dataset::language_conflicts(dataset.language, detected_language);
+        dataset.creator = self.contributor.key();
+        dataset.parent = self.parent.as_ref().map(|parent| parent.key());
         dataset.bump = bumps.dataset;
+        dataset.schema_version = DATASET_SCHEMA_VERSION;
 
-        registry.total_datasets = registry.total_datasets.checked_add(1).unwrap();
+        if let Some(milestone) = registry.record_dataset(options.featured)? {
+            emit!(RegistryMilestone {
+                registry: self.registry.key(),
+                milestone,
+                total_datasets: registry.total_datasets,
+                reached_at: now,
+            });
+        }
     }
+        registry.try_serialize(&mut &mut self.registry.try_borrow_mut_data()?[..])?;
+
+        profile!("create_dataset: index title");
+        index_title(
+            &self.registry.key(),
+            &self.dataset.key(),
+            &self.dataset.title,
+            token_indexes,
+            &self.contributor.to_account_info(),
+            &self.system_program.to_account_info(),
+            program_id,
+        )?;
+        profile!("create_dataset: title indexed");
 
-        // Increment dataset count for the contributor
-        {let reputation = &mut self.reputation;
-        reputation.dataset_count = reputation.dataset_count.checked_add(1).unwrap();
+        // Created by the registry's first upload
+        let recent = &mut self.recent_datasets;
+        if recent.registry == Pubkey::default() {
+            recent.registry = self.registry.key();
+            recent.bump = bumps.recent_datasets;
         }
+        recent.push(self.dataset.key());
 
-        // Update reputation through the dedicated handler
-        self.update_reputation(quality_score)?;
+        self.hash_registry.register(
+            content_hash,
+            self.dataset.key(),
+            self.dataset.published_contributor(),
+            now,
+            bumps.hash_registry,
+        );
+
+        // Only reserves the next PDA index; upload credit waits for finalize_reputation
+        self.reputation.record_dataset()?;
+
+        // The dataset account is the bond and spam deposit vault: close_dataset returns the bond, and the
+        // deposit unless no qualifying download released it first
+        let escrow = self.dataset.bond
+            .checked_add(self.dataset.spam_deposit)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        if escrow > 0 {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer { from: self.contributor.to_account_info(), to: self.dataset.to_account_info() },
+                ),
+                escrow,
+            )?;
+        }
+
+        // Upload credit is staged until finalize_reputation runs after the grace period
 
         
         //Emit events
-        let clock = Clock::get()?;
         emit!(DatasetCreated {
             id: self.dataset.key(),
-            contributor: self.contributor.key(),
+            contributor: self.dataset.published_contributor(),
             content_hash,
             quality_score,
-            upload_timestamp: clock.unix_timestamp,
+            upload_timestamp: now,
        });
 
-       emit!(ReputationUpdated {
-           contributor: self.contributor.key(),
-           action: "upload".to_string(),
-           new_dataset_count: self.reputation.dataset_count,
-           new_reputation_score: self.reputation.reputation_score,  // Use existing field
-       });
+        if let (Some(manifest), Some(entry)) = (&self.manifest, claimed_entry) {
+            emit!(ManifestDatasetClaimed {
+                manifest: manifest.key(),
+                dataset_id: self.dataset.key(),
+                institution: manifest.institution,
+                entry,
+            });
+        }
+
+        // Set last: a later CPI would overwrite it
+        let created = CreatedDataset::new(self.dataset.key(), self.dataset.dataset_index);
+        set_return_data(&created.try_to_vec()?);
         Ok(())
     }
-    
+
+    // Registry and config are checked by hand in the order the account constraints used to run
+    fn load_registry(&self, program_id: &Pubkey) -> Result<(Registry, RegistryConfig)> {
+        let registry = Registry::try_from_initialized(self.registry.owner, &self.registry.try_borrow_data()?)?;
+        let address = create_address(&registry_seeds(&registry.creator, &registry.name), registry.bump, program_id)?;
+        require!(address == self.registry.key(), anchor_lang::error::ErrorCode::ConstraintSeeds);
+        require!(is_admin(&registry, &self.admin.key()), ErrorCode::UnauthorizedAdmin);
+
+        require_program_owned(&self.registry_config)?;
+        let registry_config = RegistryConfig::try_deserialize(&mut &self.registry_config.try_borrow_data()?[..])?;
+        let address = Pubkey::create_program_address(
+            &[b"registry_config", self.registry.key().as_ref(), &[registry_config.bump]],
+            program_id,
+        );
+        require!(address == Ok(self.registry_config.key()), anchor_lang::error::ErrorCode::ConstraintSeeds);
+        Ok((registry, registry_config))
+    }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn created_dataset_is_versioned_and_compact() {
+        let dataset = Pubkey::new_unique();
+        let bytes = CreatedDataset::new(dataset, 7).try_to_vec().unwrap();
 
-  
\ No newline at end of file
+        assert_eq!(bytes.len(), 1 + 32 + 4);
+        assert_eq!(bytes[0], CREATED_DATASET_VERSION);
+        assert_eq!(&bytes[1..33], dataset.as_ref());
+        assert_eq!(CreatedDataset::try_from_slice(&bytes).unwrap(), CreatedDataset::new(dataset, 7));
+    }
+}
@@ -0,0 +1,106 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{Registry, Reputation};
+use crate::access::is_admin;
+use crate::error::ErrorCode;
+use crate::events::{ReputationDecayed, VacationSet};
+use crate::instructions::calculate_reputation_score;
+use crate::seeds::{REGISTRY_SEED, REPUTATION_SEED};
+use crate::time::current_time;
+
+// Permissionless crank
+#[derive(Accounts)]
+pub struct DecayReputation<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, reputation.contributor.as_ref()],
+        bump = reputation.bump,
+        constraint = reputation.is_current() @ ErrorCode::ContributorKeyRotated
+    )]
+    pub reputation: Account<'info, Reputation>,
+}
+
+#[derive(Accounts)]
+pub struct SetVacation<'info> {
+    pub contributor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, contributor.key().as_ref()],
+        bump = reputation.bump,
+        constraint = reputation.is_current() @ ErrorCode::ContributorKeyRotated
+    )]
+    pub reputation: Account<'info, Reputation>,
+}
+
+// Institutions are vouched for by a registry admin, who may pause their decay on their behalf
+#[derive(Accounts)]
+pub struct SetInstitutionVacation<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump,
+        constraint = is_admin(&registry, &admin.key()) @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, reputation.contributor.as_ref()],
+        bump = reputation.bump,
+        constraint = reputation.delegate_count > 0 @ ErrorCode::NotAnInstitution,
+        constraint = reputation.is_current() @ ErrorCode::ContributorKeyRotated
+    )]
+    pub reputation: Account<'info, Reputation>,
+}
+
+impl<'info> DecayReputation<'info> {
+    pub fn decay_reputation(&mut self) -> Result<()> {
+        apply_decay(&mut self.reputation, current_time()?)
+    }
+}
+
+impl<'info> SetVacation<'info> {
+    pub fn set_vacation(&mut self, until: i64) -> Result<()> {
+        let set_by = self.contributor.key();
+        start_vacation(&mut self.reputation, until, set_by)
+    }
+}
+
+impl<'info> SetInstitutionVacation<'info> {
+    pub fn set_institution_vacation(&mut self, until: i64) -> Result<()> {
+        let set_by = self.admin.key();
+        start_vacation(&mut self.reputation, until, set_by)
+    }
+}
+
+fn apply_decay(reputation: &mut Reputation, now: i64) -> Result<()> {
+    let removed = reputation.decay(now)?;
+    if removed > 0 {
+        calculate_reputation_score(reputation)?;
+        emit!(ReputationDecayed {
+            contributor: reputation.published_key(),
+            removed,
+            new_reputation_score: reputation.reputation_score,
+        });
+    }
+    Ok(())
+}
+
+// Decay accrued before the vacation is settled first, so it cannot be skipped
+fn start_vacation(reputation: &mut Reputation, until: i64, set_by: Pubkey) -> Result<()> {
+    let now = current_time()?;
+    apply_decay(reputation, now)?;
+    reputation.set_vacation(until, now)?;
+
+    emit!(VacationSet {
+        contributor: reputation.published_key(),
+        decay_paused_until: until,
+        set_by,
+    });
+    Ok(())
+}
@@ -0,0 +1,108 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{Delegation, Reputation};
+use crate::error::ErrorCode;
+use crate::events::{DelegationRevoked, ReputationDelegated};
+use crate::seeds::REPUTATION_SEED;
+use crate::time::current_time;
+
+#[derive(Accounts)]
+pub struct DelegateReputation<'info> {
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, delegator.key().as_ref()],
+        bump = delegator_reputation.bump,
+        constraint = delegator_reputation.is_current() @ ErrorCode::ContributorKeyRotated
+    )]
+    pub delegator_reputation: Account<'info, Reputation>,
+
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, institution_reputation.contributor.as_ref()],
+        bump = institution_reputation.bump,
+        constraint = institution_reputation.is_current() @ ErrorCode::ContributorKeyRotated
+    )]
+    pub institution_reputation: Account<'info, Reputation>,
+
+    #[account(
+        init,
+        payer = delegator,
+        space = 8 + Delegation::SPACE,
+        seeds = [b"delegation", delegator.key().as_ref()],
+        bump
+    )]
+    pub delegation: Account<'info, Delegation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeDelegation<'info> {
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, delegator.key().as_ref()],
+        bump = delegator_reputation.bump,
+        constraint = delegator_reputation.is_current() @ ErrorCode::ContributorKeyRotated
+    )]
+    pub delegator_reputation: Account<'info, Reputation>,
+
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, delegation.institution.as_ref()],
+        bump = institution_reputation.bump,
+        constraint = institution_reputation.is_current() @ ErrorCode::ContributorKeyRotated
+    )]
+    pub institution_reputation: Account<'info, Reputation>,
+
+    #[account(
+        mut,
+        close = delegator,
+        seeds = [b"delegation", delegator.key().as_ref()],
+        bump = delegation.bump
+    )]
+    pub delegation: Account<'info, Delegation>,
+}
+
+impl<'info> DelegateReputation<'info> {
+    pub fn delegate_reputation(&mut self, bumps: &DelegateReputationBumps) -> Result<()> {
+        let score = self.delegator_reputation.delegate(&mut self.institution_reputation)?;
+
+        self.delegation.set_inner(Delegation {
+            delegator: self.delegator.key(),
+            institution: self.institution_reputation.contributor,
+            score,
+            delegated_at: current_time()?,
+            bump: bumps.delegation
+        });
+
+        emit!(ReputationDelegated {
+            delegator: self.delegator.key(),
+            institution: self.institution_reputation.contributor,
+            score,
+            institution_aggregate_score: self.institution_reputation.aggregate_score(),
+        });
+        Ok(())
+    }
+}
+
+impl<'info> RevokeDelegation<'info> {
+    pub fn revoke_delegation(&mut self) -> Result<()> {
+        let score = self.delegation.score;
+        self.delegator_reputation.revoke_delegation(&mut self.institution_reputation, score);
+
+        emit!(DelegationRevoked {
+            delegator: self.delegator.key(),
+            institution: self.delegation.institution,
+            score,
+            institution_aggregate_score: self.institution_reputation.aggregate_score(),
+        });
+        Ok(())
+    }
+}
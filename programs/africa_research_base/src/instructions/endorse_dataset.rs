@@ -0,0 +1,58 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{Dataset, Endorsement};
+use crate::error::ErrorCode;
+use crate::events::DatasetEndorsed;
+use crate::seeds::DATASET_SEED;
+use crate::time::current_time;
+
+#[derive(Accounts)]
+pub struct EndorseDataset<'info> {
+    #[account(mut)]
+    pub endorser: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        constraint = dataset.contributor != endorser.key() @ ErrorCode::SelfEndorsement,
+        constraint = dataset.is_active @ ErrorCode::DatasetInactive,
+        constraint = !dataset.locked @ ErrorCode::DatasetLocked
+    )]
+    pub dataset: Account<'info, Dataset>,
+
+    // `init` makes a second endorsement from the same account fail
+    #[account(
+        init,
+        payer = endorser,
+        space = 8 + Endorsement::SPACE,
+        seeds = [b"endorse", dataset.key().as_ref(), endorser.key().as_ref()],
+        bump
+    )]
+    pub endorsement: Account<'info, Endorsement>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> EndorseDataset<'info> {
+    pub fn endorse_dataset(&mut self, bumps: &EndorseDatasetBumps) -> Result<()> {
+        let now = current_time()?;
+
+        self.endorsement.set_inner(Endorsement {
+            dataset: self.dataset.key(),
+            endorser: self.endorser.key(),
+            endorsed_at: now,
+            bump: bumps.endorsement,
+        });
+        self.dataset.record_endorsement()?;
+
+        emit!(DatasetEndorsed {
+            dataset_id: self.dataset.key(),
+            endorser: self.endorser.key(),
+            endorsement_count: self.dataset.endorsement_count,
+            endorsed_at: now,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::{Dataset, Registry};
+use crate::access::is_owner;
+use crate::ai_metadata::AiMetadata;
+use crate::error::ErrorCode;
+use crate::guard::{with_dataset_lock, GuardedDataset};
+use crate::events::MetadataExpanded;
+use crate::seeds::{DATASET_SEED, REGISTRY_SEED};
+use crate::time::current_time;
+
+#[derive(Accounts)]
+pub struct ExpandMetadata<'info> {
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+
+    #[account(
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        mut,
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        constraint = is_owner(&dataset, &contributor.key()) @ ErrorCode::UnauthorizedUpdate,
+        has_one = registry,
+        constraint = !dataset.locked @ ErrorCode::DatasetLocked,
+        constraint = !dataset.finalized @ ErrorCode::DatasetFinalized
+    )]
+    pub dataset: Account<'info, Dataset>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> GuardedDataset<'info> for ExpandMetadata<'info> {
+    fn guarded_dataset(&mut self) -> &mut Account<'info, Dataset> {
+        &mut self.dataset
+    }
+}
+
+impl<'info> ExpandMetadata<'info> {
+    // Appends `extra` to ai_metadata, reallocating the dataset when it outgrows its allocation.
+    // `rent_deposit` must be exactly the lamports the larger account is short of rent exemption, so
+    // a client can neither underfund the account nor leave surplus lamports stranded in it.
+    // The dataset stays locked across the rent deposit transfer
+    pub fn expand_metadata(&mut self, extra: Vec<u8>, rent_deposit: u64) -> Result<()> {
+        with_dataset_lock(self, |accounts| accounts.expand(extra, rent_deposit))
+    }
+
+    fn expand(&mut self, extra: Vec<u8>, rent_deposit: u64) -> Result<()> {
+        let space = 8 + self.dataset.expand_ai_metadata(&extra)?;
+        if self.registry.structured_ai_metadata {
+            let detected_language = AiMetadata::parse(&self.dataset.ai_metadata)?.detected_language;
+            self.dataset.language_mismatch = Dataset::language_conflicts(self.dataset.language, detected_language);
+        }
+
+        let info = self.dataset.to_account_info();
+        let space = space.max(info.data_len());
+        let required = Rent::get()?.minimum_balance(space).saturating_sub(info.lamports());
+        require!(rent_deposit == required, ErrorCode::IncorrectRentDeposit);
+        if required > 0 {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer { from: self.contributor.to_account_info(), to: info.clone() },
+                ),
+                required,
+            )?;
+        }
+        if space > info.data_len() {
+            info.realloc(space, false)?;
+        }
+        self.dataset.touch(current_time()?)?;
+
+        emit!(MetadataExpanded {
+            dataset_id: self.dataset.key(),
+            ai_metadata_len: self.dataset.ai_metadata.len() as u32,
+            rent_deposit,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,23 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+use crate::Dataset;
+use crate::seeds::DATASET_SEED;
+
+#[derive(Accounts)]
+pub struct ExportDataset<'info> {
+    #[account(
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump
+    )]
+    pub dataset: Account<'info, Dataset>,
+}
+
+impl<'info> ExportDataset<'info> {
+    // Read-only; callers fetch the canonical bytes from the transaction's return data
+    pub fn export_dataset(&self) -> Result<()> {
+        set_return_data(&self.dataset.to_canonical_bytes());
+        Ok(())
+    }
+}
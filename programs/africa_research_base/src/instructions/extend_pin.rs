@@ -0,0 +1,85 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::{Dataset, FeeVault};
+use crate::constants::PIN_FEE_PER_DAY;
+use crate::error::ErrorCode;
+use crate::guard::{with_dataset_lock, GuardedDataset};
+use crate::events::PinExtended;
+use crate::seeds::DATASET_SEED;
+use crate::time::current_time;
+
+#[derive(Accounts)]
+pub struct ExtendPin<'info> {
+    // Anyone who wants the dataset to stay available may fund pinning
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        constraint = !dataset.locked @ ErrorCode::DatasetLocked
+    )]
+    pub dataset: Account<'info, Dataset>,
+
+    #[account(
+        init_if_needed,
+        payer = funder,
+        space = 8 + FeeVault::SPACE,
+        seeds = [b"vault", dataset.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> GuardedDataset<'info> for ExtendPin<'info> {
+    fn guarded_dataset(&mut self) -> &mut Account<'info, Dataset> {
+        &mut self.dataset
+    }
+}
+
+impl<'info> ExtendPin<'info> {
+    // The dataset stays locked across the pin fee transfer
+    pub fn extend_pin(&mut self, days: u32, bumps: &ExtendPinBumps) -> Result<()> {
+        with_dataset_lock(self, |accounts| accounts.extend(days, bumps))
+    }
+
+    fn extend(&mut self, days: u32, bumps: &ExtendPinBumps) -> Result<()> {
+        let now = current_time()?;
+        self.dataset.extend_pin(now, days)?;
+
+        if self.fee_vault.dataset == Pubkey::default() {
+            self.fee_vault.set_inner(FeeVault::new(self.dataset.key(), self.dataset.contributor, self.dataset.registry, bumps.fee_vault));
+        }
+
+        let amount = PIN_FEE_PER_DAY
+            .checked_mul(days as u64)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.funder.to_account_info(),
+                    to: self.fee_vault.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        self.fee_vault.total_collected = self.fee_vault.total_collected
+            .checked_add(amount)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+
+        emit!(PinExtended {
+            dataset_id: self.dataset.key(),
+            funder: self.funder.key(),
+            days,
+            amount_paid: amount,
+            pinned_until: self.dataset.pinned_until,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::Dataset;
+use crate::access::is_owner;
+use crate::error::ErrorCode;
+use crate::events::DatasetFinalized;
+use crate::seeds::DATASET_SEED;
+use crate::time::current_time;
+
+// Distinct from finalize_reputation, which settles upload credit; this makes the dataset itself immutable
+#[derive(Accounts)]
+pub struct FinalizeDataset<'info> {
+    pub contributor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        constraint = is_owner(&dataset, &contributor.key()) @ ErrorCode::UnauthorizedUpdate,
+        constraint = !dataset.locked @ ErrorCode::DatasetLocked
+    )]
+    pub dataset: Account<'info, Dataset>,
+}
+
+impl<'info> FinalizeDataset<'info> {
+    pub fn finalize_dataset(&mut self) -> Result<()> {
+        let now = current_time()?;
+        self.dataset.finalize()?;
+        self.dataset.touch(now)?;
+
+        emit!(DatasetFinalized {
+            dataset_id: self.dataset.key(),
+            content_hash: self.dataset.content_hash,
+            finalized_at: now,
+        });
+        Ok(())
+    }
+}
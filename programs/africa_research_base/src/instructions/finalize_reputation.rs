@@ -0,0 +1,147 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{create_account, CreateAccount};
+
+use crate::{CoContributorCredit, Dataset, Registry, RegistryConfig, Reputation};
+use crate::access::require_program_owned;
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::events::ReputationUpdated;
+use crate::instructions::calculate_reputation_score;
+use crate::profiling::profile;
+use crate::seeds::{find_address, reputation_seeds, DATASET_SEED, REGISTRY_SEED, REPUTATION_SEED};
+use crate::time::current_time;
+
+#[derive(Accounts)]
+pub struct FinalizeReputation<'info> {
+    // Permissionless: anyone may finalize once the grace period has passed
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        seeds = [b"registry_config", registry.key().as_ref()],
+        bump = registry_config.bump
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    #[account(
+        mut,
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        has_one = registry,
+        constraint = !dataset.locked @ ErrorCode::DatasetLocked
+    )]
+    pub dataset: Account<'info, Dataset>,
+
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, dataset.contributor.as_ref()],
+        bump = reputation.bump,
+        constraint = reputation.is_current() @ ErrorCode::ContributorKeyRotated
+    )]
+    pub reputation: Account<'info, Reputation>,
+
+    pub system_program: Program<'info, System>,
+    // remaining_accounts: one writable reputation PDA per co-contributor, in `co_contributors` order
+}
+
+impl<'info> FinalizeReputation<'info> {
+    pub fn finalize_reputation(
+        &mut self,
+        co_reputations: &'info [AccountInfo<'info>],
+        program_id: &Pubkey
+    ) -> Result<()> {
+        let now = current_time()?;
+        self.dataset.ensure_reputation_finalizable(
+            now,
+            self.registry.reputation_grace_period,
+        )?;
+        require!(
+            co_reputations.len() == self.dataset.co_contributors.len(),
+            ErrorCode::InvalidCoContributorAccounts
+        );
+
+        let (primary_share, co_share) = self.dataset.quality_shares()?;
+        let reputation = &mut self.reputation;
+        reputation.credit_upload(primary_share)?;
+        let config = &self.registry_config;
+        let field_points = reputation.credit_fields_by(&self.dataset.research_fields, |field| {
+            config.weighted_points(field, upload_points(primary_share))
+        });
+        calculate_reputation_score(reputation)?;
+
+        let mut co_credits = Vec::with_capacity(co_reputations.len());
+        for (co_contributor, info) in self.dataset.co_contributors.iter().zip(co_reputations) {
+            let mut co_reputation = self.load_or_create_reputation(co_contributor, info, program_id)?;
+            co_reputation.credit_upload(co_share)?;
+            let field_points = co_reputation.credit_fields_by(&self.dataset.research_fields, |field| {
+                self.registry_config.weighted_points(field, upload_points(co_share))
+            });
+            co_credits.push(CoContributorCredit { contributor: *co_contributor, quality: co_share, field_points });
+            calculate_reputation_score(&mut co_reputation)?;
+            co_reputation.exit(program_id)?;
+
+            emit!(ReputationUpdated {
+                contributor: co_reputation.published_key(),
+                action: "upload".to_string(),
+                new_dataset_count: co_reputation.dataset_count,
+                new_reputation_score: co_reputation.reputation_score,
+            });
+        }
+        self.dataset.record_upload_credit(primary_share, field_points, co_credits);
+
+        emit!(ReputationUpdated {
+            contributor: self.dataset.published_contributor(),
+            action: "upload".to_string(),
+            new_dataset_count: self.reputation.dataset_count,
+            new_reputation_score: self.reputation.reputation_score,
+        });
+        Ok(())
+    }
+
+    // Co-contributors may never have initialized a reputation; the payer funds one for them
+    fn load_or_create_reputation(
+        &self,
+        contributor: &Pubkey,
+        info: &'info AccountInfo<'info>,
+        program_id: &Pubkey
+    ) -> Result<Account<'info, Reputation>> {
+        let (address, bump) = find_address(&reputation_seeds(contributor), program_id);
+        require_keys_eq!(*info.key, address, ErrorCode::InvalidCoContributorAccounts);
+
+        if info.data_is_empty() {
+            let space = 8 + Reputation::SPACE;
+            profile!("finalize_reputation: create co-contributor reputation");
+            create_account(
+                CpiContext::new_with_signer(
+                    self.system_program.to_account_info(),
+                    CreateAccount { from: self.payer.to_account_info(), to: info.clone() },
+                    &[&[REPUTATION_SEED, contributor.as_ref(), &[bump]]],
+                ),
+                Rent::get()?.minimum_balance(space),
+                space as u64,
+                program_id,
+            )?;
+            profile!("finalize_reputation: co-contributor reputation created");
+
+            Reputation::new(*contributor, bump).try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+        }
+        require_program_owned(info)?;
+
+        // A co-contributor who rotated keys is credited under the new key once the dataset is re-pointed
+        let reputation = Account::<Reputation>::try_from(info)?;
+        require!(reputation.is_current(), ErrorCode::ContributorKeyRotated);
+        Ok(reputation)
+    }
+}
+
+// Per-field points for an upload: the upload weight plus the author's quality share
+pub(crate) fn upload_points(quality_share: u8) -> u32 {
+    UPLOAD_WEIGHT.saturating_add((quality_share as u32).saturating_mul(QUALITY_MULTIPLIER))
+}
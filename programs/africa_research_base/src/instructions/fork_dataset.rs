@@ -0,0 +1,117 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{Dataset, Registry, Reputation};
+use crate::access::is_admin;
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::events::{DatasetForked, RegistryMilestone, ReputationUpdated};
+use crate::instructions::calculate_reputation_score;
+use crate::seeds::{DATASET_SEED, REGISTRY_SEED, REPUTATION_SEED};
+use crate::time::current_time;
+
+#[derive(Accounts)]
+pub struct ForkDataset<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub forker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump,
+        constraint = is_admin(&registry, &admin.key()) @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        mut,
+        seeds = [DATASET_SEED, source.creator.as_ref(), &source.dataset_index.to_le_bytes()],
+        bump = source.bump,
+        has_one = registry,
+        constraint = !source.locked @ ErrorCode::DatasetLocked
+    )]
+    pub source: Account<'info, Dataset>,
+
+    #[account(
+        init,
+        payer = forker,
+        space = 8 + Dataset::space_with_ai_metadata(source.ai_metadata.len()),
+        seeds = [DATASET_SEED, forker.key().as_ref(), &forker_reputation.dataset_count.to_le_bytes()],
+        bump
+    )]
+    pub fork: Account<'info, Dataset>,
+
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, forker.key().as_ref()],
+        bump = forker_reputation.bump,
+        constraint = forker_reputation.is_current() @ ErrorCode::ContributorKeyRotated
+    )]
+    pub forker_reputation: Account<'info, Reputation>,
+
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, source.contributor.as_ref()],
+        bump = source_reputation.bump,
+        constraint = source_reputation.is_current() @ ErrorCode::ContributorKeyRotated
+    )]
+    pub source_reputation: Account<'info, Reputation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ForkDataset<'info> {
+    pub fn fork_dataset(&mut self, bumps: &ForkDatasetBumps) -> Result<()> {
+        let now = current_time()?;
+
+        let fork = self.source.fork(
+            self.fork.key(),
+            self.forker.key(),
+            self.forker_reputation.dataset_count,
+            now,
+            bumps.fork
+        )?;
+        self.fork.set_inner(Dataset {
+            anonymous: self.forker_reputation.anonymous,
+            review_deadline: Dataset::review_deadline_for(now, self.registry.review_sla),
+            ..fork
+        });
+        self.source.record_fork()?;
+        if let Some(milestone) = self.registry.record_dataset(false)? {
+            emit!(RegistryMilestone {
+                registry: self.registry.key(),
+                milestone,
+                total_datasets: self.registry.total_datasets,
+                reached_at: now,
+            });
+        }
+        self.forker_reputation.record_dataset()?;
+
+        // The fork's own upload credit is staged for finalize_reputation like any other dataset;
+        // the original contributor gets a small bonus straight away
+        let reputation = &mut self.source_reputation;
+        reputation.total_forks = reputation.total_forks
+            .checked_add(1)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        reputation.credit_fields(&self.source.research_fields, FORK_WEIGHT);
+        calculate_reputation_score(reputation)?;
+
+        emit!(DatasetForked {
+            source: self.source.key(),
+            fork: self.fork.key(),
+            forker: self.fork.published_contributor(),
+            original_contributor: self.source.published_contributor(),
+            source_fork_count: self.source.fork_count,
+        });
+
+        emit!(ReputationUpdated {
+            contributor: self.source.published_contributor(),
+            action: "fork".to_string(),
+            new_dataset_count: self.source_reputation.dataset_count,
+            new_reputation_score: self.source_reputation.reputation_score,
+        });
+        Ok(())
+    }
+}
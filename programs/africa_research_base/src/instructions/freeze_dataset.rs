@@ -0,0 +1,98 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{Dataset, PenaltyRecord, Registry, Reputation, Treasury};
+use crate::access::is_admin;
+use crate::error::ErrorCode;
+use crate::events::DatasetFrozen;
+use crate::instructions::calculate_reputation_score;
+use crate::seeds::{DATASET_SEED, REGISTRY_SEED, REPUTATION_SEED};
+use crate::time::current_time;
+
+#[derive(Accounts)]
+pub struct FreezeDataset<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump,
+        constraint = is_admin(&registry, &admin.key()) @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        mut,
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        has_one = registry,
+        constraint = !dataset.locked @ ErrorCode::DatasetLocked
+    )]
+    pub dataset: Account<'info, Dataset>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + PenaltyRecord::SPACE,
+        seeds = [b"penalty", dataset.key().as_ref()],
+        bump
+    )]
+    pub penalty_record: Account<'info, PenaltyRecord>,
+
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, dataset.contributor.as_ref()],
+        bump = reputation.bump,
+        constraint = reputation.is_current() @ ErrorCode::ContributorKeyRotated
+    )]
+    pub reputation: Account<'info, Reputation>,
+
+    // Receives the dataset's forfeited quality bond
+    #[account(
+        mut,
+        seeds = [b"treasury", registry.key().as_ref()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> FreezeDataset<'info> {
+    pub fn freeze_dataset(&mut self, reason_hash: [u8; 32], bumps: &FreezeDatasetBumps) -> Result<()> {
+        let now = current_time()?;
+        let amount = self.registry.freeze_penalty;
+
+        self.dataset.freeze()?;
+        self.dataset.touch(now)?;
+        let bond_forfeited = self.dataset.forfeit_bond();
+        if bond_forfeited > 0 {
+            self.dataset.sub_lamports(bond_forfeited)?;
+            self.treasury.add_lamports(bond_forfeited)?;
+        }
+
+        self.reputation.apply_penalty(amount)?;
+        calculate_reputation_score(&mut self.reputation)?;
+
+        self.penalty_record.set_inner(PenaltyRecord {
+            dataset: self.dataset.key(),
+            contributor: self.dataset.contributor,
+            admin: self.admin.key(),
+            amount,
+            reason_hash,
+            applied_at: now,
+            bump: bumps.penalty_record
+        });
+
+        emit!(DatasetFrozen {
+            dataset_id: self.dataset.key(),
+            contributor: self.dataset.published_contributor(),
+            penalty: amount,
+            reason_hash,
+            new_reputation_score: self.reputation.reputation_score,
+            bond_forfeited,
+            frozen_at: now,
+        });
+        Ok(())
+    }
+}
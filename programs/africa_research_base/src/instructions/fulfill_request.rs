@@ -0,0 +1,54 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{DataRequest, Dataset};
+use crate::events::DataRequestFulfilled;
+use crate::seeds::DATASET_SEED;
+use crate::time::current_time;
+
+// The requester decides which dataset meets their need, so only they can release the bounty
+#[derive(Accounts)]
+pub struct FulfillRequest<'info> {
+    pub requester: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"request", requester.key().as_ref(), data_request.topic_hash.as_ref()],
+        bump = data_request.bump,
+        has_one = requester
+    )]
+    pub data_request: Account<'info, DataRequest>,
+
+    #[account(
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        constraint = dataset.registry == data_request.registry @ anchor_lang::error::ErrorCode::ConstraintHasOne
+    )]
+    pub dataset: Account<'info, Dataset>,
+
+    /// CHECK: bounty destination, must be the dataset's contributor
+    #[account(mut, address = dataset.contributor)]
+    pub contributor: UncheckedAccount<'info>,
+}
+
+impl<'info> FulfillRequest<'info> {
+    pub fn fulfill_request(&mut self) -> Result<()> {
+        let now = current_time()?;
+        let bounty = self.data_request.fulfill(&self.dataset, now)?;
+
+        // The request is program-owned, so the escrow moves without a system-program CPI
+        if bounty > 0 {
+            self.data_request.sub_lamports(bounty)?;
+            self.contributor.add_lamports(bounty)?;
+        }
+
+        emit!(DataRequestFulfilled {
+            request: self.data_request.key(),
+            dataset_id: self.dataset.key(),
+            contributor: self.dataset.published_contributor(),
+            bounty,
+            fulfilled_at: now,
+        });
+        Ok(())
+    }
+}
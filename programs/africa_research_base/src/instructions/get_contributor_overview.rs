@@ -0,0 +1,115 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::{set_return_data, MAX_RETURN_DATA};
+
+use crate::{Dataset, Earnings, Reputation};
+use crate::access::require_program_owned;
+use crate::constants::CONTRIBUTOR_OVERVIEW_VERSION;
+use crate::error::ErrorCode;
+use crate::seeds::REPUTATION_SEED;
+
+// Return data of `get_contributor_overview`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ContributorOverview {
+    pub version: u8, // CONTRIBUTOR_OVERVIEW_VERSION
+    pub total_uploads: u32, // Uploads credited so far; datasets still in their grace period are not counted
+    pub active_datasets: u32, // Of the datasets passed in, those still active
+    pub reputation_score: u32,
+    pub tier: u32,
+    pub lifetime_earnings: u64, // Lamports; 0 for a contributor without an Earnings account
+}
+
+impl ContributorOverview {
+    pub fn new<'a>(reputation: &Reputation, earnings: Option<&Earnings>, datasets: impl IntoIterator<Item = &'a Dataset>) -> Self {
+        ContributorOverview {
+            version: CONTRIBUTOR_OVERVIEW_VERSION,
+            total_uploads: reputation.total_uploads,
+            active_datasets: datasets.into_iter().filter(|d| d.is_active).count() as u32,
+            reputation_score: reputation.reputation_score,
+            tier: reputation.tier(),
+            lifetime_earnings: earnings.map_or(0, |e| e.lifetime_lamports),
+        }
+    }
+}
+
+// Read-only; meant to be simulated. There is no on-chain list of a contributor's datasets, so the
+// caller passes the ones it derived (indexes 0..dataset_count that still exist) as remaining accounts
+#[derive(Accounts)]
+pub struct GetContributorOverview<'info> {
+    #[account(
+        seeds = [REPUTATION_SEED, reputation.contributor.as_ref()],
+        bump = reputation.bump
+    )]
+    pub reputation: Account<'info, Reputation>,
+
+    #[account(
+        seeds = [b"earnings", reputation.contributor.as_ref()],
+        bump = earnings.bump
+    )]
+    pub earnings: Option<Account<'info, Earnings>>,
+}
+
+impl<'info> GetContributorOverview<'info> {
+    pub fn get_contributor_overview(&self, datasets: &'info [AccountInfo<'info>]) -> Result<()> {
+        let mut seen = Vec::with_capacity(datasets.len());
+        let datasets = datasets
+            .iter()
+            .map(|info| {
+                require_program_owned(info)?;
+                require!(!seen.contains(info.key), ErrorCode::InvalidOverviewDataset);
+                seen.push(*info.key);
+                let dataset = Account::<Dataset>::try_from(info)?;
+                require_keys_eq!(dataset.contributor, self.reputation.contributor, ErrorCode::InvalidOverviewDataset);
+                Ok(dataset)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let overview = ContributorOverview::new(
+            &self.reputation,
+            self.earnings.as_deref(),
+            datasets.iter().map(|d| &**d),
+        );
+        let data = overview.try_to_vec()?;
+        debug_assert!(data.len() <= MAX_RETURN_DATA);
+        set_return_data(&data);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overview_reflects_reputation_earnings_and_active_datasets() {
+        let reputation = Reputation { total_uploads: 2, reputation_score: 150, ..Reputation::sample() };
+        let earnings = Earnings {
+            contributor: reputation.contributor,
+            lifetime_lamports: 1_950_000,
+            withdrawn_lamports: 1_200_000,
+            lifetime_token_amounts: Vec::new(),
+            last_withdrawal: 0,
+            bump: 255,
+        };
+        let datasets = [Dataset::sample(), Dataset { is_active: false, ..Dataset::sample() }];
+
+        let overview = ContributorOverview::new(&reputation, Some(&earnings), &datasets);
+
+        assert_eq!(overview, ContributorOverview {
+            version: CONTRIBUTOR_OVERVIEW_VERSION,
+            total_uploads: 2,
+            active_datasets: 1,
+            reputation_score: 150,
+            tier: 1,
+            lifetime_earnings: 1_950_000,
+        });
+        assert_eq!(ContributorOverview::try_from_slice(&overview.try_to_vec().unwrap()).unwrap(), overview);
+    }
+
+    #[test]
+    fn contributor_without_earnings_reports_none() {
+        let overview = ContributorOverview::new(&Reputation::sample(), None, &[]);
+
+        assert_eq!((overview.active_datasets, overview.lifetime_earnings), (0, 0));
+    }
+}
@@ -0,0 +1,82 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+
+use crate::{ManifestImport, Registry};
+use crate::ed25519::verify_signature;
+use crate::error::ErrorCode;
+use crate::events::ManifestImported;
+use crate::seeds::REGISTRY_SEED;
+use crate::time::current_time;
+
+// The transaction must carry the oracle's ed25519 precompile instruction immediately before this one
+#[derive(Accounts)]
+#[instruction(manifest_hash: [u8; 32])]
+pub struct ImportSignedManifest<'info> {
+    #[account(mut)]
+    pub institution: Signer<'info>,
+
+    #[account(
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump,
+        constraint = registry.institution_oracle != Pubkey::default() @ ErrorCode::UnauthorizedOracle
+    )]
+    pub registry: Account<'info, Registry>,
+
+    // Keyed by the manifest hash; a replayed manifest fails here because the account exists
+    #[account(
+        init,
+        payer = institution,
+        space = 8 + ManifestImport::SPACE,
+        seeds = [b"manifest", registry.key().as_ref(), manifest_hash.as_ref()],
+        bump
+    )]
+    pub manifest: Account<'info, ManifestImport>,
+
+    /// CHECK: the instructions sysvar, checked by address
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ImportSignedManifest<'info> {
+    pub fn import_dataset_from_signed_manifest(
+        &mut self,
+        manifest_hash: [u8; 32],
+        content_hashes: Vec<[u8; 32]>,
+        bumps: &ImportSignedManifestBumps
+    ) -> Result<()> {
+        let registry = self.registry.key();
+        let institution = self.institution.key();
+        ManifestImport::validate(&registry, &institution, &manifest_hash, &content_hashes)?;
+
+        let instructions = self.instructions.to_account_info();
+        let current = load_current_index_checked(&instructions)?;
+        require!(current > 0, ErrorCode::InvalidManifestSignature);
+        let precompile = load_instruction_at_checked(current as usize - 1, &instructions)?;
+        verify_signature(&precompile, &self.registry.institution_oracle, &manifest_hash)?;
+
+        let now = current_time()?;
+        let dataset_count = content_hashes.len() as u8;
+        self.manifest.set_inner(ManifestImport {
+            registry,
+            institution,
+            manifest_hash,
+            content_hashes,
+            claimed: 0,
+            imported_at: now,
+            bump: bumps.manifest,
+        });
+
+        emit!(ManifestImported {
+            registry,
+            institution,
+            manifest: self.manifest.key(),
+            manifest_hash,
+            dataset_count,
+            imported_at: now,
+        });
+        Ok(())
+    }
+}
@@ -1,9 +1,13 @@
 #![allow(unexpected_cfgs)]
 use anchor_lang::prelude::*;
 
-use crate::{Registry, Reputation};
+use crate::{Registry, RegistryConfig, Reputation, Treasury};
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::seeds::{REGISTRY_SEED, REPUTATION_SEED};
 
 #[derive(Accounts)]
+#[instruction(name: [u8; 32])]
 pub struct Initialize <'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
@@ -13,15 +17,36 @@ pub struct Initialize <'info> {
 
     #[account(mut)]
     pub contributor: Signer<'info>,
+
+    // init_if_needed so a repeated call reaches the handler and fails with AlreadyInitialized,
+    // which deployment scripts can treat as success, instead of the system program's "already in use"
     #[account(
-        init,
+        init_if_needed,
         payer = admin,
-        space = 8 + Registry::INIT_SPACE,
-        seeds = [b"registry", admin.key().as_ref()],
+        space = 8 + Registry::SPACE,
+        seeds = [REGISTRY_SEED, admin.key().as_ref(), name.as_ref()],
         bump
     )]
     pub registry: Account <'info, Registry>,
 
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + Treasury::SPACE,
+        seeds = [b"treasury", registry.key().as_ref()],
+        bump
+    )]
+    pub treasury: Account <'info, Treasury>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + RegistryConfig::SPACE,
+        seeds = [b"registry_config", registry.key().as_ref()],
+        bump
+    )]
+    pub registry_config: Account <'info, RegistryConfig>,
+
     pub system_program: Program<'info, System>
 }
 
@@ -39,8 +64,8 @@ pub struct InitializeReputation <'info> {
     #[account(
         init,
         payer = contributor,
-        space = 8 + Reputation::INIT_SPACE,
-        seeds = [b"reputation", contributor.key().as_ref()],
+        space = 8 + Reputation::SPACE,
+        seeds = [REPUTATION_SEED, contributor.key().as_ref()],
         bump
     )]
     pub reputation: Account <'info, Reputation>,
@@ -52,15 +77,60 @@ pub struct InitializeReputation <'info> {
 impl <'info> Initialize <'info> {
     pub fn initialize_registry (
         &mut self,
+        name: [u8; 32],
         bumps: &InitializeBumps
     ) -> Result<()> {
+        require!(name != [0u8; 32], ErrorCode::InvalidRegistryName);
+        require!(self.registry.creator == Pubkey::default(), ErrorCode::AlreadyInitialized);
+
         self.registry.set_inner(Registry {
             admin: self.admin.key(),
+            creator: self.admin.key(),
+            name,
             total_datasets: 0,
             total_downloads: 0,
+            platform_fee_bps: 0,
+            min_quality_score: 0,
+            reputation_grace_period: DEFAULT_REPUTATION_GRACE_PERIOD,
+            min_file_size: 0,
+            archived_count: 0,
+            citation_pair_cap: DEFAULT_CITATION_PAIR_CAP,
+            featured_reputation_threshold: DEFAULT_FEATURED_REPUTATION_THRESHOLD,
+            featured_count: 0,
+            citation_oracle: Pubkey::default(),
+            curated: false,
+            freeze_penalty: DEFAULT_FREEZE_PENALTY,
+            unavailable_report_threshold: DEFAULT_UNAVAILABLE_REPORT_THRESHOLD,
+            download_oracle: Pubkey::default(),
+            quality_scale: QUALITY_SCALE_PERCENT,
+            min_rating_count: MIN_RATINGS_FOR_QUALITY,
+            structured_ai_metadata: false,
+            quality_dispute_delta: DEFAULT_QUALITY_DISPUTE_DELTA,
+            quality_bond: 0,
+            taxonomy_enabled: false,
+            review_sla: DEFAULT_REVIEW_SLA,
+            institution_oracle: Pubkey::default(),
+            spam_deposit: 0,
+            treasury_withdrawal_cap: 0,
             bump: bumps.registry
         });
 
+        self.treasury.set_inner(Treasury {
+            registry: self.registry.key(),
+            total_collected: 0,
+            total_withdrawn: 0,
+            period_start: 0,
+            period_withdrawn: 0,
+            bump: bumps.treasury
+        });
+
+        self.registry_config.set_inner(RegistryConfig {
+            registry: self.registry.key(),
+            allowed_hosts: Vec::new(),
+            field_weights: Vec::new(),
+            bump: bumps.registry_config
+        });
+
         Ok(())
 
     }
@@ -146,17 +216,7 @@ impl <'info> InitializeReputation <'info> {
         &mut self,
         bumps: &InitializeReputationBumps
     ) -> Result<()> {
-        self.reputation.set_inner(Reputation { 
-            contributor: self.contributor.key(), 
-            total_uploads: 0, 
-            dataset_count: 0, 
-            download_time: 0, 
-            total_quality_score: 0, 
-            total_downloads: 0, 
-            total_citations: 0, 
-            reputation_score: 0, 
-            bump: bumps.reputation 
-        });
+        self.reputation.set_inner(Reputation::new(self.contributor.key(), bumps.reputation));
 
         Ok(())
     }
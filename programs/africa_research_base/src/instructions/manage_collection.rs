@@ -0,0 +1,109 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{Collection, CollectionMember, Dataset, Registry};
+use crate::error::ErrorCode;
+use crate::events::CollectionUpdated;
+use crate::seeds::{DATASET_SEED, REGISTRY_SEED};
+use crate::time::current_time;
+
+#[derive(Accounts)]
+#[instruction(name: [u8; 32])]
+pub struct CreateCollection<'info> {
+    #[account(mut)]
+    pub curator: Signer<'info>,
+
+    #[account(
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        init,
+        payer = curator,
+        space = 8 + Collection::SPACE,
+        seeds = [b"collection", registry.key().as_ref(), curator.key().as_ref(), name.as_ref()],
+        bump
+    )]
+    pub collection: Account<'info, Collection>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Any dataset in the collection's registry may be added; a collection does not need its authors' consent
+#[derive(Accounts)]
+pub struct AddToCollection<'info> {
+    pub curator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"collection", collection.registry.as_ref(), collection.curator.as_ref(), collection.name.as_ref()],
+        bump = collection.bump,
+        has_one = curator @ ErrorCode::UnauthorizedCurator
+    )]
+    pub collection: Account<'info, Collection>,
+
+    #[account(
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        constraint = dataset.registry == collection.registry @ ErrorCode::CollectionRegistryMismatch
+    )]
+    pub dataset: Account<'info, Dataset>,
+}
+
+// Takes the dataset by key, so members can be removed even after their dataset is closed
+#[derive(Accounts)]
+pub struct RemoveFromCollection<'info> {
+    pub curator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"collection", collection.registry.as_ref(), collection.curator.as_ref(), collection.name.as_ref()],
+        bump = collection.bump,
+        has_one = curator @ ErrorCode::UnauthorizedCurator
+    )]
+    pub collection: Account<'info, Collection>,
+}
+
+impl<'info> CreateCollection<'info> {
+    pub fn create_collection(&mut self, name: [u8; 32], bumps: &CreateCollectionBumps) -> Result<()> {
+        self.collection.set_inner(Collection {
+            registry: self.registry.key(),
+            curator: self.curator.key(),
+            name,
+            members: Vec::new(),
+            collection_root: Collection::root(&[]),
+            updated_at: current_time()?,
+            bump: bumps.collection,
+        });
+        Ok(())
+    }
+}
+
+impl<'info> AddToCollection<'info> {
+    pub fn add_to_collection(&mut self) -> Result<()> {
+        let member = CollectionMember { content_hash: self.dataset.content_hash, dataset: self.dataset.key() };
+        self.collection.add(member, current_time()?)?;
+        emit_update(&self.collection, member.dataset, true);
+        Ok(())
+    }
+}
+
+impl<'info> RemoveFromCollection<'info> {
+    pub fn remove_from_collection(&mut self, dataset: Pubkey) -> Result<()> {
+        self.collection.remove(&dataset, current_time()?)?;
+        emit_update(&self.collection, dataset, false);
+        Ok(())
+    }
+}
+
+fn emit_update(collection: &Account<Collection>, dataset_id: Pubkey, added: bool) {
+    emit!(CollectionUpdated {
+        collection: collection.key(),
+        dataset_id,
+        added,
+        member_count: collection.members.len() as u8,
+        collection_root: collection.collection_root,
+    });
+}
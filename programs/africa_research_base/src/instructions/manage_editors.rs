@@ -0,0 +1,38 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::Dataset;
+use crate::access::is_owner;
+use crate::error::ErrorCode;
+use crate::seeds::DATASET_SEED;
+use crate::time::current_time;
+
+// Editors are managed by the contributor alone; editors cannot add or remove each other
+#[derive(Accounts)]
+pub struct ManageEditors<'info> {
+    pub contributor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        constraint = is_owner(&dataset, &contributor.key()) @ ErrorCode::UnauthorizedUpdate,
+        constraint = !dataset.locked @ ErrorCode::DatasetLocked,
+        constraint = !dataset.finalized @ ErrorCode::DatasetFinalized
+    )]
+    pub dataset: Account<'info, Dataset>,
+}
+
+impl<'info> ManageEditors<'info> {
+    pub fn add_editor(&mut self, editor: Pubkey) -> Result<()> {
+        self.dataset.add_editor(editor)?;
+        self.dataset.touch(current_time()?)?;
+        Ok(())
+    }
+
+    pub fn remove_editor(&mut self, editor: Pubkey) -> Result<()> {
+        self.dataset.remove_editor(&editor)?;
+        self.dataset.touch(current_time()?)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{Registry, Taxonomy};
+use crate::access::is_admin;
+use crate::error::ErrorCode;
+use crate::seeds::REGISTRY_SEED;
+
+// The first category added creates the taxonomy and switches the registry over to it for good
+#[derive(Accounts)]
+pub struct AddCategory<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump,
+        constraint = is_admin(&registry, &admin.key()) @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + Taxonomy::SPACE,
+        seeds = [b"taxonomy", registry.key().as_ref()],
+        bump
+    )]
+    pub taxonomy: Account<'info, Taxonomy>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RetireCategory<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump,
+        constraint = is_admin(&registry, &admin.key()) @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        mut,
+        seeds = [b"taxonomy", registry.key().as_ref()],
+        bump = taxonomy.bump
+    )]
+    pub taxonomy: Account<'info, Taxonomy>,
+}
+
+impl<'info> AddCategory<'info> {
+    pub fn add_category(&mut self, code: u8, name: [u8; 32], bumps: &AddCategoryBumps) -> Result<()> {
+        if self.taxonomy.registry == Pubkey::default() {
+            self.taxonomy.registry = self.registry.key();
+            self.taxonomy.bump = bumps.taxonomy;
+        }
+        self.taxonomy.add(code, name)?;
+        self.registry.taxonomy_enabled = true;
+        Ok(())
+    }
+}
+
+impl<'info> RetireCategory<'info> {
+    // Datasets already tagged with the code keep it; only new uploads are refused
+    pub fn retire_category(&mut self, code: u8) -> Result<()> {
+        self.taxonomy.retire(code)
+    }
+}
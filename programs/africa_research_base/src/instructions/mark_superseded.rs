@@ -0,0 +1,61 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{Dataset, Registry};
+use crate::access::is_owner;
+use crate::error::ErrorCode;
+use crate::events::DatasetSuperseded;
+use crate::seeds::{DATASET_SEED, REGISTRY_SEED};
+use crate::time::current_time;
+
+// Both authors sign: the replacement's to claim the link, the old dataset's to agree to being retired
+#[derive(Accounts)]
+pub struct MarkSuperseded<'info> {
+    pub new_contributor: Signer<'info>,
+
+    pub old_contributor: Signer<'info>,
+
+    #[account(
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        mut,
+        seeds = [DATASET_SEED, new_dataset.creator.as_ref(), &new_dataset.dataset_index.to_le_bytes()],
+        bump = new_dataset.bump,
+        has_one = registry,
+        constraint = is_owner(&new_dataset, &new_contributor.key()) @ ErrorCode::UnauthorizedUpdate,
+        constraint = !new_dataset.locked @ ErrorCode::DatasetLocked
+    )]
+    pub new_dataset: Account<'info, Dataset>,
+
+    #[account(
+        mut,
+        seeds = [DATASET_SEED, old_dataset.creator.as_ref(), &old_dataset.dataset_index.to_le_bytes()],
+        bump = old_dataset.bump,
+        has_one = registry,
+        constraint = is_owner(&old_dataset, &old_contributor.key()) @ ErrorCode::UnauthorizedUpdate,
+        constraint = !old_dataset.locked @ ErrorCode::DatasetLocked
+    )]
+    pub old_dataset: Account<'info, Dataset>,
+}
+
+impl<'info> MarkSuperseded<'info> {
+    pub fn mark_superseded(&mut self) -> Result<()> {
+        let now = current_time()?;
+        self.new_dataset.supersede(&mut self.old_dataset)?;
+        self.new_dataset.touch(now)?;
+        self.old_dataset.touch(now)?;
+
+        emit!(DatasetSuperseded {
+            old_dataset: self.old_dataset.key(),
+            new_dataset: self.new_dataset.key(),
+            old_contributor: self.old_dataset.published_contributor(),
+            new_contributor: self.new_dataset.published_contributor(),
+            superseded_at: now,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,94 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{Dataset, HashRegistry, Registry, Reputation};
+use crate::access::is_admin;
+use crate::error::ErrorCode;
+use crate::events::DatasetsMerged;
+use crate::instructions::{reverse_upload_credit, split_remaining_accounts, unindex_title};
+use crate::seeds::{DATASET_SEED, REGISTRY_SEED, REPUTATION_SEED};
+use crate::time::current_time;
+
+#[derive(Accounts)]
+pub struct MergeDatasets<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump,
+        constraint = is_admin(&registry, &admin.key()) @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        mut,
+        seeds = [DATASET_SEED, canonical.creator.as_ref(), &canonical.dataset_index.to_le_bytes()],
+        bump = canonical.bump,
+        has_one = registry,
+        constraint = !canonical.locked @ ErrorCode::DatasetLocked
+    )]
+    pub canonical: Account<'info, Dataset>,
+
+    #[account(
+        mut,
+        close = duplicate_contributor,
+        seeds = [DATASET_SEED, duplicate.creator.as_ref(), &duplicate.dataset_index.to_le_bytes()],
+        bump = duplicate.bump,
+        has_one = registry,
+        constraint = duplicate.key() != canonical.key() @ ErrorCode::SelfMerge,
+        constraint = !duplicate.locked @ ErrorCode::DatasetLocked,
+        constraint = !duplicate.finalized @ ErrorCode::DatasetFinalized
+    )]
+    pub duplicate: Account<'info, Dataset>,
+
+    /// CHECK: refund destination, must be the duplicate's contributor
+    #[account(mut, address = duplicate.contributor)]
+    pub duplicate_contributor: UncheckedAccount<'info>,
+
+    // Loses the duplicate's upload credit, as if its contributor had closed it
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, duplicate.contributor.as_ref()],
+        bump = duplicate_reputation.bump,
+        constraint = duplicate_reputation.is_current() @ ErrorCode::ContributorKeyRotated
+    )]
+    pub duplicate_reputation: Account<'info, Reputation>,
+
+    // Optional for datasets uploaded before the hash registry existed
+    #[account(
+        mut,
+        seeds = [b"content_hash", canonical.content_hash.as_ref()],
+        bump = hash_registry.bump
+    )]
+    pub hash_registry: Option<Account<'info, HashRegistry>>,
+    // remaining_accounts: the duplicate's token index PDAs and co-contributor reputations, as for close_dataset
+}
+
+impl<'info> MergeDatasets<'info> {
+    pub fn merge_datasets(&mut self, remaining_accounts: &'info [AccountInfo<'info>], program_id: &Pubkey) -> Result<()> {
+        // The duplicate goes away exactly as in close_dataset: off the title index, credit reversed
+        let (token_indexes, co_reputations) = split_remaining_accounts(&self.duplicate.title, remaining_accounts);
+        unindex_title(&self.registry.key(), &self.duplicate.key(), &self.duplicate.title, token_indexes, program_id)?;
+        reverse_upload_credit(&mut self.duplicate_reputation, &self.duplicate, co_reputations, "merge", program_id)?;
+
+        // absorb leaves the canonical's own featured state alone, so the duplicate's slot is freed
+        if self.duplicate.featured {
+            self.registry.release_featured();
+        }
+
+        self.canonical.absorb(&self.duplicate)?;
+        self.canonical.touch(current_time()?)?;
+        if let Some(hash_registry) = &mut self.hash_registry {
+            hash_registry.repoint(&self.duplicate.key(), self.canonical.key(), self.canonical.published_contributor());
+        }
+
+        emit!(DatasetsMerged {
+            canonical: self.canonical.key(),
+            duplicate: self.duplicate.key(),
+            content_hash: self.canonical.content_hash,
+            download_count: self.canonical.download_count,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::Dataset;
+use crate::events::DatasetMigrated;
+
+#[derive(Accounts)]
+pub struct MigrateDataUri<'info> {
+    // Anyone may migrate a dataset; the payer covers the extra rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: an account in an earlier schema cannot be deserialized as `Dataset`; the owner is
+    /// checked here, the discriminator, allocation and schema by Dataset::from_legacy
+    #[account(mut, owner = crate::ID)]
+    pub dataset: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateDataUri<'info> {
+    // Moves a dataset in any earlier schema to DATASET_SCHEMA_VERSION; named for the first migration,
+    // which turned data_uri into a Vec
+    pub fn migrate_data_uri(&mut self) -> Result<()> {
+        let info = self.dataset.to_account_info();
+        let dataset = Dataset::from_legacy(&info.try_borrow_data()?)?;
+
+        let space = 8 + Dataset::space_with_ai_metadata(dataset.ai_metadata.len());
+        let top_up = Rent::get()?.minimum_balance(space).saturating_sub(info.lamports());
+        if top_up > 0 {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer { from: self.payer.to_account_info(), to: info.clone() },
+                ),
+                top_up,
+            )?;
+        }
+        info.realloc(space, true)?;
+        dataset.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+        emit!(DatasetMigrated {
+            dataset_id: info.key(),
+            schema_version: dataset.schema_version,
+        });
+        Ok(())
+    }
+}
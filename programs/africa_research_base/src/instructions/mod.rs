@@ -7,5 +7,179 @@ pub use create_dataset::*;
 pub mod update_reputation;
 pub use update_reputation::*;
 
-// pub mod record_citation;
-// pub use record_citation::*;
+pub mod record_citation;
+pub use record_citation::*;
+
+pub mod config_timelock;
+pub use config_timelock::*;
+
+pub mod set_download_fee;
+pub use set_download_fee::*;
+
+pub mod record_download;
+pub use record_download::*;
+
+pub mod finalize_reputation;
+pub use finalize_reputation::*;
+
+pub mod close_dataset;
+pub use close_dataset::*;
+
+pub mod merge_datasets;
+pub use merge_datasets::*;
+
+pub mod update_registry;
+pub use update_registry::*;
+
+pub mod archive_expired;
+pub use archive_expired::*;
+
+pub mod report_external_citation;
+pub use report_external_citation::*;
+
+pub mod update_data_uri;
+pub use update_data_uri::*;
+
+pub mod review_dataset;
+pub use review_dataset::*;
+
+pub mod export_dataset;
+pub use export_dataset::*;
+
+pub mod revoke_citation;
+pub use revoke_citation::*;
+
+pub mod request_access_nonce;
+pub use request_access_nonce::*;
+
+pub mod update_leaderboard;
+pub use update_leaderboard::*;
+
+pub mod view_reputations;
+pub use view_reputations::*;
+
+pub mod fork_dataset;
+pub use fork_dataset::*;
+
+pub mod freeze_dataset;
+pub use freeze_dataset::*;
+
+pub mod report_unavailable;
+pub use report_unavailable::*;
+
+pub mod delegate_reputation;
+pub use delegate_reputation::*;
+
+pub mod rate_dataset;
+pub use rate_dataset::*;
+
+pub mod set_download_cap;
+pub use set_download_cap::*;
+
+pub mod extend_pin;
+pub use extend_pin::*;
+
+pub mod set_fee_currency;
+pub use set_fee_currency::*;
+
+pub mod manage_editors;
+pub use manage_editors::*;
+
+pub mod migrate_data_uri;
+pub use migrate_data_uri::*;
+
+pub mod decay_reputation;
+pub use decay_reputation::*;
+
+pub mod submit_download_report;
+pub use submit_download_report::*;
+
+pub mod compare_datasets;
+pub use compare_datasets::*;
+
+pub mod verify_layout;
+pub use verify_layout::*;
+
+pub mod endorse_dataset;
+pub use endorse_dataset::*;
+
+pub mod recover_stuck_vault;
+pub use recover_stuck_vault::*;
+
+pub mod update_title;
+pub use update_title::*;
+
+pub mod revert_downloads;
+pub use revert_downloads::*;
+
+pub mod view_rating_summary;
+pub use view_rating_summary::*;
+
+pub mod rotate_contributor_key;
+pub use rotate_contributor_key::*;
+
+pub mod renew_license;
+pub use renew_license::*;
+
+pub mod audit_reputation;
+pub use audit_reputation::*;
+
+pub mod mark_superseded;
+pub use mark_superseded::*;
+
+pub mod request_dataset;
+pub use request_dataset::*;
+
+pub mod fulfill_request;
+pub use fulfill_request::*;
+
+pub mod seed_reputation;
+pub use seed_reputation::*;
+
+pub mod expand_metadata;
+pub use expand_metadata::*;
+
+pub mod bulk_tag;
+pub use bulk_tag::*;
+
+pub mod resolve_by_hash;
+pub use resolve_by_hash::*;
+
+pub mod set_data_dictionary;
+pub use set_data_dictionary::*;
+
+pub mod set_anonymous;
+pub use set_anonymous::*;
+
+pub mod manage_taxonomy;
+pub use manage_taxonomy::*;
+
+pub mod auto_approve_expired;
+pub use auto_approve_expired::*;
+
+pub mod withdraw_earnings;
+pub use withdraw_earnings::*;
+
+pub mod unfeature_expired;
+pub use unfeature_expired::*;
+
+pub mod request_access;
+pub use request_access::*;
+
+pub mod import_signed_manifest;
+pub use import_signed_manifest::*;
+
+pub mod manage_collection;
+pub use manage_collection::*;
+
+pub mod finalize_dataset;
+pub use finalize_dataset::*;
+
+pub mod get_contributor_overview;
+pub use get_contributor_overview::*;
+
+pub mod treasury_withdraw;
+pub use treasury_withdraw::*;
+
+pub mod add_replica;
+pub use add_replica::*;
@@ -0,0 +1,152 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{Dataset, Rating, Registry, Reputation};
+use crate::access::is_owner;
+use crate::constants::RATING_TIER_WEIGHTS;
+use crate::error::ErrorCode;
+use crate::events::{DatasetQualityHidden, QualityDisputed};
+use crate::seeds::{DATASET_SEED, REGISTRY_SEED, REPUTATION_SEED};
+use crate::time::current_time;
+
+#[derive(Accounts)]
+pub struct RateDataset<'info> {
+    #[account(mut)]
+    pub rater: Signer<'info>,
+
+    // Hidden datasets can still be rated, so revised ratings can bring them back
+    #[account(
+        mut,
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        constraint = dataset.contributor != rater.key() @ ErrorCode::SelfRating,
+        constraint = !dataset.frozen @ ErrorCode::DatasetFrozen,
+        constraint = !dataset.locked @ ErrorCode::DatasetLocked
+    )]
+    pub dataset: Account<'info, Dataset>,
+
+    #[account(
+        init_if_needed,
+        payer = rater,
+        space = 8 + Rating::SPACE,
+        seeds = [b"rating", dataset.key().as_ref(), rater.key().as_ref()],
+        bump
+    )]
+    pub rating: Account<'info, Rating>,
+
+    // Raters without a reputation account rate at the lowest weight
+    #[account(
+        seeds = [REPUTATION_SEED, rater.key().as_ref()],
+        bump = rater_reputation.bump,
+        constraint = rater_reputation.is_current() @ ErrorCode::ContributorKeyRotated
+    )]
+    pub rater_reputation: Option<Account<'info, Reputation>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetQualityFromRatings<'info> {
+    // Permissionless crank
+    pub caller: Signer<'info>,
+
+    #[account(
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        mut,
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        has_one = registry,
+        constraint = !dataset.locked @ ErrorCode::DatasetLocked
+    )]
+    pub dataset: Account<'info, Dataset>,
+}
+
+#[derive(Accounts)]
+pub struct ReactivateDataset<'info> {
+    pub contributor: Signer<'info>,
+
+    #[account(
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        mut,
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        constraint = is_owner(&dataset, &contributor.key()) @ ErrorCode::UnauthorizedUpdate,
+        has_one = registry,
+        constraint = !dataset.locked @ ErrorCode::DatasetLocked
+    )]
+    pub dataset: Account<'info, Dataset>,
+}
+
+impl<'info> RateDataset<'info> {
+    pub fn rate_dataset(&mut self, rating: u8, bumps: &RateDatasetBumps) -> Result<()> {
+        let weight = self.rater_reputation
+            .as_ref()
+            .map_or(RATING_TIER_WEIGHTS[0], |reputation| reputation.rating_weight());
+        let record = &mut self.rating;
+        let previous = if record.rater == Pubkey::default() {
+            record.dataset = self.dataset.key();
+            record.rater = self.rater.key();
+            record.bump = bumps.rating;
+            None
+        } else {
+            Some((record.rating, record.weight))
+        };
+
+        self.dataset.rate(previous, rating, weight)?;
+        record.rating = rating;
+        record.weight = weight;
+        record.rated_at = current_time()?;
+        Ok(())
+    }
+}
+
+impl<'info> SetQualityFromRatings<'info> {
+    pub fn set_quality_from_ratings(&mut self) -> Result<()> {
+        let now = current_time()?;
+        let floor = self.registry.min_quality_score;
+
+        // Checked before the community score overwrites the one being compared
+        let reported_quality = self.dataset.quality_score;
+        let disputed = self.dataset.flag_quality_discrepancy(self.registry.quality_dispute_delta)?;
+        let hidden = self.dataset.apply_rated_quality(floor)?;
+        self.dataset.touch(now)?;
+
+        if disputed {
+            emit!(QualityDisputed {
+                dataset_id: self.dataset.key(),
+                contributor: self.dataset.published_contributor(),
+                reported_quality,
+                community_quality: self.dataset.quality_score,
+                disputed_at: now,
+            });
+        }
+        if hidden {
+            emit!(DatasetQualityHidden {
+                dataset_id: self.dataset.key(),
+                contributor: self.dataset.published_contributor(),
+                quality_score: self.dataset.quality_score,
+                min_quality_score: floor,
+                hidden_at: now,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl<'info> ReactivateDataset<'info> {
+    pub fn reactivate_dataset(&mut self) -> Result<()> {
+        self.dataset.reactivate(self.registry.min_quality_score)?;
+        self.dataset.touch(current_time()?)?;
+        Ok(())
+    }
+}
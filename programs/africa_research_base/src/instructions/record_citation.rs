@@ -0,0 +1,111 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{Citation, CitationPair, Dataset, Registry, Reputation};
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::events::CitationRecorded;
+use crate::instructions::calculate_reputation_score;
+use crate::seeds::{DATASET_SEED, REGISTRY_SEED, REPUTATION_SEED};
+use crate::time::current_time;
+
+#[derive(Accounts)]
+pub struct RecordCitation<'info> {
+    #[account(mut)]
+    pub citer: Signer<'info>,
+
+    #[account(
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        mut,
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        has_one = registry,
+        constraint = dataset.contributor != citer.key() @ ErrorCode::SelfCitation,
+        constraint = !dataset.locked @ ErrorCode::DatasetLocked
+    )]
+    pub dataset: Account<'info, Dataset>,
+
+    #[account(
+        init,
+        payer = citer,
+        space = 8 + Citation::SPACE,
+        seeds = [b"citation", dataset.key().as_ref(), citer.key().as_ref()],
+        bump
+    )]
+    pub citation: Account<'info, Citation>,
+
+    #[account(
+        init_if_needed,
+        payer = citer,
+        space = 8 + CitationPair::SPACE,
+        seeds = [b"citation_pair", citer.key().as_ref(), dataset.contributor.as_ref()],
+        bump
+    )]
+    pub citation_pair: Account<'info, CitationPair>,
+
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, dataset.contributor.as_ref()],
+        bump = reputation.bump,
+        constraint = reputation.is_current() @ ErrorCode::ContributorKeyRotated
+    )]
+    pub reputation: Account<'info, Reputation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RecordCitation<'info> {
+    pub fn record_citation(
+        &mut self,
+        published_information: Vec<u8>,
+        bumps: &RecordCitationBumps
+    ) -> Result<()> {
+        require!(
+            published_information.len() <= MAX_PUBLISHED_INFORMATION_LEN,
+            ErrorCode::PublishedInformationTooLong
+        );
+        let now = current_time()?;
+
+        self.citation.set_inner(Citation {
+            dataset_id: self.dataset.key(),
+            citer: self.citer.key(),
+            contributor: self.dataset.contributor,
+            published_information,
+            citing_time: now,
+            bump: bumps.citation
+        });
+
+        if self.citation_pair.citer == Pubkey::default() {
+            self.citation_pair.citer = self.citer.key();
+            self.citation_pair.cited = self.dataset.contributor;
+            self.citation_pair.bump = bumps.citation_pair;
+        }
+
+        self.dataset.citation_count = self.dataset.citation_count
+            .checked_add(1)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+
+        // Past the per-pair cap a citation stays in the graph but no longer lifts reputation
+        if self.citation_pair.record(self.registry.citation_pair_cap)? {
+            let reputation = &mut self.reputation;
+            reputation.total_citations = reputation.total_citations
+                .checked_add(1)
+                .ok_or(ErrorCode::NumericalOverflow)?;
+            reputation.credit_fields(&self.dataset.research_fields, CITATION_WEIGHT);
+            calculate_reputation_score(reputation)?;
+        }
+
+        emit!(CitationRecorded {
+            dataset_id: self.dataset.key(),
+            citer: self.citer.key(),
+            contributor: self.dataset.published_contributor(),
+            citing_time: now,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,278 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::{Dataset, DownloadRecord, Earnings, FeeVault, Receipt, Registry, Reputation, Treasury, TrendingDatasets, TrendingEntry};
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::guard::{with_dataset_lock, GuardedDataset};
+use crate::events::{DatasetDownloaded, SpamDepositRefunded};
+use crate::instructions::calculate_reputation_score;
+use crate::profiling::profile;
+use crate::seeds::{DATASET_SEED, REGISTRY_SEED, REPUTATION_SEED};
+use crate::time::{current_slot, current_time};
+
+#[derive(Accounts)]
+#[instruction(slot: u64)]
+pub struct RecordDownload<'info> {
+    #[account(mut)]
+    pub downloader: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        mut,
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        has_one = registry,
+        constraint = !dataset.locked @ ErrorCode::DatasetLocked
+    )]
+    pub dataset: Account<'info, Dataset>,
+
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, dataset.contributor.as_ref()],
+        bump = reputation.bump,
+        constraint = reputation.is_current() @ ErrorCode::ContributorKeyRotated
+    )]
+    pub reputation: Account<'info, Reputation>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury", registry.key().as_ref()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", dataset.key().as_ref()],
+        bump = fee_vault.bump
+    )]
+    pub fee_vault: Option<Account<'info, FeeVault>>,
+
+    // Required when the dataset charges a fee
+    #[account(
+        mut,
+        seeds = [b"earnings", dataset.contributor.as_ref()],
+        bump = earnings.bump
+    )]
+    pub earnings: Option<Box<Account<'info, Earnings>>>,
+
+    #[account(
+        init_if_needed,
+        payer = downloader,
+        space = 8 + Receipt::SPACE,
+        seeds = [b"receipt", dataset.key().as_ref(), downloader.key().as_ref(), &slot.to_le_bytes()],
+        bump
+    )]
+    pub receipt: Option<Account<'info, Receipt>>,
+
+    // Required only when the dataset sets a download_cap
+    #[account(
+        init_if_needed,
+        payer = downloader,
+        space = 8 + DownloadRecord::SPACE,
+        seeds = [b"download_record", dataset.key().as_ref(), downloader.key().as_ref()],
+        bump
+    )]
+    pub download_record: Option<Account<'info, DownloadRecord>>,
+
+    // Created by the registry's first recorded download
+    #[account(
+        init_if_needed,
+        payer = downloader,
+        space = 8 + TrendingDatasets::SPACE,
+        seeds = [b"trending", registry.key().as_ref()],
+        bump
+    )]
+    pub trending: Box<Account<'info, TrendingDatasets>>,
+
+    /// CHECK: only receives the refunded spam deposit; required while the dataset still holds one
+    #[account(mut, address = dataset.contributor)]
+    pub contributor: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> GuardedDataset<'info> for RecordDownload<'info> {
+    fn guarded_dataset(&mut self) -> &mut Account<'info, Dataset> {
+        &mut self.dataset
+    }
+}
+
+impl<'info> RecordDownload<'info> {
+    fn pay(&self, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        profile!("record_download: transfer");
+        transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.downloader.to_account_info(),
+                    to,
+                },
+            ),
+            amount,
+        )?;
+        profile!("record_download: transferred");
+        Ok(())
+    }
+
+    fn enforce_download_cap(&mut self, now: i64, bumps: &RecordDownloadBumps) -> Result<()> {
+        let (cap, window) = (self.dataset.download_cap, self.dataset.download_window);
+        let record = self.download_record
+            .as_mut()
+            .ok_or(ErrorCode::DownloadRecordRequired)?;
+
+        if record.dataset == Pubkey::default() {
+            record.dataset = self.dataset.key();
+            record.downloader = self.downloader.key();
+            record.bump = bumps.download_record.unwrap_or_default();
+        }
+        record.record(now, cap, window)
+    }
+
+    fn collect_fee(&mut self, slot: u64, bumps: &RecordDownloadBumps) -> Result<()> {
+        let fee = self.dataset.download_fee;
+        let now = current_time()?;
+        let current = current_slot()?;
+        require!(
+            slot <= current && current - slot <= RECEIPT_SLOT_WINDOW,
+            ErrorCode::InvalidReceiptSlot
+        );
+        require!(
+            self.fee_vault.is_some() && self.receipt.is_some(),
+            ErrorCode::ReceiptRequired
+        );
+        require!(self.earnings.is_some(), ErrorCode::EarningsRequired);
+
+        // Fees are paid in lamports; vaults quoting another mint cannot collect yet
+        if let Some(fee_vault) = &self.fee_vault {
+            require_keys_eq!(fee_vault.mint, NATIVE_MINT, ErrorCode::UnsupportedFeeMint);
+        }
+
+        let (platform_share, contributor_share) = self.registry.split_fee(fee)?;
+        self.pay(self.treasury.to_account_info(), platform_share)?;
+        if let Some(fee_vault) = &self.fee_vault {
+            self.pay(fee_vault.to_account_info(), contributor_share)?;
+        }
+
+        self.treasury.total_collected = self.treasury.total_collected
+            .checked_add(platform_share)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        if let Some(fee_vault) = &mut self.fee_vault {
+            fee_vault.total_collected = fee_vault.total_collected
+                .checked_add(contributor_share)
+                .ok_or(ErrorCode::NumericalOverflow)?;
+        }
+        if let Some(earnings) = &mut self.earnings {
+            earnings.accrue(NATIVE_MINT, contributor_share)?;
+        }
+
+        let dataset = self.dataset.key();
+        let downloader = self.downloader.key();
+        if let Some(receipt) = &mut self.receipt {
+            if receipt.dataset == Pubkey::default() {
+                receipt.set_inner(Receipt {
+                    dataset,
+                    downloader,
+                    mint: NATIVE_MINT,
+                    amount_paid: 0,
+                    slot,
+                    paid_at: now,
+                    bump: bumps.receipt.unwrap_or_default()
+                });
+            }
+            receipt.record_payment(NATIVE_MINT, fee, now)?;
+        }
+        Ok(())
+    }
+
+    fn refund_spam_deposit(&mut self, now: i64) -> Result<()> {
+        if self.dataset.spam_deposit == 0 {
+            return Ok(());
+        }
+        let downloader = self.downloader.key();
+        let refund = self.dataset.release_spam_deposit(&downloader, now);
+        if refund == 0 {
+            return Ok(());
+        }
+        let contributor = self.contributor
+            .as_ref()
+            .ok_or(ErrorCode::DepositRefundRequired)?;
+        self.dataset.sub_lamports(refund)?;
+        contributor.add_lamports(refund)?;
+
+        emit!(SpamDepositRefunded {
+            dataset_id: self.dataset.key(),
+            downloader,
+            amount: refund,
+        });
+        Ok(())
+    }
+
+    // The dataset stays locked across the fee transfers
+    pub fn record_download(
+        &mut self,
+        slot: u64,
+        bumps: &RecordDownloadBumps
+    ) -> Result<()> {
+        with_dataset_lock(self, |accounts| accounts.download(slot, bumps))
+    }
+
+    fn download(
+        &mut self,
+        slot: u64,
+        bumps: &RecordDownloadBumps
+    ) -> Result<()> {
+        let now = current_time()?;
+        require!(!self.dataset.is_expired(now), ErrorCode::DatasetExpired);
+        require!(self.dataset.is_active, ErrorCode::DatasetInactive);
+        require!(!self.dataset.is_license_expired(now), ErrorCode::LicenseExpired);
+        self.dataset.ensure_downloadable(self.registry.curated)?;
+
+        if self.dataset.download_cap > 0 {
+            self.enforce_download_cap(now, bumps)?;
+        }
+        if self.dataset.download_fee > 0 {
+            self.collect_fee(slot, bumps)?;
+        }
+
+        self.refund_spam_deposit(now)?;
+        self.dataset.record_downloads(1)?;
+        let trending = &mut self.trending;
+        if trending.registry == Pubkey::default() {
+            trending.registry = self.registry.key();
+            trending.bump = bumps.trending;
+        }
+        trending.upsert(TrendingEntry::from_dataset(&self.dataset), now);
+        self.registry.total_downloads = self.registry.total_downloads
+            .checked_add(1)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+
+        let reputation = &mut self.reputation;
+        reputation.total_downloads = reputation.total_downloads
+            .checked_add(1)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        reputation.download_time = now;
+        reputation.credit_fields(&self.dataset.research_fields, DOWNLOAD_WEIGHT);
+        calculate_reputation_score(reputation)?;
+
+        emit!(DatasetDownloaded {
+            dataset_id: self.dataset.key(),
+            downloader: self.downloader.key(),
+            contributor: self.dataset.published_contributor(),
+            amount_paid: self.dataset.download_fee,
+            download_time: now,
+        });
+        Ok(())
+    }
+}
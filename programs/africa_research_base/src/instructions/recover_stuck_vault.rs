@@ -0,0 +1,85 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{Earnings, FeeVault, Registry, Treasury};
+use crate::access::{is_admin, is_closed};
+use crate::error::ErrorCode;
+use crate::events::VaultRecovered;
+use crate::seeds::REGISTRY_SEED;
+use crate::time::current_time;
+
+#[derive(Accounts)]
+pub struct RecoverStuckVault<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump,
+        constraint = is_admin(&registry, &admin.key()) @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury", registry.key().as_ref()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    // The seeds tie the vault to the dataset it was created for, and only that dataset's registry
+    // may recover it. The rent reserve left after refunding the contributor goes to the treasury
+    #[account(
+        mut,
+        close = treasury,
+        seeds = [b"vault", fee_vault.dataset.as_ref()],
+        bump = fee_vault.bump,
+        has_one = registry @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    /// CHECK: receives the vault's unwithdrawn income; pinned to the contributor the vault records
+    #[account(mut, address = fee_vault.contributor)]
+    pub contributor: UncheckedAccount<'info>,
+
+    // Absent for vaults that only ever held pin funding
+    #[account(
+        mut,
+        seeds = [b"earnings", fee_vault.contributor.as_ref()],
+        bump = earnings.bump
+    )]
+    pub earnings: Option<Account<'info, Earnings>>,
+
+    /// CHECK: only read to confirm the vault's dataset has been closed
+    #[account(
+        address = fee_vault.dataset,
+        constraint = is_closed(&dataset) @ ErrorCode::DatasetStillExists
+    )]
+    pub dataset: UncheckedAccount<'info>,
+}
+
+impl<'info> RecoverStuckVault<'info> {
+    pub fn recover_stuck_vault(&mut self) -> Result<()> {
+        let info = self.fee_vault.to_account_info();
+        let (refunded, swept) = FeeVault::recovery_split(info.lamports(), Rent::get()?.minimum_balance(info.data_len()));
+        if refunded > 0 {
+            self.fee_vault.sub_lamports(refunded)?;
+            self.contributor.add_lamports(refunded)?;
+            // Keep the earnings summary in step with what was actually paid out
+            if let Some(earnings) = &mut self.earnings {
+                let settled = refunded.min(earnings.available_lamports());
+                if settled > 0 {
+                    earnings.withdraw(settled, current_time()?)?;
+                }
+            }
+        }
+
+        emit!(VaultRecovered {
+            vault: self.fee_vault.key(),
+            dataset_id: self.dataset.key(),
+            treasury: self.treasury.key(),
+            contributor_refund: refunded,
+            lamports: swept,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::Dataset;
+use crate::access::is_owner;
+use crate::error::ErrorCode;
+use crate::events::LicenseRenewed;
+use crate::seeds::DATASET_SEED;
+use crate::time::current_time;
+
+#[derive(Accounts)]
+pub struct RenewLicense<'info> {
+    pub contributor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        constraint = is_owner(&dataset, &contributor.key()) @ ErrorCode::UnauthorizedUpdate,
+        constraint = !dataset.locked @ ErrorCode::DatasetLocked
+    )]
+    pub dataset: Account<'info, Dataset>,
+}
+
+impl<'info> RenewLicense<'info> {
+    // Extends the data-use license to `license_expires_at`, re-enabling downloads if it had lapsed
+    pub fn renew_license(&mut self, license_expires_at: i64) -> Result<()> {
+        let now = current_time()?;
+        self.dataset.renew_license(license_expires_at, now)?;
+        self.dataset.touch(now)?;
+
+        emit!(LicenseRenewed {
+            dataset_id: self.dataset.key(),
+            contributor: self.dataset.published_contributor(),
+            license_expires_at,
+        });
+        Ok(())
+    }
+}
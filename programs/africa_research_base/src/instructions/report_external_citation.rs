@@ -0,0 +1,93 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{Dataset, ExternalCitation, Registry, Reputation};
+use crate::access::is_oracle;
+use crate::constants::CITATION_WEIGHT;
+use crate::error::ErrorCode;
+use crate::events::ExternalCitationReported;
+use crate::instructions::calculate_reputation_score;
+use crate::seeds::{DATASET_SEED, REGISTRY_SEED, REPUTATION_SEED};
+use crate::time::current_time;
+
+#[derive(Accounts)]
+#[instruction(publication_hash: [u8; 32])]
+pub struct ReportExternalCitation<'info> {
+    #[account(mut)]
+    pub oracle: Signer<'info>,
+
+    #[account(
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump,
+        constraint = is_oracle(&registry, &oracle.key()) @ ErrorCode::UnauthorizedOracle
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        mut,
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        has_one = registry,
+        constraint = !dataset.locked @ ErrorCode::DatasetLocked
+    )]
+    pub dataset: Account<'info, Dataset>,
+
+    // One record per (dataset, publication), so a paper can't be credited twice
+    #[account(
+        init,
+        payer = oracle,
+        space = 8 + ExternalCitation::SPACE,
+        seeds = [b"external_citation", dataset.key().as_ref(), publication_hash.as_ref()],
+        bump
+    )]
+    pub external_citation: Account<'info, ExternalCitation>,
+
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, dataset.contributor.as_ref()],
+        bump = reputation.bump,
+        constraint = reputation.is_current() @ ErrorCode::ContributorKeyRotated
+    )]
+    pub reputation: Account<'info, Reputation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ReportExternalCitation<'info> {
+    pub fn report_external_citation(
+        &mut self,
+        publication_hash: [u8; 32],
+        bumps: &ReportExternalCitationBumps
+    ) -> Result<()> {
+        let now = current_time()?;
+
+        self.external_citation.set_inner(ExternalCitation {
+            dataset: self.dataset.key(),
+            contributor: self.dataset.contributor,
+            oracle: self.oracle.key(),
+            publication_hash,
+            reported_at: now,
+            bump: bumps.external_citation
+        });
+
+        self.dataset.citation_count = self.dataset.citation_count
+            .checked_add(1)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+
+        let reputation = &mut self.reputation;
+        reputation.total_citations = reputation.total_citations
+            .checked_add(1)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        reputation.credit_fields(&self.dataset.research_fields, CITATION_WEIGHT);
+        calculate_reputation_score(reputation)?;
+
+        emit!(ExternalCitationReported {
+            dataset_id: self.dataset.key(),
+            contributor: self.dataset.published_contributor(),
+            oracle: self.oracle.key(),
+            publication_hash,
+            reported_at: now,
+        });
+        Ok(())
+    }
+}
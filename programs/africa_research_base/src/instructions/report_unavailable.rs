@@ -0,0 +1,73 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{AvailabilityReport, Dataset, Registry};
+use crate::error::ErrorCode;
+use crate::events::DatasetUnavailable;
+use crate::seeds::{DATASET_SEED, REGISTRY_SEED};
+use crate::time::current_time;
+
+#[derive(Accounts)]
+pub struct ReportUnavailable<'info> {
+    // Permissionless; the registry's threshold of distinct reporters guards against a single griefer
+    #[account(mut)]
+    pub reporter: Signer<'info>,
+
+    #[account(
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        mut,
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        has_one = registry,
+        constraint = !dataset.locked @ ErrorCode::DatasetLocked
+    )]
+    pub dataset: Account<'info, Dataset>,
+
+    #[account(
+        init_if_needed,
+        payer = reporter,
+        space = 8 + AvailabilityReport::SPACE,
+        seeds = [b"availability", dataset.key().as_ref()],
+        bump
+    )]
+    pub availability_report: Account<'info, AvailabilityReport>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ReportUnavailable<'info> {
+    pub fn report_unavailable(&mut self, bumps: &ReportUnavailableBumps) -> Result<()> {
+        require!(self.dataset.is_active, ErrorCode::DatasetInactive);
+
+        let report = &mut self.availability_report;
+        if report.dataset == Pubkey::default() {
+            report.dataset = self.dataset.key();
+            report.bump = bumps.availability_report;
+        }
+
+        let threshold_reached = report.record(
+            self.reporter.key(),
+            &self.dataset.data_uri,
+            self.registry.unavailable_report_threshold
+        )?;
+
+        if threshold_reached {
+            let now = current_time()?;
+            self.dataset.mark_unavailable();
+            self.dataset.touch(now)?;
+
+            emit!(DatasetUnavailable {
+                dataset_id: self.dataset.key(),
+                contributor: self.dataset.published_contributor(),
+                report_count: self.availability_report.reporters.len() as u8,
+                flagged_at: now,
+            });
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,123 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{AccessRequest, Dataset, DatasetGrant};
+use crate::access::is_owner;
+use crate::constants::ACCESS_PENDING;
+use crate::error::ErrorCode;
+use crate::events::{AccessRequestAnswered, AccessRequested};
+use crate::seeds::DATASET_SEED;
+use crate::time::current_time;
+
+// One request per requester and dataset; a denial is final
+#[derive(Accounts)]
+pub struct RequestAccess<'info> {
+    #[account(mut)]
+    pub requester: Signer<'info>,
+
+    #[account(
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump
+    )]
+    pub dataset: Account<'info, Dataset>,
+
+    #[account(
+        init,
+        payer = requester,
+        space = 8 + AccessRequest::SPACE,
+        seeds = [b"access_req", dataset.key().as_ref(), requester.key().as_ref()],
+        bump
+    )]
+    pub access_request: Account<'info, AccessRequest>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RespondToAccessRequest<'info> {
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+
+    #[account(
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        constraint = is_owner(&dataset, &contributor.key()) @ ErrorCode::UnauthorizedUpdate
+    )]
+    pub dataset: Account<'info, Dataset>,
+
+    #[account(
+        mut,
+        seeds = [b"access_req", dataset.key().as_ref(), access_request.requester.as_ref()],
+        bump = access_request.bump,
+        has_one = dataset
+    )]
+    pub access_request: Account<'info, AccessRequest>,
+
+    // Required when approving and refused when denying: its mere existence reads as access
+    #[account(
+        init,
+        payer = contributor,
+        space = 8 + DatasetGrant::SPACE,
+        seeds = [b"grant", dataset.key().as_ref(), access_request.requester.as_ref()],
+        bump
+    )]
+    pub grant: Option<Account<'info, DatasetGrant>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RequestAccess<'info> {
+    pub fn request_access(&mut self, justification_hash: [u8; 32], bumps: &RequestAccessBumps) -> Result<()> {
+        let now = current_time()?;
+        self.access_request.set_inner(AccessRequest {
+            dataset: self.dataset.key(),
+            requester: self.requester.key(),
+            justification_hash,
+            status: ACCESS_PENDING,
+            requested_at: now,
+            responded_at: None,
+            bump: bumps.access_request,
+        });
+
+        emit!(AccessRequested {
+            dataset_id: self.dataset.key(),
+            requester: self.requester.key(),
+            justification_hash,
+            requested_at: now,
+        });
+        Ok(())
+    }
+}
+
+impl<'info> RespondToAccessRequest<'info> {
+    pub fn respond_to_access_request(&mut self, approved: bool, bumps: &RespondToAccessRequestBumps) -> Result<()> {
+        // A denial that passed the grant would still have it initialized, zeroed, by the constraint
+        require!(approved || self.grant.is_none(), ErrorCode::GrantNotAllowed);
+        let now = current_time()?;
+        self.access_request.respond(approved, now)?;
+
+        let grant = if approved {
+            let grant = self.grant.as_mut().ok_or(ErrorCode::GrantRequired)?;
+            grant.set_inner(DatasetGrant {
+                dataset: self.dataset.key(),
+                grantee: self.access_request.requester,
+                granted_by: self.contributor.key(),
+                access_request: self.access_request.key(),
+                granted_at: now,
+                bump: bumps.grant.unwrap_or_default(),
+            });
+            Some(grant.key())
+        } else {
+            None
+        };
+
+        emit!(AccessRequestAnswered {
+            dataset_id: self.dataset.key(),
+            requester: self.access_request.requester,
+            approved,
+            grant,
+            responded_at: now,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,108 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::{AccessNonce, Dataset, Receipt};
+use crate::access::is_owner;
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::events::{AccessNonceConsumed, AccessNonceIssued};
+use crate::seeds::DATASET_SEED;
+use crate::time::{current_slot, current_time};
+
+#[derive(Accounts)]
+pub struct RequestAccessNonce<'info> {
+    // Only a paying downloader, identified by their receipt, is granted access
+    #[account(mut)]
+    pub grantee: Signer<'info>,
+
+    #[account(
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump
+    )]
+    pub dataset: Account<'info, Dataset>,
+
+    #[account(
+        seeds = [b"receipt", dataset.key().as_ref(), grantee.key().as_ref(), &receipt.slot.to_le_bytes()],
+        bump = receipt.bump,
+        constraint = receipt.downloader == grantee.key() @ ErrorCode::ReceiptRequired
+    )]
+    pub receipt: Account<'info, Receipt>,
+
+    #[account(
+        init_if_needed,
+        payer = grantee,
+        space = 8 + AccessNonce::SPACE,
+        seeds = [b"access_nonce", receipt.key().as_ref()],
+        bump
+    )]
+    pub access_nonce: Account<'info, AccessNonce>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ConsumeNonce<'info> {
+    // The contributor's serving infrastructure redeems the nonce when it hands out the file
+    pub contributor: Signer<'info>,
+
+    #[account(
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        constraint = is_owner(&dataset, &contributor.key()) @ ErrorCode::UnauthorizedUpdate
+    )]
+    pub dataset: Account<'info, Dataset>,
+
+    #[account(
+        mut,
+        seeds = [b"access_nonce", access_nonce.receipt.as_ref()],
+        bump = access_nonce.bump,
+        constraint = access_nonce.dataset == dataset.key() @ ErrorCode::NonceMismatch
+    )]
+    pub access_nonce: Account<'info, AccessNonce>,
+}
+
+impl<'info> RequestAccessNonce<'info> {
+    pub fn request_access_nonce(&mut self, bumps: &RequestAccessNonceBumps) -> Result<()> {
+        let now = current_time()?;
+        let receipt = self.receipt.key();
+
+        if self.access_nonce.receipt == Pubkey::default() {
+            self.access_nonce.receipt = receipt;
+            self.access_nonce.dataset = self.dataset.key();
+            self.access_nonce.grantee = self.grantee.key();
+            self.access_nonce.bump = bumps.access_nonce;
+        }
+
+        // Unpredictable enough for a single-use token: bound to the receipt and the current slot
+        let nonce = hashv(&[
+            receipt.as_ref(),
+            &current_slot()?.to_le_bytes(),
+            &self.access_nonce.nonce,
+        ])
+        .to_bytes();
+        self.access_nonce.issue(nonce, now, ACCESS_NONCE_TTL)?;
+
+        emit!(AccessNonceIssued {
+            dataset_id: self.dataset.key(),
+            grantee: self.grantee.key(),
+            nonce,
+            expires_at: self.access_nonce.expires_at,
+        });
+        Ok(())
+    }
+}
+
+impl<'info> ConsumeNonce<'info> {
+    pub fn consume_nonce(&mut self, nonce: [u8; 32]) -> Result<()> {
+        let now = current_time()?;
+        self.access_nonce.consume(&nonce, now)?;
+
+        emit!(AccessNonceConsumed {
+            dataset_id: self.dataset.key(),
+            grantee: self.access_nonce.grantee,
+            consumed_at: now,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,81 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::{DataRequest, Registry};
+use crate::events::DataRequested;
+use crate::seeds::REGISTRY_SEED;
+use crate::time::current_time;
+
+#[derive(Accounts)]
+#[instruction(topic_hash: [u8; 32])]
+pub struct RequestDataset<'info> {
+    #[account(mut)]
+    pub requester: Signer<'info>,
+
+    #[account(
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        init,
+        payer = requester,
+        space = 8 + DataRequest::SPACE,
+        seeds = [b"request", requester.key().as_ref(), topic_hash.as_ref()],
+        bump
+    )]
+    pub data_request: Account<'info, DataRequest>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RequestDataset<'info> {
+    // Posts the request and escrows `bounty` lamports in it; 0 for an unpaid request
+    pub fn request_dataset(
+        &mut self,
+        topic_hash: [u8; 32],
+        topic: Vec<u8>,
+        description_hash: [u8; 32],
+        bounty: u64,
+        bumps: &RequestDatasetBumps
+    ) -> Result<()> {
+        DataRequest::validate_topic(&topic, &topic_hash)?;
+
+        if bounty > 0 {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.requester.to_account_info(),
+                        to: self.data_request.to_account_info(),
+                    },
+                ),
+                bounty,
+            )?;
+        }
+
+        self.data_request.set_inner(DataRequest {
+            requester: self.requester.key(),
+            registry: self.registry.key(),
+            topic_hash,
+            topic,
+            description_hash,
+            bounty,
+            created_at: current_time()?,
+            fulfilled_by: None,
+            fulfilled_at: None,
+            bump: bumps.data_request
+        });
+
+        emit!(DataRequested {
+            request: self.data_request.key(),
+            requester: self.requester.key(),
+            registry: self.registry.key(),
+            topic_hash,
+            bounty,
+        });
+        Ok(())
+    }
+}
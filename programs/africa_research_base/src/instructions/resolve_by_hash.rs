@@ -0,0 +1,124 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+use crate::HashRegistry;
+use crate::access::is_closed;
+use crate::constants::HASH_RESOLUTION_VERSION;
+use crate::error::ErrorCode;
+
+// Return data of `resolve_by_hash`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HashResolution {
+    pub version: u8, // HASH_RESOLUTION_VERSION
+    pub found: bool,
+    pub dataset: Pubkey, // Default keys when not found
+    pub contributor: Pubkey,
+}
+
+impl HashResolution {
+    pub fn new(entry: Option<&HashRegistry>) -> Self {
+        HashResolution {
+            version: HASH_RESOLUTION_VERSION,
+            found: entry.is_some(),
+            dataset: entry.map(|entry| entry.dataset).unwrap_or_default(),
+            contributor: entry.map(|entry| entry.contributor).unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(content_hash: [u8; 32])]
+pub struct ResolveByHash<'info> {
+    /// CHECK: the hash's registry PDA, which does not exist for content that was never uploaded
+    #[account(seeds = [b"content_hash", content_hash.as_ref()], bump)]
+    pub hash_registry: UncheckedAccount<'info>,
+
+    /// CHECK: the dataset the entry names, required while the hash is claimed; only its liveness is read
+    pub dataset: Option<UncheckedAccount<'info>>,
+}
+
+impl<'info> ResolveByHash<'info> {
+    // Read-only; callers fetch the resolution from the transaction's return data
+    pub fn resolve_by_hash(&self, content_hash: [u8; 32]) -> Result<()> {
+        let info = self.hash_registry.to_account_info();
+        let entry = if is_closed(&info) {
+            None
+        } else {
+            Some(HashRegistry::try_deserialize(&mut &info.try_borrow_data()?[..])?)
+        };
+        // The seeds already bind the account to the hash; this guards the stored copy
+        if let Some(entry) = &entry {
+            require!(entry.content_hash == content_hash, ErrorCode::HashMismatch);
+        }
+        let resolution = resolve(entry.as_ref(), self.dataset.as_ref().map(|dataset| dataset.as_ref()))?;
+        set_return_data(&resolution.try_to_vec()?);
+        Ok(())
+    }
+}
+
+// An unclaimed entry, or one left naming a dataset closed before close_dataset released hashes, is
+// not found
+pub(crate) fn resolve(entry: Option<&HashRegistry>, dataset: Option<&AccountInfo>) -> Result<HashResolution> {
+    let Some(entry) = entry.filter(|entry| entry.is_claimed()) else {
+        return Ok(HashResolution::new(None));
+    };
+    let dataset = dataset.ok_or(ErrorCode::HashDatasetRequired)?;
+    require_keys_eq!(*dataset.key, entry.dataset, ErrorCode::HashDatasetRequired);
+    Ok(HashResolution::new((!is_closed(dataset)).then_some(entry)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolution_reports_the_canonical_record_or_not_found() {
+        let entry = HashRegistry {
+            content_hash: [7; 32],
+            dataset: Pubkey::new_unique(),
+            contributor: Pubkey::new_unique(),
+            registered_at: 1_000,
+            bump: 254,
+        };
+
+        let found = HashResolution::new(Some(&entry));
+        assert!(found.found);
+        assert_eq!((found.dataset, found.contributor), (entry.dataset, entry.contributor));
+        assert_eq!(HashResolution::try_from_slice(&found.try_to_vec().unwrap()).unwrap(), found);
+
+        let missing = HashResolution::new(None);
+        assert_eq!(missing.version, HASH_RESOLUTION_VERSION);
+        assert!(!missing.found);
+        assert_eq!((missing.dataset, missing.contributor), (Pubkey::default(), Pubkey::default()));
+    }
+
+    #[test]
+    fn a_closed_or_released_dataset_resolves_as_not_found() {
+        let entry = HashRegistry {
+            content_hash: [7; 32],
+            dataset: Pubkey::new_unique(),
+            contributor: Pubkey::new_unique(),
+            registered_at: 1_000,
+            bump: 254,
+        };
+        let (mut lamports, mut data) = (1_000_000, vec![1u8; 16]);
+        let open = AccountInfo::new(&entry.dataset, false, false, &mut lamports, &mut data, &crate::ID, false, 0);
+        assert!(resolve(Some(&entry), Some(&open)).unwrap().found);
+
+        let system = Pubkey::default();
+        let (mut lamports, mut data) = (0, Vec::new());
+        let closed = AccountInfo::new(&entry.dataset, false, false, &mut lamports, &mut data, &system, false, 0);
+        assert_eq!(resolve(Some(&entry), Some(&closed)).unwrap(), HashResolution::new(None));
+
+        // The liveness check can't be skipped or pointed at another account
+        assert_eq!(resolve(Some(&entry), None).unwrap_err(), ErrorCode::HashDatasetRequired.into());
+        let other = Pubkey::new_unique();
+        let (mut lamports, mut data) = (1_000_000, vec![1u8; 16]);
+        let impostor = AccountInfo::new(&other, false, false, &mut lamports, &mut data, &crate::ID, false, 0);
+        assert_eq!(resolve(Some(&entry), Some(&impostor)).unwrap_err(), ErrorCode::HashDatasetRequired.into());
+
+        let released = HashRegistry { dataset: Pubkey::default(), contributor: Pubkey::default(), ..entry };
+        assert!(!resolve(Some(&released), None).unwrap().found);
+    }
+}
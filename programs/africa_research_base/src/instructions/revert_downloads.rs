@@ -0,0 +1,102 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{Dataset, DownloadReversal, Registry, Reputation};
+use crate::access::is_admin;
+use crate::error::ErrorCode;
+use crate::events::{DownloadsReverted, ReputationUpdated};
+use crate::instructions::calculate_reputation_score;
+use crate::seeds::{DATASET_SEED, REGISTRY_SEED, REPUTATION_SEED};
+use crate::time::current_time;
+
+#[derive(Accounts)]
+#[instruction(reversal_id: u64)]
+pub struct RevertDownloads<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump,
+        constraint = is_admin(&registry, &admin.key()) @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        mut,
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        has_one = registry,
+        constraint = !dataset.locked @ ErrorCode::DatasetLocked
+    )]
+    pub dataset: Account<'info, Dataset>,
+
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, dataset.contributor.as_ref()],
+        bump = reputation.bump,
+        constraint = reputation.is_current() @ ErrorCode::ContributorKeyRotated
+    )]
+    pub reputation: Account<'info, Reputation>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + DownloadReversal::SPACE,
+        seeds = [b"download_reversal", dataset.key().as_ref(), &reversal_id.to_le_bytes()],
+        bump
+    )]
+    pub reversal: Account<'info, DownloadReversal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RevertDownloads<'info> {
+    pub fn revert_downloads(
+        &mut self,
+        reversal_id: u64,
+        count: u32,
+        reason_hash: [u8; 32],
+        bumps: &RevertDownloadsBumps
+    ) -> Result<()> {
+        require!(count > 0, ErrorCode::InvalidReversalCount);
+        let now = current_time()?;
+
+        // The registry and reputation totals lose exactly what the dataset did
+        let removed = self.dataset.revert_downloads(count);
+        self.registry.total_downloads = self.registry.total_downloads.saturating_sub(removed as u64);
+        self.reputation.revert_downloads(removed);
+        calculate_reputation_score(&mut self.reputation)?;
+
+        self.reversal.set_inner(DownloadReversal {
+            dataset: self.dataset.key(),
+            contributor: self.dataset.contributor,
+            admin: self.admin.key(),
+            reversal_id,
+            requested: count,
+            removed,
+            reason_hash,
+            reverted_at: now,
+            bump: bumps.reversal
+        });
+
+        emit!(DownloadsReverted {
+            dataset_id: self.dataset.key(),
+            contributor: self.dataset.published_contributor(),
+            admin: self.admin.key(),
+            reversal_id,
+            removed,
+            download_count: self.dataset.download_count,
+            new_reputation_score: self.reputation.reputation_score,
+        });
+
+        emit!(ReputationUpdated {
+            contributor: self.dataset.published_contributor(),
+            action: "revert_downloads".to_string(),
+            new_dataset_count: self.reputation.dataset_count,
+            new_reputation_score: self.reputation.reputation_score,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{Dataset, Registry};
+use crate::access::is_admin;
+use crate::error::ErrorCode;
+use crate::events::{DatasetReviewed, ReviewerAssigned};
+use crate::seeds::{DATASET_SEED, REGISTRY_SEED};
+use crate::time::current_time;
+
+#[derive(Accounts)]
+pub struct AssignReviewer<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump,
+        constraint = is_admin(&registry, &admin.key()) @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        mut,
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        has_one = registry,
+        constraint = !dataset.locked @ ErrorCode::DatasetLocked
+    )]
+    pub dataset: Account<'info, Dataset>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitReview<'info> {
+    pub reviewer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        constraint = dataset.reviewer == reviewer.key() @ ErrorCode::UnauthorizedReviewer,
+        constraint = !dataset.locked @ ErrorCode::DatasetLocked
+    )]
+    pub dataset: Account<'info, Dataset>,
+}
+
+impl<'info> AssignReviewer<'info> {
+    pub fn assign_reviewer(&mut self, reviewer: Pubkey) -> Result<()> {
+        self.dataset.assign_reviewer(reviewer)?;
+
+        emit!(ReviewerAssigned {
+            dataset_id: self.dataset.key(),
+            reviewer,
+        });
+        Ok(())
+    }
+}
+
+impl<'info> SubmitReview<'info> {
+    pub fn submit_review(&mut self, approved: bool, note_hash: [u8; 32]) -> Result<()> {
+        let now = current_time()?;
+        self.dataset.submit_review(&self.reviewer.key(), approved, note_hash)?;
+        self.dataset.touch(now)?;
+
+        emit!(DatasetReviewed {
+            dataset_id: self.dataset.key(),
+            reviewer: self.reviewer.key(),
+            approved,
+            note_hash,
+            reviewed_at: now,
+        });
+        Ok(())
+    }
+}
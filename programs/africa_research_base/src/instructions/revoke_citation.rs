@@ -0,0 +1,83 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{Citation, CitationPair, Dataset, Registry, Reputation};
+use crate::access::is_admin;
+use crate::constants::CITATION_WEIGHT;
+use crate::error::ErrorCode;
+use crate::events::CitationRevoked;
+use crate::instructions::calculate_reputation_score;
+use crate::seeds::{DATASET_SEED, REGISTRY_SEED, REPUTATION_SEED};
+use crate::time::current_time;
+
+#[derive(Accounts)]
+pub struct RevokeCitation<'info> {
+    // The original citer, or the registry admin
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        mut,
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        has_one = registry,
+        constraint = !dataset.locked @ ErrorCode::DatasetLocked
+    )]
+    pub dataset: Account<'info, Dataset>,
+
+    #[account(
+        mut,
+        close = citer,
+        seeds = [b"citation", dataset.key().as_ref(), citation.citer.as_ref()],
+        bump = citation.bump,
+        constraint = citation.citer == authority.key()
+            || is_admin(&registry, &authority.key()) @ ErrorCode::UnauthorizedRevocation
+    )]
+    pub citation: Account<'info, Citation>,
+
+    #[account(
+        mut,
+        seeds = [b"citation_pair", citation.citer.as_ref(), dataset.contributor.as_ref()],
+        bump = citation_pair.bump
+    )]
+    pub citation_pair: Account<'info, CitationPair>,
+
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, dataset.contributor.as_ref()],
+        bump = reputation.bump,
+        constraint = reputation.is_current() @ ErrorCode::ContributorKeyRotated
+    )]
+    pub reputation: Account<'info, Reputation>,
+
+    /// CHECK: rent refund destination, must be the original citer
+    #[account(mut, address = citation.citer)]
+    pub citer: UncheckedAccount<'info>,
+}
+
+impl<'info> RevokeCitation<'info> {
+    pub fn revoke_citation(&mut self) -> Result<()> {
+        self.dataset.citation_count = self.dataset.citation_count.saturating_sub(1);
+
+        if self.citation_pair.revoke(self.registry.citation_pair_cap)? {
+            // Saturating: changing the pair cap after citations were recorded can skew the counted total
+            let reputation = &mut self.reputation;
+            reputation.total_citations = reputation.total_citations.saturating_sub(1);
+            reputation.debit_fields(&self.dataset.research_fields, CITATION_WEIGHT);
+            calculate_reputation_score(reputation)?;
+        }
+
+        emit!(CitationRevoked {
+            dataset_id: self.dataset.key(),
+            citer: self.citation.citer,
+            revoked_by: self.authority.key(),
+            revoked_at: current_time()?,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,144 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{Dataset, Earnings, FeeVault, Reputation};
+use crate::access::require_program_owned;
+use crate::anonymity::published_key;
+use crate::error::ErrorCode;
+use crate::events::{ContributorKeyRotated, DatasetsRepointed};
+use crate::seeds::REPUTATION_SEED;
+use crate::time::current_time;
+
+// Both keys sign: the old one to authorise the move, the new one to prove it is controlled by the
+// same contributor. The old reputation stays behind as a tombstone pointing at the new key.
+#[derive(Accounts)]
+pub struct RotateContributorKey<'info> {
+    #[account(mut)]
+    pub old_contributor: Signer<'info>,
+
+    #[account(mut)]
+    pub new_contributor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, old_contributor.key().as_ref()],
+        bump = old_reputation.bump
+    )]
+    pub old_reputation: Account<'info, Reputation>,
+
+    #[account(
+        init,
+        payer = new_contributor,
+        space = 8 + Reputation::SPACE,
+        seeds = [REPUTATION_SEED, new_contributor.key().as_ref()],
+        bump
+    )]
+    pub new_reputation: Account<'info, Reputation>,
+
+    // Moved along with the reputation when the old key has one; fees accrue under the new key afterwards
+    #[account(
+        mut,
+        close = old_contributor,
+        seeds = [b"earnings", old_contributor.key().as_ref()],
+        bump = old_earnings.bump
+    )]
+    pub old_earnings: Option<Account<'info, Earnings>>,
+
+    #[account(
+        init,
+        payer = new_contributor,
+        space = 8 + Earnings::SPACE,
+        seeds = [b"earnings", new_contributor.key().as_ref()],
+        bump
+    )]
+    pub new_earnings: Option<Account<'info, Earnings>>,
+
+    pub system_program: Program<'info, System>,
+    // remaining_accounts: the old key's datasets and their fee vaults, re-pointed to the new key in the
+    // same transaction
+}
+
+impl<'info> RotateContributorKey<'info> {
+    // Datasets and vaults not passed here keep answering to the old key until repoint_datasets moves
+    // them, so a contributor rotating away from a compromised key should pass all of them
+    pub fn rotate_contributor_key(
+        &mut self,
+        datasets: &'info [AccountInfo<'info>],
+        program_id: &Pubkey,
+        bumps: &RotateContributorKeyBumps
+    ) -> Result<()> {
+        let rotated = self.old_reputation.rotate(self.new_contributor.key(), bumps.new_reputation)?;
+        self.new_reputation.set_inner(rotated);
+
+        match (&self.old_earnings, &mut self.new_earnings) {
+            (Some(old), Some(new)) => {
+                let bump = bumps.new_earnings.ok_or(ErrorCode::InvalidRepoint)?;
+                new.set_inner(Earnings { contributor: self.new_contributor.key(), bump, ..(**old).clone() });
+            }
+            (None, None) => {}
+            _ => return err!(ErrorCode::InvalidRepoint),
+        }
+
+        let repointed = repoint_datasets(&self.old_reputation, datasets, program_id)?;
+
+        emit!(ContributorKeyRotated {
+            old_contributor: published_key(&self.old_contributor.key(), self.new_reputation.anonymous),
+            new_contributor: self.new_reputation.published_key(),
+            dataset_count: self.new_reputation.dataset_count,
+            datasets_repointed: repointed,
+            reputation_score: self.new_reputation.reputation_score,
+            rotated_at: current_time()?,
+        });
+        Ok(())
+    }
+}
+
+// Permissionless follow-up for datasets a rotation didn't carry: it only completes a move that both
+// keys already signed
+#[derive(Accounts)]
+pub struct RepointDatasets<'info> {
+    #[account(
+        seeds = [REPUTATION_SEED, rotated_reputation.contributor.as_ref()],
+        bump = rotated_reputation.bump,
+        constraint = !rotated_reputation.is_current() @ ErrorCode::InvalidRepoint
+    )]
+    pub rotated_reputation: Account<'info, Reputation>,
+    // remaining_accounts: datasets and fee vaults whose contributor is still rotated_reputation.contributor
+}
+
+impl<'info> RepointDatasets<'info> {
+    pub fn repoint_datasets(&self, datasets: &'info [AccountInfo<'info>], program_id: &Pubkey) -> Result<()> {
+        let repointed = repoint_datasets(&self.rotated_reputation, datasets, program_id)?;
+
+        let rotated = &self.rotated_reputation;
+        emit!(DatasetsRepointed {
+            old_contributor: rotated.published_key(),
+            new_contributor: published_key(&rotated.rotated_to, rotated.anonymous),
+            count: repointed,
+        });
+        Ok(())
+    }
+}
+
+// Each account is a dataset or a fee vault, told apart by discriminator; only datasets are counted
+pub(crate) fn repoint_datasets<'info>(
+    rotated: &Reputation,
+    accounts: &'info [AccountInfo<'info>],
+    program_id: &Pubkey
+) -> Result<u32> {
+    let mut repointed = 0;
+    for info in accounts {
+        require_program_owned(info)?;
+        if info.try_borrow_data()?.get(..8) == Some(FeeVault::DISCRIMINATOR) {
+            let mut vault = Account::<FeeVault>::try_from(info)?;
+            vault.repoint_contributor(rotated)?;
+            vault.exit(program_id)?;
+        } else {
+            let mut dataset = Account::<Dataset>::try_from(info)?;
+            dataset.repoint_contributor(rotated)?;
+            dataset.exit(program_id)?;
+            repointed += 1;
+        }
+    }
+    Ok(repointed)
+}
@@ -0,0 +1,66 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::Reputation;
+use crate::error::ErrorCode;
+use crate::events::ReputationSeeded;
+use crate::instructions::calculate_reputation_score;
+use crate::program::AfricaResearchBase;
+use crate::seeds::REPUTATION_SEED;
+
+// Reputation is shared by every registry and registries are permissionless, so seeding is reserved
+// for the program's upgrade authority rather than any registry admin
+#[derive(Accounts)]
+pub struct SeedReputation<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, AfricaResearchBase>,
+
+    #[account(constraint = program_data.upgrade_authority_address == Some(authority.key()) @ ErrorCode::UnauthorizedAdmin)]
+    pub program_data: Account<'info, ProgramData>,
+
+    /// CHECK: only the key is used; the contributor being migrated need not be online
+    pub contributor: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + Reputation::SPACE,
+        seeds = [REPUTATION_SEED, contributor.key().as_ref()],
+        bump,
+        constraint = reputation.is_current() @ ErrorCode::ContributorKeyRotated
+    )]
+    pub reputation: Account<'info, Reputation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SeedReputation<'info> {
+    // One call per contributor; an account the contributor already initialized may be seeded until it
+    // records any activity
+    pub fn seed_reputation(
+        &mut self,
+        total_uploads: u32,
+        total_quality_score: u64,
+        total_downloads: u64,
+        total_citations: u32,
+        bumps: &SeedReputationBumps
+    ) -> Result<()> {
+        let reputation = &mut self.reputation;
+        if reputation.contributor == Pubkey::default() {
+            reputation.set_inner(Reputation::new(self.contributor.key(), bumps.reputation));
+        }
+        reputation.seed(total_uploads, total_quality_score, total_downloads, total_citations)?;
+        calculate_reputation_score(reputation)?;
+
+        emit!(ReputationSeeded {
+            contributor: self.reputation.published_key(),
+            authority: self.authority.key(),
+            total_uploads,
+            reputation_score: self.reputation.reputation_score,
+        });
+        Ok(())
+    }
+}
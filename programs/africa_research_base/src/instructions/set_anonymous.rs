@@ -0,0 +1,29 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::Reputation;
+use crate::error::ErrorCode;
+use crate::seeds::REPUTATION_SEED;
+
+#[derive(Accounts)]
+pub struct SetAnonymous<'info> {
+    pub contributor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, contributor.key().as_ref()],
+        bump = reputation.bump,
+        constraint = reputation.is_current() @ ErrorCode::ContributorKeyRotated
+    )]
+    pub reputation: Account<'info, Reputation>,
+}
+
+impl<'info> SetAnonymous<'info> {
+    // Covers datasets created from now on; each dataset keeps the setting it was created with.
+    // Authorization and reputation still use the real key, which also stays visible as the
+    // transaction signer and in account data; only events and return data carry the pseudonym.
+    pub fn set_anonymous(&mut self, anonymous: bool) -> Result<()> {
+        self.reputation.anonymous = anonymous;
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{ColumnDef, DataDictionary, Dataset};
+use crate::access::is_owner;
+use crate::error::ErrorCode;
+use crate::events::DataDictionarySet;
+use crate::seeds::DATASET_SEED;
+use crate::time::current_time;
+
+#[derive(Accounts)]
+pub struct SetDataDictionary<'info> {
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+
+    #[account(
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        constraint = is_owner(&dataset, &contributor.key()) @ ErrorCode::UnauthorizedUpdate,
+        constraint = !dataset.finalized @ ErrorCode::DatasetFinalized
+    )]
+    pub dataset: Account<'info, Dataset>,
+
+    // Created on first use; later calls replace the columns
+    #[account(
+        init_if_needed,
+        payer = contributor,
+        space = 8 + DataDictionary::SPACE,
+        seeds = [b"dict", dataset.key().as_ref()],
+        bump
+    )]
+    pub dictionary: Account<'info, DataDictionary>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetDataDictionary<'info> {
+    pub fn set_data_dictionary(&mut self, columns: Vec<ColumnDef>, bumps: &SetDataDictionaryBumps) -> Result<()> {
+        self.dictionary.set(&self.dataset, columns, current_time()?, bumps.dictionary)?;
+
+        emit!(DataDictionarySet {
+            dataset_id: self.dataset.key(),
+            dictionary: self.dictionary.key(),
+            column_count: self.dictionary.columns.len() as u8,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::Dataset;
+use crate::access::is_owner;
+use crate::error::ErrorCode;
+use crate::seeds::DATASET_SEED;
+use crate::time::current_time;
+
+#[derive(Accounts)]
+pub struct SetDownloadCap<'info> {
+    pub contributor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        constraint = is_owner(&dataset, &contributor.key()) @ ErrorCode::UnauthorizedUpdate,
+        constraint = !dataset.locked @ ErrorCode::DatasetLocked
+    )]
+    pub dataset: Account<'info, Dataset>,
+}
+
+impl<'info> SetDownloadCap<'info> {
+    // Limits each downloader to `download_cap` downloads per `download_window` seconds; 0 lifts the cap
+    pub fn set_download_cap(&mut self, download_cap: u32, download_window: i64) -> Result<()> {
+        self.dataset.set_download_cap(download_cap, download_window)?;
+        self.dataset.touch(current_time()?)?;
+        Ok(())
+    }
+}
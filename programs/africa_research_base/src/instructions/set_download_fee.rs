@@ -0,0 +1,64 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{Dataset, Earnings, FeeVault};
+use crate::access::is_owner;
+use crate::error::ErrorCode;
+use crate::seeds::DATASET_SEED;
+use crate::time::current_time;
+
+#[derive(Accounts)]
+pub struct SetDownloadFee<'info> {
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        constraint = is_owner(&dataset, &contributor.key()) @ ErrorCode::UnauthorizedUpdate,
+        constraint = !dataset.locked @ ErrorCode::DatasetLocked
+    )]
+    pub dataset: Account<'info, Dataset>,
+
+    #[account(
+        init_if_needed,
+        payer = contributor,
+        space = 8 + FeeVault::SPACE,
+        seeds = [b"vault", dataset.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    // Shared by all of the contributor's datasets; created by their first priced dataset
+    #[account(
+        init_if_needed,
+        payer = contributor,
+        space = 8 + Earnings::SPACE,
+        seeds = [b"earnings", contributor.key().as_ref()],
+        bump
+    )]
+    pub earnings: Account<'info, Earnings>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetDownloadFee<'info> {
+    pub fn set_download_fee(
+        &mut self,
+        download_fee: u64,
+        bumps: &SetDownloadFeeBumps
+    ) -> Result<()> {
+        if self.fee_vault.dataset == Pubkey::default() {
+            self.fee_vault.set_inner(FeeVault::new(self.dataset.key(), self.contributor.key(), self.dataset.registry, bumps.fee_vault));
+        }
+        if self.earnings.contributor == Pubkey::default() {
+            self.earnings.contributor = self.contributor.key();
+            self.earnings.bump = bumps.earnings;
+        }
+
+        self.dataset.download_fee = download_fee;
+        self.dataset.touch(current_time()?)?;
+        Ok(())
+    }
+}
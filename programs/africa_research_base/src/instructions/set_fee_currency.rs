@@ -0,0 +1,40 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{Dataset, FeeVault};
+use crate::access::is_owner;
+use crate::error::ErrorCode;
+use crate::seeds::DATASET_SEED;
+use crate::time::current_time;
+
+#[derive(Accounts)]
+pub struct SetFeeCurrency<'info> {
+    pub contributor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        constraint = is_owner(&dataset, &contributor.key()) @ ErrorCode::UnauthorizedUpdate,
+        constraint = !dataset.locked @ ErrorCode::DatasetLocked
+    )]
+    pub dataset: Account<'info, Dataset>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", dataset.key().as_ref()],
+        bump = fee_vault.bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    /// CHECK: owner and layout are validated by FeeVault::set_currency
+    pub fee_mint: UncheckedAccount<'info>,
+}
+
+impl<'info> SetFeeCurrency<'info> {
+    pub fn set_fee_currency(&mut self, fee_symbol: [u8; 8]) -> Result<()> {
+        self.fee_vault.set_currency(&self.fee_mint.to_account_info(), fee_symbol)?;
+        self.dataset.touch(current_time()?)?;
+        Ok(())
+    }
+}
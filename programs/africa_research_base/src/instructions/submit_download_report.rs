@@ -0,0 +1,164 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{Dataset, DownloadClaim, DownloadReport, Registry, Reputation};
+use crate::access::{is_download_oracle, is_owner};
+use crate::constants::DOWNLOAD_WEIGHT;
+use crate::error::ErrorCode;
+use crate::events::{DownloadDeltaClaimed, DownloadReportSubmitted};
+use crate::instructions::calculate_reputation_score;
+use crate::profiling::profile;
+use crate::seeds::{DATASET_SEED, REGISTRY_SEED, REPUTATION_SEED};
+use crate::time::current_time;
+
+#[derive(Accounts)]
+#[instruction(report_id: u64)]
+pub struct SubmitDownloadReport<'info> {
+    #[account(mut)]
+    pub oracle: Signer<'info>,
+
+    #[account(
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump,
+        constraint = is_download_oracle(&registry, &oracle.key()) @ ErrorCode::UnauthorizedOracle
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        init,
+        payer = oracle,
+        space = 8 + DownloadReport::SPACE,
+        seeds = [b"download_report", registry.key().as_ref(), &report_id.to_le_bytes()],
+        bump
+    )]
+    pub download_report: Account<'info, DownloadReport>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimDownloadDelta<'info> {
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        mut,
+        seeds = [b"download_report", registry.key().as_ref(), &download_report.report_id.to_le_bytes()],
+        bump = download_report.bump,
+        has_one = registry
+    )]
+    pub download_report: Account<'info, DownloadReport>,
+
+    #[account(
+        mut,
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        constraint = is_owner(&dataset, &contributor.key()) @ ErrorCode::UnauthorizedUpdate,
+        has_one = registry,
+        constraint = !dataset.locked @ ErrorCode::DatasetLocked
+    )]
+    pub dataset: Account<'info, Dataset>,
+
+    // One claim per (report, dataset), so a delta can't be credited twice
+    #[account(
+        init,
+        payer = contributor,
+        space = 8 + DownloadClaim::SPACE,
+        seeds = [b"download_claim", download_report.key().as_ref(), dataset.key().as_ref()],
+        bump
+    )]
+    pub download_claim: Account<'info, DownloadClaim>,
+
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, contributor.key().as_ref()],
+        bump = reputation.bump,
+        constraint = reputation.is_current() @ ErrorCode::ContributorKeyRotated
+    )]
+    pub reputation: Account<'info, Reputation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SubmitDownloadReport<'info> {
+    pub fn submit_download_report(
+        &mut self,
+        report_id: u64,
+        root: [u8; 32],
+        bumps: &SubmitDownloadReportBumps
+    ) -> Result<()> {
+        let now = current_time()?;
+        self.download_report.set_inner(DownloadReport {
+            registry: self.registry.key(),
+            oracle: self.oracle.key(),
+            report_id,
+            root,
+            claimed_downloads: 0,
+            submitted_at: now,
+            bump: bumps.download_report
+        });
+
+        emit!(DownloadReportSubmitted {
+            registry: self.registry.key(),
+            report: self.download_report.key(),
+            report_id,
+            root,
+            submitted_at: now,
+        });
+        Ok(())
+    }
+}
+
+impl<'info> ClaimDownloadDelta<'info> {
+    pub fn claim_download_delta(
+        &mut self,
+        delta: u32,
+        proof: Vec<[u8; 32]>,
+        bumps: &ClaimDownloadDeltaBumps
+    ) -> Result<()> {
+        profile!("claim_download_delta: verify proof");
+        self.download_report.verify(&self.dataset.key(), delta, &proof)?;
+        profile!("claim_download_delta: proof verified");
+        let now = current_time()?;
+
+        self.download_claim.set_inner(DownloadClaim {
+            report: self.download_report.key(),
+            dataset: self.dataset.key(),
+            delta,
+            claimed_at: now,
+            bump: bumps.download_claim
+        });
+
+        self.dataset.record_downloads(delta)?;
+        self.download_report.claimed_downloads = self.download_report.claimed_downloads
+            .checked_add(delta as u64)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        self.registry.total_downloads = self.registry.total_downloads
+            .checked_add(delta as u64)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+
+        let reputation = &mut self.reputation;
+        reputation.total_downloads = reputation.total_downloads
+            .checked_add(delta as u64)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        reputation.download_time = now;
+        reputation.credit_fields(&self.dataset.research_fields, DOWNLOAD_WEIGHT.saturating_mul(delta));
+        calculate_reputation_score(reputation)?;
+
+        emit!(DownloadDeltaClaimed {
+            report: self.download_report.key(),
+            dataset_id: self.dataset.key(),
+            contributor: self.dataset.published_contributor(),
+            delta,
+            download_count: self.dataset.download_count,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{Registry, Treasury};
+use crate::access::is_admin;
+use crate::error::ErrorCode;
+use crate::events::TreasuryWithdrawn;
+use crate::seeds::REGISTRY_SEED;
+use crate::time::current_time;
+
+// Immediate, but bounded by treasury_withdrawal_cap, which only a timelocked config change can raise.
+// A compromised admin key can take at most one period's cap before the change window is noticed
+#[derive(Accounts)]
+pub struct TreasuryWithdraw<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump,
+        constraint = is_admin(&registry, &admin.key()) @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury", registry.key().as_ref()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// CHECK: any account may receive the lamports
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+}
+
+impl<'info> TreasuryWithdraw<'info> {
+    // The treasury always keeps its rent-exempt minimum
+    pub fn treasury_withdraw(&mut self, lamports: u64) -> Result<()> {
+        let info = self.treasury.to_account_info();
+        let withdrawable = info.lamports().saturating_sub(Rent::get()?.minimum_balance(info.data_len()));
+        require!(lamports <= withdrawable, ErrorCode::InsufficientTreasuryBalance);
+
+        let now = current_time()?;
+        self.treasury.record_withdrawal(lamports, self.registry.treasury_withdrawal_cap, now)?;
+        self.treasury.sub_lamports(lamports)?;
+        self.destination.add_lamports(lamports)?;
+
+        emit!(TreasuryWithdrawn {
+            registry: self.registry.key(),
+            destination: self.destination.key(),
+            lamports,
+            period_withdrawn: self.treasury.period_withdrawn,
+            withdrawn_at: now,
+        });
+        Ok(())
+    }
+}
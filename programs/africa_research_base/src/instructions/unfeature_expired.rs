@@ -0,0 +1,45 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{Dataset, Registry};
+use crate::error::ErrorCode;
+use crate::events::DatasetUnfeatured;
+use crate::seeds::{DATASET_SEED, REGISTRY_SEED};
+use crate::time::current_time;
+
+// Permissionless: anyone may end a featured promotion once its window has closed
+#[derive(Accounts)]
+pub struct UnfeatureExpired<'info> {
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        mut,
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        has_one = registry,
+        constraint = !dataset.locked @ ErrorCode::DatasetLocked
+    )]
+    pub dataset: Account<'info, Dataset>,
+}
+
+impl<'info> UnfeatureExpired<'info> {
+    pub fn unfeature_expired(&mut self) -> Result<()> {
+        let now = current_time()?;
+        self.dataset.unfeature_expired(now)?;
+        self.dataset.touch(now)?;
+        self.registry.release_featured();
+
+        emit!(DatasetUnfeatured {
+            dataset_id: self.dataset.key(),
+            registry: self.registry.key(),
+            featured_count: self.registry.featured_count,
+            unfeatured_at: now,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{Dataset, Registry, RegistryConfig};
+use crate::access::{is_editor, is_owner};
+use crate::error::ErrorCode;
+use crate::seeds::{DATASET_SEED, REGISTRY_SEED};
+use crate::time::current_time;
+
+#[derive(Accounts)]
+pub struct UpdateDataUri<'info> {
+    // The contributor or one of the dataset's editors
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        seeds = [b"registry_config", registry.key().as_ref()],
+        bump = registry_config.bump
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    #[account(
+        mut,
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        constraint = is_editor(&dataset, &authority.key()) @ ErrorCode::UnauthorizedUpdate,
+        has_one = registry,
+        constraint = !dataset.locked @ ErrorCode::DatasetLocked,
+        constraint = !dataset.finalized @ ErrorCode::DatasetFinalized
+    )]
+    pub dataset: Account<'info, Dataset>,
+}
+
+#[derive(Accounts)]
+pub struct LockDataUri<'info> {
+    pub contributor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        constraint = is_owner(&dataset, &contributor.key()) @ ErrorCode::UnauthorizedUpdate,
+        constraint = !dataset.locked @ ErrorCode::DatasetLocked,
+        constraint = !dataset.finalized @ ErrorCode::DatasetFinalized
+    )]
+    pub dataset: Account<'info, Dataset>,
+}
+
+impl<'info> UpdateDataUri<'info> {
+    // Moves a dataset to new storage, e.g. after re-pinning; the content hash is unchanged
+    pub fn update_data_uri(&mut self, data_uri: Vec<u8>) -> Result<()> {
+        self.registry_config.ensure_host_allowed(&data_uri)?;
+
+        self.dataset.set_data_uri(&data_uri)?;
+        self.dataset.clear_unavailable();
+        self.dataset.touch(current_time()?)?;
+        Ok(())
+    }
+}
+
+impl<'info> LockDataUri<'info> {
+    // Pins the storage location for citability
+    pub fn lock_data_uri(&mut self) -> Result<()> {
+        self.dataset.lock_data_uri()?;
+        self.dataset.touch(current_time()?)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{Leaderboard, LeaderboardEntry, Registry, Reputation};
+use crate::error::ErrorCode;
+use crate::seeds::{REGISTRY_SEED, REPUTATION_SEED};
+
+#[derive(Accounts)]
+pub struct UpdateLeaderboard<'info> {
+    // Permissionless: anyone may push a contributor's current score onto the board
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + Leaderboard::SPACE,
+        seeds = [b"leaderboard", registry.key().as_ref()],
+        bump
+    )]
+    pub leaderboard: Account<'info, Leaderboard>,
+
+    #[account(
+        seeds = [REPUTATION_SEED, reputation.contributor.as_ref()],
+        bump = reputation.bump,
+        constraint = reputation.is_current() @ ErrorCode::ContributorKeyRotated
+    )]
+    pub reputation: Account<'info, Reputation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> UpdateLeaderboard<'info> {
+    pub fn update_leaderboard(&mut self, bumps: &UpdateLeaderboardBumps) -> Result<()> {
+        if self.leaderboard.registry == Pubkey::default() {
+            self.leaderboard.registry = self.registry.key();
+            self.leaderboard.bump = bumps.leaderboard;
+        }
+
+        self.leaderboard.upsert(LeaderboardEntry::from_reputation(&self.reputation));
+        Ok(())
+    }
+}
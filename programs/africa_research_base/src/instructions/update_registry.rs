@@ -0,0 +1,151 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::access::is_admin;
+use crate::{Registry, RegistryConfig};
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::seeds::REGISTRY_SEED;
+
+// Non-sensitive registry settings the admin may change directly, without the config timelock
+#[derive(Accounts)]
+pub struct UpdateRegistry<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump,
+        constraint = is_admin(&registry, &admin.key()) @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub registry: Account<'info, Registry>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateRegistryConfig<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump,
+        constraint = is_admin(&registry, &admin.key()) @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        mut,
+        seeds = [b"registry_config", registry.key().as_ref()],
+        bump = registry_config.bump
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+}
+
+impl<'info> UpdateRegistry<'info> {
+    pub fn set_min_file_size(&mut self, min_file_size: u64) -> Result<()> {
+        require!(min_file_size <= MAX_FILE_SIZE, ErrorCode::FileTooLarge);
+        self.registry.min_file_size = min_file_size;
+        Ok(())
+    }
+
+    pub fn set_citation_pair_cap(&mut self, citation_pair_cap: u8) -> Result<()> {
+        self.registry.citation_pair_cap = citation_pair_cap;
+        Ok(())
+    }
+
+    pub fn set_citation_oracle(&mut self, citation_oracle: Pubkey) -> Result<()> {
+        self.registry.citation_oracle = citation_oracle;
+        Ok(())
+    }
+
+    pub fn set_download_oracle(&mut self, download_oracle: Pubkey) -> Result<()> {
+        self.registry.download_oracle = download_oracle;
+        Ok(())
+    }
+
+    pub fn set_institution_oracle(&mut self, institution_oracle: Pubkey) -> Result<()> {
+        self.registry.institution_oracle = institution_oracle;
+        Ok(())
+    }
+
+    pub fn set_curated(&mut self, curated: bool) -> Result<()> {
+        self.registry.curated = curated;
+        Ok(())
+    }
+
+    pub fn set_freeze_penalty(&mut self, freeze_penalty: u32) -> Result<()> {
+        self.registry.freeze_penalty = freeze_penalty;
+        Ok(())
+    }
+
+    pub fn set_unavailable_report_threshold(&mut self, threshold: u8) -> Result<()> {
+        require!(
+            threshold > 0 && threshold as usize <= MAX_UNAVAILABLE_REPORTERS,
+            ErrorCode::InvalidReportThreshold
+        );
+        self.registry.unavailable_report_threshold = threshold;
+        Ok(())
+    }
+
+    pub fn set_quality_scale(&mut self, quality_scale: u8) -> Result<()> {
+        self.registry.set_quality_scale(quality_scale)
+    }
+
+    pub fn set_min_rating_count(&mut self, min_rating_count: u32) -> Result<()> {
+        self.registry.min_rating_count = min_rating_count;
+        Ok(())
+    }
+
+    // Applies to datasets created from now on; existing ai_metadata is left as stored
+    pub fn set_structured_ai_metadata(&mut self, structured_ai_metadata: bool) -> Result<()> {
+        self.registry.structured_ai_metadata = structured_ai_metadata;
+        Ok(())
+    }
+
+    pub fn set_quality_dispute_delta(&mut self, quality_dispute_delta: u8) -> Result<()> {
+        require!(quality_dispute_delta <= 100, ErrorCode::InvalidQualityDisputeDelta);
+        self.registry.quality_dispute_delta = quality_dispute_delta;
+        Ok(())
+    }
+
+    // Applies to datasets created from now on; bonds already posted keep their amount
+    pub fn set_quality_bond(&mut self, quality_bond: u64) -> Result<()> {
+        require!(quality_bond <= MAX_QUALITY_BOND, ErrorCode::InvalidQualityBond);
+        self.registry.quality_bond = quality_bond;
+        Ok(())
+    }
+
+    // Applies to datasets created from now on; deposits already held keep their amount
+    pub fn set_spam_deposit(&mut self, spam_deposit: u64) -> Result<()> {
+        require!(spam_deposit <= MAX_SPAM_DEPOSIT, ErrorCode::InvalidSpamDeposit);
+        self.registry.spam_deposit = spam_deposit;
+        Ok(())
+    }
+
+    // 0 disables auto_approve_expired, including for datasets whose deadline already passed
+    pub fn set_review_sla(&mut self, review_sla: i64) -> Result<()> {
+        require!(review_sla >= 0, ErrorCode::InvalidReviewSla);
+        self.registry.review_sla = review_sla;
+        Ok(())
+    }
+
+    pub fn set_featured_threshold(&mut self, featured_reputation_threshold: u32) -> Result<()> {
+        self.registry.featured_reputation_threshold = featured_reputation_threshold;
+        Ok(())
+    }
+}
+
+impl<'info> UpdateRegistryConfig<'info> {
+    pub fn allow_host(&mut self, host_hash: [u8; 32]) -> Result<()> {
+        self.registry_config.allow_host(host_hash)
+    }
+
+    pub fn disallow_host(&mut self, host_hash: [u8; 32]) -> Result<()> {
+        self.registry_config.disallow_host(host_hash);
+        Ok(())
+    }
+
+    // Applies to upload credit finalized from now on; credit already granted is not rescaled
+    pub fn set_field_weight(&mut self, field: u8, weight: u8) -> Result<()> {
+        self.registry_config.set_field_weight(field, weight)
+    }
+}
@@ -1,28 +1,15 @@
 #![allow(unexpected_cfgs)]
 use anchor_lang::prelude::*;
 
-use crate::{Dataset, Reputation};
+use crate::{Dataset, Registry, Reputation};
+use crate::constants::{MAX_REPUTATION_SCORE, REPUTATION_SCORE_KNEE};
+use crate::instructions::ReputationBreakdown;
 use crate::error::ErrorCode;
-use crate::constants::*;
+use crate::seeds::{DATASET_SEED, REGISTRY_SEED, REPUTATION_SEED};
+use crate::time::current_time;
 
-
-#[derive(Accounts)]
-pub struct UpdateReputationOnUpload<'info> {
-    #[account(mut)]
-    pub admin: Signer<'info>,
-
-    #[account(mut)]
-    pub contributor: Signer<'info>,
-
-    #[account(
-        mut,
-        seeds = [b"reputation", contributor.key().as_ref()],
-        bump = reputation.bump
-    )]
-    pub reputation: Account<'info, Reputation>,
-
-    pub system_program: Program<'info, System>,
-}
+// Upload credit has no instruction of its own: it is granted only by finalize_reputation, once the
+// dataset has survived the registry's grace period
 
 #[derive(Accounts)]
 pub struct UpdateReputationOnDownload<'info> {
@@ -34,19 +21,28 @@ pub struct UpdateReputationOnDownload<'info> {
 
     #[account(
         mut,
-        seeds = [b"reputation", contributor.key().as_ref()],
-        bump = reputation.bump
+        seeds = [REPUTATION_SEED, contributor.key().as_ref()],
+        bump = reputation.bump,
+        constraint = reputation.is_current() @ ErrorCode::ContributorKeyRotated
     )]
     pub reputation: Account<'info, Reputation>,
 
     #[account(
         mut,
-        seeds = [b"dataset", contributor.key().as_ref(), &dataset.dataset_index.to_le_bytes()],
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
         bump,
-        constraint = dataset.contributor == contributor.key()
+        constraint = dataset.contributor == contributor.key(),
+        has_one = registry,
+        constraint = !dataset.locked @ ErrorCode::DatasetLocked
     )]
     pub dataset: Account<'info, Dataset>,
 
+    #[account(
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -60,76 +56,56 @@ pub struct UpdateReputationOnCitation<'info> {
 
     #[account(
         mut,
-        seeds = [b"reputation", contributor.key().as_ref()],
-        bump = reputation.bump
+        seeds = [REPUTATION_SEED, contributor.key().as_ref()],
+        bump = reputation.bump,
+        constraint = reputation.is_current() @ ErrorCode::ContributorKeyRotated
     )]
     pub reputation: Account<'info, Reputation>,
 
     #[account(
         mut,
-        seeds = [b"dataset", contributor.key().as_ref(), &dataset.dataset_index.to_le_bytes()],
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
         bump,
-        constraint = dataset.contributor == contributor.key()
+        constraint = dataset.contributor == contributor.key(),
+        has_one = registry,
+        constraint = !dataset.locked @ ErrorCode::DatasetLocked
     )]
     pub dataset: Account<'info, Dataset>,
 
+    #[account(
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
     pub system_program: Program<'info, System>,
 }
 
-// Standalone helper for reputation score calculation
-fn calculate_reputation_score(reputation: &mut Reputation) -> Result<()> {
-    let upload_score = (reputation.total_uploads as u32)
-        .checked_mul(UPLOAD_WEIGHT)
-        .ok_or(ErrorCode::NumericalOverflow)?;
-
-    let quality_score = if reputation.total_uploads > 0 {
-        ((reputation.total_quality_score as u32)
-            .checked_mul(QUALITY_MULTIPLIER)
-            .ok_or(ErrorCode::NumericalOverflow)?)
-            .checked_div(reputation.total_uploads as u32)
-            .unwrap_or(0)
-    } else {
-        0
-    };
-
-    let download_score = (reputation.total_downloads as u32)
-        .checked_mul(DOWNLOAD_WEIGHT)
-        .ok_or(ErrorCode::NumericalOverflow)?;
-
-    let citation_score = reputation.total_citations
-        .checked_mul(CITATION_WEIGHT)
-        .ok_or(ErrorCode::NumericalOverflow)?;
-
-    reputation.reputation_score = upload_score
-        .checked_add(quality_score)
-        .ok_or(ErrorCode::NumericalOverflow)?
-        .checked_add(download_score)
-        .ok_or(ErrorCode::NumericalOverflow)?
-        .checked_add(citation_score)
-        .ok_or(ErrorCode::NumericalOverflow)?;
+// Standalone helper for reputation score calculation; the formula itself lives in ReputationBreakdown
+pub(crate) fn calculate_reputation_score(reputation: &mut Reputation) -> Result<()> {
+    reputation.reputation_score = ReputationBreakdown::new(reputation)?.score();
     Ok(())
 }
 
-impl<'info> UpdateReputationOnUpload<'info> {
-    
-    pub fn update_reputation_upload(
-        &mut self,
-        quality_score: u8,
-    ) -> Result<()> {
-        let reputation = &mut self.reputation;
-        reputation.total_uploads = reputation.total_uploads.checked_add(1).ok_or(ErrorCode::NumericalOverflow)?;
-        reputation.total_quality_score = reputation.total_quality_score.checked_add(quality_score as u64).ok_or(ErrorCode::NumericalOverflow)?;
-        calculate_reputation_score(reputation)?;
-        Ok(())
+// Maps a raw score onto 0..MAX_REPUTATION_SCORE against the fixed headroom above the knee: an excess
+// of `x` becomes headroom * x / (x + headroom), so a raw score one headroom past the knee lands
+// halfway to the cap. Non-decreasing, so contributors keep their relative order.
+pub(crate) fn normalize_reputation_score(raw: u32) -> u32 {
+    if raw <= REPUTATION_SCORE_KNEE {
+        return raw;
     }
+    let excess = (raw - REPUTATION_SCORE_KNEE) as u64;
+    let headroom = (MAX_REPUTATION_SCORE - REPUTATION_SCORE_KNEE) as u64;
+    // excess * headroom / (excess + headroom) < headroom, so the sum stays below the cap
+    REPUTATION_SCORE_KNEE + (excess * headroom / (excess + headroom)) as u32
 }
 
 impl<'info> UpdateReputationOnDownload<'info> {
     pub fn update_reputation_download(&mut self) -> Result<()> {
         let reputation = &mut self.reputation;
         reputation.total_downloads = reputation.total_downloads.checked_add(1).ok_or(ErrorCode::NumericalOverflow)?;
-        let clock = Clock::get()?;
-        reputation.download_time = clock.unix_timestamp;
+        let now = current_time()?;
+        reputation.download_time = now;
         calculate_reputation_score(reputation)?;
         Ok(())
     }
@@ -140,6 +116,37 @@ impl<'info> UpdateReputationOnCitation<'info> {
         let reputation = &mut self.reputation;
         reputation.total_citations = reputation.total_citations.checked_add(1).ok_or(ErrorCode::NumericalOverflow)?;
         calculate_reputation_score(reputation)?;
+        self.dataset.citation_count = self.dataset.citation_count.checked_add(1).ok_or(ErrorCode::NumericalOverflow)?;
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::MAX_SEEDED_CITATIONS;
+
+    #[test]
+    fn scores_never_exceed_the_cap() {
+        for raw in [0, 1, REPUTATION_SCORE_KNEE, REPUTATION_SCORE_KNEE + 1, MAX_REPUTATION_SCORE, 1_000_000, u32::MAX] {
+            assert!(normalize_reputation_score(raw) <= MAX_REPUTATION_SCORE);
+        }
+        assert_eq!(normalize_reputation_score(REPUTATION_SCORE_KNEE), REPUTATION_SCORE_KNEE);
+        assert_eq!(normalize_reputation_score(MAX_REPUTATION_SCORE), 7_500);
+
+        let mut reputation = Reputation { total_citations: MAX_SEEDED_CITATIONS, ..Reputation::sample() };
+        calculate_reputation_score(&mut reputation).unwrap();
+        assert!(reputation.reputation_score > REPUTATION_SCORE_KNEE && reputation.reputation_score < MAX_REPUTATION_SCORE);
+    }
+
+    #[test]
+    fn normalization_preserves_relative_order() {
+        let raws = [0, 99, 500, 2_000, REPUTATION_SCORE_KNEE, 6_000, 20_000, 150_000, 2_000_000, u32::MAX];
+        let normalized: Vec<u32> = raws.iter().map(|&raw| normalize_reputation_score(raw)).collect();
+
+        assert!(normalized.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!((0..1_000_000u32).step_by(997).all(|raw| normalize_reputation_score(raw) <= normalize_reputation_score(raw + 1)));
+        // Scores below the knee, and so every tier threshold, are left as they were
+        assert_eq!(&normalized[..5], &raws[..5]);
+    }
+}
@@ -0,0 +1,70 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{Dataset, Registry, TokenIndex};
+use crate::access::is_editor;
+use crate::error::ErrorCode;
+use crate::instructions::{index_title, unindex_title};
+use crate::seeds::{DATASET_SEED, REGISTRY_SEED};
+use crate::time::current_time;
+
+#[derive(Accounts)]
+pub struct UpdateTitle<'info> {
+    // The contributor or one of the dataset's editors; pays for index accounts of new words
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        mut,
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        constraint = is_editor(&dataset, &authority.key()) @ ErrorCode::UnauthorizedUpdate,
+        has_one = registry,
+        constraint = !dataset.locked @ ErrorCode::DatasetLocked,
+        constraint = !dataset.finalized @ ErrorCode::DatasetFinalized
+    )]
+    pub dataset: Account<'info, Dataset>,
+
+    pub system_program: Program<'info, System>,
+    // remaining_accounts: the token index PDAs of the current title, then those of the new title
+}
+
+impl<'info> UpdateTitle<'info> {
+    // Renames the dataset for display and search; file_name is left as it is
+    pub fn update_title(
+        &mut self,
+        mut title: Vec<u8>,
+        token_indexes: &'info [AccountInfo<'info>],
+        program_id: &Pubkey
+    ) -> Result<()> {
+        Dataset::trim_file_name(&mut title);
+        Dataset::validate_title(&title)?;
+
+        let current = TokenIndex::tokenize(&self.dataset.title).len();
+        require!(token_indexes.len() >= current, ErrorCode::InvalidTokenIndexAccounts);
+        let (current_indexes, new_indexes) = token_indexes.split_at(current);
+
+        let registry = self.registry.key();
+        let dataset = self.dataset.key();
+        unindex_title(&registry, &dataset, &self.dataset.title, current_indexes, program_id)?;
+        index_title(
+            &registry,
+            &dataset,
+            &title,
+            new_indexes,
+            &self.authority.to_account_info(),
+            &self.system_program.to_account_info(),
+            program_id,
+        )?;
+
+        self.dataset.title = title;
+        self.dataset.touch(current_time()?)?;
+        Ok(())
+    }
+}
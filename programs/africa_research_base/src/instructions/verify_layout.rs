@@ -0,0 +1,139 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+use crate::{Dataset, Registry, Reputation};
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::seeds::{create_address, dataset_seeds, registry_seeds, reputation_seeds};
+
+#[derive(Accounts)]
+pub struct VerifyLayout<'info> {
+    /// CHECK: any program account; its type is picked by discriminator in check_layout
+    #[account(owner = crate::ID)]
+    pub account: UncheckedAccount<'info>,
+}
+
+impl<'info> VerifyLayout<'info> {
+    // Read-only self-check to run against known accounts right after an upgrade
+    pub fn verify_layout(&self, program_id: &Pubkey) -> Result<()> {
+        check_layout(&self.account.key(), &self.account.try_borrow_data()?, program_id)
+    }
+}
+
+fn decode<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    T::try_deserialize(&mut &data[..]).map_err(|_| error!(ErrorCode::LayoutMismatch))
+}
+
+// The canary is the account's own PDA: it is re-derived from the seeds and bump stored in the
+// data, which a shifted layout decodes as garbage that no longer maps back to the address
+pub(crate) fn check_layout(key: &Pubkey, data: &[u8], program_id: &Pubkey) -> Result<()> {
+    let discriminator = data.get(..8).ok_or(ErrorCode::LayoutMismatch)?;
+
+    let derived = if discriminator == Registry::DISCRIMINATOR {
+        let registry: Registry = decode(data)?;
+        create_address(&registry_seeds(&registry.creator, &registry.name), registry.bump, program_id)
+    } else if discriminator == Dataset::DISCRIMINATOR {
+        // Earlier schemas were allocated smaller unless expand_ai_metadata grew them; those still fail
+        // the schema_version check
+        require!(data.len() >= 8 + Dataset::SPACE, ErrorCode::LayoutMismatch);
+        let dataset: Dataset = decode(data)?;
+        require!(dataset.schema_version == DATASET_SCHEMA_VERSION, ErrorCode::LayoutMismatch);
+        create_address(&dataset_seeds(&dataset.creator, dataset.dataset_index), dataset.bump, program_id)
+    } else if discriminator == Reputation::DISCRIMINATOR {
+        let reputation: Reputation = decode(data)?;
+        create_address(&reputation_seeds(&reputation.contributor), reputation.bump, program_id)
+    } else {
+        return err!(ErrorCode::LayoutMismatch);
+    };
+
+    require!(derived.ok() == Some(*key), ErrorCode::LayoutMismatch);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode<T: AccountSerialize>(account: &T) -> Vec<u8> {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    fn registry_account() -> (Pubkey, Registry) {
+        let mut registry = Registry::sample();
+        let (key, bump) = Pubkey::find_program_address(
+            &[b"registry", registry.creator.as_ref(), registry.name.as_ref()],
+            &crate::ID,
+        );
+        registry.bump = bump;
+        (key, registry)
+    }
+
+    #[test]
+    fn initialized_accounts_pass_the_layout_check() {
+        let (key, registry) = registry_account();
+        check_layout(&key, &encode(&registry), &crate::ID).unwrap();
+
+        let mut dataset = Dataset { dataset_index: 3, ..Dataset::sample() };
+        let (key, bump) = Pubkey::find_program_address(
+            &[b"dataset", dataset.creator.as_ref(), &3u32.to_le_bytes()],
+            &crate::ID,
+        );
+        dataset.bump = bump;
+        // Datasets are allocated at their full SPACE
+        let mut data = encode(&dataset);
+        data.resize(8 + Dataset::SPACE, 0);
+        check_layout(&key, &data, &crate::ID).unwrap();
+
+        let contributor = Pubkey::new_unique();
+        let (key, bump) = Pubkey::find_program_address(&[b"reputation", contributor.as_ref()], &crate::ID);
+        check_layout(&key, &encode(&Reputation::new(contributor, bump)), &crate::ID).unwrap();
+    }
+
+    #[test]
+    fn shifted_or_foreign_data_is_a_layout_mismatch() {
+        let (key, registry) = registry_account();
+        let data = encode(&registry);
+
+        // A field inserted ahead of the seeds shifts every later byte
+        let mut shifted = data.clone();
+        shifted.insert(8, 0);
+        assert_eq!(check_layout(&key, &shifted, &crate::ID).unwrap_err(), ErrorCode::LayoutMismatch.into());
+
+        assert_eq!(
+            check_layout(&Pubkey::new_unique(), &data, &crate::ID).unwrap_err(),
+            ErrorCode::LayoutMismatch.into()
+        );
+        assert_eq!(check_layout(&key, &[0u8; 4], &crate::ID).unwrap_err(), ErrorCode::LayoutMismatch.into());
+        assert_eq!(check_layout(&key, &[9u8; 64], &crate::ID).unwrap_err(), ErrorCode::LayoutMismatch.into());
+    }
+
+    #[test]
+    fn datasets_in_an_earlier_schema_are_a_layout_mismatch() {
+        for legacy in [
+            &include_bytes!("../../fixtures/dataset_schema_1.bin")[..],
+            &include_bytes!("../../fixtures/dataset_schema_2.bin")[..],
+            &include_bytes!("../../fixtures/dataset_schema_3.bin")[..],
+        ] {
+            let migrated = Dataset::from_legacy(legacy).unwrap();
+            let (key, _) = Pubkey::find_program_address(
+                &[b"dataset", migrated.creator.as_ref(), &migrated.dataset_index.to_le_bytes()],
+                &crate::ID,
+            );
+            assert_eq!(check_layout(&key, legacy, &crate::ID).unwrap_err(), ErrorCode::LayoutMismatch.into());
+        }
+
+        // A current encoding that still carries an earlier version is not trusted either
+        let mut dataset = Dataset { dataset_index: 3, schema_version: 2, ..Dataset::sample() };
+        let (key, bump) = Pubkey::find_program_address(
+            &[b"dataset", dataset.creator.as_ref(), &3u32.to_le_bytes()],
+            &crate::ID,
+        );
+        dataset.bump = bump;
+        let mut data = encode(&dataset);
+        data.resize(8 + Dataset::SPACE, 0);
+        assert_eq!(check_layout(&key, &data, &crate::ID).unwrap_err(), ErrorCode::LayoutMismatch.into());
+    }
+}
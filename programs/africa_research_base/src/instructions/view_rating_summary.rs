@@ -0,0 +1,85 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+use crate::{Dataset, Registry};
+use crate::constants::RATING_SUMMARY_VERSION;
+use crate::seeds::{DATASET_SEED, REGISTRY_SEED};
+
+// Return data of `view_rating_summary`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RatingSummary {
+    pub version: u8, // RATING_SUMMARY_VERSION
+    pub average_rating: Option<u16>, // Hundredths of a star; withheld while provisional
+    pub rating_count: u32,
+    pub ratings_provisional: bool, // Fewer than the registry's min_rating_count raters so far
+}
+
+impl RatingSummary {
+    pub fn new(dataset: &Dataset, min_rating_count: u32) -> Self {
+        let meaningful = dataset.ratings_are_meaningful(min_rating_count);
+        RatingSummary {
+            version: RATING_SUMMARY_VERSION,
+            average_rating: dataset.average_rating().filter(|_| meaningful),
+            rating_count: dataset.rating_count,
+            ratings_provisional: !meaningful,
+        }
+    }
+}
+
+#[derive(Accounts)]
+pub struct ViewRatingSummary<'info> {
+    #[account(
+        seeds = [REGISTRY_SEED, registry.creator.as_ref(), registry.name.as_ref()],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        has_one = registry
+    )]
+    pub dataset: Account<'info, Dataset>,
+}
+
+impl<'info> ViewRatingSummary<'info> {
+    // Read-only; callers fetch the summary from the transaction's return data
+    pub fn view_rating_summary(&self) -> Result<()> {
+        let summary = RatingSummary::new(&self.dataset, self.registry.min_rating_count);
+        set_return_data(&summary.try_to_vec()?);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rated(ratings: &[u8]) -> Dataset {
+        let mut dataset = Dataset::sample();
+        for &rating in ratings {
+            dataset.rate(None, rating, 1).unwrap();
+        }
+        dataset
+    }
+
+    #[test]
+    fn few_ratings_are_provisional_and_withhold_the_average() {
+        let summary = RatingSummary::new(&rated(&[5]), 3);
+
+        assert!(summary.ratings_provisional);
+        assert_eq!(summary.average_rating, None);
+        assert_eq!(summary.rating_count, 1);
+        assert!(RatingSummary::new(&Dataset::sample(), 0).average_rating.is_none());
+    }
+
+    #[test]
+    fn ratings_at_the_threshold_report_the_average() {
+        let summary = RatingSummary::new(&rated(&[5, 4, 4]), 3);
+
+        assert!(!summary.ratings_provisional);
+        assert_eq!(summary.average_rating, Some(433));
+        assert_eq!(RatingSummary::try_from_slice(&summary.try_to_vec().unwrap()).unwrap(), summary);
+    }
+}
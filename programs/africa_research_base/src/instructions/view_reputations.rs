@@ -0,0 +1,94 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::{set_return_data, MAX_RETURN_DATA};
+
+use crate::Reputation;
+use crate::access::require_program_owned;
+use crate::constants::MAX_REPUTATION_VIEW;
+use crate::error::ErrorCode;
+
+// Compact per-contributor summary returned by `view_reputations`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReputationView {
+    pub contributor: Pubkey,
+    pub reputation_score: u32,
+    pub tier: u32,
+}
+
+impl ReputationView {
+    pub fn from_reputation(reputation: &Reputation) -> Self {
+        ReputationView {
+            contributor: reputation.published_key(),
+            reputation_score: reputation.reputation_score,
+            tier: reputation.tier(),
+        }
+    }
+}
+
+// Read-only; meant to be simulated, with reputation accounts passed as remaining accounts
+#[derive(Accounts)]
+pub struct ViewReputations<'info> {
+    // Needs no accounts of its own; this fixed address gives the struct the lifetime the generated
+    // CPI client expects
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ViewReputations<'info> {
+    pub fn view_reputations(reputations: &'info [AccountInfo<'info>]) -> Result<()> {
+        require!(reputations.len() <= MAX_REPUTATION_VIEW, ErrorCode::TooManyReputations);
+
+        let views = reputations
+            .iter()
+            .map(|info| {
+                require_program_owned(info)?;
+                Account::<Reputation>::try_from(info).map(|r| ReputationView::from_reputation(&r))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let data = views.try_to_vec()?;
+        debug_assert!(data.len() <= MAX_RETURN_DATA);
+        set_return_data(&data);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn three_reputations_encode_scores_and_tiers() {
+        let views: Vec<ReputationView> = [50, 150, 2_500]
+            .into_iter()
+            .map(|reputation_score| Reputation { reputation_score, ..Reputation::sample() })
+            .map(|reputation| ReputationView::from_reputation(&reputation))
+            .collect();
+
+        let decoded = Vec::<ReputationView>::try_from_slice(&views.try_to_vec().unwrap()).unwrap();
+
+        let summary: Vec<(u32, u32)> = decoded.iter().map(|v| (v.reputation_score, v.tier)).collect();
+        assert_eq!(summary, [(50, 0), (150, 1), (2_500, 3)]);
+        assert_eq!(decoded, views);
+    }
+
+    #[test]
+    fn foreign_owned_reputation_is_rejected() {
+        let key = Pubkey::new_unique();
+        let mut lamports = 1_000_000;
+        let mut data = Vec::new();
+        Reputation::sample().try_serialize(&mut data).unwrap();
+        let impostor = Pubkey::new_unique();
+        let reputations = [AccountInfo::new(&key, false, false, &mut lamports, &mut data, &impostor, false, 0)];
+
+        assert_eq!(
+            ViewReputations::view_reputations(&reputations).unwrap_err(),
+            ErrorCode::UnauthorizedUpdate.into()
+        );
+    }
+
+    #[test]
+    fn full_view_fits_return_data() {
+        let views = vec![ReputationView::from_reputation(&Reputation::sample()); MAX_REPUTATION_VIEW];
+
+        assert!(views.try_to_vec().unwrap().len() <= MAX_RETURN_DATA);
+    }
+}
@@ -0,0 +1,59 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+
+use crate::{Dataset, Earnings, FeeVault};
+use crate::access::is_owner;
+use crate::error::ErrorCode;
+use crate::events::EarningsWithdrawn;
+use crate::seeds::DATASET_SEED;
+use crate::time::current_time;
+
+// Pays the contributor out of one dataset's fee vault, drawing down their earnings summary
+#[derive(Accounts)]
+pub struct WithdrawEarnings<'info> {
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+
+    #[account(
+        seeds = [DATASET_SEED, dataset.creator.as_ref(), &dataset.dataset_index.to_le_bytes()],
+        bump = dataset.bump,
+        constraint = is_owner(&dataset, &contributor.key()) @ ErrorCode::UnauthorizedUpdate
+    )]
+    pub dataset: Account<'info, Dataset>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", dataset.key().as_ref()],
+        bump = fee_vault.bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    #[account(
+        mut,
+        seeds = [b"earnings", contributor.key().as_ref()],
+        bump = earnings.bump
+    )]
+    pub earnings: Account<'info, Earnings>,
+}
+
+impl<'info> WithdrawEarnings<'info> {
+    // The vault always keeps its rent-exempt minimum
+    pub fn withdraw_earnings(&mut self, lamports: u64) -> Result<()> {
+        let info = self.fee_vault.to_account_info();
+        let withdrawable = info.lamports().saturating_sub(Rent::get()?.minimum_balance(info.data_len()));
+        require!(lamports <= withdrawable, ErrorCode::InsufficientEarnings);
+        self.earnings.withdraw(lamports, current_time()?)?;
+
+        self.fee_vault.sub_lamports(lamports)?;
+        self.contributor.add_lamports(lamports)?;
+
+        emit!(EarningsWithdrawn {
+            contributor: self.dataset.published_contributor(),
+            dataset_id: self.dataset.key(),
+            lamports,
+            lifetime_lamports: self.earnings.lifetime_lamports,
+            available_lamports: self.earnings.available_lamports(),
+        });
+        Ok(())
+    }
+}
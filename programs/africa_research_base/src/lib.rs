@@ -1,9 +1,21 @@
 #![allow(unexpected_cfgs)]
+// The generated CPI client mirrors create_dataset's arguments, outside the reach of its own allow
+#![cfg_attr(feature = "cpi", allow(clippy::too_many_arguments))]
+pub mod access;
+pub mod ai_metadata;
+pub mod anonymity;
 pub mod constants;
+pub mod ed25519;
 pub mod error;
 pub mod instructions;
 pub mod state;
 pub mod events;
+pub mod guard;
+pub mod uri;
+pub mod seeds;
+pub mod validation;
+pub mod time;
+mod profiling;
 
 use anchor_lang::prelude::*;
 
@@ -19,27 +31,27 @@ pub mod africa_research_base {
 
     pub fn initialize_registry (
         ctx: Context<Initialize>,
+        name: [u8; 32],
     ) -> Result<()> {
-        ctx.accounts.initialize_registry(&ctx.bumps)
+        ctx.accounts.initialize_registry(name, &ctx.bumps)
     }
 
 
-    pub fn create_dataset(
-        ctx: Context<CreateDataset>,
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_dataset<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateDataset<'info>>,
         content_hash: [u8; 32],
         ai_metadata: Vec<u8>,
         file_name: Vec<u8>,
         file_size: u64,
-        data_uri: [u8; 256],
+        data_uri: Vec<u8>,
         column_count: u64,
         row_count: u64,
         quality_score: u8,
-        // upload_timestamp: i64,
-        // last_updated: Option<i64>,
-        // download_count: u32,
-        // is_active: bool,
+        preview_hash: [u8; 32],
+        options: DatasetOptions,
     ) -> Result<()> {
-        ctx.accounts.create_dataset(content_hash, ai_metadata, file_name, file_size, data_uri, column_count, row_count, quality_score, &ctx.bumps)?;
+        ctx.accounts.create_dataset(content_hash, ai_metadata, file_name, file_size, data_uri, column_count, row_count, quality_score, preview_hash, options, ctx.remaining_accounts, ctx.program_id, &ctx.bumps)?;
 
         Ok(())
     }
@@ -49,13 +61,6 @@ pub mod africa_research_base {
         ctx.accounts.initialize_reputation(&ctx.bumps)
     }
 
-    pub fn update_reputation_upload(
-        ctx: Context<UpdateReputationOnUpload>,
-        quality_score: u8
-    ) -> Result<()> {
-        ctx.accounts.update_reputation_upload(quality_score)
-    }
-
     pub fn update_reputation_download(
         ctx: Context<UpdateReputationOnDownload>
     ) -> Result<()> {
@@ -67,4 +72,502 @@ pub mod africa_research_base {
     ) -> Result<()> {
         ctx.accounts.update_reputation_citation()
     }
+
+    pub fn propose_config_change(
+        ctx: Context<ProposeConfigChange>,
+        change: ConfigChange
+    ) -> Result<()> {
+        ctx.accounts.propose_config_change(change, &ctx.bumps)
+    }
+
+    pub fn execute_config_change(
+        ctx: Context<ExecuteConfigChange>
+    ) -> Result<()> {
+        ctx.accounts.execute_config_change()
+    }
+
+    pub fn set_download_fee(
+        ctx: Context<SetDownloadFee>,
+        download_fee: u64
+    ) -> Result<()> {
+        ctx.accounts.set_download_fee(download_fee, &ctx.bumps)
+    }
+
+    pub fn record_download(
+        ctx: Context<RecordDownload>,
+        slot: u64
+    ) -> Result<()> {
+        ctx.accounts.record_download(slot, &ctx.bumps)
+    }
+
+    pub fn finalize_reputation<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FinalizeReputation<'info>>
+    ) -> Result<()> {
+        ctx.accounts.finalize_reputation(ctx.remaining_accounts, ctx.program_id)
+    }
+
+    pub fn close_dataset<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseDataset<'info>>
+    ) -> Result<()> {
+        ctx.accounts.close_dataset(ctx.remaining_accounts, ctx.program_id)
+    }
+
+    pub fn merge_datasets<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MergeDatasets<'info>>
+    ) -> Result<()> {
+        ctx.accounts.merge_datasets(ctx.remaining_accounts, ctx.program_id)
+    }
+
+    pub fn set_min_file_size(
+        ctx: Context<UpdateRegistry>,
+        min_file_size: u64
+    ) -> Result<()> {
+        ctx.accounts.set_min_file_size(min_file_size)
+    }
+
+    pub fn archive_expired(
+        ctx: Context<ArchiveExpired>
+    ) -> Result<()> {
+        ctx.accounts.archive_expired()
+    }
+
+    pub fn set_citation_pair_cap(
+        ctx: Context<UpdateRegistry>,
+        citation_pair_cap: u8
+    ) -> Result<()> {
+        ctx.accounts.set_citation_pair_cap(citation_pair_cap)
+    }
+
+    pub fn set_featured_threshold(
+        ctx: Context<UpdateRegistry>,
+        featured_reputation_threshold: u32
+    ) -> Result<()> {
+        ctx.accounts.set_featured_threshold(featured_reputation_threshold)
+    }
+
+    pub fn set_download_oracle(
+        ctx: Context<UpdateRegistry>,
+        download_oracle: Pubkey
+    ) -> Result<()> {
+        ctx.accounts.set_download_oracle(download_oracle)
+    }
+
+    pub fn set_institution_oracle(
+        ctx: Context<UpdateRegistry>,
+        institution_oracle: Pubkey
+    ) -> Result<()> {
+        ctx.accounts.set_institution_oracle(institution_oracle)
+    }
+
+    pub fn set_citation_oracle(
+        ctx: Context<UpdateRegistry>,
+        citation_oracle: Pubkey
+    ) -> Result<()> {
+        ctx.accounts.set_citation_oracle(citation_oracle)
+    }
+
+    pub fn report_external_citation(
+        ctx: Context<ReportExternalCitation>,
+        publication_hash: [u8; 32]
+    ) -> Result<()> {
+        ctx.accounts.report_external_citation(publication_hash, &ctx.bumps)
+    }
+
+    pub fn record_citation(
+        ctx: Context<RecordCitation>,
+        published_information: Vec<u8>
+    ) -> Result<()> {
+        ctx.accounts.record_citation(published_information, &ctx.bumps)
+    }
+
+    pub fn allow_host(
+        ctx: Context<UpdateRegistryConfig>,
+        host_hash: [u8; 32]
+    ) -> Result<()> {
+        ctx.accounts.allow_host(host_hash)
+    }
+
+    pub fn disallow_host(
+        ctx: Context<UpdateRegistryConfig>,
+        host_hash: [u8; 32]
+    ) -> Result<()> {
+        ctx.accounts.disallow_host(host_hash)
+    }
+
+    pub fn set_field_weight(
+        ctx: Context<UpdateRegistryConfig>,
+        field: u8,
+        weight: u8
+    ) -> Result<()> {
+        ctx.accounts.set_field_weight(field, weight)
+    }
+
+    pub fn update_data_uri(
+        ctx: Context<UpdateDataUri>,
+        data_uri: Vec<u8>
+    ) -> Result<()> {
+        ctx.accounts.update_data_uri(data_uri)
+    }
+
+    pub fn set_curated(
+        ctx: Context<UpdateRegistry>,
+        curated: bool
+    ) -> Result<()> {
+        ctx.accounts.set_curated(curated)
+    }
+
+    pub fn assign_reviewer(
+        ctx: Context<AssignReviewer>,
+        reviewer: Pubkey
+    ) -> Result<()> {
+        ctx.accounts.assign_reviewer(reviewer)
+    }
+
+    pub fn submit_review(
+        ctx: Context<SubmitReview>,
+        approved: bool,
+        note_hash: [u8; 32]
+    ) -> Result<()> {
+        ctx.accounts.submit_review(approved, note_hash)
+    }
+
+    pub fn export_dataset(ctx: Context<ExportDataset>) -> Result<()> {
+        ctx.accounts.export_dataset()
+    }
+
+    pub fn revoke_citation(ctx: Context<RevokeCitation>) -> Result<()> {
+        ctx.accounts.revoke_citation()
+    }
+
+    pub fn request_access_nonce(ctx: Context<RequestAccessNonce>) -> Result<()> {
+        ctx.accounts.request_access_nonce(&ctx.bumps)
+    }
+
+    pub fn consume_nonce(
+        ctx: Context<ConsumeNonce>,
+        nonce: [u8; 32]
+    ) -> Result<()> {
+        ctx.accounts.consume_nonce(nonce)
+    }
+
+    pub fn update_leaderboard(ctx: Context<UpdateLeaderboard>) -> Result<()> {
+        ctx.accounts.update_leaderboard(&ctx.bumps)
+    }
+
+    pub fn lock_data_uri(ctx: Context<LockDataUri>) -> Result<()> {
+        ctx.accounts.lock_data_uri()
+    }
+
+    pub fn view_reputations<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ViewReputations<'info>>
+    ) -> Result<()> {
+        ViewReputations::view_reputations(ctx.remaining_accounts)
+    }
+
+    pub fn fork_dataset(ctx: Context<ForkDataset>) -> Result<()> {
+        ctx.accounts.fork_dataset(&ctx.bumps)
+    }
+
+    pub fn set_freeze_penalty(
+        ctx: Context<UpdateRegistry>,
+        freeze_penalty: u32
+    ) -> Result<()> {
+        ctx.accounts.set_freeze_penalty(freeze_penalty)
+    }
+
+    pub fn freeze_dataset(
+        ctx: Context<FreezeDataset>,
+        reason_hash: [u8; 32]
+    ) -> Result<()> {
+        ctx.accounts.freeze_dataset(reason_hash, &ctx.bumps)
+    }
+
+    pub fn set_unavailable_report_threshold(
+        ctx: Context<UpdateRegistry>,
+        threshold: u8
+    ) -> Result<()> {
+        ctx.accounts.set_unavailable_report_threshold(threshold)
+    }
+
+    pub fn report_unavailable(ctx: Context<ReportUnavailable>) -> Result<()> {
+        ctx.accounts.report_unavailable(&ctx.bumps)
+    }
+
+    pub fn delegate_reputation(ctx: Context<DelegateReputation>) -> Result<()> {
+        ctx.accounts.delegate_reputation(&ctx.bumps)
+    }
+
+    pub fn revoke_delegation(ctx: Context<RevokeDelegation>) -> Result<()> {
+        ctx.accounts.revoke_delegation()
+    }
+
+    pub fn rate_dataset(ctx: Context<RateDataset>, rating: u8) -> Result<()> {
+        ctx.accounts.rate_dataset(rating, &ctx.bumps)
+    }
+
+    pub fn set_quality_from_ratings(ctx: Context<SetQualityFromRatings>) -> Result<()> {
+        ctx.accounts.set_quality_from_ratings()
+    }
+
+    pub fn reactivate_dataset(ctx: Context<ReactivateDataset>) -> Result<()> {
+        ctx.accounts.reactivate_dataset()
+    }
+
+    pub fn set_download_cap(
+        ctx: Context<SetDownloadCap>,
+        download_cap: u32,
+        download_window: i64
+    ) -> Result<()> {
+        ctx.accounts.set_download_cap(download_cap, download_window)
+    }
+
+    pub fn extend_pin(ctx: Context<ExtendPin>, days: u32) -> Result<()> {
+        ctx.accounts.extend_pin(days, &ctx.bumps)
+    }
+
+    pub fn set_fee_currency(
+        ctx: Context<SetFeeCurrency>,
+        fee_symbol: [u8; 8]
+    ) -> Result<()> {
+        ctx.accounts.set_fee_currency(fee_symbol)
+    }
+
+    pub fn add_editor(ctx: Context<ManageEditors>, editor: Pubkey) -> Result<()> {
+        ctx.accounts.add_editor(editor)
+    }
+
+    pub fn remove_editor(ctx: Context<ManageEditors>, editor: Pubkey) -> Result<()> {
+        ctx.accounts.remove_editor(editor)
+    }
+
+    pub fn migrate_data_uri(ctx: Context<MigrateDataUri>) -> Result<()> {
+        ctx.accounts.migrate_data_uri()
+    }
+
+    pub fn decay_reputation(ctx: Context<DecayReputation>) -> Result<()> {
+        ctx.accounts.decay_reputation()
+    }
+
+    pub fn set_vacation(ctx: Context<SetVacation>, until: i64) -> Result<()> {
+        ctx.accounts.set_vacation(until)
+    }
+
+    pub fn set_institution_vacation(ctx: Context<SetInstitutionVacation>, until: i64) -> Result<()> {
+        ctx.accounts.set_institution_vacation(until)
+    }
+
+    pub fn submit_download_report(
+        ctx: Context<SubmitDownloadReport>,
+        report_id: u64,
+        root: [u8; 32]
+    ) -> Result<()> {
+        ctx.accounts.submit_download_report(report_id, root, &ctx.bumps)
+    }
+
+    pub fn claim_download_delta(
+        ctx: Context<ClaimDownloadDelta>,
+        delta: u32,
+        proof: Vec<[u8; 32]>
+    ) -> Result<()> {
+        ctx.accounts.claim_download_delta(delta, proof, &ctx.bumps)
+    }
+
+    pub fn compare_datasets(ctx: Context<CompareDatasets>) -> Result<()> {
+        ctx.accounts.compare_datasets()
+    }
+
+    pub fn set_quality_scale(ctx: Context<UpdateRegistry>, quality_scale: u8) -> Result<()> {
+        ctx.accounts.set_quality_scale(quality_scale)
+    }
+
+    pub fn verify_layout(ctx: Context<VerifyLayout>) -> Result<()> {
+        ctx.accounts.verify_layout(ctx.program_id)
+    }
+
+    pub fn endorse_dataset(ctx: Context<EndorseDataset>) -> Result<()> {
+        ctx.accounts.endorse_dataset(&ctx.bumps)
+    }
+
+    pub fn recover_stuck_vault(ctx: Context<RecoverStuckVault>) -> Result<()> {
+        ctx.accounts.recover_stuck_vault()
+    }
+
+    pub fn update_title<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UpdateTitle<'info>>,
+        title: Vec<u8>
+    ) -> Result<()> {
+        ctx.accounts.update_title(title, ctx.remaining_accounts, ctx.program_id)
+    }
+
+    pub fn revert_downloads(
+        ctx: Context<RevertDownloads>,
+        reversal_id: u64,
+        count: u32,
+        reason_hash: [u8; 32]
+    ) -> Result<()> {
+        ctx.accounts.revert_downloads(reversal_id, count, reason_hash, &ctx.bumps)
+    }
+
+    pub fn view_rating_summary(ctx: Context<ViewRatingSummary>) -> Result<()> {
+        ctx.accounts.view_rating_summary()
+    }
+
+    pub fn set_min_rating_count(ctx: Context<UpdateRegistry>, min_rating_count: u32) -> Result<()> {
+        ctx.accounts.set_min_rating_count(min_rating_count)
+    }
+
+    pub fn rotate_contributor_key<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RotateContributorKey<'info>>
+    ) -> Result<()> {
+        ctx.accounts.rotate_contributor_key(ctx.remaining_accounts, ctx.program_id, &ctx.bumps)
+    }
+
+    pub fn renew_license(ctx: Context<RenewLicense>, license_expires_at: i64) -> Result<()> {
+        ctx.accounts.renew_license(license_expires_at)
+    }
+
+    pub fn audit_reputation(ctx: Context<AuditReputation>) -> Result<()> {
+        ctx.accounts.audit_reputation()
+    }
+
+    pub fn mark_superseded(ctx: Context<MarkSuperseded>) -> Result<()> {
+        ctx.accounts.mark_superseded()
+    }
+
+    pub fn request_dataset(
+        ctx: Context<RequestDataset>,
+        topic_hash: [u8; 32],
+        topic: Vec<u8>,
+        description_hash: [u8; 32],
+        bounty: u64
+    ) -> Result<()> {
+        ctx.accounts.request_dataset(topic_hash, topic, description_hash, bounty, &ctx.bumps)
+    }
+
+    pub fn fulfill_request(ctx: Context<FulfillRequest>) -> Result<()> {
+        ctx.accounts.fulfill_request()
+    }
+
+    pub fn set_structured_ai_metadata(ctx: Context<UpdateRegistry>, structured_ai_metadata: bool) -> Result<()> {
+        ctx.accounts.set_structured_ai_metadata(structured_ai_metadata)
+    }
+
+    pub fn set_quality_dispute_delta(ctx: Context<UpdateRegistry>, quality_dispute_delta: u8) -> Result<()> {
+        ctx.accounts.set_quality_dispute_delta(quality_dispute_delta)
+    }
+
+    pub fn seed_reputation(
+        ctx: Context<SeedReputation>,
+        total_uploads: u32,
+        total_quality_score: u64,
+        total_downloads: u64,
+        total_citations: u32
+    ) -> Result<()> {
+        ctx.accounts.seed_reputation(total_uploads, total_quality_score, total_downloads, total_citations, &ctx.bumps)
+    }
+
+    pub fn expand_metadata(ctx: Context<ExpandMetadata>, extra: Vec<u8>, rent_deposit: u64) -> Result<()> {
+        ctx.accounts.expand_metadata(extra, rent_deposit)
+    }
+
+    pub fn bulk_tag<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BulkTag<'info>>,
+        research_fields: Vec<u8>
+    ) -> Result<()> {
+        ctx.accounts.bulk_tag(research_fields, ctx.remaining_accounts, ctx.program_id)
+    }
+
+    pub fn set_quality_bond(ctx: Context<UpdateRegistry>, quality_bond: u64) -> Result<()> {
+        ctx.accounts.set_quality_bond(quality_bond)
+    }
+
+    pub fn set_spam_deposit(ctx: Context<UpdateRegistry>, spam_deposit: u64) -> Result<()> {
+        ctx.accounts.set_spam_deposit(spam_deposit)
+    }
+
+    pub fn resolve_by_hash(ctx: Context<ResolveByHash>, content_hash: [u8; 32]) -> Result<()> {
+        ctx.accounts.resolve_by_hash(content_hash)
+    }
+
+    pub fn set_data_dictionary(ctx: Context<SetDataDictionary>, columns: Vec<ColumnDef>) -> Result<()> {
+        ctx.accounts.set_data_dictionary(columns, &ctx.bumps)
+    }
+
+    pub fn set_anonymous(ctx: Context<SetAnonymous>, anonymous: bool) -> Result<()> {
+        ctx.accounts.set_anonymous(anonymous)
+    }
+
+    pub fn add_category(ctx: Context<AddCategory>, code: u8, name: [u8; 32]) -> Result<()> {
+        ctx.accounts.add_category(code, name, &ctx.bumps)
+    }
+
+    pub fn retire_category(ctx: Context<RetireCategory>, code: u8) -> Result<()> {
+        ctx.accounts.retire_category(code)
+    }
+
+    pub fn set_review_sla(ctx: Context<UpdateRegistry>, review_sla: i64) -> Result<()> {
+        ctx.accounts.set_review_sla(review_sla)
+    }
+
+    pub fn auto_approve_expired(ctx: Context<AutoApproveExpired>) -> Result<()> {
+        ctx.accounts.auto_approve_expired()
+    }
+
+    pub fn withdraw_earnings(ctx: Context<WithdrawEarnings>, lamports: u64) -> Result<()> {
+        ctx.accounts.withdraw_earnings(lamports)
+    }
+
+    pub fn unfeature_expired(ctx: Context<UnfeatureExpired>) -> Result<()> {
+        ctx.accounts.unfeature_expired()
+    }
+
+    pub fn request_access(ctx: Context<RequestAccess>, justification_hash: [u8; 32]) -> Result<()> {
+        ctx.accounts.request_access(justification_hash, &ctx.bumps)
+    }
+
+    pub fn respond_to_access_request(ctx: Context<RespondToAccessRequest>, approved: bool) -> Result<()> {
+        ctx.accounts.respond_to_access_request(approved, &ctx.bumps)
+    }
+
+    pub fn import_dataset_from_signed_manifest(
+        ctx: Context<ImportSignedManifest>,
+        manifest_hash: [u8; 32],
+        content_hashes: Vec<[u8; 32]>
+    ) -> Result<()> {
+        ctx.accounts.import_dataset_from_signed_manifest(manifest_hash, content_hashes, &ctx.bumps)
+    }
+
+    pub fn create_collection(ctx: Context<CreateCollection>, name: [u8; 32]) -> Result<()> {
+        ctx.accounts.create_collection(name, &ctx.bumps)
+    }
+
+    pub fn add_to_collection(ctx: Context<AddToCollection>) -> Result<()> {
+        ctx.accounts.add_to_collection()
+    }
+
+    pub fn remove_from_collection(ctx: Context<RemoveFromCollection>, dataset: Pubkey) -> Result<()> {
+        ctx.accounts.remove_from_collection(dataset)
+    }
+
+    pub fn finalize_dataset(ctx: Context<FinalizeDataset>) -> Result<()> {
+        ctx.accounts.finalize_dataset()
+    }
+
+    pub fn get_contributor_overview<'info>(
+        ctx: Context<'_, '_, 'info, 'info, GetContributorOverview<'info>>
+    ) -> Result<()> {
+        ctx.accounts.get_contributor_overview(ctx.remaining_accounts)
+    }
+
+    pub fn treasury_withdraw(ctx: Context<TreasuryWithdraw>, lamports: u64) -> Result<()> {
+        ctx.accounts.treasury_withdraw(lamports)
+    }
+
+    pub fn add_replica(ctx: Context<AddReplica>, backend: u8, cid: [u8; 64]) -> Result<()> {
+        ctx.accounts.add_replica(backend, cid, &ctx.bumps)
+    }
+
+    pub fn repoint_datasets<'info>(ctx: Context<'_, '_, 'info, 'info, RepointDatasets<'info>>) -> Result<()> {
+        ctx.accounts.repoint_datasets(ctx.remaining_accounts, ctx.program_id)
+    }
 }
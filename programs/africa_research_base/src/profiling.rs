@@ -0,0 +1,47 @@
+// Compute-unit checkpoints for diagnosing heavy handlers. Built with `--features compute-profiling`,
+// each `profile!` logs its label followed by the remaining compute units; otherwise it expands to
+// nothing, so mainnet builds pay no compute for them.
+#[cfg(feature = "compute-profiling")]
+macro_rules! profile {
+    ($label:literal) => {{
+        anchor_lang::prelude::msg!(concat!("profile: ", $label));
+        anchor_lang::solana_program::log::sol_log_compute_units();
+    }};
+}
+
+#[cfg(not(feature = "compute-profiling"))]
+macro_rules! profile {
+    ($label:literal) => {};
+}
+
+pub(crate) use profile;
+
+#[cfg(all(test, feature = "compute-profiling"))]
+mod tests {
+    use anchor_lang::solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+    use std::sync::{Arc, Mutex};
+
+    use crate::time::SYSCALL_STUBS_LOCK;
+
+    // Off-chain `msg!` prints straight to stdout, so only the compute-unit syscall reaches the stubs
+    struct CountingStubs(Arc<Mutex<u32>>);
+
+    impl SyscallStubs for CountingStubs {
+        fn sol_log_compute_units(&self) {
+            *self.0.lock().unwrap() += 1;
+        }
+    }
+
+    #[test]
+    fn each_checkpoint_logs_compute_units() {
+        let _guard = SYSCALL_STUBS_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let calls = Arc::new(Mutex::new(0));
+        let previous = set_syscall_stubs(Box::new(CountingStubs(calls.clone())));
+
+        profile!("before validation");
+        profile!("after validation");
+        set_syscall_stubs(previous);
+
+        assert_eq!(*calls.lock().unwrap(), 2);
+    }
+}
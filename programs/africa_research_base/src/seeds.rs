@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+
+// PDA seeds of the core accounts. The prefixes are what the account constraints use, and the
+// functions spell out the full seed list so off-chain clients can reproduce each derivation.
+
+#[constant]
+pub const REGISTRY_SEED: &[u8] = b"registry";
+#[constant]
+pub const DATASET_SEED: &[u8] = b"dataset";
+#[constant]
+pub const REPUTATION_SEED: &[u8] = b"reputation";
+
+// Keyed by the original creator, so handing the registry to a new admin keeps its address
+pub fn registry_seeds(creator: &Pubkey, name: &[u8; 32]) -> Vec<Vec<u8>> {
+    vec![REGISTRY_SEED.to_vec(), creator.to_bytes().to_vec(), name.to_vec()]
+}
+
+// `dataset_index` is the contributor's Reputation::dataset_count when the dataset was created
+pub fn dataset_seeds(contributor: &Pubkey, dataset_index: u32) -> Vec<Vec<u8>> {
+    vec![DATASET_SEED.to_vec(), contributor.to_bytes().to_vec(), dataset_index.to_le_bytes().to_vec()]
+}
+
+pub fn reputation_seeds(contributor: &Pubkey) -> Vec<Vec<u8>> {
+    vec![REPUTATION_SEED.to_vec(), contributor.to_bytes().to_vec()]
+}
+
+pub fn find_address(seeds: &[Vec<u8>], program_id: &Pubkey) -> (Pubkey, u8) {
+    let seeds: Vec<&[u8]> = seeds.iter().map(Vec::as_slice).collect();
+    Pubkey::find_program_address(&seeds, program_id)
+}
+
+// Checks a stored bump against the address, as the `bump = account.bump` constraints do
+pub fn create_address(seeds: &[Vec<u8>], bump: u8, program_id: &Pubkey) -> Result<Pubkey> {
+    let bump = [bump];
+    let mut seeds: Vec<&[u8]> = seeds.iter().map(Vec::as_slice).collect();
+    seeds.push(&bump);
+    Pubkey::create_program_address(&seeds, program_id).map_err(|_| error!(ErrorCode::ConstraintSeeds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn name(label: &[u8]) -> [u8; 32] {
+        let mut name = [0u8; 32];
+        name[..label.len()].copy_from_slice(label);
+        name
+    }
+
+    // Fixed inputs and the addresses they derive to under the program ID, for clients in other
+    // languages to check their derivations against
+    #[test]
+    fn helpers_reproduce_the_reference_vectors() {
+        let key = Pubkey::new_from_array([1; 32]);
+
+        assert_eq!(
+            find_address(&registry_seeds(&key, &name(b"main")), &crate::ID),
+            (pubkey!("82T2a4nSRoBSBqMmztSvcTL8JP7RadrmMYHRLmoK7NfH"), 254)
+        );
+        assert_eq!(
+            find_address(&dataset_seeds(&key, 7), &crate::ID),
+            (pubkey!("FM1T8wNbe26ScjZpPQQCWjKRpmhAaFrnPdgnftRnXeHK"), 255)
+        );
+        assert_eq!(
+            find_address(&reputation_seeds(&key), &crate::ID),
+            (pubkey!("5awzLym73BScFunwaWrpf2kp2HFsVhgwxtYmKKntr6Qk"), 255)
+        );
+    }
+
+    // The account constraints spell the seeds out inline; these are the same lists
+    #[test]
+    fn helpers_match_the_constraint_seed_layout() {
+        let creator = Pubkey::new_unique();
+        let contributor = Pubkey::new_unique();
+        let name = name(b"health");
+
+        assert_eq!(
+            find_address(&registry_seeds(&creator, &name), &crate::ID),
+            Pubkey::find_program_address(&[b"registry", creator.as_ref(), name.as_ref()], &crate::ID)
+        );
+        assert_eq!(
+            find_address(&dataset_seeds(&contributor, 42), &crate::ID),
+            Pubkey::find_program_address(&[b"dataset", contributor.as_ref(), &42u32.to_le_bytes()], &crate::ID)
+        );
+        assert_eq!(
+            find_address(&reputation_seeds(&contributor), &crate::ID),
+            Pubkey::find_program_address(&[b"reputation", contributor.as_ref()], &crate::ID)
+        );
+    }
+
+    #[test]
+    fn create_address_accepts_only_the_canonical_bump() {
+        let seeds = reputation_seeds(&Pubkey::new_unique());
+        let (address, bump) = find_address(&seeds, &crate::ID);
+
+        assert_eq!(create_address(&seeds, bump, &crate::ID).unwrap(), address);
+        assert_ne!(create_address(&seeds, bump.wrapping_sub(1), &crate::ID).ok(), Some(address));
+    }
+}
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+
+// Short-lived token an off-chain server checks before serving a paid download, one per receipt
+#[account]
+#[derive(InitSpace)]
+pub struct AccessNonce {
+    pub receipt: Pubkey,
+    pub dataset: Pubkey,
+    pub grantee: Pubkey,
+    pub nonce: [u8; 32],
+    pub expires_at: i64,
+    pub consumed: bool,
+    pub bump: u8
+}
+
+impl AccessNonce {
+    pub const SPACE: usize = Self::INIT_SPACE;
+
+    // A fresh nonce may replace one that has been used or has lapsed, never a live one
+    pub fn issue(&mut self, nonce: [u8; 32], now: i64, ttl: i64) -> Result<()> {
+        require!(self.consumed || self.is_expired(now), ErrorCode::NonceStillValid);
+        self.nonce = nonce;
+        self.expires_at = now.checked_add(ttl).ok_or(ErrorCode::NumericalOverflow)?;
+        self.consumed = false;
+        Ok(())
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        now >= self.expires_at
+    }
+
+    pub fn consume(&mut self, nonce: &[u8; 32], now: i64) -> Result<()> {
+        require!(self.nonce == *nonce, ErrorCode::NonceMismatch);
+        require!(!self.consumed, ErrorCode::NonceConsumed);
+        require!(!self.is_expired(now), ErrorCode::NonceExpired);
+        self.consumed = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::ACCESS_NONCE_TTL;
+
+    fn issued(now: i64) -> AccessNonce {
+        let mut access = AccessNonce {
+            receipt: Pubkey::new_unique(),
+            dataset: Pubkey::new_unique(),
+            grantee: Pubkey::new_unique(),
+            nonce: [0u8; 32],
+            expires_at: 0,
+            consumed: false,
+            bump: 255,
+        };
+        access.issue([7u8; 32], now, ACCESS_NONCE_TTL).unwrap();
+        access
+    }
+
+    #[test]
+    fn issued_nonce_can_be_consumed_once() {
+        let mut access = issued(1_000);
+        assert_eq!(access.expires_at, 1_000 + ACCESS_NONCE_TTL);
+
+        access.consume(&[7u8; 32], 1_001).unwrap();
+        assert_eq!(access.consume(&[7u8; 32], 1_002).unwrap_err(), ErrorCode::NonceConsumed.into());
+    }
+
+    #[test]
+    fn expired_nonce_is_rejected() {
+        let mut access = issued(1_000);
+
+        assert_eq!(
+            access.consume(&[7u8; 32], 1_000 + ACCESS_NONCE_TTL).unwrap_err(),
+            ErrorCode::NonceExpired.into()
+        );
+        assert_eq!(access.consume(&[8u8; 32], 1_001).unwrap_err(), ErrorCode::NonceMismatch.into());
+    }
+
+    #[test]
+    fn live_nonce_cannot_be_replaced() {
+        let mut access = issued(1_000);
+
+        assert_eq!(
+            access.issue([9u8; 32], 1_001, ACCESS_NONCE_TTL).unwrap_err(),
+            ErrorCode::NonceStillValid.into()
+        );
+        access.issue([9u8; 32], 1_000 + ACCESS_NONCE_TTL, ACCESS_NONCE_TTL).unwrap();
+        assert_eq!(access.nonce, [9u8; 32]);
+    }
+}
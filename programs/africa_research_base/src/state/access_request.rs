@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{ACCESS_APPROVED, ACCESS_DENIED, ACCESS_PENDING};
+use crate::error::ErrorCode;
+
+// A user's request for access to a dataset, answered once by its contributor
+#[account]
+#[derive(InitSpace)]
+pub struct AccessRequest {
+    pub dataset: Pubkey,
+    pub requester: Pubkey,
+    pub justification_hash: [u8; 32], // Hash of the off-chain statement of intended use
+    pub status: u8, // One of the ACCESS_* constants
+    pub requested_at: i64,
+    pub responded_at: Option<i64>,
+    pub bump: u8
+}
+
+impl AccessRequest {
+    pub const SPACE: usize = Self::INIT_SPACE;
+
+    pub fn respond(&mut self, approved: bool, now: i64) -> Result<()> {
+        require!(self.status == ACCESS_PENDING, ErrorCode::AccessRequestResolved);
+        self.status = if approved { ACCESS_APPROVED } else { ACCESS_DENIED };
+        self.responded_at = Some(now);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending() -> AccessRequest {
+        AccessRequest {
+            dataset: Pubkey::new_unique(),
+            requester: Pubkey::new_unique(),
+            justification_hash: [3u8; 32],
+            status: ACCESS_PENDING,
+            requested_at: 1_000,
+            responded_at: None,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn request_is_answered_exactly_once() {
+        let mut approved = pending();
+        approved.respond(true, 2_000).unwrap();
+        assert_eq!((approved.status, approved.responded_at), (ACCESS_APPROVED, Some(2_000)));
+        assert_eq!(approved.respond(false, 3_000).unwrap_err(), ErrorCode::AccessRequestResolved.into());
+
+        let mut denied = pending();
+        denied.respond(false, 2_000).unwrap();
+        assert_eq!(denied.status, ACCESS_DENIED);
+        assert_eq!(denied.respond(true, 3_000).unwrap_err(), ErrorCode::AccessRequestResolved.into());
+        assert_eq!(denied.responded_at, Some(2_000));
+    }
+}
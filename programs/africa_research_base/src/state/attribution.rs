@@ -8,4 +8,9 @@ pub struct Attribution {
     pub contributor: Pubkey,
     pub download_time: i64,
     pub bump: u8
-}
\ No newline at end of file
+}
+
+impl Attribution {
+    // Fixed-size account, so the derived InitSpace is exact
+    pub const SPACE: usize = Self::INIT_SPACE;
+}
@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+use crate::constants::MAX_UNAVAILABLE_REPORTERS;
+use crate::error::ErrorCode;
+use crate::uri::uri_bytes;
+
+// Distinct accounts that found the dataset's current data_uri dead
+#[account]
+#[derive(InitSpace)]
+pub struct AvailabilityReport {
+    pub dataset: Pubkey,
+    pub data_uri_hash: [u8; 32], // URI the reports refer to; reports against an older URI are discarded
+    #[max_len(MAX_UNAVAILABLE_REPORTERS)]
+    pub reporters: Vec<Pubkey>,
+    pub bump: u8
+}
+
+impl AvailabilityReport {
+    pub const SPACE: usize = Self::INIT_SPACE;
+
+    pub fn uri_hash(data_uri: &[u8]) -> [u8; 32] {
+        hash(uri_bytes(data_uri)).to_bytes()
+    }
+
+    // Adds a report and returns whether the threshold has now been reached
+    pub fn record(&mut self, reporter: Pubkey, data_uri: &[u8], threshold: u8) -> Result<bool> {
+        let data_uri_hash = Self::uri_hash(data_uri);
+        if self.data_uri_hash != data_uri_hash {
+            self.data_uri_hash = data_uri_hash;
+            self.reporters.clear();
+        }
+
+        require!(!self.reporters.contains(&reporter), ErrorCode::DuplicateUnavailableReport);
+        // The threshold never exceeds MAX_UNAVAILABLE_REPORTERS, so a full list has already triggered
+        require!(self.reporters.len() < MAX_UNAVAILABLE_REPORTERS, ErrorCode::DatasetInactive);
+        self.reporters.push(reporter);
+
+        Ok(self.reporters.len() >= threshold as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> AvailabilityReport {
+        AvailabilityReport {
+            dataset: Pubkey::new_unique(),
+            data_uri_hash: [0u8; 32],
+            reporters: Vec::new(),
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn threshold_of_distinct_reporters_triggers() {
+        let mut report = report();
+        let uri = b"ipfs://bafy-dead";
+
+        assert!(!report.record(Pubkey::new_unique(), uri, 2).unwrap());
+        assert!(report.record(Pubkey::new_unique(), uri, 2).unwrap());
+    }
+
+    #[test]
+    fn same_reporter_counts_once() {
+        let mut report = report();
+        let reporter = Pubkey::new_unique();
+        report.record(reporter, b"ipfs://bafy-dead", 3).unwrap();
+
+        assert_eq!(
+            report.record(reporter, b"ipfs://bafy-dead", 3).unwrap_err(),
+            ErrorCode::DuplicateUnavailableReport.into()
+        );
+        assert_eq!(report.reporters.len(), 1);
+    }
+
+    #[test]
+    fn new_uri_discards_earlier_reports() {
+        let mut report = report();
+        let reporter = Pubkey::new_unique();
+        report.record(reporter, b"ipfs://bafy-old", 2).unwrap();
+
+        assert!(!report.record(reporter, b"ipfs://bafy-new", 2).unwrap());
+        assert_eq!(report.reporters, vec![reporter]);
+    }
+}
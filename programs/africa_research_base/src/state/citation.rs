@@ -1,14 +1,26 @@
 use anchor_lang::prelude::*;
+use anchor_lang::Space;
+
+use crate::constants::MAX_PUBLISHED_INFORMATION_LEN;
 
 #[account]
-#[derive(InitSpace)]
 pub struct Citation {
     pub dataset_id: Pubkey,
     pub citer: Pubkey,
     pub contributor: Pubkey,
-    
-    #[max_len(1000)]
-    pub published_information: Vec<u8>,
+    pub published_information: Vec<u8>, // At most MAX_PUBLISHED_INFORMATION_LEN bytes
     pub citing_time: i64,
     pub bump: u8
-}
\ No newline at end of file
+}
+
+impl Citation {
+    // Serialized size excluding the 8-byte discriminator
+    pub const SPACE: usize = 32 * 3 // dataset_id, citer, contributor
+        + 4 + MAX_PUBLISHED_INFORMATION_LEN // published_information
+        + 8 // citing_time
+        + 1; // bump
+}
+
+impl Space for Citation {
+    const INIT_SPACE: usize = Self::SPACE;
+}
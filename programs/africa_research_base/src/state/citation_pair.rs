@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+
+// How often `citer` has cited datasets owned by `cited`, used to cap citation rings
+#[account]
+#[derive(InitSpace)]
+pub struct CitationPair {
+    pub citer: Pubkey,
+    pub cited: Pubkey,
+    pub count: u32,
+    pub bump: u8
+}
+
+impl CitationPair {
+    pub const SPACE: usize = Self::INIT_SPACE;
+
+    // Records one more citation; returns whether it still counts toward reputation
+    pub fn record(&mut self, cap: u8) -> Result<bool> {
+        let counts = self.count < cap as u32;
+        self.count = self.count
+            .checked_add(1)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        Ok(counts)
+    }
+
+    // Removes one citation; returns whether it was among those counted toward reputation
+    pub fn revoke(&mut self, cap: u8) -> Result<bool> {
+        let counted = self.count <= cap as u32;
+        self.count = self.count
+            .checked_sub(1)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        Ok(counted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn citations_beyond_cap_do_not_count() {
+        let mut pair = CitationPair {
+            citer: Pubkey::new_unique(),
+            cited: Pubkey::new_unique(),
+            count: 0,
+            bump: 255,
+        };
+
+        assert!(pair.record(3).unwrap());
+        assert!(pair.record(3).unwrap());
+        assert!(pair.record(3).unwrap());
+        // The 4th citation is still recorded in the graph but earns nothing
+        assert!(!pair.record(3).unwrap());
+        assert_eq!(pair.count, 4);
+    }
+
+    #[test]
+    fn revoking_only_uncounts_citations_within_cap() {
+        let mut pair = CitationPair {
+            citer: Pubkey::new_unique(),
+            cited: Pubkey::new_unique(),
+            count: 4,
+            bump: 255,
+        };
+
+        // Four recorded, three counted: dropping one still leaves three counted
+        assert!(!pair.revoke(3).unwrap());
+        assert!(pair.revoke(3).unwrap());
+        assert_eq!(pair.count, 2);
+    }
+}
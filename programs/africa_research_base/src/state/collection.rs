@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::constants::MAX_COLLECTION_DATASETS;
+use crate::error::ErrorCode;
+
+const COLLECTION_TAG: &[u8] = b"collection";
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct CollectionMember {
+    pub content_hash: [u8; 32],
+    pub dataset: Pubkey,
+}
+
+// A curated bundle of datasets from one registry
+#[account]
+#[derive(InitSpace)]
+pub struct Collection {
+    pub registry: Pubkey,
+    pub curator: Pubkey,
+    pub name: [u8; 32], // Zero-padded, part of the seeds
+    // Kept sorted by (content_hash, dataset), so the root never depends on the order members were added
+    #[max_len(MAX_COLLECTION_DATASETS)]
+    pub members: Vec<CollectionMember>,
+    pub collection_root: [u8; 32], // See Collection::root
+    pub updated_at: i64,
+    pub bump: u8
+}
+
+impl Collection {
+    pub const SPACE: usize = Self::INIT_SPACE;
+
+    // SHA-256 of "collection" followed by every member content hash in sorted order. A consumer
+    // holding the member files can recompute it and check the whole collection against one value
+    pub fn root(members: &[CollectionMember]) -> [u8; 32] {
+        let mut parts: Vec<&[u8]> = vec![COLLECTION_TAG];
+        parts.extend(members.iter().map(|member| member.content_hash.as_slice()));
+        hashv(&parts).to_bytes()
+    }
+
+    pub fn add(&mut self, member: CollectionMember, now: i64) -> Result<()> {
+        require!(
+            !self.members.iter().any(|m| m.dataset == member.dataset),
+            ErrorCode::DuplicateCollectionMember
+        );
+        require!(self.members.len() < MAX_COLLECTION_DATASETS, ErrorCode::CollectionFull);
+
+        let position = self.members
+            .binary_search_by(|m| (m.content_hash, m.dataset).cmp(&(member.content_hash, member.dataset)))
+            .unwrap_or_else(|position| position);
+        self.members.insert(position, member);
+        self.refresh(now);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, dataset: &Pubkey, now: i64) -> Result<()> {
+        let position = self.members
+            .iter()
+            .position(|m| m.dataset == *dataset)
+            .ok_or(ErrorCode::NotACollectionMember)?;
+        self.members.remove(position);
+        self.refresh(now);
+        Ok(())
+    }
+
+    fn refresh(&mut self, now: i64) {
+        self.collection_root = Self::root(&self.members);
+        self.updated_at = now;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty() -> Collection {
+        Collection {
+            registry: Pubkey::new_unique(),
+            curator: Pubkey::new_unique(),
+            name: [0u8; 32],
+            members: Vec::new(),
+            collection_root: Collection::root(&[]),
+            updated_at: 0,
+            bump: 255,
+        }
+    }
+
+    fn member(byte: u8) -> CollectionMember {
+        CollectionMember { content_hash: [byte; 32], dataset: Pubkey::new_unique() }
+    }
+
+    #[test]
+    fn root_follows_membership_not_insertion_order() {
+        let (a, b, c) = (member(3), member(1), member(2));
+        let mut forward = empty();
+        let mut backward = empty();
+        for m in [a, b, c] {
+            forward.add(m, 10).unwrap();
+        }
+        for m in [c, b, a] {
+            backward.add(m, 10).unwrap();
+        }
+
+        assert_eq!(forward.collection_root, backward.collection_root);
+        assert_eq!(forward.members, vec![b, c, a]);
+        assert_eq!(forward.collection_root, Collection::root(&[b, c, a]));
+        assert_ne!(forward.collection_root, Collection::root(&[]));
+    }
+
+    #[test]
+    fn removing_a_member_restores_the_previous_root() {
+        let mut collection = empty();
+        collection.add(member(1), 10).unwrap();
+        let before = collection.collection_root;
+        let extra = member(2);
+
+        collection.add(extra, 20).unwrap();
+        assert_ne!(collection.collection_root, before);
+        collection.remove(&extra.dataset, 30).unwrap();
+
+        assert_eq!(collection.collection_root, before);
+        assert_eq!(collection.updated_at, 30);
+        assert_eq!(collection.remove(&extra.dataset, 40).unwrap_err(), ErrorCode::NotACollectionMember.into());
+    }
+
+    #[test]
+    fn members_are_unique_and_bounded() {
+        let mut collection = empty();
+        let first = member(1);
+        collection.add(first, 10).unwrap();
+        assert_eq!(collection.add(first, 10).unwrap_err(), ErrorCode::DuplicateCollectionMember.into());
+
+        for byte in 1..MAX_COLLECTION_DATASETS as u8 {
+            collection.add(member(byte), 10).unwrap();
+        }
+        assert_eq!(collection.add(member(0), 10).unwrap_err(), ErrorCode::CollectionFull.into());
+
+        let mut data = Vec::new();
+        collection.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), 8 + Collection::SPACE);
+    }
+}
@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum ConfigChange {
+    SetPlatformFee { fee_bps: u16 },
+    SetMinQuality { min_quality_score: u8 },
+    TransferAdmin { new_admin: Pubkey },
+    SetReputationGracePeriod { seconds: i64 },
+    SetTreasuryWithdrawalCap { lamports: u64 },
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct PendingConfigChange {
+    pub registry: Pubkey,
+    pub proposer: Pubkey,
+    pub change: ConfigChange,
+    pub proposed_at: i64,
+    pub unlock_at: i64,
+    pub bump: u8
+}
+
+impl PendingConfigChange {
+    // Fixed-size account, so the derived InitSpace is exact
+    pub const SPACE: usize = Self::INIT_SPACE;
+
+    pub fn ensure_unlocked(&self, now: i64) -> Result<()> {
+        require!(now >= self.unlock_at, ErrorCode::TimelockActive);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{CONFIG_TIMELOCK_DELAY, MAX_PLATFORM_FEE_BPS};
+    use crate::state::Registry;
+
+    fn pending(change: ConfigChange, proposed_at: i64) -> PendingConfigChange {
+        PendingConfigChange {
+            registry: Pubkey::new_unique(),
+            proposer: Pubkey::new_unique(),
+            change,
+            proposed_at,
+            unlock_at: proposed_at + CONFIG_TIMELOCK_DELAY,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn early_execution_is_rejected() {
+        let pending = pending(ConfigChange::SetPlatformFee { fee_bps: 250 }, 1_000);
+
+        assert_eq!(
+            pending.ensure_unlocked(1_000).unwrap_err(),
+            ErrorCode::TimelockActive.into()
+        );
+        assert_eq!(
+            pending.ensure_unlocked(pending.unlock_at - 1).unwrap_err(),
+            ErrorCode::TimelockActive.into()
+        );
+    }
+
+    #[test]
+    fn delayed_execution_applies_change() {
+        let mut registry = Registry::sample();
+        let new_admin = Pubkey::new_unique();
+
+        for change in [
+            ConfigChange::SetPlatformFee { fee_bps: 250 },
+            ConfigChange::SetMinQuality { min_quality_score: 40 },
+            ConfigChange::TransferAdmin { new_admin },
+            ConfigChange::SetTreasuryWithdrawalCap { lamports: 2_000_000_000 },
+        ] {
+            let pending = pending(change, 1_000);
+            pending.ensure_unlocked(pending.unlock_at).unwrap();
+            registry.apply_config_change(&pending.change).unwrap();
+        }
+
+        assert_eq!(registry.platform_fee_bps, 250);
+        assert_eq!(registry.min_quality_score, 40);
+        assert_eq!(registry.admin, new_admin);
+        assert_eq!(registry.treasury_withdrawal_cap, 2_000_000_000);
+    }
+
+    #[test]
+    fn invalid_changes_are_rejected() {
+        let mut registry = Registry::sample();
+
+        assert_eq!(
+            registry
+                .apply_config_change(&ConfigChange::SetPlatformFee { fee_bps: MAX_PLATFORM_FEE_BPS + 1 })
+                .unwrap_err(),
+            ErrorCode::InvalidPlatformFee.into()
+        );
+        assert_eq!(
+            registry
+                .apply_config_change(&ConfigChange::SetMinQuality { min_quality_score: 101 })
+                .unwrap_err(),
+            ErrorCode::InvalidQualityScore.into()
+        );
+        assert_eq!(registry.platform_fee_bps, 0);
+    }
+}
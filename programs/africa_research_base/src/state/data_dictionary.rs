@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_COLUMNS;
+use crate::error::ErrorCode;
+use crate::state::Dataset;
+
+// `dtype` is a client-defined type code; the program only stores it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct ColumnDef {
+    pub name: [u8; 32], // Zero-padded
+    pub dtype: u8,
+}
+
+// Column-level schema of a tabular dataset, one entry per column in order
+#[account]
+#[derive(InitSpace)]
+pub struct DataDictionary {
+    pub dataset: Pubkey,
+    #[max_len(MAX_COLUMNS)]
+    pub columns: Vec<ColumnDef>,
+    pub updated_at: i64,
+    pub bump: u8
+}
+
+impl DataDictionary {
+    pub const SPACE: usize = Self::INIT_SPACE;
+
+    // Replaces the columns; there must be exactly one per column the dataset declares
+    pub fn set(&mut self, dataset: &Dataset, columns: Vec<ColumnDef>, now: i64, bump: u8) -> Result<()> {
+        require!(columns.len() as u64 == dataset.column_count, ErrorCode::ColumnCountMismatch);
+
+        *self = DataDictionary { dataset: dataset.id, columns, updated_at: now, bump };
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(label: &[u8], dtype: u8) -> ColumnDef {
+        let mut name = [0u8; 32];
+        name[..label.len()].copy_from_slice(label);
+        ColumnDef { name, dtype }
+    }
+
+    fn empty() -> DataDictionary {
+        DataDictionary { dataset: Pubkey::default(), columns: Vec::new(), updated_at: 0, bump: 0 }
+    }
+
+    #[test]
+    fn matching_dictionary_is_stored() {
+        let dataset = Dataset { column_count: 2, ..Dataset::sample() };
+        let columns = vec![column(b"region", 1), column(b"rainfall_mm", 2)];
+        let mut dictionary = empty();
+
+        dictionary.set(&dataset, columns.clone(), 1_700_000_000, 254).unwrap();
+
+        assert_eq!(dictionary.dataset, dataset.id);
+        assert_eq!(dictionary.columns, columns);
+        assert_eq!(dictionary.updated_at, 1_700_000_000);
+        assert_eq!(dictionary.bump, 254);
+    }
+
+    #[test]
+    fn column_count_mismatch_is_rejected() {
+        let dataset = Dataset { column_count: 3, ..Dataset::sample() };
+        let mut dictionary = empty();
+
+        assert_eq!(
+            dictionary.set(&dataset, vec![column(b"region", 1), column(b"year", 3)], 1_700_000_000, 254).unwrap_err(),
+            ErrorCode::ColumnCountMismatch.into()
+        );
+        assert!(dictionary.columns.is_empty());
+    }
+
+    #[test]
+    fn full_dictionary_fits_its_allocation() {
+        let dataset = Dataset { column_count: MAX_COLUMNS as u64, ..Dataset::sample() };
+        let mut dictionary = empty();
+        dictionary.set(&dataset, vec![column(b"c", 0); MAX_COLUMNS], 0, 255).unwrap();
+
+        let mut data = Vec::new();
+        dictionary.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), 8 + DataDictionary::SPACE);
+    }
+}
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+use crate::constants::MAX_REQUEST_TOPIC_LEN;
+use crate::error::ErrorCode;
+use crate::state::Dataset;
+
+// A researcher's call for a dataset that doesn't exist yet, with any bounty escrowed in this account
+#[account]
+#[derive(InitSpace)]
+pub struct DataRequest {
+    pub requester: Pubkey,
+    pub registry: Pubkey, // Only datasets in this registry can fulfill the request
+    pub topic_hash: [u8; 32], // Hash of `topic`, part of the seeds
+    #[max_len(MAX_REQUEST_TOPIC_LEN)]
+    pub topic: Vec<u8>,
+    pub description_hash: [u8; 32], // Hash of the off-chain description of the data wanted
+    pub bounty: u64, // Lamports escrowed above rent, paid to the fulfilling dataset's contributor
+    pub created_at: i64,
+    pub fulfilled_by: Option<Pubkey>, // Dataset accepted by the requester
+    pub fulfilled_at: Option<i64>,
+    pub bump: u8
+}
+
+impl DataRequest {
+    pub const SPACE: usize = Self::INIT_SPACE;
+
+    pub fn validate_topic(topic: &[u8], topic_hash: &[u8; 32]) -> Result<()> {
+        require!(
+            !topic.is_empty() && topic.len() <= MAX_REQUEST_TOPIC_LEN && hash(topic).to_bytes() == *topic_hash,
+            ErrorCode::InvalidRequestTopic
+        );
+        Ok(())
+    }
+
+    // Links `dataset` to the request and returns the bounty now owed to its contributor
+    pub fn fulfill(&mut self, dataset: &Dataset, now: i64) -> Result<u64> {
+        require!(self.fulfilled_by.is_none(), ErrorCode::RequestAlreadyFulfilled);
+        require!(dataset.is_active, ErrorCode::DatasetInactive);
+        require!(dataset.upload_timestamp >= self.created_at, ErrorCode::DatasetPredatesRequest);
+
+        self.fulfilled_by = Some(dataset.id);
+        self.fulfilled_at = Some(now);
+        Ok(self.bounty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(created_at: i64) -> DataRequest {
+        let topic = b"Rainfall in the Sahel, 1990-2020".to_vec();
+        DataRequest {
+            requester: Pubkey::new_unique(),
+            registry: Pubkey::new_unique(),
+            topic_hash: hash(&topic).to_bytes(),
+            topic,
+            description_hash: [1u8; 32],
+            bounty: 5_000_000,
+            created_at,
+            fulfilled_by: None,
+            fulfilled_at: None,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn newer_dataset_fulfills_the_request_once() {
+        let mut request = request(1_000);
+        let dataset = Dataset { upload_timestamp: 1_500, ..Dataset::sample() };
+
+        assert_eq!(request.fulfill(&dataset, 2_000).unwrap(), 5_000_000);
+        assert_eq!(request.fulfilled_by, Some(dataset.id));
+        assert_eq!(request.fulfilled_at, Some(2_000));
+
+        let other = Dataset { upload_timestamp: 1_500, ..Dataset::sample() };
+        assert_eq!(request.fulfill(&other, 2_000).unwrap_err(), ErrorCode::RequestAlreadyFulfilled.into());
+    }
+
+    #[test]
+    fn older_or_inactive_datasets_cannot_fulfill() {
+        let mut request = request(1_000);
+        let older = Dataset { upload_timestamp: 999, ..Dataset::sample() };
+        let inactive = Dataset { upload_timestamp: 1_500, is_active: false, ..Dataset::sample() };
+
+        assert_eq!(request.fulfill(&older, 2_000).unwrap_err(), ErrorCode::DatasetPredatesRequest.into());
+        assert_eq!(request.fulfill(&inactive, 2_000).unwrap_err(), ErrorCode::DatasetInactive.into());
+        assert_eq!(request.fulfilled_by, None);
+    }
+
+    #[test]
+    fn topic_must_be_bounded_and_match_its_hash() {
+        let topic = b"Rainfall";
+        DataRequest::validate_topic(topic, &hash(topic).to_bytes()).unwrap();
+
+        let long = [b'a'; MAX_REQUEST_TOPIC_LEN + 1];
+        for (topic, topic_hash) in [(&b""[..], hash(b"").to_bytes()), (&long, hash(&long).to_bytes()), (topic, [0u8; 32])] {
+            assert_eq!(DataRequest::validate_topic(topic, &topic_hash).unwrap_err(), ErrorCode::InvalidRequestTopic.into());
+        }
+    }
+}
@@ -1,26 +1,2001 @@
 use anchor_lang::prelude::*;
 
+use anchor_lang::Space;
+
+use crate::anonymity::published_key;
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::state::{DatasetV1, DatasetV2, DatasetV3, FieldScore, Reputation};
+use crate::uri::uri_bytes;
+
+// One co-contributor's share of a finalized upload, kept so close and merge can take it back
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CoContributorCredit {
+    pub contributor: Pubkey, // Follows rotate_contributor_key like co_contributors
+    pub quality: u8,
+    pub field_points: Vec<FieldScore>, // At most MAX_RESEARCH_FIELDS
+}
+
+impl CoContributorCredit {
+    pub const SPACE: usize = 32 + 1 + 4 + (1 + 4) * MAX_RESEARCH_FIELDS;
+}
+
 #[account]
-#[derive(InitSpace)]
 pub struct Dataset {
     pub id: Pubkey,
+    pub registry: Pubkey,
     pub contributor: Pubkey,
+    pub co_contributors: Vec<Pubkey>, // At most MAX_CO_CONTRIBUTORS, each sharing the upload credit
+    pub editors: Vec<Pubkey>, // At most MAX_EDITORS, allowed to update the dataset alongside the contributor
     pub content_hash: [u8; 32],
+    pub preview_hash: [u8; 32], // Hash of the first `preview_byte_count(file_size)` bytes
 
-    #[max_len(1500)]
-    pub ai_metadata: Vec<u8>,
-
-    #[max_len(100)]
-    pub file_name: Vec<u8>,
+    pub ai_metadata: Vec<u8>, // At most MAX_AI_METADATA_LEN bytes
+    pub file_name: Vec<u8>, // At most MAX_FILE_NAME_LEN bytes
+    pub research_fields: Vec<u8>, // Distinct field ids, at most MAX_RESEARCH_FIELDS
     pub dataset_index: u32,
     pub file_size: u64,
-    pub data_uri: [u8; 256],
+    pub data_uri: Vec<u8>, // At most DATA_URI_LEN bytes, stored without padding
+    pub uri_locked: bool, // Set once by the contributor; data_uri can never change afterwards
+    pub thumbnail_uri: Option<[u8; 128]>, // Small preview image or data sample, omitted for text datasets
     pub column_count: u64,
     pub row_count: u64,
     pub quality_score: u8,
     pub upload_timestamp: i64,
     pub last_updated: Option<i64>,
-    pub download_count: u32,
+    pub download_count: u32, // Kept at u32 to preserve the account layout; increments are checked, never wrapped
+    pub download_fee: u64, // Lamports per download, 0 for free datasets
+    pub download_cap: u32, // Downloads one account may make per window, 0 for unlimited
+    pub download_window: i64, // Window length in seconds for download_cap
+    pub rating_sum: u64, // Each rating times its rater's weight
+    pub rating_count: u32,
+    pub citation_count: u32,
     pub is_active: bool,
-    pub bump: u8
-}
\ No newline at end of file
+    pub featured: bool, // High-visibility listing, reserved for trusted contributors
+    pub expires_at: Option<i64>, // Time-sensitive datasets are archived once this passes
+    pub reputation_finalized: bool, // Upload credit applied to the contributor's reputation
+    pub review_status: u8, // One of the REVIEW_* constants
+    pub reviewer: Pubkey, // Default until an admin assigns one
+    pub review_note_hash: [u8; 32], // Hash of the reviewer's off-chain notes
+    pub forked_from: Pubkey, // Source dataset for forks, default for originals
+    pub fork_count: u32,
+    pub frozen: bool, // Moderation hold; unlike archiving, only an admin action sets it
+    pub unavailable: bool, // Taken offline after enough dead-URI reports; a new data_uri clears it
+    pub quality_hidden: bool, // Taken offline because rating-derived quality fell below the registry floor
+    pub pinned_until: i64, // Storage pinning is funded up to this time, 0 if never funded
+    pub pin_seconds_funded: u64, // Cumulative pin time ever paid for
+    pub endorsement_count: u32, // Public endorsements, one per endorser; separate from ratings
+    pub rating_weight: u64, // Sum of the weights behind rating_sum
+    pub title: Vec<u8>, // Human-readable name, at most MAX_TITLE_LEN bytes; file_name stays the literal on-disk name
+    pub license_expires_at: Option<i64>, // End of a time-limited data-use license; downloads stop until renewed
+    pub supersedes: Option<Pubkey>, // Older dataset, possibly another author's, that this one replaces; unrelated to forked_from
+    pub superseded_by: Option<Pubkey>, // Set on the replaced dataset, which stays inactive from then on
+    pub quality_disputed: bool, // Self-reported quality sat well above the community's when ratings replaced it
+    pub bond: u64, // Quality bond lamports held in this account on top of its rent; 0 once forfeited
+    pub locked: bool, // Held while a guarded instruction is mid-mutation; see lock()
+    pub anonymous: bool, // The contributor's Reputation::anonymous when the dataset was created
+    pub review_deadline: i64, // When an unreviewed dataset becomes auto-approvable; 0 for never
+    pub featured_until: i64, // End of the featured promotion; 0 keeps a featured dataset featured
+    // Marked final for citation: content, metadata and editors can no longer change and it cannot be
+    // closed or merged away. One-way; usage counters, reviews and moderation still apply
+    pub finalized: bool,
+    pub spam_deposit: u64, // Anti-spam deposit lamports held on top of rent and bond; refunded by the first qualifying download, forfeited on close before one
+    pub language: Option<[u8; 2]>, // Declared lowercase ISO 639-1 code of the content
+    pub language_mismatch: bool, // Warning only: structured ai_metadata detected a different language than declared
+    // Key the PDA was derived from; fixed at creation, while contributor follows rotate_contributor_key
+    pub creator: Pubkey,
+    // Exactly what finalize_reputation credited the contributor, so closing takes back the same amount
+    // even after the quality is re-rated or field weights change
+    pub credited_quality: u8,
+    pub credited_field_points: Vec<FieldScore>, // At most MAX_RESEARCH_FIELDS
+    pub parent: Option<Pubkey>, // Previous version by the same contributor; unlike forked_from, same lineage and owner
+    pub co_credits: Vec<CoContributorCredit>, // What finalize_reputation credited each co-contributor, at most MAX_CO_CONTRIBUTORS
+    pub bump: u8,
+    pub schema_version: u8, // DATASET_SCHEMA_VERSION; kept last so layout changes stay easy to detect
+}
+
+impl Dataset {
+    // Serialized size excluding the 8-byte discriminator; update alongside any field change
+    pub const SPACE: usize = 32 * 5 // id, registry, contributor, content_hash, preview_hash
+        + 4 + 32 * MAX_CO_CONTRIBUTORS // co_contributors
+        + 4 + 32 * MAX_EDITORS // editors
+        + 4 + MAX_AI_METADATA_LEN // ai_metadata
+        + 4 + MAX_FILE_NAME_LEN // file_name
+        + 4 + MAX_RESEARCH_FIELDS // research_fields
+        + 4 // dataset_index
+        + 8 // file_size
+        + 4 + DATA_URI_LEN // data_uri
+        + 1 // uri_locked
+        + 1 + THUMBNAIL_URI_LEN // thumbnail_uri
+        + 8 + 8 // column_count, row_count
+        + 1 // quality_score
+        + 8 // upload_timestamp
+        + 1 + 8 // last_updated
+        + 4 // download_count
+        + 8 // download_fee
+        + 4 + 8 // download_cap, download_window
+        + 8 + 4 // rating_sum, rating_count
+        + 4 // citation_count
+        + 1 // is_active
+        + 1 // featured
+        + 1 + 8 // expires_at
+        + 1 // reputation_finalized
+        + 1 // review_status
+        + 32 // reviewer
+        + 32 // review_note_hash
+        + 32 // forked_from
+        + 4 // fork_count
+        + 1 // frozen
+        + 1 // unavailable
+        + 1 // quality_hidden
+        + 8 + 8 // pinned_until, pin_seconds_funded
+        + 4 // endorsement_count
+        + 8 // rating_weight
+        + 4 + MAX_TITLE_LEN // title
+        + 1 + 8 // license_expires_at
+        + 1 + 32 // supersedes
+        + 1 + 32 // superseded_by
+        + 1 // quality_disputed
+        + 8 // bond
+        + 1 // locked
+        + 1 // anonymous
+        + 8 // review_deadline
+        + 8 // featured_until
+        + 1 // finalized
+        + 8 // spam_deposit
+        + 1 + 2 // language
+        + 1 // language_mismatch
+        + 32 // creator
+        + 1 // credited_quality
+        + 4 + (1 + 4) * MAX_RESEARCH_FIELDS // credited_field_points
+        + 1 + 32 // parent
+        + 4 + CoContributorCredit::SPACE * MAX_CO_CONTRIBUTORS // co_credits
+        + 1 // bump
+        + 1; // schema_version
+
+    // Allocation for a dataset whose ai_metadata was expanded past the MAX_AI_METADATA_LEN SPACE covers
+    pub fn space_with_ai_metadata(ai_metadata_len: usize) -> usize {
+        Self::SPACE + ai_metadata_len.saturating_sub(MAX_AI_METADATA_LEN)
+    }
+
+    // Appends to ai_metadata within the growth caps and returns the space the account now needs
+    pub fn expand_ai_metadata(&mut self, extra: &[u8]) -> Result<usize> {
+        require!(extra.len() <= MAX_AI_METADATA_GROWTH, ErrorCode::GrowthExceeded);
+        let len = self.ai_metadata.len() + extra.len();
+        require!(len <= MAX_AI_METADATA, ErrorCode::GrowthExceeded);
+
+        self.ai_metadata.extend_from_slice(extra);
+        Ok(Self::space_with_ai_metadata(len))
+    }
+
+    // Downloads discounted by age, so a new dataset needs fewer downloads than an old one to rank
+    // as high. Datasets uploaded "in the future" by clock skew count as brand new.
+    pub fn trending_score_of(download_count: u32, upload_timestamp: i64, now: i64) -> u64 {
+        let age_hours = now.saturating_sub(upload_timestamp).max(0) as u64 / 3_600;
+        let decay = (age_hours + 2).saturating_pow(TRENDING_GRAVITY);
+        download_count as u64 * TRENDING_SCALE / decay
+    }
+
+    pub fn trending_score(&self, now: i64) -> u64 {
+        Self::trending_score_of(self.download_count, self.upload_timestamp, now)
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        matches!(self.expires_at, Some(expires_at) if now > expires_at)
+    }
+
+    // Unlike `is_expired` this never archives the dataset; renewing the license lifts it
+    pub fn is_license_expired(&self, now: i64) -> bool {
+        matches!(self.license_expires_at, Some(license_expires_at) if now > license_expires_at)
+    }
+
+    // Licenses only ever move forward, so a renewal cannot shorten terms downloaders agreed to
+    pub fn renew_license(&mut self, license_expires_at: i64, now: i64) -> Result<()> {
+        require!(license_expires_at > now, ErrorCode::InvalidLicenseExpiry);
+        if let Some(current) = self.license_expires_at {
+            require!(license_expires_at > current, ErrorCode::InvalidLicenseExpiry);
+        }
+        self.license_expires_at = Some(license_expires_at);
+        Ok(())
+    }
+
+    // Bytes covered by `preview_hash`: the first PREVIEW_BYTES of the file, or all of a smaller file
+    pub const fn preview_byte_count(file_size: u64) -> u64 {
+        if file_size < PREVIEW_BYTES {
+            file_size
+        } else {
+            PREVIEW_BYTES
+        }
+    }
+
+    // Files too large to hash cheaply client-side must carry a preview hash
+    pub fn validate_preview_hash(file_size: u64, preview_hash: &[u8; 32]) -> Result<()> {
+        if file_size > PREVIEW_BYTES {
+            require!(*preview_hash != [0u8; 32], ErrorCode::PreviewHashRequired);
+        }
+        Ok(())
+    }
+
+    // Adds `count` downloads, failing rather than wrapping at u32::MAX; the count is left untouched on error
+    pub fn record_downloads(&mut self, count: u32) -> Result<()> {
+        self.download_count = self.download_count
+            .checked_add(count)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        Ok(())
+    }
+
+    // Removes up to `count` disputed downloads, floored at zero; returns how many were removed
+    pub fn revert_downloads(&mut self, count: u32) -> u32 {
+        let removed = count.min(self.download_count);
+        self.download_count -= removed;
+        removed
+    }
+
+    // Folds a duplicate registration's usage counters into this canonical dataset
+    pub fn absorb(&mut self, duplicate: &Dataset) -> Result<()> {
+        require!(self.content_hash == duplicate.content_hash, ErrorCode::HashMismatch);
+
+        self.record_downloads(duplicate.download_count)?;
+        self.rating_sum = self.rating_sum
+            .checked_add(duplicate.rating_sum)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        self.rating_count = self.rating_count
+            .checked_add(duplicate.rating_count)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        self.rating_weight = self.rating_weight
+            .checked_add(duplicate.rating_weight)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        self.citation_count = self.citation_count
+            .checked_add(duplicate.citation_count)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        Ok(())
+    }
+
+    pub fn validate_co_contributors(contributor: &Pubkey, co_contributors: &[Pubkey]) -> Result<()> {
+        require!(co_contributors.len() <= MAX_CO_CONTRIBUTORS, ErrorCode::TooManyCoContributors);
+        for (i, co_contributor) in co_contributors.iter().enumerate() {
+            require!(
+                co_contributor != contributor && !co_contributors[..i].contains(co_contributor),
+                ErrorCode::DuplicateCoContributor
+            );
+        }
+        Ok(())
+    }
+
+    // Accepts zero-padded input from older clients; padding is dropped before storing
+    pub fn set_data_uri(&mut self, data_uri: &[u8]) -> Result<()> {
+        require!(!self.uri_locked, ErrorCode::UriLocked);
+        require!(data_uri.len() <= DATA_URI_LEN, ErrorCode::DataUriTooLong);
+        self.data_uri = uri_bytes(data_uri).to_vec();
+        Ok(())
+    }
+
+    // Re-encodes an account (discriminator included) in an earlier schema into the current layout.
+    // Schemas 1 and 2 are told apart by their fixed allocation, schema 3 by its schema_version
+    pub fn from_legacy(data: &[u8]) -> Result<Dataset> {
+        require!(data.get(..8) == Some(Dataset::DISCRIMINATOR), ErrorCode::NotLegacyDataset);
+        let fields = &data[8..];
+        let v3 = match fields.len() {
+            DatasetV1::SPACE => decode_prefix::<DatasetV1>(fields).map(DatasetV2::from).map(DatasetV3::from),
+            DatasetV2::SPACE => decode_prefix::<DatasetV2>(fields)
+                .filter(|v2| v2.schema_version == DatasetV2::SCHEMA_VERSION)
+                .map(DatasetV3::from),
+            // expand_ai_metadata may have grown a schema 3 account past its SPACE
+            len if len >= DatasetV3::SPACE => decode_prefix::<DatasetV3>(fields)
+                .filter(|v3| v3.schema_version == DatasetV3::SCHEMA_VERSION),
+            _ => None,
+        };
+        v3.map(Dataset::from).ok_or_else(|| error!(ErrorCode::NotLegacyDataset))
+    }
+
+    // One-way: there is deliberately no way to unlock
+    pub fn lock_data_uri(&mut self) -> Result<()> {
+        require!(!self.uri_locked, ErrorCode::UriLocked);
+        self.uri_locked = true;
+        Ok(())
+    }
+
+    // Drops trailing whitespace, which is invisible in listings and only causes near-duplicate names
+    pub fn trim_file_name(file_name: &mut Vec<u8>) {
+        while file_name.last().is_some_and(u8::is_ascii_whitespace) {
+            file_name.pop();
+        }
+    }
+
+    // Names are shown and logged verbatim, so they must be non-empty UTF-8 without control characters
+    pub fn validate_file_name(file_name: &[u8]) -> Result<()> {
+        let valid = std::str::from_utf8(file_name)
+            .is_ok_and(|name| !name.is_empty() && !name.chars().any(char::is_control));
+        require!(valid, ErrorCode::InvalidFileName);
+        Ok(())
+    }
+
+    // Titles are searched through the token index and shown like file names, under the same rules
+    pub fn validate_title(title: &[u8]) -> Result<()> {
+        require!(title.len() <= MAX_TITLE_LEN, ErrorCode::TitleTooLong);
+        Self::validate_file_name(title).map_err(|_| error!(ErrorCode::InvalidTitle))
+    }
+
+    pub fn validate_language(language: &[u8; 2]) -> Result<()> {
+        require!(language.iter().all(u8::is_ascii_alphabetic), ErrorCode::InvalidLanguageCode);
+        Ok(())
+    }
+
+    // Codes are compared case-insensitively; nothing declared or nothing detected is no conflict
+    pub fn language_conflicts(declared: Option<[u8; 2]>, detected: Option<[u8; 2]>) -> bool {
+        matches!((declared, detected), (Some(declared), Some(detected)) if !declared.eq_ignore_ascii_case(&detected))
+    }
+
+    // An uninitialized client value reads as 0, so a zero score must be asked for explicitly
+    pub fn validate_quality_intent(quality_score: u8, allow_zero_quality: bool) -> Result<()> {
+        require!(quality_score != 0 || allow_zero_quality, ErrorCode::ZeroQualityScore);
+        Ok(())
+    }
+
+    pub fn validate_research_fields(research_fields: &[u8]) -> Result<()> {
+        require!(research_fields.len() <= MAX_RESEARCH_FIELDS, ErrorCode::TooManyResearchFields);
+        for (i, field) in research_fields.iter().enumerate() {
+            require!(!research_fields[..i].contains(field), ErrorCode::DuplicateResearchField);
+        }
+        Ok(())
+    }
+
+    // Adds the fields not listed yet; the merged list is held to the same cap as at creation
+    pub fn add_research_fields(&mut self, research_fields: &[u8]) -> Result<()> {
+        Self::validate_research_fields(research_fields)?;
+        let new_fields = research_fields.iter().filter(|field| !self.research_fields.contains(field));
+        let merged: Vec<u8> = self.research_fields.iter().chain(new_fields).copied().collect();
+        require!(merged.len() <= MAX_RESEARCH_FIELDS, ErrorCode::TooManyResearchFields);
+
+        self.research_fields = merged;
+        Ok(())
+    }
+
+    // Splits the quality score evenly across all authors as (primary share, each co-contributor's
+    // share); the primary contributor keeps any remainder so the shares always sum to quality_score
+    pub fn quality_shares(&self) -> Result<(u8, u8)> {
+        let co_authors = self.co_contributors.len() as u8;
+        let authors = co_authors.checked_add(1).ok_or(ErrorCode::NumericalOverflow)?;
+        let share = self.quality_score / authors;
+        let co_total = share.checked_mul(co_authors).ok_or(ErrorCode::NumericalOverflow)?;
+        let primary = self.quality_score.checked_sub(co_total).ok_or(ErrorCode::NumericalOverflow)?;
+        require!(
+            primary.checked_add(co_total) == Some(self.quality_score),
+            ErrorCode::NumericalOverflow
+        );
+        Ok((primary, share))
+    }
+
+    // Stable, versioned export independent of the account layout. After the version byte, in order:
+    // registry, published contributor, content_hash, preview_hash (32 bytes each), file_size, column_count,
+    // row_count (u64 LE), quality_score (u8), upload_timestamp (i64 LE), then file_name and the
+    // trimmed data_uri, each as a u32 LE length followed by the bytes. Usage counters, review
+    // state and the account address are deliberately left out. Version 2 exports the pseudonym for an
+    // anonymous dataset where version 1 leaked the real key.
+    pub fn to_canonical_bytes(&self) -> Vec<u8> {
+        let data_uri = uri_bytes(&self.data_uri);
+        let mut bytes = Vec::with_capacity(1 + 32 * 4 + 8 * 5 + 1 + 8 + self.file_name.len() + data_uri.len());
+
+        bytes.push(CANONICAL_LAYOUT_VERSION);
+        bytes.extend_from_slice(self.registry.as_ref());
+        bytes.extend_from_slice(self.published_contributor().as_ref());
+        bytes.extend_from_slice(&self.content_hash);
+        bytes.extend_from_slice(&self.preview_hash);
+        bytes.extend_from_slice(&self.file_size.to_le_bytes());
+        bytes.extend_from_slice(&self.column_count.to_le_bytes());
+        bytes.extend_from_slice(&self.row_count.to_le_bytes());
+        bytes.push(self.quality_score);
+        bytes.extend_from_slice(&self.upload_timestamp.to_le_bytes());
+        for field in [self.file_name.as_slice(), data_uri] {
+            bytes.extend_from_slice(&(field.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(field);
+        }
+        bytes
+    }
+
+    // Hands a submitted dataset to a reviewer; reassigning is allowed until the review is in
+    pub fn assign_reviewer(&mut self, reviewer: Pubkey) -> Result<()> {
+        require!(
+            matches!(self.review_status, REVIEW_SUBMITTED | REVIEW_UNDER_REVIEW),
+            ErrorCode::InvalidReviewStatus
+        );
+        require!(reviewer != Pubkey::default(), ErrorCode::UnauthorizedReviewer);
+        self.reviewer = reviewer;
+        self.review_status = REVIEW_UNDER_REVIEW;
+        Ok(())
+    }
+
+    pub fn submit_review(&mut self, reviewer: &Pubkey, approved: bool, note_hash: [u8; 32]) -> Result<()> {
+        require!(self.review_status == REVIEW_UNDER_REVIEW, ErrorCode::InvalidReviewStatus);
+        require!(self.reviewer == *reviewer, ErrorCode::UnauthorizedReviewer);
+        self.review_status = if approved { REVIEW_APPROVED } else { REVIEW_REJECTED };
+        self.review_note_hash = note_hash;
+        Ok(())
+    }
+
+    // Deadline for a dataset submitted at `now`; an SLA of 0 sets none
+    pub fn review_deadline_for(now: i64, review_sla: i64) -> i64 {
+        if review_sla == 0 {
+            return 0;
+        }
+        now.saturating_add(review_sla)
+    }
+
+    // Approves a dataset no reviewer decided on by its deadline, whether or not one was assigned
+    pub fn auto_approve(&mut self, now: i64) -> Result<()> {
+        require!(
+            matches!(self.review_status, REVIEW_SUBMITTED | REVIEW_UNDER_REVIEW),
+            ErrorCode::InvalidReviewStatus
+        );
+        require!(self.review_deadline != 0, ErrorCode::AutoApproveDisabled);
+        require!(now >= self.review_deadline, ErrorCode::ReviewDeadlineNotReached);
+        self.review_status = REVIEW_APPROVED;
+        Ok(())
+    }
+
+    // Ends a featured promotion whose window has closed
+    pub fn unfeature_expired(&mut self, now: i64) -> Result<()> {
+        require!(self.featured, ErrorCode::NotFeatured);
+        require!(self.featured_until != 0 && now >= self.featured_until, ErrorCode::FeatureNotExpired);
+        self.featured = false;
+        self.featured_until = 0;
+        Ok(())
+    }
+
+    // Stamps a change at `now`. A validator clock running behind the last change is rejected rather
+    // than clamped, so last_updated never moves backwards and no edit is recorded at a time it did not happen
+    pub fn touch(&mut self, now: i64) -> Result<()> {
+        require!(!matches!(self.last_updated, Some(last) if now < last), ErrorCode::NonMonotonicTimestamp);
+        self.last_updated = Some(now);
+        Ok(())
+    }
+
+    pub fn finalize(&mut self) -> Result<()> {
+        require!(!self.finalized, ErrorCode::DatasetFinalized);
+        self.finalized = true;
+        Ok(())
+    }
+
+    // Hands back the anti-spam deposit on the first qualifying download: by someone other than the
+    // contributor, a co-contributor or an editor, and made after SPAM_DEPOSIT_HOLD_PERIOD. A fee does
+    // not shorten the hold, since the contributor sets it and collects it from a fresh key of their
+    // own. Returns the lamports to refund; 0 when none is held or the download does not qualify
+    pub fn release_spam_deposit(&mut self, downloader: &Pubkey, now: i64) -> u64 {
+        let insider = self.contributor == *downloader
+            || self.co_contributors.contains(downloader)
+            || self.editors.contains(downloader);
+        let held = now < self.upload_timestamp.saturating_add(SPAM_DEPOSIT_HOLD_PERIOD);
+        if insider || held {
+            return 0;
+        }
+        std::mem::take(&mut self.spam_deposit)
+    }
+
+    // A dataset closed before any qualifying download sends its deposit to the treasury; returns the
+    // lamports to move
+    pub fn forfeit_spam_deposit(&mut self) -> u64 {
+        std::mem::take(&mut self.spam_deposit)
+    }
+
+    pub fn ensure_downloadable(&self, curated: bool) -> Result<()> {
+        if curated {
+            require!(self.review_status == REVIEW_APPROVED, ErrorCode::DatasetNotApproved);
+        }
+        Ok(())
+    }
+
+    // Marks the upload credit applied and remembers it for close to reverse
+    pub fn record_upload_credit(
+        &mut self,
+        quality_score: u8,
+        field_points: Vec<FieldScore>,
+        co_credits: Vec<CoContributorCredit>
+    ) {
+        self.reputation_finalized = true;
+        self.credited_quality = quality_score;
+        self.credited_field_points = field_points;
+        self.co_credits = co_credits;
+    }
+
+    pub fn ensure_reputation_finalizable(&self, now: i64, grace_period: i64) -> Result<()> {
+        require!(!self.reputation_finalized, ErrorCode::ReputationAlreadyFinalized);
+        require!(
+            now >= self.upload_timestamp.saturating_add(grace_period),
+            ErrorCode::GracePeriodActive
+        );
+        Ok(())
+    }
+
+    // A fork starts a new lineage under the forker: the content description carries over,
+    // while ownership, usage counters, review and reputation state start fresh
+    pub fn fork(&self, id: Pubkey, contributor: Pubkey, dataset_index: u32, now: i64, bump: u8) -> Result<Dataset> {
+        require!(contributor != self.contributor, ErrorCode::SelfFork);
+        require!(self.is_active, ErrorCode::DatasetInactive);
+        require!(!self.is_expired(now), ErrorCode::DatasetExpired);
+
+        Ok(Dataset {
+            id,
+            registry: self.registry,
+            contributor,
+            co_contributors: Vec::new(),
+            editors: Vec::new(),
+            content_hash: self.content_hash,
+            preview_hash: self.preview_hash,
+            ai_metadata: self.ai_metadata.clone(),
+            file_name: self.file_name.clone(),
+            research_fields: self.research_fields.clone(),
+            dataset_index,
+            file_size: self.file_size,
+            data_uri: self.data_uri.clone(),
+            uri_locked: false,
+            thumbnail_uri: self.thumbnail_uri,
+            column_count: self.column_count,
+            row_count: self.row_count,
+            quality_score: self.quality_score,
+            upload_timestamp: now,
+            last_updated: None,
+            download_count: 0,
+            download_fee: 0,
+            download_cap: 0,
+            download_window: 0,
+            rating_sum: 0,
+            rating_count: 0,
+            citation_count: 0,
+            is_active: true,
+            featured: false,
+            expires_at: None,
+            reputation_finalized: false,
+            review_status: REVIEW_SUBMITTED,
+            reviewer: Pubkey::default(),
+            review_note_hash: [0u8; 32],
+            forked_from: self.id,
+            fork_count: 0,
+            frozen: false,
+            unavailable: false,
+            quality_hidden: false,
+            pinned_until: 0,
+            pin_seconds_funded: 0,
+            endorsement_count: 0,
+            rating_weight: 0,
+            title: self.title.clone(),
+            license_expires_at: self.license_expires_at,
+            supersedes: None,
+            superseded_by: None,
+            quality_disputed: false,
+            bond: 0,
+            locked: false,
+            anonymous: false,
+            review_deadline: 0,
+            featured_until: 0,
+            finalized: false,
+            spam_deposit: 0,
+            language: self.language,
+            language_mismatch: self.language_mismatch,
+            creator: contributor,
+            credited_quality: 0,
+            credited_field_points: Vec::new(),
+            parent: None,
+            co_credits: Vec::new(),
+            bump,
+            schema_version: DATASET_SCHEMA_VERSION,
+        })
+    }
+
+    // Follows one rotate_contributor_key step: `rotated` is the tombstone of a key this dataset still
+    // names as contributor or co-contributor. The PDA stays derived from `creator`, so only the
+    // authority and credit move
+    pub fn repoint_contributor(&mut self, rotated: &Reputation) -> Result<()> {
+        let (old, new) = (rotated.contributor, rotated.rotated_to);
+        require!(!rotated.is_current(), ErrorCode::InvalidRepoint);
+        require!(!self.locked, ErrorCode::DatasetLocked);
+
+        if self.contributor == old {
+            self.editors.retain(|editor| *editor != new);
+            self.co_contributors.retain(|co| *co != new);
+            self.contributor = new;
+        } else if let Some(co) = self.co_contributors.iter_mut().find(|co| **co == old) {
+            *co = new;
+        } else {
+            return err!(ErrorCode::InvalidRepoint);
+        }
+        // The credit moved with the reputation, so it is taken back from the new key
+        for credit in self.co_credits.iter_mut().filter(|credit| credit.contributor == old) {
+            credit.contributor = new;
+        }
+        Ok(())
+    }
+
+    pub fn add_editor(&mut self, editor: Pubkey) -> Result<()> {
+        require!(
+            editor != self.contributor && !self.editors.contains(&editor),
+            ErrorCode::DuplicateEditor
+        );
+        require!(self.editors.len() < MAX_EDITORS, ErrorCode::TooManyEditors);
+        self.editors.push(editor);
+        Ok(())
+    }
+
+    pub fn remove_editor(&mut self, editor: &Pubkey) -> Result<()> {
+        let position = self.editors
+            .iter()
+            .position(|entry| entry == editor)
+            .ok_or(ErrorCode::EditorNotFound)?;
+        self.editors.remove(position);
+        Ok(())
+    }
+
+    pub fn freeze(&mut self) -> Result<()> {
+        require!(!self.frozen, ErrorCode::DatasetFrozen);
+        self.frozen = true;
+        self.is_active = false;
+        Ok(())
+    }
+
+    // The contributor key events and return data about this dataset carry
+    pub fn published_contributor(&self) -> Pubkey {
+        published_key(&self.contributor, self.anonymous)
+    }
+
+    // Guarded instructions persist the lock before their first CPI, so any nested instruction that
+    // reaches this dataset while the outer one is still running sees it held and is rejected
+    pub fn lock(&mut self) -> Result<()> {
+        require!(!self.locked, ErrorCode::DatasetLocked);
+        self.locked = true;
+        Ok(())
+    }
+
+    pub fn unlock(&mut self) {
+        self.locked = false;
+    }
+
+    // Freezing for a policy violation sends the bond to the treasury; returns the lamports to move
+    pub fn forfeit_bond(&mut self) -> u64 {
+        std::mem::take(&mut self.bond)
+    }
+
+    pub fn mark_unavailable(&mut self) {
+        self.unavailable = true;
+        self.is_active = false;
+    }
+
+    // Brings a dataset taken offline for a dead URI back, unless it is also held for another reason
+    pub fn clear_unavailable(&mut self) {
+        if self.unavailable {
+            self.unavailable = false;
+            self.restore_active();
+        }
+    }
+
+    // Replaces `old` with this dataset. Both must be active, so a link never points at a dataset
+    // that was itself already replaced, and at most one link leaves each dataset.
+    pub fn supersede(&mut self, old: &mut Dataset) -> Result<()> {
+        require!(self.id != old.id, ErrorCode::SelfSupersession);
+        require!(self.is_active && old.is_active, ErrorCode::DatasetInactive);
+        require!(self.supersedes.is_none(), ErrorCode::AlreadySupersedes);
+
+        self.supersedes = Some(old.id);
+        old.superseded_by = Some(self.id);
+        old.is_active = false;
+        Ok(())
+    }
+
+    fn restore_active(&mut self) {
+        self.is_active = !self.frozen && !self.unavailable && !self.quality_hidden && self.superseded_by.is_none();
+    }
+
+    pub fn set_download_cap(&mut self, download_cap: u32, download_window: i64) -> Result<()> {
+        require!(download_cap == 0 || download_window > 0, ErrorCode::InvalidDownloadWindow);
+        self.download_cap = download_cap;
+        self.download_window = download_window;
+        Ok(())
+    }
+
+    // Records a rating, replacing `previous` when the rater is revising an earlier one
+    // `previous` is the rater's earlier (rating, weight), taken back out exactly before the new one counts
+    pub fn rate(&mut self, previous: Option<(u8, u8)>, rating: u8, weight: u8) -> Result<()> {
+        require!((1..=MAX_RATING).contains(&rating), ErrorCode::InvalidRating);
+        match previous {
+            Some((previous, previous_weight)) => {
+                self.rating_sum = self.rating_sum.saturating_sub(previous as u64 * previous_weight as u64);
+                self.rating_weight = self.rating_weight.saturating_sub(previous_weight as u64);
+            }
+            None => {
+                self.rating_count = self.rating_count
+                    .checked_add(1)
+                    .ok_or(ErrorCode::NumericalOverflow)?;
+            }
+        }
+        self.rating_sum = self.rating_sum
+            .checked_add(rating as u64 * weight as u64)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        self.rating_weight = self.rating_weight
+            .checked_add(weight as u64)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        Ok(())
+    }
+
+    // One rating is no consensus; below `min` raters the average is only provisional
+    pub fn ratings_are_meaningful(&self, min: u32) -> bool {
+        self.rating_count >= min
+    }
+
+    // Weighted mean rating in hundredths of a star, e.g. 425 for 4.25; `None` before any rating
+    pub fn average_rating(&self) -> Option<u16> {
+        if self.rating_weight == 0 {
+            return None;
+        }
+        Some((self.rating_sum * 100 / self.rating_weight) as u16)
+    }
+
+    // Weighted mean rating scaled to 0-100; `None` until MIN_RATINGS_FOR_QUALITY raters are in
+    pub fn quality_from_ratings(&self) -> Option<u8> {
+        if self.rating_count < MIN_RATINGS_FOR_QUALITY || self.rating_weight == 0 {
+            return None;
+        }
+        let max_sum = self.rating_weight * MAX_RATING as u64;
+        Some((self.rating_sum * 100 / max_sum) as u8)
+    }
+
+    // Flags a dataset whose current quality, the self-report until ratings first replace it, exceeds
+    // the community's by more than `max_delta`. The flag is sticky; returns whether this call set it.
+    pub fn flag_quality_discrepancy(&mut self, max_delta: u8) -> Result<bool> {
+        let community = self.quality_from_ratings().ok_or(ErrorCode::NotEnoughRatings)?;
+        if self.quality_disputed || self.quality_score <= community.saturating_add(max_delta) {
+            return Ok(false);
+        }
+        self.quality_disputed = true;
+        Ok(true)
+    }
+
+    // Replaces the self-reported quality with the community's; returns whether this hid the dataset
+    pub fn apply_rated_quality(&mut self, floor: u8) -> Result<bool> {
+        let quality = self.quality_from_ratings().ok_or(ErrorCode::NotEnoughRatings)?;
+        self.quality_score = quality;
+
+        if quality < floor && !self.quality_hidden {
+            self.quality_hidden = true;
+            self.is_active = false;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    // Lets the contributor bring back a hidden dataset once revised ratings clear the floor again
+    pub fn reactivate(&mut self, floor: u8) -> Result<()> {
+        require!(self.quality_hidden, ErrorCode::DatasetNotHidden);
+        let quality = self.quality_from_ratings().ok_or(ErrorCode::NotEnoughRatings)?;
+        require!(quality >= floor, ErrorCode::QualityBelowMinimum);
+
+        self.quality_score = quality;
+        self.quality_hidden = false;
+        self.restore_active();
+        Ok(())
+    }
+
+    pub fn is_pin_funded(&self, now: i64) -> bool {
+        self.pinned_until > now
+    }
+
+    // Funding stacks on time already paid for, or starts now if the previous funding lapsed
+    pub fn extend_pin(&mut self, now: i64, days: u32) -> Result<()> {
+        require!(days > 0, ErrorCode::InvalidPinDuration);
+        let seconds = (days as i64)
+            .checked_mul(SECONDS_PER_DAY)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+
+        self.pinned_until = self.pinned_until
+            .max(now)
+            .checked_add(seconds)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        self.pin_seconds_funded = self.pin_seconds_funded
+            .checked_add(seconds as u64)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        Ok(())
+    }
+
+    pub fn record_endorsement(&mut self) -> Result<()> {
+        self.endorsement_count = self.endorsement_count
+            .checked_add(1)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        Ok(())
+    }
+
+    pub fn record_fork(&mut self) -> Result<()> {
+        self.fork_count = self.fork_count
+            .checked_add(1)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        Ok(())
+    }
+
+    // Two datasets share a lineage when one is the other's direct parent version. Forks are another
+    // contributor's derivative, not a version, so forked_from doesn't count
+    pub fn is_version_of(&self, other: &Dataset) -> bool {
+        self.contributor == other.contributor
+            && (self.parent == Some(other.id) || other.parent == Some(self.id))
+    }
+
+    // DIFF_* bits for the content fields that differ; research fields compare as a set
+    pub fn diff(&self, other: &Dataset) -> Result<u8> {
+        require!(self.is_version_of(other), ErrorCode::NotInLineage);
+
+        let mut mask = 0;
+        if self.file_size != other.file_size {
+            mask |= DIFF_FILE_SIZE;
+        }
+        if self.row_count != other.row_count {
+            mask |= DIFF_ROW_COUNT;
+        }
+        if self.column_count != other.column_count {
+            mask |= DIFF_COLUMN_COUNT;
+        }
+        if self.content_hash != other.content_hash {
+            mask |= DIFF_CONTENT_HASH;
+        }
+        if self.research_fields.len() != other.research_fields.len()
+            || self.research_fields.iter().any(|field| !other.research_fields.contains(field))
+        {
+            mask |= DIFF_RESEARCH_FIELDS;
+        }
+        Ok(mask)
+    }
+}
+
+impl Space for Dataset {
+    const INIT_SPACE: usize = Self::SPACE;
+}
+
+#[cfg(test)]
+impl Dataset {
+    pub(crate) fn sample() -> Self {
+        let contributor = Pubkey::new_unique();
+        Dataset {
+            id: Pubkey::new_unique(),
+            registry: Pubkey::new_unique(),
+            contributor,
+            co_contributors: Vec::new(),
+            editors: Vec::new(),
+            content_hash: [7u8; 32],
+            preview_hash: [0u8; 32],
+            ai_metadata: Vec::new(),
+            file_name: b"sample.csv".to_vec(),
+            research_fields: Vec::new(),
+            dataset_index: 0,
+            file_size: 4_096,
+            data_uri: Vec::new(),
+            uri_locked: false,
+            thumbnail_uri: None,
+            column_count: 5,
+            row_count: 100,
+            quality_score: 80,
+            upload_timestamp: 1_700_000_000,
+            last_updated: None,
+            download_count: 0,
+            download_fee: 0,
+            download_cap: 0,
+            download_window: 0,
+            rating_sum: 0,
+            rating_count: 0,
+            citation_count: 0,
+            is_active: true,
+            featured: false,
+            expires_at: None,
+            reputation_finalized: false,
+            review_status: REVIEW_SUBMITTED,
+            reviewer: Pubkey::default(),
+            review_note_hash: [0u8; 32],
+            forked_from: Pubkey::default(),
+            fork_count: 0,
+            frozen: false,
+            unavailable: false,
+            quality_hidden: false,
+            pinned_until: 0,
+            pin_seconds_funded: 0,
+            endorsement_count: 0,
+            rating_weight: 0,
+            title: b"Sample dataset".to_vec(),
+            license_expires_at: None,
+            supersedes: None,
+            superseded_by: None,
+            quality_disputed: false,
+            bond: 0,
+            locked: false,
+            anonymous: false,
+            review_deadline: 0,
+            featured_until: 0,
+            finalized: false,
+            spam_deposit: 0,
+            language: None,
+            language_mismatch: false,
+            creator: contributor,
+            credited_quality: 0,
+            credited_field_points: Vec::new(),
+            parent: None,
+            co_credits: Vec::new(),
+            bump: 255,
+            schema_version: DATASET_SCHEMA_VERSION,
+        }
+    }
+}
+
+// Borsh decode that ignores the zeroed tail of the allocation
+fn decode_prefix<T: AnchorDeserialize>(mut fields: &[u8]) -> Option<T> {
+    T::deserialize(&mut fields).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::DEFAULT_REPUTATION_GRACE_PERIOD;
+    use crate::access::is_owner;
+    use crate::instructions::{calculate_reputation_score, reverse_upload_credit, upload_points};
+    use crate::seeds::{find_address, reputation_seeds};
+    use crate::state::Reputation;
+    use crate::time::{current_time, with_clock};
+
+    #[test]
+    fn finalizing_inside_grace_period_is_rejected() {
+        let dataset = Dataset::sample();
+        let now = dataset.upload_timestamp + DEFAULT_REPUTATION_GRACE_PERIOD - 1;
+
+        assert_eq!(
+            dataset
+                .ensure_reputation_finalizable(now, DEFAULT_REPUTATION_GRACE_PERIOD)
+                .unwrap_err(),
+            ErrorCode::GracePeriodActive.into()
+        );
+    }
+
+    #[test]
+    fn upload_earns_no_reputation_until_finalized() {
+        // Creation stages the credit; a dataset closed before this point never applies it
+        let mut dataset = Dataset::sample();
+        let mut reputation = Reputation::sample();
+        assert_eq!(reputation.total_uploads, 0);
+
+        let now = dataset.upload_timestamp + DEFAULT_REPUTATION_GRACE_PERIOD;
+        dataset
+            .ensure_reputation_finalizable(now, DEFAULT_REPUTATION_GRACE_PERIOD)
+            .unwrap();
+        reputation.credit_upload(dataset.quality_score).unwrap();
+        dataset.reputation_finalized = true;
+
+        assert_eq!(reputation.total_uploads, 1);
+        assert_eq!(reputation.total_quality_score, 80);
+    }
+
+    #[test]
+    fn closing_before_finalization_leaves_nothing_to_finalize() {
+        let dataset = Dataset::sample();
+        let mut reputation = Reputation::sample();
+        // create_dataset only advances the PDA index
+        reputation.record_dataset().unwrap();
+
+        reverse_upload_credit(&mut reputation, &dataset, &[], "close", &crate::ID).unwrap();
+        assert_eq!(reputation.dataset_count, 1);
+        assert_eq!((reputation.total_uploads, reputation.total_quality_score, reputation.reputation_score), (0, 0, 0));
+
+        // close hands the account back to the system program empty, so finalize_reputation can't load it
+        let system = Pubkey::default();
+        let (mut lamports, mut data) = (0, Vec::new());
+        let closed = AccountInfo::new(&dataset.id, false, true, &mut lamports, &mut data, &system, false, 0);
+        assert_eq!(
+            Account::<Dataset>::try_from(&closed).err(),
+            Some(anchor_lang::error::ErrorCode::AccountNotInitialized.into())
+        );
+        assert_eq!(reputation.total_uploads, 0);
+    }
+
+    #[test]
+    fn closing_after_a_quality_change_takes_back_exactly_the_finalized_credit() {
+        let mut dataset = Dataset { research_fields: vec![1, 4], ..Dataset::sample() };
+        let mut reputation = Reputation::sample();
+        // An earlier upload that must survive the close untouched
+        reputation.credit_upload(50).unwrap();
+        reputation.credit_fields(&[1], upload_points(50));
+        calculate_reputation_score(&mut reputation).unwrap();
+        let before = reputation.clone();
+
+        let (primary_share, _) = dataset.quality_shares().unwrap();
+        reputation.credit_upload(primary_share).unwrap();
+        let field_points = reputation.credit_fields_by(&dataset.research_fields, |field| {
+            (field as u32 + 1) * upload_points(primary_share)
+        });
+        dataset.record_upload_credit(primary_share, field_points, Vec::new());
+        calculate_reputation_score(&mut reputation).unwrap();
+
+        // Ratings pull the quality well below what was credited
+        dataset.quality_score = 20;
+        reverse_upload_credit(&mut reputation, &dataset, &[], "close", &crate::ID).unwrap();
+
+        assert_eq!(reputation.total_uploads, before.total_uploads);
+        assert_eq!(reputation.total_quality_score, before.total_quality_score);
+        assert_eq!(reputation.score_for_field(1), before.score_for_field(1));
+        assert_eq!(reputation.score_for_field(4), 0);
+        assert_eq!(reputation.reputation_score, before.reputation_score);
+    }
+
+    fn credited_reputation(key: Pubkey, fields: &[u8], share: u8) -> (Reputation, Vec<FieldScore>) {
+        let (_, bump) = find_address(&reputation_seeds(&key), &crate::ID);
+        let mut reputation = Reputation::new(key, bump);
+        reputation.credit_upload(share).unwrap();
+        let field_points = reputation.credit_fields_by(fields, |_| upload_points(share));
+        calculate_reputation_score(&mut reputation).unwrap();
+        (reputation, field_points)
+    }
+
+    fn assert_uncredited(reputation: &Reputation, fields: &[u8]) {
+        assert_eq!((reputation.total_uploads, reputation.total_quality_score, reputation.reputation_score), (0, 0, 0));
+        assert!(fields.iter().all(|&field| reputation.score_for_field(field) == 0));
+    }
+
+    #[test]
+    fn closing_takes_back_every_co_contributor_share() {
+        let co = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut dataset = Dataset { co_contributors: co.to_vec(), research_fields: vec![1, 4], ..Dataset::sample() };
+        let fields = dataset.research_fields.clone();
+        let (primary_share, co_share) = dataset.quality_shares().unwrap();
+
+        let (mut primary, field_points) = credited_reputation(dataset.contributor, &fields, primary_share);
+        let credited: Vec<(Reputation, Vec<FieldScore>)> =
+            co.iter().map(|&key| credited_reputation(key, &fields, co_share)).collect();
+        let co_credits = co.iter().zip(&credited).map(|(&contributor, (_, field_points))| CoContributorCredit {
+            contributor,
+            quality: co_share,
+            field_points: field_points.clone(),
+        });
+        dataset.record_upload_credit(primary_share, field_points, co_credits.collect());
+
+        let keys: Vec<Pubkey> = co.iter().map(|key| find_address(&reputation_seeds(key), &crate::ID).0).collect();
+        let mut data: Vec<Vec<u8>> = credited
+            .iter()
+            .map(|(reputation, _)| {
+                let mut bytes = Vec::new();
+                reputation.try_serialize(&mut bytes).unwrap();
+                bytes
+            })
+            .collect();
+        let mut lamports = [1_000_000u64; 2];
+        let [first, second] = &mut data[..] else { unreachable!() };
+        let [first_lamports, second_lamports] = &mut lamports;
+        let co_reputations = [
+            AccountInfo::new(&keys[0], false, true, first_lamports, first, &crate::ID, false, 0),
+            AccountInfo::new(&keys[1], false, true, second_lamports, second, &crate::ID, false, 0),
+        ];
+
+        // Every credited co-contributor must be passed
+        assert_eq!(
+            reverse_upload_credit(&mut primary.clone(), &dataset, &co_reputations[..1], "close", &crate::ID).unwrap_err(),
+            ErrorCode::InvalidCoContributorAccounts.into()
+        );
+        reverse_upload_credit(&mut primary, &dataset, &co_reputations, "close", &crate::ID).unwrap();
+
+        assert_uncredited(&primary, &fields);
+        for info in &co_reputations {
+            let stored = Reputation::try_deserialize(&mut &info.try_borrow_data().unwrap()[..]).unwrap();
+            assert_uncredited(&stored, &fields);
+        }
+    }
+
+    #[test]
+    fn co_contributor_credit_follows_a_key_rotation() {
+        let (old, new) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut dataset = Dataset { co_contributors: vec![old], ..Dataset::sample() };
+        let credit = CoContributorCredit { contributor: old, quality: 40, field_points: Vec::new() };
+        dataset.record_upload_credit(40, Vec::new(), vec![credit]);
+
+        let mut rotated = Reputation::new(old, 255);
+        rotated.rotated_to = new;
+        dataset.repoint_contributor(&rotated).unwrap();
+
+        assert_eq!(dataset.co_contributors, vec![new]);
+        assert_eq!(dataset.co_credits[0].contributor, new);
+    }
+
+    #[test]
+    fn large_dataset_requires_preview_hash() {
+        assert_eq!(
+            Dataset::validate_preview_hash(PREVIEW_BYTES + 1, &[0u8; 32]).unwrap_err(),
+            ErrorCode::PreviewHashRequired.into()
+        );
+        Dataset::validate_preview_hash(PREVIEW_BYTES + 1, &[3u8; 32]).unwrap();
+    }
+
+    #[test]
+    fn small_dataset_may_omit_preview_hash() {
+        Dataset::validate_preview_hash(PREVIEW_BYTES, &[0u8; 32]).unwrap();
+        assert_eq!(Dataset::preview_byte_count(512), 512);
+        assert_eq!(Dataset::preview_byte_count(PREVIEW_BYTES * 10), PREVIEW_BYTES);
+    }
+
+    #[test]
+    fn dataset_expires_strictly_after_expiry_time() {
+        let dataset = Dataset { expires_at: Some(2_000), ..Dataset::sample() };
+
+        assert!(!dataset.is_expired(1_999));
+        assert!(!dataset.is_expired(2_000));
+        assert!(dataset.is_expired(2_001));
+        assert!(!Dataset::sample().is_expired(i64::MAX));
+    }
+
+    #[test]
+    fn renewal_lifts_an_expired_license() {
+        let mut dataset = Dataset { license_expires_at: Some(2_000), ..Dataset::sample() };
+        assert!(dataset.is_license_expired(2_001));
+        assert!(!dataset.is_expired(2_001));
+
+        dataset.renew_license(5_000, 2_001).unwrap();
+
+        assert!(!dataset.is_license_expired(2_001));
+        assert!(dataset.is_license_expired(5_001));
+        assert!(!Dataset::sample().is_license_expired(i64::MAX));
+    }
+
+    #[test]
+    fn renewal_must_extend_the_license() {
+        let mut dataset = Dataset { license_expires_at: Some(5_000), ..Dataset::sample() };
+
+        assert_eq!(dataset.renew_license(4_000, 1_000).unwrap_err(), ErrorCode::InvalidLicenseExpiry.into());
+        assert_eq!(dataset.renew_license(5_000, 1_000).unwrap_err(), ErrorCode::InvalidLicenseExpiry.into());
+        assert_eq!(dataset.renew_license(900, 1_000).unwrap_err(), ErrorCode::InvalidLicenseExpiry.into());
+        assert_eq!(dataset.license_expires_at, Some(5_000));
+
+        let mut perpetual = Dataset::sample();
+        perpetual.renew_license(1_001, 1_000).unwrap();
+        assert_eq!(perpetual.license_expires_at, Some(1_001));
+    }
+
+    #[test]
+    fn reverted_downloads_floor_at_zero() {
+        let mut dataset = Dataset { download_count: 10, ..Dataset::sample() };
+
+        assert_eq!(dataset.revert_downloads(4), 4);
+        assert_eq!(dataset.download_count, 6);
+        assert_eq!(dataset.revert_downloads(50), 6);
+        assert_eq!(dataset.download_count, 0);
+    }
+
+    #[test]
+    fn absorb_sums_counters() {
+        let mut canonical = Dataset {
+            download_count: 10,
+            rating_sum: 40,
+            rating_count: 10,
+            rating_weight: 14,
+            citation_count: 2,
+            ..Dataset::sample()
+        };
+        let duplicate = Dataset {
+            download_count: 5,
+            rating_sum: 12,
+            rating_count: 3,
+            rating_weight: 3,
+            citation_count: 1,
+            ..Dataset::sample()
+        };
+
+        canonical.absorb(&duplicate).unwrap();
+
+        assert_eq!(canonical.download_count, 15);
+        assert_eq!(canonical.rating_sum, 52);
+        assert_eq!(canonical.rating_count, 13);
+        assert_eq!(canonical.rating_weight, 17);
+        assert_eq!(canonical.citation_count, 3);
+    }
+
+    #[test]
+    fn absorb_rejects_different_content() {
+        let mut canonical = Dataset::sample();
+        let other = Dataset { content_hash: [9u8; 32], download_count: 5, ..Dataset::sample() };
+
+        assert_eq!(canonical.absorb(&other).unwrap_err(), ErrorCode::HashMismatch.into());
+        assert_eq!(canonical.download_count, 0);
+    }
+
+    #[test]
+    fn finalizing_twice_is_rejected() {
+        let dataset = Dataset { reputation_finalized: true, ..Dataset::sample() };
+
+        assert_eq!(
+            dataset.ensure_reputation_finalizable(i64::MAX, 0).unwrap_err(),
+            ErrorCode::ReputationAlreadyFinalized.into()
+        );
+    }
+
+    #[test]
+    fn thumbnail_uri_survives_account_round_trip() {
+        let mut thumbnail = [0u8; THUMBNAIL_URI_LEN];
+        thumbnail[..19].copy_from_slice(b"ipfs://bafy-preview");
+        let dataset = Dataset { thumbnail_uri: Some(thumbnail), ..Dataset::sample() };
+
+        let mut data = Vec::new();
+        dataset.try_serialize(&mut data).unwrap();
+        let stored = Dataset::try_deserialize(&mut data.as_slice()).unwrap();
+
+        assert_eq!(stored.thumbnail_uri, Some(thumbnail));
+        assert_eq!(Dataset::sample().thumbnail_uri, None);
+    }
+
+    #[test]
+    fn downloads_past_u32_ceiling_overflow_instead_of_wrapping() {
+        let mut dataset = Dataset { download_count: u32::MAX - 10, ..Dataset::sample() };
+
+        assert_eq!(dataset.record_downloads(11).unwrap_err(), ErrorCode::NumericalOverflow.into());
+        assert_eq!(dataset.download_count, u32::MAX - 10);
+
+        dataset.record_downloads(10).unwrap();
+        assert_eq!(dataset.download_count, u32::MAX);
+        assert_eq!(dataset.record_downloads(1).unwrap_err(), ErrorCode::NumericalOverflow.into());
+    }
+
+    #[test]
+    fn review_flow_approves_and_unblocks_curated_download() {
+        let mut dataset = Dataset::sample();
+        let reviewer = Pubkey::new_unique();
+        assert_eq!(dataset.ensure_downloadable(true).unwrap_err(), ErrorCode::DatasetNotApproved.into());
+        dataset.ensure_downloadable(false).unwrap();
+
+        dataset.assign_reviewer(reviewer).unwrap();
+        assert_eq!(dataset.review_status, REVIEW_UNDER_REVIEW);
+        dataset.submit_review(&reviewer, true, [4u8; 32]).unwrap();
+
+        assert_eq!(dataset.review_status, REVIEW_APPROVED);
+        assert_eq!(dataset.review_note_hash, [4u8; 32]);
+        dataset.ensure_downloadable(true).unwrap();
+    }
+
+    #[test]
+    fn only_assigned_reviewer_may_review() {
+        let mut dataset = Dataset::sample();
+        let contributor = dataset.contributor;
+        dataset.assign_reviewer(Pubkey::new_unique()).unwrap();
+
+        assert_eq!(
+            dataset.submit_review(&contributor, true, [0u8; 32]).unwrap_err(),
+            ErrorCode::UnauthorizedReviewer.into()
+        );
+        assert_eq!(dataset.review_status, REVIEW_UNDER_REVIEW);
+    }
+
+    #[test]
+    fn featured_dataset_is_unfeatured_after_its_promotion_window() {
+        let mut dataset = Dataset { featured: true, featured_until: 1_000, ..Dataset::sample() };
+
+        assert_eq!(dataset.unfeature_expired(999).unwrap_err(), ErrorCode::FeatureNotExpired.into());
+        dataset.unfeature_expired(1_000).unwrap();
+        assert!(!dataset.featured);
+        assert_eq!(dataset.featured_until, 0);
+        assert_eq!(dataset.unfeature_expired(2_000).unwrap_err(), ErrorCode::NotFeatured.into());
+
+        let mut open_ended = Dataset { featured: true, ..Dataset::sample() };
+        assert_eq!(open_ended.unfeature_expired(i64::MAX).unwrap_err(), ErrorCode::FeatureNotExpired.into());
+    }
+
+    #[test]
+    fn only_disagreeing_languages_conflict() {
+        assert!(Dataset::language_conflicts(Some(*b"sw"), Some(*b"fr")));
+        assert!(!Dataset::language_conflicts(Some(*b"sw"), Some(*b"SW")));
+        assert!(!Dataset::language_conflicts(None, Some(*b"fr")));
+        assert!(!Dataset::language_conflicts(Some(*b"sw"), None));
+
+        Dataset::validate_language(b"Yo").unwrap();
+        assert_eq!(Dataset::validate_language(b"s1").unwrap_err(), ErrorCode::InvalidLanguageCode.into());
+    }
+
+    #[test]
+    fn last_updated_never_moves_backwards() {
+        let mut dataset = Dataset::sample();
+        with_clock(1_700_000_100, 1, || dataset.touch(current_time().unwrap())).unwrap();
+        with_clock(1_700_000_100, 2, || dataset.touch(current_time().unwrap())).unwrap();
+
+        // A validator whose clock has drifted behind the last change
+        let skewed = with_clock(1_700_000_099, 3, || dataset.touch(current_time().unwrap()));
+
+        assert_eq!(skewed.unwrap_err(), ErrorCode::NonMonotonicTimestamp.into());
+        assert_eq!(dataset.last_updated, Some(1_700_000_100));
+    }
+
+    #[test]
+    fn finalization_is_one_way() {
+        let mut dataset = Dataset::sample();
+        dataset.finalize().unwrap();
+
+        assert!(dataset.finalized);
+        assert_eq!(dataset.finalize().unwrap_err(), ErrorCode::DatasetFinalized.into());
+    }
+
+    #[test]
+    fn spam_deposit_is_released_once_by_the_first_outside_download() {
+        let (co_contributor, editor, outsider) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let mut dataset = Dataset {
+            co_contributors: vec![co_contributor],
+            editors: vec![editor],
+            spam_deposit: 5_000_000,
+            ..Dataset::sample()
+        };
+        let contributor = dataset.contributor;
+        let now = dataset.upload_timestamp + SPAM_DEPOSIT_HOLD_PERIOD;
+
+        for insider in [contributor, co_contributor, editor] {
+            assert_eq!(dataset.release_spam_deposit(&insider, now), 0);
+        }
+        assert_eq!(dataset.spam_deposit, 5_000_000);
+
+        assert_eq!(dataset.release_spam_deposit(&outsider, now), 5_000_000);
+        assert_eq!(dataset.spam_deposit, 0);
+        assert_eq!(dataset.release_spam_deposit(&Pubkey::new_unique(), now), 0);
+    }
+
+    #[test]
+    fn spam_deposit_is_held_for_the_full_period_whatever_the_fee() {
+        // A 1-lamport fee set by the contributor and paid from a fresh key must not count
+        let mut dataset = Dataset { spam_deposit: 5_000_000, download_fee: 1, ..Dataset::sample() };
+        let released_at = dataset.upload_timestamp + SPAM_DEPOSIT_HOLD_PERIOD;
+
+        assert_eq!(dataset.release_spam_deposit(&Pubkey::new_unique(), dataset.upload_timestamp + 60), 0);
+        assert_eq!(dataset.release_spam_deposit(&Pubkey::new_unique(), released_at - 1), 0);
+        assert_eq!(dataset.spam_deposit, 5_000_000);
+        assert_eq!(dataset.release_spam_deposit(&Pubkey::new_unique(), released_at), 5_000_000);
+    }
+
+    #[test]
+    fn closing_before_a_qualifying_download_forfeits_the_spam_deposit() {
+        let mut dataset = Dataset { spam_deposit: 5_000_000, ..Dataset::sample() };
+        let (contributor, now) = (dataset.contributor, dataset.upload_timestamp + SPAM_DEPOSIT_HOLD_PERIOD);
+
+        assert_eq!(dataset.release_spam_deposit(&contributor, now), 0);
+        assert_eq!(dataset.forfeit_spam_deposit(), 5_000_000);
+        assert_eq!(dataset.forfeit_spam_deposit(), 0);
+        assert_eq!(dataset.release_spam_deposit(&Pubkey::new_unique(), now), 0);
+    }
+
+    #[test]
+    fn unreviewed_dataset_auto_approves_after_its_deadline() {
+        let now = 1_700_000_000;
+        let deadline = Dataset::review_deadline_for(now, DEFAULT_REVIEW_SLA);
+        let mut dataset = Dataset { review_deadline: deadline, ..Dataset::sample() };
+        dataset.assign_reviewer(Pubkey::new_unique()).unwrap();
+
+        assert_eq!(dataset.auto_approve(deadline - 1).unwrap_err(), ErrorCode::ReviewDeadlineNotReached.into());
+        dataset.auto_approve(deadline).unwrap();
+
+        assert_eq!(dataset.review_status, REVIEW_APPROVED);
+        dataset.ensure_downloadable(true).unwrap();
+        assert_eq!(dataset.auto_approve(deadline).unwrap_err(), ErrorCode::InvalidReviewStatus.into());
+    }
+
+    #[test]
+    fn decided_or_deadline_free_reviews_never_auto_approve() {
+        let reviewer = Pubkey::new_unique();
+        let mut rejected = Dataset { review_deadline: 1_000, ..Dataset::sample() };
+        rejected.assign_reviewer(reviewer).unwrap();
+        rejected.submit_review(&reviewer, false, [0u8; 32]).unwrap();
+        assert_eq!(rejected.auto_approve(2_000).unwrap_err(), ErrorCode::InvalidReviewStatus.into());
+
+        let mut open_ended = Dataset { review_deadline: Dataset::review_deadline_for(1_000, 0), ..Dataset::sample() };
+        assert_eq!(open_ended.review_deadline, 0);
+        assert_eq!(open_ended.auto_approve(i64::MAX).unwrap_err(), ErrorCode::AutoApproveDisabled.into());
+    }
+
+    #[test]
+    fn completed_review_is_final() {
+        let mut dataset = Dataset::sample();
+        let reviewer = Pubkey::new_unique();
+        dataset.assign_reviewer(reviewer).unwrap();
+        dataset.submit_review(&reviewer, false, [1u8; 32]).unwrap();
+        assert_eq!(dataset.review_status, REVIEW_REJECTED);
+
+        assert_eq!(
+            dataset.submit_review(&reviewer, true, [1u8; 32]).unwrap_err(),
+            ErrorCode::InvalidReviewStatus.into()
+        );
+        assert_eq!(
+            dataset.assign_reviewer(Pubkey::new_unique()).unwrap_err(),
+            ErrorCode::InvalidReviewStatus.into()
+        );
+    }
+
+    #[test]
+    fn canonical_bytes_ignore_usage_and_account_state() {
+        let original = Dataset { data_uri: b"ipfs://bafy-ab".to_vec(), ..Dataset::sample() };
+        let used = Dataset {
+            id: Pubkey::new_unique(),
+            download_count: 42,
+            citation_count: 3,
+            review_status: REVIEW_APPROVED,
+            last_updated: Some(1_800_000_000),
+            bump: 254,
+            ..original.clone()
+        };
+
+        let bytes = original.to_canonical_bytes();
+        assert_eq!(bytes, used.to_canonical_bytes());
+        assert_eq!(bytes[0], CANONICAL_LAYOUT_VERSION);
+        assert_eq!(bytes.len(), 1 + 32 * 4 + 8 * 3 + 1 + 8 + 4 + 10 + 4 + 14);
+        assert!(bytes.ends_with(b"ipfs://bafy-ab"));
+
+        let renamed = Dataset { file_name: b"other.csv".to_vec(), ..original };
+        assert_ne!(renamed.to_canonical_bytes(), bytes);
+    }
+
+    #[test]
+    fn canonical_bytes_publish_only_the_pseudonym_of_an_anonymous_contributor() {
+        let dataset = Dataset { anonymous: true, ..Dataset::sample() };
+
+        let bytes = dataset.to_canonical_bytes();
+        assert_eq!(&bytes[33..65], dataset.published_contributor().as_ref());
+        assert!(!bytes.windows(32).any(|window| window == dataset.contributor.as_ref()));
+
+        let public = Dataset { anonymous: false, ..dataset };
+        assert_eq!(&public.to_canonical_bytes()[33..65], public.contributor.as_ref());
+    }
+
+    #[test]
+    fn co_contributor_splits_quality_credit_in_half() {
+        let dataset = Dataset { co_contributors: vec![Pubkey::new_unique()], ..Dataset::sample() };
+        let mut primary = Reputation::sample();
+        let mut co_contributor = Reputation::sample();
+
+        let (primary_share, co_share) = dataset.quality_shares().unwrap();
+        primary.credit_upload(primary_share).unwrap();
+        co_contributor.credit_upload(co_share).unwrap();
+
+        assert_eq!(primary.total_quality_score, 40);
+        assert_eq!(co_contributor.total_quality_score, 40);
+        assert_eq!(Dataset::sample().quality_shares().unwrap().0, 80);
+    }
+
+    #[test]
+    fn uneven_split_leaves_remainder_with_primary() {
+        let dataset = Dataset {
+            co_contributors: vec![Pubkey::new_unique(), Pubkey::new_unique()],
+            ..Dataset::sample()
+        };
+
+        assert_eq!(dataset.quality_shares().unwrap(), (28, 26));
+    }
+
+    #[test]
+    fn split_credit_sums_to_the_quality_score() {
+        let dataset = Dataset {
+            quality_score: 97,
+            co_contributors: vec![Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()],
+            ..Dataset::sample()
+        };
+        let (primary_share, co_share) = dataset.quality_shares().unwrap();
+        assert_eq!((primary_share, co_share), (25, 24));
+        assert_eq!(primary_share as u32 + 3 * co_share as u32, 97);
+
+        for co_authors in 0..=MAX_CO_CONTRIBUTORS {
+            for quality_score in 0..=u8::MAX {
+                let dataset = Dataset {
+                    quality_score,
+                    co_contributors: vec![Pubkey::default(); co_authors],
+                    ..dataset.clone()
+                };
+                let (primary_share, co_share) = dataset.quality_shares().unwrap();
+                assert_eq!(primary_share as usize + co_authors * co_share as usize, quality_score as usize);
+                assert!(primary_share >= co_share);
+            }
+        }
+    }
+
+    #[test]
+    fn co_contributors_must_be_distinct_and_bounded() {
+        let contributor = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+
+        Dataset::validate_co_contributors(&contributor, &[other]).unwrap();
+        assert_eq!(
+            Dataset::validate_co_contributors(&contributor, &[other, other]).unwrap_err(),
+            ErrorCode::DuplicateCoContributor.into()
+        );
+        assert_eq!(
+            Dataset::validate_co_contributors(&contributor, &[contributor]).unwrap_err(),
+            ErrorCode::DuplicateCoContributor.into()
+        );
+        let too_many: Vec<Pubkey> = (0..=MAX_CO_CONTRIBUTORS).map(|_| Pubkey::new_unique()).collect();
+        assert_eq!(
+            Dataset::validate_co_contributors(&contributor, &too_many).unwrap_err(),
+            ErrorCode::TooManyCoContributors.into()
+        );
+    }
+
+    #[test]
+    fn locked_uri_cannot_change_or_be_relocked() {
+        let mut dataset = Dataset::sample();
+        dataset.set_data_uri(&[1u8; 256]).unwrap();
+        dataset.lock_data_uri().unwrap();
+
+        assert_eq!(dataset.set_data_uri(&[2u8; 256]).unwrap_err(), ErrorCode::UriLocked.into());
+        assert_eq!(dataset.lock_data_uri().unwrap_err(), ErrorCode::UriLocked.into());
+        assert_eq!(dataset.data_uri, vec![1u8; 256]);
+        assert!(dataset.uri_locked);
+    }
+
+    #[test]
+    fn endorsements_count_up_without_wrapping() {
+        let mut dataset = Dataset::sample();
+        dataset.record_endorsement().unwrap();
+        assert_eq!(dataset.endorsement_count, 1);
+
+        let mut dataset = Dataset { endorsement_count: u32::MAX, ..Dataset::sample() };
+        assert_eq!(dataset.record_endorsement().unwrap_err(), ErrorCode::NumericalOverflow.into());
+        assert_eq!(dataset.endorsement_count, u32::MAX);
+    }
+
+    #[test]
+    fn fork_links_to_source_under_new_owner() {
+        let mut source = Dataset {
+            download_count: 40,
+            citation_count: 3,
+            featured: true,
+            language: Some(*b"sw"),
+            ..Dataset::sample()
+        };
+        let forker = Pubkey::new_unique();
+        let fork_key = Pubkey::new_unique();
+        let now = source.upload_timestamp + 10;
+
+        let fork = source.fork(fork_key, forker, 2, now, 254).unwrap();
+        source.record_fork().unwrap();
+
+        assert_eq!(fork.id, fork_key);
+        assert_eq!(fork.forked_from, source.id);
+        assert_eq!(fork.contributor, forker);
+        assert_eq!(fork.dataset_index, 2);
+        assert_eq!(fork.content_hash, source.content_hash);
+        assert_eq!(fork.file_name, source.file_name);
+        assert_eq!(fork.language, Some(*b"sw"));
+        assert_eq!(fork.upload_timestamp, now);
+        assert_eq!(fork.download_count, 0);
+        assert_eq!(fork.citation_count, 0);
+        assert!(!fork.featured);
+        assert!(!fork.reputation_finalized);
+        assert_eq!(fork.fork_count, 0);
+        assert_eq!(source.fork_count, 1);
+    }
+
+    #[test]
+    fn version_with_changed_row_count_reports_only_that_bit() {
+        let source = Dataset { research_fields: vec![1, 4], ..Dataset::sample() };
+        let mut version = Dataset {
+            id: Pubkey::new_unique(),
+            contributor: source.contributor,
+            research_fields: source.research_fields.clone(),
+            parent: Some(source.id),
+            ..Dataset::sample()
+        };
+        assert_eq!(version.diff(&source).unwrap(), 0);
+
+        version.row_count += 1;
+        assert_eq!(version.diff(&source).unwrap(), DIFF_ROW_COUNT);
+        assert_eq!(source.diff(&version).unwrap(), DIFF_ROW_COUNT);
+
+        version.research_fields.reverse();
+        assert_eq!(version.diff(&source).unwrap(), DIFF_ROW_COUNT);
+        version.research_fields.push(99);
+        assert_eq!(version.diff(&source).unwrap(), DIFF_ROW_COUNT | DIFF_RESEARCH_FIELDS);
+    }
+
+    #[test]
+    fn forks_and_other_contributors_are_not_versions() {
+        let source = Dataset::sample();
+        let fork = source.fork(Pubkey::new_unique(), Pubkey::new_unique(), 0, source.upload_timestamp, 254).unwrap();
+        assert_eq!(fork.diff(&source).unwrap_err(), ErrorCode::NotInLineage.into());
+
+        let foreign = Dataset { parent: Some(source.id), ..Dataset::sample() };
+        assert_eq!(foreign.diff(&source).unwrap_err(), ErrorCode::NotInLineage.into());
+    }
+
+    #[test]
+    fn unrelated_datasets_cannot_be_compared() {
+        let first = Dataset::sample();
+        let second = Dataset::sample();
+        assert_eq!(first.diff(&second).unwrap_err(), ErrorCode::NotInLineage.into());
+    }
+
+    #[test]
+    fn fork_requires_another_contributor_and_live_source() {
+        let source = Dataset { expires_at: Some(1_700_000_100), ..Dataset::sample() };
+        let now = source.upload_timestamp;
+
+        assert_eq!(
+            source.fork(Pubkey::new_unique(), source.contributor, 0, now, 255).err().unwrap(),
+            ErrorCode::SelfFork.into()
+        );
+        assert_eq!(
+            source.fork(Pubkey::new_unique(), Pubkey::new_unique(), 0, 1_700_000_101, 255).err().unwrap(),
+            ErrorCode::DatasetExpired.into()
+        );
+
+        let inactive = Dataset { is_active: false, ..Dataset::sample() };
+        assert_eq!(
+            inactive.fork(Pubkey::new_unique(), Pubkey::new_unique(), 0, now, 255).err().unwrap(),
+            ErrorCode::DatasetInactive.into()
+        );
+    }
+
+    #[test]
+    fn rotation_repoints_authority_and_reputation_but_not_the_pda() {
+        let mut dataset = Dataset::sample();
+        let old_key = dataset.contributor;
+        let new_key = Pubkey::new_unique();
+        let mut reputation = Reputation::new(old_key, 255);
+        let rotated = reputation.rotate(new_key, 254).unwrap();
+
+        dataset.repoint_contributor(&reputation).unwrap();
+
+        assert!(is_owner(&dataset, &new_key));
+        assert!(!is_owner(&dataset, &old_key));
+        assert_eq!(dataset.creator, old_key);
+        // Reputation lookups keyed by dataset.contributor now land on the moved account
+        assert_eq!(
+            find_address(&reputation_seeds(&dataset.contributor), &crate::ID).0,
+            find_address(&reputation_seeds(&rotated.contributor), &crate::ID).0
+        );
+        assert_eq!(dataset.repoint_contributor(&reputation).unwrap_err(), ErrorCode::InvalidRepoint.into());
+    }
+
+    #[test]
+    fn rotation_repoints_co_contributors_and_needs_a_tombstone() {
+        let co_contributor = Pubkey::new_unique();
+        let mut dataset = Dataset { co_contributors: vec![co_contributor], ..Dataset::sample() };
+        let mut reputation = Reputation::new(co_contributor, 255);
+        assert_eq!(dataset.repoint_contributor(&reputation).unwrap_err(), ErrorCode::InvalidRepoint.into());
+
+        let new_key = Pubkey::new_unique();
+        reputation.rotate(new_key, 254).unwrap();
+        dataset.repoint_contributor(&reputation).unwrap();
+        assert_eq!(dataset.co_contributors, vec![new_key]);
+        assert_ne!(dataset.contributor, new_key);
+    }
+
+    #[test]
+    fn freezing_deactivates_once() {
+        let mut dataset = Dataset::sample();
+
+        dataset.freeze().unwrap();
+        assert!(dataset.frozen);
+        assert!(!dataset.is_active);
+
+        assert_eq!(dataset.freeze().unwrap_err(), ErrorCode::DatasetFrozen.into());
+    }
+
+    #[test]
+    fn new_uri_brings_unavailable_dataset_back_unless_frozen() {
+        let mut dataset = Dataset::sample();
+        dataset.mark_unavailable();
+        assert!(!dataset.is_active);
+
+        dataset.clear_unavailable();
+        assert!(dataset.is_active);
+        assert!(!dataset.unavailable);
+
+        let mut frozen = Dataset::sample();
+        frozen.mark_unavailable();
+        frozen.freeze().unwrap();
+        frozen.clear_unavailable();
+        assert!(!frozen.is_active);
+    }
+
+    #[test]
+    fn superseding_retires_the_old_dataset_for_good() {
+        let mut old = Dataset::sample();
+        let mut new = Dataset::sample();
+
+        new.supersede(&mut old).unwrap();
+
+        assert_eq!(new.supersedes, Some(old.id));
+        assert_eq!(old.superseded_by, Some(new.id));
+        assert!(!old.is_active && new.is_active);
+        old.mark_unavailable();
+        old.clear_unavailable();
+        assert!(!old.is_active);
+    }
+
+    #[test]
+    fn supersession_links_only_active_datasets_once() {
+        let mut old = Dataset::sample();
+        let mut new = Dataset::sample();
+        new.supersede(&mut old).unwrap();
+
+        let mut newer = Dataset::sample();
+        assert_eq!(newer.supersede(&mut old).unwrap_err(), ErrorCode::DatasetInactive.into());
+        assert_eq!(old.supersede(&mut newer).unwrap_err(), ErrorCode::DatasetInactive.into());
+        let mut other = Dataset::sample();
+        assert_eq!(new.supersede(&mut other).unwrap_err(), ErrorCode::AlreadySupersedes.into());
+        let mut itself = other.clone();
+        assert_eq!(other.supersede(&mut itself).unwrap_err(), ErrorCode::SelfSupersession.into());
+    }
+
+    #[test]
+    fn file_name_with_control_byte_is_rejected() {
+        for name in [&b"data\0.csv"[..], b"data\n.csv", b"\x1b[31mred.csv", b"", b"\xff\xfe.csv"] {
+            assert_eq!(
+                Dataset::validate_file_name(name).unwrap_err(),
+                ErrorCode::InvalidFileName.into()
+            );
+        }
+    }
+
+    #[test]
+    fn research_fields_must_be_distinct_and_bounded() {
+        Dataset::validate_research_fields(&[]).unwrap();
+        Dataset::validate_research_fields(&[1, 4]).unwrap();
+
+        for fields in [&[1u8, 1, 1][..], &[1, 4, 1]] {
+            assert_eq!(Dataset::validate_research_fields(fields).unwrap_err(), ErrorCode::DuplicateResearchField.into());
+        }
+        assert_eq!(
+            Dataset::validate_research_fields(&[1, 2, 3, 4, 5][..MAX_RESEARCH_FIELDS + 1]).unwrap_err(),
+            ErrorCode::TooManyResearchFields.into()
+        );
+    }
+
+    #[test]
+    fn title_is_bounded_and_printable() {
+        Dataset::validate_title("Enquête santé — 2024".as_bytes()).unwrap();
+        Dataset::validate_title(&[b'a'; MAX_TITLE_LEN]).unwrap();
+
+        assert_eq!(Dataset::validate_title(&[b'a'; MAX_TITLE_LEN + 1]).unwrap_err(), ErrorCode::TitleTooLong.into());
+        for title in [&b""[..], b"tab\there", &[0xff, 0xfe]] {
+            assert_eq!(Dataset::validate_title(title).unwrap_err(), ErrorCode::InvalidTitle.into());
+        }
+    }
+
+    #[test]
+    fn clean_file_name_is_accepted_after_trimming() {
+        let mut name = "enquête_santé 2024.csv \t".as_bytes().to_vec();
+        Dataset::trim_file_name(&mut name);
+
+        assert_eq!(name, "enquête_santé 2024.csv".as_bytes());
+        assert!(Dataset::validate_file_name(&name).is_ok());
+    }
+
+    #[test]
+    fn large_gap_above_community_quality_is_flagged() {
+        // Three 2-star ratings derive a community quality of 40
+        let mut dataset = Dataset { quality_score: 100, ..Dataset::sample() };
+        for _ in 0..MIN_RATINGS_FOR_QUALITY {
+            dataset.rate(None, 2, 1).unwrap();
+        }
+
+        assert!(dataset.flag_quality_discrepancy(DEFAULT_QUALITY_DISPUTE_DELTA).unwrap());
+        assert!(dataset.quality_disputed);
+        assert!(!dataset.flag_quality_discrepancy(DEFAULT_QUALITY_DISPUTE_DELTA).unwrap());
+    }
+
+    #[test]
+    fn small_gap_or_lower_self_report_is_not_flagged() {
+        let mut dataset = Dataset { quality_score: 65, ..Dataset::sample() };
+        for _ in 0..MIN_RATINGS_FOR_QUALITY {
+            dataset.rate(None, 2, 1).unwrap();
+        }
+
+        assert!(!dataset.flag_quality_discrepancy(25).unwrap());
+        dataset.quality_score = 10;
+        assert!(!dataset.flag_quality_discrepancy(0).unwrap());
+        assert!(!dataset.quality_disputed);
+        assert_eq!(Dataset::sample().flag_quality_discrepancy(25).unwrap_err(), ErrorCode::NotEnoughRatings.into());
+    }
+
+    #[test]
+    fn low_ratings_hide_dataset_until_revised() {
+        let mut dataset = Dataset::sample();
+        let floor = 50;
+        for _ in 0..MIN_RATINGS_FOR_QUALITY {
+            dataset.rate(None, 1, 1).unwrap();
+        }
+
+        assert!(dataset.apply_rated_quality(floor).unwrap());
+        assert_eq!(dataset.quality_score, 20);
+        assert!(dataset.quality_hidden);
+        assert!(!dataset.is_active);
+        // Already hidden, so a second crank reports nothing new
+        assert!(!dataset.apply_rated_quality(floor).unwrap());
+
+        assert_eq!(dataset.reactivate(floor).unwrap_err(), ErrorCode::QualityBelowMinimum.into());
+        for _ in 0..MIN_RATINGS_FOR_QUALITY {
+            dataset.rate(Some((1, 1)), 5, 1).unwrap();
+        }
+        dataset.reactivate(floor).unwrap();
+        assert_eq!(dataset.quality_score, 100);
+        assert!(dataset.is_active);
+    }
+
+    #[test]
+    fn high_reputation_rater_moves_the_average_more() {
+        let rated = |weight| {
+            let mut dataset = Dataset::sample();
+            for _ in 0..MIN_RATINGS_FOR_QUALITY {
+                dataset.rate(None, 1, RATING_TIER_WEIGHTS[0]).unwrap();
+            }
+            dataset.rate(None, 5, weight).unwrap();
+            dataset
+        };
+
+        let newcomer = rated(RATING_TIER_WEIGHTS[0]).quality_from_ratings().unwrap();
+        let expert = rated(RATING_TIER_WEIGHTS[3]).quality_from_ratings().unwrap();
+        assert_eq!(newcomer, 40); // 1 + 1 + 1 + 5 of a possible 4 * 5
+        assert_eq!(expert, 65); // 1 + 1 + 1 + 5 * 4 of a possible 7 * 5
+        assert!(expert > newcomer);
+    }
+
+    #[test]
+    fn revised_rating_reverses_its_original_weight() {
+        let mut dataset = Dataset::sample();
+        dataset.rate(None, 2, 1).unwrap();
+        dataset.rate(None, 4, 3).unwrap();
+
+        // The rater has since moved up a tier; the old weight comes out, the new one goes in
+        dataset.rate(Some((4, 3)), 5, 4).unwrap();
+        assert_eq!((dataset.rating_sum, dataset.rating_weight, dataset.rating_count), (22, 5, 2));
+        dataset.rate(Some((5, 4)), 4, 3).unwrap();
+        assert_eq!((dataset.rating_sum, dataset.rating_weight, dataset.rating_count), (14, 4, 2));
+    }
+
+    #[test]
+    fn quality_needs_minimum_ratings_in_range() {
+        let mut dataset = Dataset::sample();
+        assert_eq!(dataset.rate(None, 0, 1).unwrap_err(), ErrorCode::InvalidRating.into());
+        assert_eq!(dataset.rate(None, MAX_RATING + 1, 1).unwrap_err(), ErrorCode::InvalidRating.into());
+
+        dataset.rate(None, 4, 1).unwrap();
+        assert_eq!(dataset.quality_from_ratings(), None);
+        assert_eq!(dataset.apply_rated_quality(0).unwrap_err(), ErrorCode::NotEnoughRatings.into());
+        assert_eq!(dataset.quality_score, 80);
+    }
+
+    #[test]
+    fn download_cap_needs_a_window() {
+        let mut dataset = Dataset::sample();
+
+        assert_eq!(dataset.set_download_cap(5, 0).unwrap_err(), ErrorCode::InvalidDownloadWindow.into());
+        dataset.set_download_cap(5, 86_400).unwrap();
+        dataset.set_download_cap(0, 0).unwrap();
+        assert_eq!(dataset.download_cap, 0);
+    }
+
+    #[test]
+    fn pin_extensions_stack_and_restart_after_lapse() {
+        let mut dataset = Dataset::sample();
+        let now = 1_700_000_000;
+        assert!(!dataset.is_pin_funded(now));
+
+        dataset.extend_pin(now, 30).unwrap();
+        dataset.extend_pin(now + 10, 5).unwrap();
+        assert_eq!(dataset.pinned_until, now + 35 * SECONDS_PER_DAY);
+        assert!(dataset.is_pin_funded(now + 35 * SECONDS_PER_DAY - 1));
+        assert!(!dataset.is_pin_funded(now + 35 * SECONDS_PER_DAY));
+
+        let later = now + 100 * SECONDS_PER_DAY;
+        dataset.extend_pin(later, 1).unwrap();
+        assert_eq!(dataset.pinned_until, later + SECONDS_PER_DAY);
+        assert_eq!(dataset.pin_seconds_funded, 36 * SECONDS_PER_DAY as u64);
+
+        assert_eq!(dataset.extend_pin(later, 0).unwrap_err(), ErrorCode::InvalidPinDuration.into());
+    }
+
+    #[test]
+    fn editors_are_distinct_and_capped() {
+        let mut dataset = Dataset::sample();
+        let editor = Pubkey::new_unique();
+        dataset.add_editor(editor).unwrap();
+        assert_eq!(dataset.add_editor(editor).unwrap_err(), ErrorCode::DuplicateEditor.into());
+        assert_eq!(dataset.add_editor(dataset.contributor).unwrap_err(), ErrorCode::DuplicateEditor.into());
+
+        for _ in 1..MAX_EDITORS {
+            dataset.add_editor(Pubkey::new_unique()).unwrap();
+        }
+        assert_eq!(dataset.add_editor(Pubkey::new_unique()).unwrap_err(), ErrorCode::TooManyEditors.into());
+
+        dataset.remove_editor(&editor).unwrap();
+        assert!(!dataset.editors.contains(&editor));
+        assert_eq!(dataset.remove_editor(&editor).unwrap_err(), ErrorCode::EditorNotFound.into());
+        dataset.add_editor(Pubkey::new_unique()).unwrap();
+    }
+
+    #[test]
+    fn over_length_uri_is_rejected() {
+        let mut dataset = Dataset::sample();
+        assert_eq!(
+            dataset.set_data_uri(&[b'a'; DATA_URI_LEN + 1]).unwrap_err(),
+            ErrorCode::DataUriTooLong.into()
+        );
+
+        // Padded input from older clients is stored trimmed
+        let mut padded = [0u8; DATA_URI_LEN];
+        padded[..9].copy_from_slice(b"ar://tx-1");
+        dataset.set_data_uri(&padded).unwrap();
+        assert_eq!(dataset.data_uri, b"ar://tx-1".to_vec());
+    }
+
+    #[test]
+    fn expanding_ai_metadata_past_the_base_allocation_grows_the_space() {
+        let mut dataset = Dataset { ai_metadata: vec![1; MAX_AI_METADATA_LEN - 12], ..Dataset::sample() };
+
+        assert_eq!(dataset.expand_ai_metadata(&[2; 12]).unwrap(), Dataset::SPACE);
+        assert_eq!(dataset.expand_ai_metadata(&[3; MAX_AI_METADATA_GROWTH]).unwrap(), Dataset::SPACE + MAX_AI_METADATA_GROWTH);
+        assert_eq!(dataset.ai_metadata.len(), MAX_AI_METADATA_LEN + MAX_AI_METADATA_GROWTH);
+        assert!(dataset.try_to_vec().unwrap().len() <= Dataset::space_with_ai_metadata(dataset.ai_metadata.len()));
+    }
+
+    #[test]
+    fn expanding_ai_metadata_beyond_the_caps_is_rejected() {
+        let mut dataset = Dataset::sample();
+        assert_eq!(
+            dataset.expand_ai_metadata(&[0; MAX_AI_METADATA_GROWTH + 1]).unwrap_err(),
+            ErrorCode::GrowthExceeded.into()
+        );
+
+        dataset.ai_metadata = vec![0; MAX_AI_METADATA - 1];
+        assert_eq!(dataset.expand_ai_metadata(&[0; 2]).unwrap_err(), ErrorCode::GrowthExceeded.into());
+        assert_eq!(dataset.ai_metadata.len(), MAX_AI_METADATA - 1);
+    }
+
+    #[test]
+    fn zero_quality_needs_to_be_explicit() {
+        assert_eq!(Dataset::validate_quality_intent(0, false).unwrap_err(), ErrorCode::ZeroQualityScore.into());
+        Dataset::validate_quality_intent(0, true).unwrap();
+        Dataset::validate_quality_intent(1, false).unwrap();
+    }
+
+    #[test]
+    fn adding_research_fields_merges_without_duplicates() {
+        let mut dataset = Dataset { research_fields: vec![1, 4], ..Dataset::sample() };
+
+        dataset.add_research_fields(&[4, 7]).unwrap();
+        assert_eq!(dataset.research_fields, vec![1, 4, 7]);
+
+        assert_eq!(dataset.add_research_fields(&[2, 3]).unwrap_err(), ErrorCode::TooManyResearchFields.into());
+        assert_eq!(dataset.add_research_fields(&[2, 2]).unwrap_err(), ErrorCode::DuplicateResearchField.into());
+        assert_eq!(dataset.research_fields, vec![1, 4, 7]);
+    }
+
+    #[test]
+    fn forfeiting_the_bond_empties_it_once() {
+        let mut dataset = Dataset { bond: 100_000_000, ..Dataset::sample() };
+        dataset.freeze().unwrap();
+
+        assert_eq!(dataset.forfeit_bond(), 100_000_000);
+        assert_eq!(dataset.forfeit_bond(), 0);
+        assert_eq!(dataset.bond, 0);
+    }
+
+    #[test]
+    fn anonymous_dataset_publishes_a_pseudonym_but_authorizes_the_real_key() {
+        let dataset = Dataset { anonymous: true, ..Dataset::sample() };
+
+        assert_ne!(dataset.published_contributor(), dataset.contributor);
+        assert_eq!(dataset.published_contributor(), crate::anonymity::pseudonym(&dataset.contributor));
+        assert!(crate::access::is_owner(&dataset, &dataset.contributor));
+        assert!(!crate::access::is_owner(&dataset, &dataset.published_contributor()));
+
+        let public = Dataset::sample();
+        assert_eq!(public.published_contributor(), public.contributor);
+    }
+
+    #[test]
+    fn a_held_lock_cannot_be_taken_again() {
+        let mut dataset = Dataset::sample();
+        dataset.lock().unwrap();
+
+        assert_eq!(dataset.lock().unwrap_err(), ErrorCode::DatasetLocked.into());
+        dataset.unlock();
+        dataset.lock().unwrap();
+        assert!(dataset.locked);
+    }
+
+    #[test]
+    fn trending_score_decays_with_age() {
+        let now = 1_000_000;
+        let fresh = Dataset { download_count: 40, upload_timestamp: now - 3_600, ..Dataset::sample() };
+        let old = Dataset { download_count: 40, upload_timestamp: now - 48 * 3_600, ..Dataset::sample() };
+
+        assert_eq!(fresh.trending_score(now), 40 * 1_000_000 / 9);
+        assert!(fresh.trending_score(now) > old.trending_score(now));
+        assert_eq!(Dataset { download_count: 0, ..fresh }.trending_score(now), 0);
+        assert_eq!(Dataset::trending_score_of(u32::MAX, now, now), u32::MAX as u64 * 1_000_000 / 4);
+        assert_eq!(Dataset::trending_score_of(u32::MAX, i64::MIN, i64::MAX), 0);
+    }
+}
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+// Standing permission for `grantee` to access a dataset, issued by its contributor
+#[account]
+#[derive(InitSpace)]
+pub struct DatasetGrant {
+    pub dataset: Pubkey,
+    pub grantee: Pubkey,
+    pub granted_by: Pubkey,
+    pub access_request: Pubkey, // The request this grant answered
+    pub granted_at: i64,
+    pub bump: u8
+}
+
+impl DatasetGrant {
+    pub const SPACE: usize = Self::INIT_SPACE;
+}
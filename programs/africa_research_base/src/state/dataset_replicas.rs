@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{BACKEND_HTTPS, MAX_REPLICAS, REPLICA_CID_LEN};
+use crate::error::ErrorCode;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct StorageBackend {
+    pub backend: u8, // BACKEND_* code
+    pub cid: [u8; 64], // REPLICA_CID_LEN bytes, zero-padded: CID, transaction id or URL path on that backend
+    pub added_at: i64,
+}
+
+// Additional copies of a dataset's content beyond its data_uri, as reported by the contributor
+#[account]
+#[derive(InitSpace)]
+pub struct DatasetReplicas {
+    pub dataset: Pubkey,
+    #[max_len(MAX_REPLICAS)]
+    pub replicas: Vec<StorageBackend>,
+    pub bump: u8
+}
+
+impl DatasetReplicas {
+    pub const SPACE: usize = Self::INIT_SPACE;
+
+    pub fn add(&mut self, backend: u8, cid: [u8; REPLICA_CID_LEN], now: i64) -> Result<()> {
+        require!(backend <= BACKEND_HTTPS && cid != [0u8; REPLICA_CID_LEN], ErrorCode::InvalidReplica);
+        require!(
+            !self.replicas.iter().any(|r| r.backend == backend && r.cid == cid),
+            ErrorCode::DuplicateReplica
+        );
+        require!(self.replicas.len() < MAX_REPLICAS, ErrorCode::ReplicasFull);
+        self.replicas.push(StorageBackend { backend, cid, added_at: now });
+        Ok(())
+    }
+
+    // Durability indicator for clients: registered copies, not counting data_uri itself
+    pub fn replica_count(&self) -> u8 {
+        self.replicas.len() as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{BACKEND_ARWEAVE, BACKEND_IPFS};
+
+    fn cid(label: &[u8]) -> [u8; REPLICA_CID_LEN] {
+        let mut cid = [0u8; REPLICA_CID_LEN];
+        cid[..label.len()].copy_from_slice(label);
+        cid
+    }
+
+    fn empty() -> DatasetReplicas {
+        DatasetReplicas { dataset: Pubkey::new_unique(), replicas: Vec::new(), bump: 255 }
+    }
+
+    #[test]
+    fn two_backends_are_recorded() {
+        let mut replicas = empty();
+        replicas.add(BACKEND_IPFS, cid(b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"), 10).unwrap();
+        replicas.add(BACKEND_ARWEAVE, cid(b"bNbA3TEQVL60xlgCcqdz4ZPHFZ711cZ3hmkpGttDt_U"), 20).unwrap();
+
+        assert_eq!(replicas.replica_count(), 2);
+        assert_eq!((replicas.replicas[1].backend, replicas.replicas[1].added_at), (BACKEND_ARWEAVE, 20));
+        assert_eq!(
+            replicas.add(BACKEND_IPFS, cid(b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"), 30).unwrap_err(),
+            ErrorCode::DuplicateReplica.into()
+        );
+    }
+
+    #[test]
+    fn unknown_backends_and_empty_cids_are_rejected() {
+        let mut replicas = empty();
+
+        assert_eq!(replicas.add(BACKEND_HTTPS + 1, cid(b"x"), 0).unwrap_err(), ErrorCode::InvalidReplica.into());
+        assert_eq!(replicas.add(BACKEND_IPFS, cid(b""), 0).unwrap_err(), ErrorCode::InvalidReplica.into());
+        assert_eq!(replicas.replica_count(), 0);
+    }
+
+    #[test]
+    fn replicas_are_capped_and_fit_their_allocation() {
+        let mut replicas = empty();
+        for i in 0..MAX_REPLICAS as u8 {
+            replicas.add(BACKEND_IPFS, cid(&[b'a' + i]), 0).unwrap();
+        }
+
+        assert_eq!(replicas.add(BACKEND_IPFS, cid(b"z"), 0).unwrap_err(), ErrorCode::ReplicasFull.into());
+        let mut data = Vec::new();
+        replicas.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), 8 + DatasetReplicas::SPACE);
+    }
+}
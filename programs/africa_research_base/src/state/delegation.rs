@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+// A contributor's reputation counted toward an institution's aggregate
+#[account]
+#[derive(InitSpace)]
+pub struct Delegation {
+    pub delegator: Pubkey,
+    pub institution: Pubkey,
+    pub score: u32, // Delegator's score when delegated; revoking subtracts this exact amount
+    pub delegated_at: i64,
+    pub bump: u8
+}
+
+impl Delegation {
+    pub const SPACE: usize = Self::INIT_SPACE;
+}
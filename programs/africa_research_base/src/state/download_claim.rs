@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+// Marks a dataset's delta in one download report as claimed; its existence blocks replays
+#[account]
+#[derive(InitSpace)]
+pub struct DownloadClaim {
+    pub report: Pubkey,
+    pub dataset: Pubkey,
+    pub delta: u32,
+    pub claimed_at: i64,
+    pub bump: u8
+}
+
+impl DownloadClaim {
+    pub const SPACE: usize = Self::INIT_SPACE;
+}
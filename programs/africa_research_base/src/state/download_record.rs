@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+
+// One account's recent downloads of a capped dataset
+#[account]
+#[derive(InitSpace)]
+pub struct DownloadRecord {
+    pub dataset: Pubkey,
+    pub downloader: Pubkey,
+    pub window_start: i64,
+    pub count: u32, // Downloads since window_start
+    pub bump: u8
+}
+
+impl DownloadRecord {
+    pub const SPACE: usize = Self::INIT_SPACE;
+
+    // Counts one download, starting a fresh window once `window` seconds have passed
+    pub fn record(&mut self, now: i64, cap: u32, window: i64) -> Result<()> {
+        if now >= self.window_start.saturating_add(window) {
+            self.window_start = now;
+            self.count = 0;
+        }
+        require!(self.count < cap, ErrorCode::DownloadRateLimited);
+        self.count += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record() -> DownloadRecord {
+        DownloadRecord {
+            dataset: Pubkey::new_unique(),
+            downloader: Pubkey::new_unique(),
+            window_start: 0,
+            count: 0,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn downloads_past_cap_are_rate_limited() {
+        let mut record = record();
+        let now = 1_700_000_000;
+
+        record.record(now, 2, 3_600).unwrap();
+        record.record(now + 10, 2, 3_600).unwrap();
+
+        assert_eq!(record.record(now + 20, 2, 3_600).unwrap_err(), ErrorCode::DownloadRateLimited.into());
+        assert_eq!(record.count, 2);
+    }
+
+    #[test]
+    fn cap_resets_after_window() {
+        let mut record = record();
+        let now = 1_700_000_000;
+        record.record(now, 1, 3_600).unwrap();
+        assert!(record.record(now + 3_599, 1, 3_600).is_err());
+
+        record.record(now + 3_600, 1, 3_600).unwrap();
+        assert_eq!(record.window_start, now + 3_600);
+        assert_eq!(record.count, 1);
+    }
+}
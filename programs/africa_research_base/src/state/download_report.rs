@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::constants::MAX_MERKLE_PROOF_LEN;
+use crate::error::ErrorCode;
+
+// Domain tags, so a leaf can never be passed off as an interior node or vice versa
+const LEAF_TAG: &[u8] = &[0];
+const NODE_TAG: &[u8] = &[1];
+
+// Oracle-signed Merkle root over (dataset, download delta) leaves served off-chain
+#[account]
+#[derive(InitSpace)]
+pub struct DownloadReport {
+    pub registry: Pubkey,
+    pub oracle: Pubkey,
+    pub report_id: u64,
+    pub root: [u8; 32],
+    pub claimed_downloads: u64,
+    pub submitted_at: i64,
+    pub bump: u8
+}
+
+impl DownloadReport {
+    pub const SPACE: usize = Self::INIT_SPACE;
+
+    pub fn leaf(dataset: &Pubkey, delta: u32) -> [u8; 32] {
+        hashv(&[LEAF_TAG, dataset.as_ref(), &delta.to_le_bytes()]).to_bytes()
+    }
+
+    // Pairs are hashed in sorted order, so proofs carry no left/right flags
+    pub fn node(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        let (low, high) = if a <= b { (a, b) } else { (b, a) };
+        hashv(&[NODE_TAG, low, high]).to_bytes()
+    }
+
+    pub fn verify(&self, dataset: &Pubkey, delta: u32, proof: &[[u8; 32]]) -> Result<()> {
+        require!(proof.len() <= MAX_MERKLE_PROOF_LEN, ErrorCode::MerkleProofTooLong);
+        let computed = proof
+            .iter()
+            .fold(Self::leaf(dataset, delta), |hash, sibling| Self::node(&hash, sibling));
+        require!(computed == self.root, ErrorCode::InvalidMerkleProof);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(root: [u8; 32]) -> DownloadReport {
+        DownloadReport {
+            registry: Pubkey::new_unique(),
+            oracle: Pubkey::new_unique(),
+            report_id: 1,
+            root,
+            claimed_downloads: 0,
+            submitted_at: 1_700_000_000,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn proofs_verify_each_leaf_of_a_three_leaf_tree() {
+        let datasets = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let leaves = [
+            DownloadReport::leaf(&datasets[0], 5),
+            DownloadReport::leaf(&datasets[1], 3),
+            DownloadReport::leaf(&datasets[2], 9),
+        ];
+        let left = DownloadReport::node(&leaves[0], &leaves[1]);
+        let report = report(DownloadReport::node(&left, &leaves[2]));
+
+        report.verify(&datasets[0], 5, &[leaves[1], leaves[2]]).unwrap();
+        report.verify(&datasets[1], 3, &[leaves[0], leaves[2]]).unwrap();
+        report.verify(&datasets[2], 9, &[left]).unwrap();
+    }
+
+    #[test]
+    fn wrong_delta_or_dataset_fails() {
+        let dataset = Pubkey::new_unique();
+        let sibling = DownloadReport::leaf(&Pubkey::new_unique(), 1);
+        let report = report(DownloadReport::node(&DownloadReport::leaf(&dataset, 5), &sibling));
+
+        assert_eq!(
+            report.verify(&dataset, 6, &[sibling]).unwrap_err(),
+            ErrorCode::InvalidMerkleProof.into()
+        );
+        assert_eq!(
+            report.verify(&Pubkey::new_unique(), 5, &[sibling]).unwrap_err(),
+            ErrorCode::InvalidMerkleProof.into()
+        );
+        // An interior node cannot be claimed as a leaf
+        assert_eq!(
+            report.verify(&dataset, 5, &[]).unwrap_err(),
+            ErrorCode::InvalidMerkleProof.into()
+        );
+        assert_eq!(
+            report.verify(&dataset, 5, &[sibling; MAX_MERKLE_PROOF_LEN + 1]).unwrap_err(),
+            ErrorCode::MerkleProofTooLong.into()
+        );
+    }
+}
@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+// Audit trail of an admin reversing disputed downloads, e.g. traffic from a bot farm
+#[account]
+#[derive(InitSpace)]
+pub struct DownloadReversal {
+    pub dataset: Pubkey,
+    pub contributor: Pubkey,
+    pub admin: Pubkey,
+    pub reversal_id: u64, // Chosen by the admin; unique per dataset
+    pub requested: u32,
+    pub removed: u32, // Less than `requested` when the dataset had fewer downloads left
+    pub reason_hash: [u8; 32], // Hash of the off-chain fraud finding
+    pub reverted_at: i64,
+    pub bump: u8
+}
+
+impl DownloadReversal {
+    pub const SPACE: usize = Self::INIT_SPACE;
+}
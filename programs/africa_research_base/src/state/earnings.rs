@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{MAX_EARNINGS_MINTS, NATIVE_MINT};
+use crate::error::ErrorCode;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct TokenEarnings {
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+// A contributor's download fee income across all of their datasets' fee vaults
+#[account]
+#[derive(InitSpace)]
+pub struct Earnings {
+    pub contributor: Pubkey,
+    pub lifetime_lamports: u64, // Contributor share of every SOL fee, after the platform cut
+    pub withdrawn_lamports: u64,
+    // Fees quoted in other mints; mints beyond the bound go untracked
+    #[max_len(MAX_EARNINGS_MINTS)]
+    pub lifetime_token_amounts: Vec<TokenEarnings>,
+    pub last_withdrawal: i64, // 0 until the first withdrawal
+    pub bump: u8
+}
+
+impl Earnings {
+    pub const SPACE: usize = Self::INIT_SPACE;
+
+    pub fn accrue(&mut self, mint: Pubkey, amount: u64) -> Result<()> {
+        if mint == NATIVE_MINT {
+            self.lifetime_lamports = self.lifetime_lamports
+                .checked_add(amount)
+                .ok_or(ErrorCode::NumericalOverflow)?;
+            return Ok(());
+        }
+        let tracked = &mut self.lifetime_token_amounts;
+        if let Some(earned) = tracked.iter_mut().find(|t| t.mint == mint) {
+            earned.amount = earned.amount
+                .checked_add(amount)
+                .ok_or(ErrorCode::NumericalOverflow)?;
+        } else if tracked.len() < MAX_EARNINGS_MINTS {
+            tracked.push(TokenEarnings { mint, amount });
+        }
+        Ok(())
+    }
+
+    pub fn available_lamports(&self) -> u64 {
+        self.lifetime_lamports.saturating_sub(self.withdrawn_lamports)
+    }
+
+    pub fn withdraw(&mut self, lamports: u64, now: i64) -> Result<()> {
+        require!(lamports > 0 && lamports <= self.available_lamports(), ErrorCode::InsufficientEarnings);
+        self.withdrawn_lamports += lamports;
+        self.last_withdrawal = now;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn earnings() -> Earnings {
+        Earnings {
+            contributor: Pubkey::new_unique(),
+            lifetime_lamports: 0,
+            withdrawn_lamports: 0,
+            lifetime_token_amounts: Vec::new(),
+            last_withdrawal: 0,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn two_downloads_and_a_withdrawal_leave_the_remainder_available() {
+        let mut earnings = earnings();
+        earnings.accrue(NATIVE_MINT, 975_000).unwrap();
+        earnings.accrue(NATIVE_MINT, 975_000).unwrap();
+
+        earnings.withdraw(1_200_000, 1_700_000_000).unwrap();
+
+        assert_eq!(earnings.lifetime_lamports, 1_950_000);
+        assert_eq!(earnings.available_lamports(), 750_000);
+        assert_eq!(earnings.last_withdrawal, 1_700_000_000);
+    }
+
+    #[test]
+    fn withdrawals_are_capped_at_the_available_balance() {
+        let mut earnings = earnings();
+        earnings.accrue(NATIVE_MINT, 1_000).unwrap();
+
+        assert_eq!(earnings.withdraw(1_001, 10).unwrap_err(), ErrorCode::InsufficientEarnings.into());
+        assert_eq!(earnings.withdraw(0, 10).unwrap_err(), ErrorCode::InsufficientEarnings.into());
+        earnings.withdraw(1_000, 10).unwrap();
+        assert_eq!(earnings.withdraw(1, 20).unwrap_err(), ErrorCode::InsufficientEarnings.into());
+        assert_eq!((earnings.lifetime_lamports, earnings.last_withdrawal), (1_000, 10));
+    }
+
+    #[test]
+    fn token_fees_are_tracked_per_mint_up_to_the_bound() {
+        let mut earnings = earnings();
+        let mint = Pubkey::new_unique();
+        earnings.accrue(mint, 5).unwrap();
+        earnings.accrue(mint, 7).unwrap();
+        for _ in 1..MAX_EARNINGS_MINTS {
+            earnings.accrue(Pubkey::new_unique(), 1).unwrap();
+        }
+        earnings.accrue(Pubkey::new_unique(), 1).unwrap();
+
+        assert_eq!(earnings.lifetime_token_amounts.len(), MAX_EARNINGS_MINTS);
+        assert_eq!(earnings.lifetime_token_amounts[0], TokenEarnings { mint, amount: 12 });
+        assert_eq!(earnings.lifetime_lamports, 0);
+
+        let mut data = Vec::new();
+        earnings.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), 8 + Earnings::SPACE);
+    }
+}
@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+// A public endorsement of a dataset, carrying no rating; one per endorser, enforced by the PDA
+#[account]
+#[derive(InitSpace)]
+pub struct Endorsement {
+    pub dataset: Pubkey,
+    pub endorser: Pubkey,
+    pub endorsed_at: i64,
+    pub bump: u8
+}
+
+impl Endorsement {
+    pub const SPACE: usize = Self::INIT_SPACE;
+}
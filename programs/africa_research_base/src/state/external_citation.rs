@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+// An oracle-attested citation of a dataset in an off-chain publication
+#[account]
+#[derive(InitSpace)]
+pub struct ExternalCitation {
+    pub dataset: Pubkey,
+    pub contributor: Pubkey,
+    pub oracle: Pubkey,
+    pub publication_hash: [u8; 32], // Hash of the publication identifier, e.g. a DOI
+    pub reported_at: i64,
+    pub bump: u8
+}
+
+impl ExternalCitation {
+    pub const SPACE: usize = Self::INIT_SPACE;
+}
@@ -0,0 +1,172 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{NATIVE_DECIMALS, NATIVE_MINT, NATIVE_SYMBOL, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID};
+use crate::error::ErrorCode;
+use crate::Reputation;
+
+// SPL mint layout: COption<Pubkey> authority (36) and supply (8) precede decimals, then is_initialized
+const MINT_LEN: usize = 82;
+const MINT_DECIMALS_OFFSET: usize = 44;
+const MINT_INITIALIZED_OFFSET: usize = 45;
+
+// Holds a dataset's accrued download fees until the contributor withdraws them
+#[account]
+#[derive(InitSpace)]
+pub struct FeeVault {
+    pub dataset: Pubkey,
+    pub contributor: Pubkey,
+    pub registry: Pubkey, // The dataset's registry; only its admin may recover the vault once the dataset is gone
+    pub total_collected: u64,
+    // Currency fees are quoted in, so frontends can format prices without a mint lookup
+    pub mint: Pubkey,
+    pub fee_decimals: u8,
+    pub fee_symbol: [u8; 8], // ASCII, zero-padded
+    pub bump: u8
+}
+
+impl FeeVault {
+    // Fixed-size account, so the derived InitSpace is exact
+    pub const SPACE: usize = Self::INIT_SPACE;
+
+    // New vaults quote fees in SOL
+    pub fn new(dataset: Pubkey, contributor: Pubkey, registry: Pubkey, bump: u8) -> Self {
+        FeeVault {
+            dataset,
+            contributor,
+            registry,
+            total_collected: 0,
+            mint: NATIVE_MINT,
+            fee_decimals: NATIVE_DECIMALS,
+            fee_symbol: NATIVE_SYMBOL,
+            bump
+        }
+    }
+
+    // Decimals are read from the mint account itself rather than trusted from the caller
+    pub fn set_currency(&mut self, mint: &AccountInfo, symbol: [u8; 8]) -> Result<()> {
+        require!(valid_symbol(&symbol), ErrorCode::InvalidFeeSymbol);
+        self.fee_decimals = mint_decimals(mint)?;
+        self.mint = mint.key();
+        self.fee_symbol = symbol;
+        Ok(())
+    }
+
+    // Splits an orphaned vault's balance on recovery: everything above the rent reserve is the
+    // contributor's unwithdrawn income and goes back to them; only the reserve is swept
+    pub fn recovery_split(lamports: u64, rent_minimum: u64) -> (u64, u64) {
+        let to_contributor = lamports.saturating_sub(rent_minimum);
+        (to_contributor, lamports - to_contributor)
+    }
+
+    // Follows one rotate_contributor_key step alongside the vault's dataset, so a later recovery
+    // refunds the new key and settles the earnings that moved with it
+    pub fn repoint_contributor(&mut self, rotated: &Reputation) -> Result<()> {
+        require!(!rotated.is_current(), ErrorCode::InvalidRepoint);
+        require_keys_eq!(self.contributor, rotated.contributor, ErrorCode::InvalidRepoint);
+        self.contributor = rotated.rotated_to;
+        Ok(())
+    }
+}
+
+fn mint_decimals(mint: &AccountInfo) -> Result<u8> {
+    require!(
+        *mint.owner == TOKEN_PROGRAM_ID || *mint.owner == TOKEN_2022_PROGRAM_ID,
+        ErrorCode::InvalidFeeMint
+    );
+    let data = mint.try_borrow_data()?;
+    require!(
+        data.len() >= MINT_LEN && data[MINT_INITIALIZED_OFFSET] == 1,
+        ErrorCode::InvalidFeeMint
+    );
+    Ok(data[MINT_DECIMALS_OFFSET])
+}
+
+// One to eight ASCII letters or digits, then zero padding
+fn valid_symbol(symbol: &[u8; 8]) -> bool {
+    let len = symbol.iter().position(|&b| b == 0).unwrap_or(symbol.len());
+    len > 0
+        && symbol[..len].iter().all(u8::is_ascii_alphanumeric)
+        && symbol[len..].iter().all(|&b| b == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mint_data(decimals: u8) -> Vec<u8> {
+        let mut data = vec![0u8; MINT_LEN];
+        data[MINT_DECIMALS_OFFSET] = decimals;
+        data[MINT_INITIALIZED_OFFSET] = 1;
+        data
+    }
+
+    fn vault() -> FeeVault {
+        FeeVault::new(Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), 255)
+    }
+
+    #[test]
+    fn stored_decimals_match_the_mint() {
+        let key = Pubkey::new_unique();
+        let mut lamports = 1_000_000;
+        let mut data = mint_data(6);
+        let mint = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &TOKEN_PROGRAM_ID, false, 0);
+
+        let mut vault = vault();
+        assert_eq!(vault.fee_decimals, NATIVE_DECIMALS);
+        vault.set_currency(&mint, *b"USDC\0\0\0\0").unwrap();
+        assert_eq!(vault.mint, key);
+        assert_eq!(vault.fee_decimals, 6);
+        assert_eq!(&vault.fee_symbol, b"USDC\0\0\0\0");
+    }
+
+    #[test]
+    fn mint_must_be_an_initialized_token_program_account() {
+        let key = Pubkey::new_unique();
+        let mut lamports = 1_000_000;
+        let mut data = mint_data(6);
+        let impostor = Pubkey::new_unique();
+        let foreign = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &impostor, false, 0);
+        assert_eq!(
+            vault().set_currency(&foreign, *b"USDC\0\0\0\0").unwrap_err(),
+            ErrorCode::InvalidFeeMint.into()
+        );
+
+        let mut lamports = 1_000_000;
+        let mut data = vec![0u8; MINT_LEN];
+        let uninitialized = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &TOKEN_2022_PROGRAM_ID, false, 0);
+        assert_eq!(
+            vault().set_currency(&uninitialized, *b"USDC\0\0\0\0").unwrap_err(),
+            ErrorCode::InvalidFeeMint.into()
+        );
+    }
+
+    #[test]
+    fn symbol_must_be_padded_ascii() {
+        assert!(valid_symbol(&NATIVE_SYMBOL));
+        assert!(valid_symbol(b"ABCDEFGH"));
+        assert!(!valid_symbol(&[0u8; 8]));
+        assert!(!valid_symbol(b"US DC\0\0\0"));
+        assert!(!valid_symbol(b"US\0DC\0\0\0"));
+    }
+
+    #[test]
+    fn recovery_returns_unwithdrawn_income_to_the_contributor() {
+        assert_eq!(FeeVault::recovery_split(3_500_000, 1_500_000), (2_000_000, 1_500_000));
+        // A vault holding only dust below the reserve has nothing of the contributor's left
+        assert_eq!(FeeVault::recovery_split(1_000_000, 1_500_000), (0, 1_000_000));
+    }
+
+    #[test]
+    fn repointed_vault_answers_to_the_new_key() {
+        let mut vault = vault();
+        let mut rotated = Reputation { contributor: vault.contributor, ..Reputation::sample() };
+        let new_key = Pubkey::new_unique();
+        assert_eq!(vault.repoint_contributor(&rotated).unwrap_err(), ErrorCode::InvalidRepoint.into());
+
+        rotated.rotated_to = new_key;
+        vault.repoint_contributor(&rotated).unwrap();
+        assert_eq!(vault.contributor, new_key);
+        // Already moved: the old key no longer owns it
+        assert_eq!(vault.repoint_contributor(&rotated).unwrap_err(), ErrorCode::InvalidRepoint.into());
+    }
+}
@@ -0,0 +1,207 @@
+// Deterministic, fully populated accounts written to target/account-fixtures (or ACCOUNT_FIXTURES_DIR)
+// by `cargo test`, each as the discriminator followed by its Borsh fields. Off-chain clients decode
+// them to check their layouts against the program's. Every field is spelled out, with no `..`
+// defaults, so adding one fails to compile here until it is given a fixture value too.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::state::{CoContributorCredit, Dataset, FieldScore, Registry, Reputation};
+
+fn key(byte: u8) -> Pubkey {
+    Pubkey::new_from_array([byte; 32])
+}
+
+pub(crate) fn dataset_fixture() -> Dataset {
+    Dataset {
+        id: key(1),
+        registry: key(2),
+        contributor: key(3),
+        co_contributors: vec![key(4), key(5)],
+        editors: vec![key(6)],
+        content_hash: [7u8; 32],
+        preview_hash: [8u8; 32],
+        ai_metadata: b"{\"model\":\"fixture\"}".to_vec(),
+        file_name: b"rainfall_2020.csv".to_vec(),
+        research_fields: vec![1, 3],
+        dataset_index: 9,
+        file_size: 10_485_760,
+        data_uri: b"ipfs://QmFixture".to_vec(),
+        uri_locked: true,
+        thumbnail_uri: Some([b't'; THUMBNAIL_URI_LEN]),
+        column_count: 12,
+        row_count: 3_650,
+        quality_score: 87,
+        upload_timestamp: 1_700_000_000,
+        last_updated: Some(1_700_000_100),
+        download_count: 42,
+        download_fee: 1_000_000,
+        download_cap: 5,
+        download_window: 86_400,
+        rating_sum: 460,
+        rating_count: 6,
+        citation_count: 3,
+        is_active: true,
+        featured: true,
+        expires_at: Some(1_800_000_000),
+        reputation_finalized: true,
+        review_status: REVIEW_APPROVED,
+        reviewer: key(10),
+        review_note_hash: [11u8; 32],
+        forked_from: key(12),
+        fork_count: 2,
+        frozen: false,
+        unavailable: false,
+        quality_hidden: false,
+        pinned_until: 1_750_000_000,
+        pin_seconds_funded: 2_592_000,
+        endorsement_count: 4,
+        rating_weight: 9,
+        title: b"Rainfall Accra 2020".to_vec(),
+        license_expires_at: Some(1_900_000_000),
+        supersedes: Some(key(13)),
+        superseded_by: None,
+        quality_disputed: true,
+        bond: 50_000_000,
+        locked: false,
+        anonymous: true,
+        review_deadline: 1_701_209_600,
+        featured_until: 1_702_000_000,
+        finalized: true,
+        spam_deposit: 2_000_000,
+        language: Some(*b"sw"),
+        language_mismatch: true,
+        creator: key(3),
+        credited_quality: 87,
+        credited_field_points: vec![FieldScore { field: 1, score: 97 }],
+        parent: Some(key(15)),
+        co_credits: vec![CoContributorCredit {
+            contributor: key(4),
+            quality: 29,
+            field_points: vec![FieldScore { field: 3, score: 31 }],
+        }],
+        bump: 254,
+        schema_version: DATASET_SCHEMA_VERSION,
+    }
+}
+
+pub(crate) fn reputation_fixture() -> Reputation {
+    Reputation {
+        contributor: key(3),
+        total_uploads: 14,
+        dataset_count: 15,
+        download_time: 1_700_000_200,
+        total_quality_score: 1_190,
+        total_downloads: 320,
+        total_citations: 11,
+        total_forks: 5,
+        total_penalties: 20,
+        total_decay: 7,
+        last_decay_at: 1_700_500_000,
+        decay_paused_until: 1_701_000_000,
+        delegated_to: key(14),
+        delegate_count: 0,
+        delegated_score: 0,
+        reputation_score: 1_293,
+        field_scores: vec![FieldScore { field: 1, score: 410 }, FieldScore { field: 3, score: 96 }],
+        anonymous: true,
+        rotated_to: Pubkey::default(),
+        bump: 253,
+    }
+}
+
+pub(crate) fn registry_fixture() -> Registry {
+    Registry {
+        admin: key(20),
+        creator: key(21),
+        name: *b"african-climate-data\0\0\0\0\0\0\0\0\0\0\0\0",
+        total_datasets: 128,
+        total_downloads: 4_096,
+        platform_fee_bps: 250,
+        min_quality_score: 40,
+        reputation_grace_period: DEFAULT_REPUTATION_GRACE_PERIOD,
+        min_file_size: 1_024,
+        archived_count: 6,
+        citation_pair_cap: DEFAULT_CITATION_PAIR_CAP,
+        featured_reputation_threshold: DEFAULT_FEATURED_REPUTATION_THRESHOLD,
+        featured_count: 8,
+        citation_oracle: key(22),
+        curated: true,
+        freeze_penalty: DEFAULT_FREEZE_PENALTY,
+        unavailable_report_threshold: DEFAULT_UNAVAILABLE_REPORT_THRESHOLD,
+        download_oracle: key(23),
+        quality_scale: 5,
+        min_rating_count: MIN_RATINGS_FOR_QUALITY,
+        structured_ai_metadata: true,
+        quality_dispute_delta: DEFAULT_QUALITY_DISPUTE_DELTA,
+        quality_bond: 10_000_000,
+        taxonomy_enabled: true,
+        review_sla: DEFAULT_REVIEW_SLA,
+        institution_oracle: key(24),
+        spam_deposit: 1_000_000,
+        treasury_withdrawal_cap: 5_000_000_000,
+        bump: 252,
+    }
+}
+
+fn fixtures_dir() -> PathBuf {
+    std::env::var_os("ACCOUNT_FIXTURES_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../target/account-fixtures"))
+}
+
+// Round-trips the account through its on-chain encoding, writes the bytes out and returns them
+fn emit<T: AccountSerialize + AccountDeserialize>(name: &str, account: &T) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    account.try_serialize(&mut bytes).unwrap();
+
+    let decoded = T::try_deserialize(&mut bytes.as_slice()).unwrap();
+    let mut reencoded = Vec::new();
+    decoded.try_serialize(&mut reencoded).unwrap();
+    assert_eq!(reencoded, bytes, "{name} changed across a serialize/deserialize round trip");
+
+    let dir = fixtures_dir();
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join(format!("{name}.bin")), &bytes).unwrap();
+    bytes
+}
+
+#[test]
+fn dataset_fixture_round_trips() {
+    let bytes = emit("dataset", &dataset_fixture());
+    let decoded = Dataset::try_deserialize(&mut bytes.as_slice()).unwrap();
+
+    assert_eq!(decoded.co_contributors, vec![key(4), key(5)]);
+    assert_eq!(decoded.thumbnail_uri, Some([b't'; THUMBNAIL_URI_LEN]));
+    assert_eq!((decoded.supersedes, decoded.superseded_by), (Some(key(13)), None));
+    assert_eq!((decoded.finalized, decoded.bump, decoded.schema_version), (true, 254, DATASET_SCHEMA_VERSION));
+}
+
+#[test]
+fn reputation_fixture_round_trips() {
+    let bytes = emit("reputation", &reputation_fixture());
+    let decoded = Reputation::try_deserialize(&mut bytes.as_slice()).unwrap();
+
+    assert_eq!(decoded.field_scores, reputation_fixture().field_scores);
+    assert_eq!((decoded.reputation_score, decoded.anonymous, decoded.bump), (1_293, true, 253));
+}
+
+#[test]
+fn registry_fixture_round_trips() {
+    let bytes = emit("registry", &registry_fixture());
+    let decoded = Registry::try_deserialize(&mut bytes.as_slice()).unwrap();
+
+    // Fixed-size, so the fixture is exactly the allocation
+    assert_eq!(bytes.len(), 8 + Registry::INIT_SPACE);
+    assert_eq!((decoded.institution_oracle, decoded.bump), (key(24), 252));
+}
+
+#[test]
+fn fixtures_are_deterministic() {
+    assert_eq!(dataset_fixture().try_to_vec().unwrap(), dataset_fixture().try_to_vec().unwrap());
+    assert_eq!(reputation_fixture().try_to_vec().unwrap(), reputation_fixture().try_to_vec().unwrap());
+    assert_eq!(registry_fixture().try_to_vec().unwrap(), registry_fixture().try_to_vec().unwrap());
+}
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+
+// Global content-hash lookup: the first open dataset registered with a hash, in any registry, is its
+// canonical record. Closing that dataset releases the entry for the next upload of the same content.
+#[account]
+#[derive(InitSpace)]
+pub struct HashRegistry {
+    pub content_hash: [u8; 32],
+    pub dataset: Pubkey,
+    pub contributor: Pubkey, // Dataset::published_contributor, so a pseudonym for anonymous contributors
+    pub registered_at: i64,
+    pub bump: u8
+}
+
+impl HashRegistry {
+    pub const SPACE: usize = Self::INIT_SPACE;
+
+    // Claims an unclaimed hash for `dataset`; returns whether it became the canonical record
+    pub fn register(&mut self, content_hash: [u8; 32], dataset: Pubkey, contributor: Pubkey, now: i64, bump: u8) -> bool {
+        if self.is_claimed() {
+            return false;
+        }
+        *self = HashRegistry { content_hash, dataset, contributor, registered_at: now, bump };
+        true
+    }
+
+    // close_dataset releases the hash held by the closed dataset, leaving it unclaimed
+    pub fn release(&mut self, dataset: &Pubkey) {
+        if self.dataset == *dataset {
+            self.dataset = Pubkey::default();
+            self.contributor = Pubkey::default();
+        }
+    }
+
+    pub fn is_claimed(&self) -> bool {
+        self.dataset != Pubkey::default()
+    }
+
+    // A merge closes `duplicate`; the hash moves to the dataset that absorbed it
+    pub fn repoint(&mut self, duplicate: &Pubkey, canonical: Pubkey, contributor: Pubkey) {
+        if self.dataset == *duplicate {
+            self.dataset = canonical;
+            self.contributor = contributor;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unclaimed() -> HashRegistry {
+        HashRegistry {
+            content_hash: [0; 32],
+            dataset: Pubkey::default(),
+            contributor: Pubkey::default(),
+            registered_at: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn first_registration_is_canonical() {
+        let mut entry = unclaimed();
+        let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let contributor = Pubkey::new_unique();
+
+        assert!(entry.register([7; 32], first, contributor, 1_000, 254));
+        assert!(!entry.register([7; 32], second, Pubkey::new_unique(), 2_000, 254));
+        assert_eq!((entry.dataset, entry.contributor, entry.registered_at), (first, contributor, 1_000));
+    }
+
+    #[test]
+    fn closing_the_canonical_record_frees_the_hash() {
+        let mut entry = unclaimed();
+        let (closed, other, next) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        entry.register([7; 32], closed, Pubkey::new_unique(), 1_000, 254);
+
+        entry.release(&other);
+        assert_eq!(entry.dataset, closed);
+
+        entry.release(&closed);
+        assert!(!entry.is_claimed());
+        let contributor = Pubkey::new_unique();
+        assert!(entry.register([7; 32], next, contributor, 2_000, 254));
+        assert_eq!((entry.dataset, entry.contributor, entry.registered_at), (next, contributor, 2_000));
+    }
+
+    #[test]
+    fn merging_the_canonical_record_repoints_it() {
+        let mut entry = unclaimed();
+        let (duplicate, canonical, other) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        entry.register([7; 32], duplicate, Pubkey::new_unique(), 1_000, 254);
+
+        entry.repoint(&other, Pubkey::new_unique(), Pubkey::new_unique());
+        assert_eq!(entry.dataset, duplicate);
+
+        let contributor = Pubkey::new_unique();
+        entry.repoint(&duplicate, canonical, contributor);
+        assert_eq!((entry.dataset, entry.contributor), (canonical, contributor));
+    }
+}
@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use core::cmp::Ordering;
+
+use crate::constants::LEADERBOARD_SIZE;
+use crate::state::Reputation;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct LeaderboardEntry {
+    pub contributor: Pubkey,
+    pub reputation_score: u32,
+    pub total_uploads: u32,
+}
+
+impl LeaderboardEntry {
+    pub fn from_reputation(reputation: &Reputation) -> Self {
+        LeaderboardEntry {
+            contributor: reputation.contributor,
+            reputation_score: reputation.reputation_score,
+            total_uploads: reputation.total_uploads,
+        }
+    }
+
+    // Leaderboard order: higher score first, then more uploads, then the lower contributor key.
+    // Keys are unique, so the order is total and independent of insertion order.
+    pub fn rank_cmp(&self, other: &Self) -> Ordering {
+        other.reputation_score
+            .cmp(&self.reputation_score)
+            .then(other.total_uploads.cmp(&self.total_uploads))
+            .then(self.contributor.cmp(&other.contributor))
+    }
+}
+
+// Top contributors of a registry, kept sorted by `LeaderboardEntry::rank_cmp`
+#[account]
+#[derive(InitSpace)]
+pub struct Leaderboard {
+    pub registry: Pubkey,
+    #[max_len(LEADERBOARD_SIZE)]
+    pub entries: Vec<LeaderboardEntry>,
+    pub bump: u8
+}
+
+impl Leaderboard {
+    pub const SPACE: usize = Self::INIT_SPACE;
+
+    // Inserts or refreshes a contributor's entry, dropping whoever falls off the end
+    pub fn upsert(&mut self, entry: LeaderboardEntry) {
+        self.entries.retain(|existing| existing.contributor != entry.contributor);
+        let index = self.entries
+            .iter()
+            .position(|existing| entry.rank_cmp(existing) == Ordering::Less)
+            .unwrap_or(self.entries.len());
+        if index < LEADERBOARD_SIZE {
+            self.entries.insert(index, entry);
+            self.entries.truncate(LEADERBOARD_SIZE);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(key: u8, reputation_score: u32, total_uploads: u32) -> LeaderboardEntry {
+        LeaderboardEntry { contributor: Pubkey::new_from_array([key; 32]), reputation_score, total_uploads }
+    }
+
+    fn leaderboard() -> Leaderboard {
+        Leaderboard { registry: Pubkey::new_unique(), entries: Vec::new(), bump: 255 }
+    }
+
+    #[test]
+    fn equal_scores_place_deterministically() {
+        let (low_key, high_key) = (entry(1, 500, 4), entry(2, 500, 4));
+
+        let mut first = leaderboard();
+        first.upsert(high_key);
+        first.upsert(low_key);
+        let mut second = leaderboard();
+        second.upsert(low_key);
+        second.upsert(high_key);
+
+        assert_eq!(first.entries, vec![low_key, high_key]);
+        assert_eq!(first.entries, second.entries);
+    }
+
+    #[test]
+    fn more_uploads_break_score_ties_before_keys() {
+        let mut board = leaderboard();
+        board.upsert(entry(1, 500, 4));
+        board.upsert(entry(2, 500, 9));
+        board.upsert(entry(3, 700, 1));
+
+        let order: Vec<u8> = board.entries.iter().map(|e| e.contributor.to_bytes()[0]).collect();
+        assert_eq!(order, [3, 2, 1]);
+    }
+
+    #[test]
+    fn refreshed_entry_moves_and_board_stays_bounded() {
+        let mut board = leaderboard();
+        for key in 0..=LEADERBOARD_SIZE as u8 {
+            board.upsert(entry(key, 100 + key as u32, 0));
+        }
+        assert_eq!(board.entries.len(), LEADERBOARD_SIZE);
+        assert!(!board.entries.iter().any(|e| e.contributor == entry(0, 0, 0).contributor));
+
+        board.upsert(entry(1, 1_000, 0));
+        assert_eq!(board.entries[0], entry(1, 1_000, 0));
+        assert_eq!(board.entries.len(), LEADERBOARD_SIZE);
+    }
+}
@@ -0,0 +1,567 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::DATASET_SCHEMA_VERSION;
+use crate::state::{Dataset, FieldScore};
+use crate::uri::uri_bytes;
+
+// Frozen copies of every earlier Dataset layout, so migrations keep decoding them however the live
+// struct moves on. Sizes and array lengths are literals on purpose: never derive them from the
+// current constants or the current struct, and never edit a schema here once accounts exist in it.
+// A layout change adds a schema, bumps DATASET_SCHEMA_VERSION and migrates the previous one upward.
+
+// Schema 1: data_uri as a zero-padded [u8; 256] and no schema_version
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct DatasetV1 {
+    pub id: Pubkey,
+    pub registry: Pubkey,
+    pub contributor: Pubkey,
+    pub co_contributors: Vec<Pubkey>,
+    pub editors: Vec<Pubkey>,
+    pub content_hash: [u8; 32],
+    pub preview_hash: [u8; 32],
+    pub ai_metadata: Vec<u8>,
+    pub file_name: Vec<u8>,
+    pub research_fields: Vec<u8>,
+    pub dataset_index: u32,
+    pub file_size: u64,
+    pub data_uri: [u8; 256],
+    pub uri_locked: bool,
+    pub thumbnail_uri: Option<[u8; 128]>,
+    pub column_count: u64,
+    pub row_count: u64,
+    pub quality_score: u8,
+    pub upload_timestamp: i64,
+    pub last_updated: Option<i64>,
+    pub download_count: u32,
+    pub download_fee: u64,
+    pub download_cap: u32,
+    pub download_window: i64,
+    pub rating_sum: u64, // Unweighted: every rating counted once
+    pub rating_count: u32,
+    pub citation_count: u32,
+    pub is_active: bool,
+    pub featured: bool,
+    pub expires_at: Option<i64>,
+    pub reputation_finalized: bool,
+    pub review_status: u8,
+    pub reviewer: Pubkey,
+    pub review_note_hash: [u8; 32],
+    pub forked_from: Pubkey,
+    pub fork_count: u32,
+    pub frozen: bool,
+    pub unavailable: bool,
+    pub quality_hidden: bool,
+    pub pinned_until: i64,
+    pub pin_seconds_funded: u64,
+    pub bump: u8
+}
+
+impl DatasetV1 {
+    // Allocation excluding the discriminator: 8 co-contributors, 5 editors, 1500 bytes of
+    // ai_metadata, 100 of file_name and 4 research fields at most
+    pub const SPACE: usize = 2_805;
+}
+
+// Schema 2: schema 1 with data_uri as a Vec of at most 256 bytes and a trailing schema_version
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct DatasetV2 {
+    pub id: Pubkey,
+    pub registry: Pubkey,
+    pub contributor: Pubkey,
+    pub co_contributors: Vec<Pubkey>,
+    pub editors: Vec<Pubkey>,
+    pub content_hash: [u8; 32],
+    pub preview_hash: [u8; 32],
+    pub ai_metadata: Vec<u8>,
+    pub file_name: Vec<u8>,
+    pub research_fields: Vec<u8>,
+    pub dataset_index: u32,
+    pub file_size: u64,
+    pub data_uri: Vec<u8>,
+    pub uri_locked: bool,
+    pub thumbnail_uri: Option<[u8; 128]>,
+    pub column_count: u64,
+    pub row_count: u64,
+    pub quality_score: u8,
+    pub upload_timestamp: i64,
+    pub last_updated: Option<i64>,
+    pub download_count: u32,
+    pub download_fee: u64,
+    pub download_cap: u32,
+    pub download_window: i64,
+    pub rating_sum: u64, // Unweighted, as in schema 1
+    pub rating_count: u32,
+    pub citation_count: u32,
+    pub is_active: bool,
+    pub featured: bool,
+    pub expires_at: Option<i64>,
+    pub reputation_finalized: bool,
+    pub review_status: u8,
+    pub reviewer: Pubkey,
+    pub review_note_hash: [u8; 32],
+    pub forked_from: Pubkey,
+    pub fork_count: u32,
+    pub frozen: bool,
+    pub unavailable: bool,
+    pub quality_hidden: bool,
+    pub pinned_until: i64,
+    pub pin_seconds_funded: u64,
+    pub bump: u8,
+    pub schema_version: u8
+}
+
+impl DatasetV2 {
+    pub const SCHEMA_VERSION: u8 = 2;
+    // DatasetV1::SPACE plus the data_uri length prefix and schema_version
+    pub const SPACE: usize = 2_810;
+}
+
+impl From<DatasetV1> for DatasetV2 {
+    fn from(v1: DatasetV1) -> Self {
+        DatasetV2 {
+            id: v1.id,
+            registry: v1.registry,
+            contributor: v1.contributor,
+            co_contributors: v1.co_contributors,
+            editors: v1.editors,
+            content_hash: v1.content_hash,
+            preview_hash: v1.preview_hash,
+            ai_metadata: v1.ai_metadata,
+            file_name: v1.file_name,
+            research_fields: v1.research_fields,
+            dataset_index: v1.dataset_index,
+            file_size: v1.file_size,
+            data_uri: uri_bytes(&v1.data_uri).to_vec(),
+            uri_locked: v1.uri_locked,
+            thumbnail_uri: v1.thumbnail_uri,
+            column_count: v1.column_count,
+            row_count: v1.row_count,
+            quality_score: v1.quality_score,
+            upload_timestamp: v1.upload_timestamp,
+            last_updated: v1.last_updated,
+            download_count: v1.download_count,
+            download_fee: v1.download_fee,
+            download_cap: v1.download_cap,
+            download_window: v1.download_window,
+            rating_sum: v1.rating_sum,
+            rating_count: v1.rating_count,
+            citation_count: v1.citation_count,
+            is_active: v1.is_active,
+            featured: v1.featured,
+            expires_at: v1.expires_at,
+            reputation_finalized: v1.reputation_finalized,
+            review_status: v1.review_status,
+            reviewer: v1.reviewer,
+            review_note_hash: v1.review_note_hash,
+            forked_from: v1.forked_from,
+            fork_count: v1.fork_count,
+            frozen: v1.frozen,
+            unavailable: v1.unavailable,
+            quality_hidden: v1.quality_hidden,
+            pinned_until: v1.pinned_until,
+            pin_seconds_funded: v1.pin_seconds_funded,
+            bump: v1.bump,
+            schema_version: DatasetV2::SCHEMA_VERSION,
+        }
+    }
+}
+
+// Schema 3 added every field from endorsement_count through parent. Each takes the value a schema 2
+// dataset implicitly had: no endorsements, bond, deposit or links, the PDA's own key as creator and
+// weight-1 ratings. The upload credit was always the full self-reported quality; field points went
+// unrecorded, so closing a migrated dataset takes back only the quality.
+impl From<DatasetV2> for DatasetV3 {
+    fn from(v2: DatasetV2) -> Self {
+        let credited_quality = if v2.reputation_finalized { v2.quality_score } else { 0 };
+        DatasetV3 {
+            id: v2.id,
+            registry: v2.registry,
+            contributor: v2.contributor,
+            co_contributors: v2.co_contributors,
+            editors: v2.editors,
+            content_hash: v2.content_hash,
+            preview_hash: v2.preview_hash,
+            ai_metadata: v2.ai_metadata,
+            title: v2.file_name.clone(),
+            file_name: v2.file_name,
+            research_fields: v2.research_fields,
+            dataset_index: v2.dataset_index,
+            file_size: v2.file_size,
+            data_uri: v2.data_uri,
+            uri_locked: v2.uri_locked,
+            thumbnail_uri: v2.thumbnail_uri,
+            column_count: v2.column_count,
+            row_count: v2.row_count,
+            quality_score: v2.quality_score,
+            upload_timestamp: v2.upload_timestamp,
+            last_updated: v2.last_updated,
+            download_count: v2.download_count,
+            download_fee: v2.download_fee,
+            download_cap: v2.download_cap,
+            download_window: v2.download_window,
+            rating_sum: v2.rating_sum,
+            rating_count: v2.rating_count,
+            citation_count: v2.citation_count,
+            is_active: v2.is_active,
+            featured: v2.featured,
+            expires_at: v2.expires_at,
+            reputation_finalized: v2.reputation_finalized,
+            review_status: v2.review_status,
+            reviewer: v2.reviewer,
+            review_note_hash: v2.review_note_hash,
+            forked_from: v2.forked_from,
+            fork_count: v2.fork_count,
+            frozen: v2.frozen,
+            unavailable: v2.unavailable,
+            quality_hidden: v2.quality_hidden,
+            pinned_until: v2.pinned_until,
+            pin_seconds_funded: v2.pin_seconds_funded,
+            endorsement_count: 0,
+            rating_weight: v2.rating_count as u64,
+            license_expires_at: None,
+            supersedes: None,
+            superseded_by: None,
+            quality_disputed: false,
+            bond: 0,
+            locked: false,
+            anonymous: false,
+            review_deadline: 0,
+            featured_until: 0,
+            finalized: false,
+            spam_deposit: 0,
+            language: None,
+            language_mismatch: false,
+            creator: v2.contributor,
+            credited_quality,
+            credited_field_points: Vec::new(),
+            parent: None,
+            bump: v2.bump,
+            schema_version: DatasetV3::SCHEMA_VERSION,
+        }
+    }
+}
+
+// Schema 3: schema 2 with endorsement_count through parent appended before bump. FieldScore is
+// frozen too, by Reputation's own layout
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct DatasetV3 {
+    pub id: Pubkey,
+    pub registry: Pubkey,
+    pub contributor: Pubkey,
+    pub co_contributors: Vec<Pubkey>,
+    pub editors: Vec<Pubkey>,
+    pub content_hash: [u8; 32],
+    pub preview_hash: [u8; 32],
+    pub ai_metadata: Vec<u8>,
+    pub file_name: Vec<u8>,
+    pub research_fields: Vec<u8>,
+    pub dataset_index: u32,
+    pub file_size: u64,
+    pub data_uri: Vec<u8>,
+    pub uri_locked: bool,
+    pub thumbnail_uri: Option<[u8; 128]>,
+    pub column_count: u64,
+    pub row_count: u64,
+    pub quality_score: u8,
+    pub upload_timestamp: i64,
+    pub last_updated: Option<i64>,
+    pub download_count: u32,
+    pub download_fee: u64,
+    pub download_cap: u32,
+    pub download_window: i64,
+    pub rating_sum: u64,
+    pub rating_count: u32,
+    pub citation_count: u32,
+    pub is_active: bool,
+    pub featured: bool,
+    pub expires_at: Option<i64>,
+    pub reputation_finalized: bool,
+    pub review_status: u8,
+    pub reviewer: Pubkey,
+    pub review_note_hash: [u8; 32],
+    pub forked_from: Pubkey,
+    pub fork_count: u32,
+    pub frozen: bool,
+    pub unavailable: bool,
+    pub quality_hidden: bool,
+    pub pinned_until: i64,
+    pub pin_seconds_funded: u64,
+    pub endorsement_count: u32,
+    pub rating_weight: u64,
+    pub title: Vec<u8>,
+    pub license_expires_at: Option<i64>,
+    pub supersedes: Option<Pubkey>,
+    pub superseded_by: Option<Pubkey>,
+    pub quality_disputed: bool,
+    pub bond: u64,
+    pub locked: bool,
+    pub anonymous: bool,
+    pub review_deadline: i64,
+    pub featured_until: i64,
+    pub finalized: bool,
+    pub spam_deposit: u64,
+    pub language: Option<[u8; 2]>,
+    pub language_mismatch: bool,
+    pub creator: Pubkey,
+    pub credited_quality: u8,
+    pub credited_field_points: Vec<FieldScore>,
+    pub parent: Option<Pubkey>,
+    pub bump: u8,
+    pub schema_version: u8,
+}
+
+impl DatasetV3 {
+    pub const SCHEMA_VERSION: u8 = 3;
+    // Unexpanded allocation; expand_ai_metadata grows an account past it
+    pub const SPACE: usize = 3_151;
+}
+
+// Schema 4 added co_credits. Co-contributor shares went unrecorded before it, so closing a migrated
+// dataset takes back only the contributor's own credit, as closing it in schema 3 would have.
+impl From<DatasetV3> for Dataset {
+    fn from(v3: DatasetV3) -> Self {
+        Dataset {
+            id: v3.id,
+            registry: v3.registry,
+            contributor: v3.contributor,
+            co_contributors: v3.co_contributors,
+            editors: v3.editors,
+            content_hash: v3.content_hash,
+            preview_hash: v3.preview_hash,
+            ai_metadata: v3.ai_metadata,
+            file_name: v3.file_name,
+            research_fields: v3.research_fields,
+            dataset_index: v3.dataset_index,
+            file_size: v3.file_size,
+            data_uri: v3.data_uri,
+            uri_locked: v3.uri_locked,
+            thumbnail_uri: v3.thumbnail_uri,
+            column_count: v3.column_count,
+            row_count: v3.row_count,
+            quality_score: v3.quality_score,
+            upload_timestamp: v3.upload_timestamp,
+            last_updated: v3.last_updated,
+            download_count: v3.download_count,
+            download_fee: v3.download_fee,
+            download_cap: v3.download_cap,
+            download_window: v3.download_window,
+            rating_sum: v3.rating_sum,
+            rating_count: v3.rating_count,
+            citation_count: v3.citation_count,
+            is_active: v3.is_active,
+            featured: v3.featured,
+            expires_at: v3.expires_at,
+            reputation_finalized: v3.reputation_finalized,
+            review_status: v3.review_status,
+            reviewer: v3.reviewer,
+            review_note_hash: v3.review_note_hash,
+            forked_from: v3.forked_from,
+            fork_count: v3.fork_count,
+            frozen: v3.frozen,
+            unavailable: v3.unavailable,
+            quality_hidden: v3.quality_hidden,
+            pinned_until: v3.pinned_until,
+            pin_seconds_funded: v3.pin_seconds_funded,
+            endorsement_count: v3.endorsement_count,
+            rating_weight: v3.rating_weight,
+            title: v3.title,
+            license_expires_at: v3.license_expires_at,
+            supersedes: v3.supersedes,
+            superseded_by: v3.superseded_by,
+            quality_disputed: v3.quality_disputed,
+            bond: v3.bond,
+            locked: v3.locked,
+            anonymous: v3.anonymous,
+            review_deadline: v3.review_deadline,
+            featured_until: v3.featured_until,
+            finalized: v3.finalized,
+            spam_deposit: v3.spam_deposit,
+            language: v3.language,
+            language_mismatch: v3.language_mismatch,
+            creator: v3.creator,
+            credited_quality: v3.credited_quality,
+            credited_field_points: v3.credited_field_points,
+            parent: v3.parent,
+            co_credits: Vec::new(),
+            bump: v3.bump,
+            schema_version: DATASET_SCHEMA_VERSION,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::Discriminator;
+
+    use crate::error::ErrorCode;
+
+    // Written by the program as it stood at each schema, at the full allocation
+    const SCHEMA_1_FIXTURE: &[u8] = include_bytes!("../../fixtures/dataset_schema_1.bin");
+    const SCHEMA_2_FIXTURE: &[u8] = include_bytes!("../../fixtures/dataset_schema_2.bin");
+    // The schema 3 fixtures::dataset_fixture(), padded to the allocation
+    const SCHEMA_3_FIXTURE: &[u8] = include_bytes!("../../fixtures/dataset_schema_3.bin");
+
+    fn key(byte: u8) -> Pubkey {
+        Pubkey::new_from_array([byte; 32])
+    }
+
+    fn full_v1() -> DatasetV1 {
+        DatasetV1 {
+            id: key(1),
+            registry: key(2),
+            contributor: key(3),
+            co_contributors: vec![key(4); 8],
+            editors: vec![key(5); 5],
+            content_hash: [6u8; 32],
+            preview_hash: [7u8; 32],
+            ai_metadata: vec![u8::MAX; 1_500],
+            file_name: vec![b'a'; 100],
+            research_fields: vec![u8::MAX; 4],
+            dataset_index: u32::MAX,
+            file_size: u64::MAX,
+            data_uri: [b'u'; 256],
+            uri_locked: true,
+            thumbnail_uri: Some([b't'; 128]),
+            column_count: u64::MAX,
+            row_count: u64::MAX,
+            quality_score: u8::MAX,
+            upload_timestamp: i64::MAX,
+            last_updated: Some(i64::MAX),
+            download_count: u32::MAX,
+            download_fee: u64::MAX,
+            download_cap: u32::MAX,
+            download_window: i64::MAX,
+            rating_sum: u64::MAX,
+            rating_count: u32::MAX,
+            citation_count: u32::MAX,
+            is_active: true,
+            featured: true,
+            expires_at: Some(i64::MAX),
+            reputation_finalized: true,
+            review_status: u8::MAX,
+            reviewer: key(8),
+            review_note_hash: [9u8; 32],
+            forked_from: key(10),
+            fork_count: u32::MAX,
+            frozen: true,
+            unavailable: true,
+            quality_hidden: true,
+            pinned_until: i64::MAX,
+            pin_seconds_funded: u64::MAX,
+            bump: u8::MAX,
+        }
+    }
+
+    // A schema 1 account as create_dataset allocated it
+    fn allocated(v1: &DatasetV1) -> Vec<u8> {
+        let mut data = Dataset::DISCRIMINATOR.to_vec();
+        v1.serialize(&mut data).unwrap();
+        data.resize(8 + DatasetV1::SPACE, 0);
+        data
+    }
+
+    #[test]
+    fn frozen_sizes_match_the_fixtures_and_the_largest_encodings() {
+        assert_eq!(SCHEMA_1_FIXTURE.len(), 8 + DatasetV1::SPACE);
+        assert_eq!(SCHEMA_2_FIXTURE.len(), 8 + DatasetV2::SPACE);
+        assert_eq!(SCHEMA_3_FIXTURE.len(), 8 + DatasetV3::SPACE);
+
+        let v1 = full_v1();
+        assert_eq!(v1.try_to_vec().unwrap().len(), DatasetV1::SPACE);
+        assert_eq!(DatasetV2::from(v1).try_to_vec().unwrap().len(), DatasetV2::SPACE);
+    }
+
+    #[test]
+    fn current_layout_is_pinned_to_its_schema_version() {
+        // Changing Dataset's fields or SPACE must freeze the current layout here as the next schema,
+        // bump DATASET_SCHEMA_VERSION and migrate the frozen schema forward; then update this pin
+        assert_eq!((DATASET_SCHEMA_VERSION, Dataset::SPACE), (4, 3_611));
+    }
+
+    #[test]
+    fn schema_1_fixture_migrates_to_the_current_layout() {
+        let migrated = Dataset::from_legacy(SCHEMA_1_FIXTURE).unwrap();
+
+        assert_eq!(migrated.data_uri, b"ipfs://QmFixture".to_vec());
+        assert_eq!(migrated.co_contributors, vec![key(4), key(5)]);
+        assert_eq!(migrated.file_name, b"rainfall_2020.csv".to_vec());
+        assert_eq!(migrated.title, migrated.file_name);
+        assert_eq!(migrated.thumbnail_uri, Some([b't'; 128]));
+        assert_eq!((migrated.quality_score, migrated.pin_seconds_funded, migrated.bump), (87, 2_592_000, 254));
+        assert_eq!((migrated.creator, migrated.contributor), (key(3), key(3)));
+        assert_eq!((migrated.rating_sum, migrated.rating_weight), (460, 6));
+        assert_eq!(migrated.credited_quality, 87);
+        assert_eq!(migrated.schema_version, DATASET_SCHEMA_VERSION);
+
+        let mut bytes = Vec::new();
+        migrated.try_serialize(&mut bytes).unwrap();
+        assert!(bytes.len() <= 8 + Dataset::SPACE);
+        assert_eq!(Dataset::try_deserialize(&mut bytes.as_slice()).unwrap().data_uri, migrated.data_uri);
+    }
+
+    #[test]
+    fn schema_2_fixture_migrates_to_the_same_dataset() {
+        let from_2 = Dataset::from_legacy(SCHEMA_2_FIXTURE).unwrap();
+        let from_1 = Dataset::from_legacy(SCHEMA_1_FIXTURE).unwrap();
+        assert_eq!(from_2.try_to_vec().unwrap(), from_1.try_to_vec().unwrap());
+    }
+
+    #[test]
+    fn schema_3_fixture_migrates_without_co_credits() {
+        let migrated = Dataset::from_legacy(SCHEMA_3_FIXTURE).unwrap();
+
+        assert_eq!(migrated.title, b"Rainfall Accra 2020".to_vec());
+        assert_eq!(migrated.co_contributors, vec![key(4), key(5)]);
+        assert_eq!((migrated.credited_quality, migrated.parent), (87, Some(key(15))));
+        assert_eq!(migrated.credited_field_points, vec![FieldScore { field: 1, score: 97 }]);
+        assert!(migrated.co_credits.is_empty());
+        assert_eq!((migrated.bump, migrated.schema_version), (254, DATASET_SCHEMA_VERSION));
+    }
+
+    #[test]
+    fn expanded_schema_3_account_keeps_its_ai_metadata() {
+        let mut v3 = DatasetV3::deserialize(&mut &SCHEMA_3_FIXTURE[8..]).unwrap();
+        v3.ai_metadata = vec![b'm'; 1_500 + 512];
+        let mut data = Dataset::DISCRIMINATOR.to_vec();
+        v3.serialize(&mut data).unwrap();
+        data.resize(8 + DatasetV3::SPACE + 512, 0);
+
+        let migrated = Dataset::from_legacy(&data).unwrap();
+        assert_eq!(migrated.ai_metadata, v3.ai_metadata);
+        assert!(migrated.try_to_vec().unwrap().len() <= Dataset::space_with_ai_metadata(migrated.ai_metadata.len()));
+    }
+
+    #[test]
+    fn full_schema_1_account_migrates_without_padding() {
+        let v1 = full_v1();
+        let migrated = Dataset::from_legacy(&allocated(&v1)).unwrap();
+
+        assert_eq!(migrated.data_uri, vec![b'u'; 256]);
+        assert_eq!(migrated.thumbnail_uri, v1.thumbnail_uri);
+        assert_eq!(migrated.pin_seconds_funded, u64::MAX);
+        assert!(migrated.try_to_vec().unwrap().len() <= Dataset::SPACE);
+    }
+
+    #[test]
+    fn current_or_malformed_accounts_are_not_legacy() {
+        let mut current = Vec::new();
+        Dataset::sample().try_serialize(&mut current).unwrap();
+        current.resize(8 + Dataset::SPACE, 0);
+
+        let mut foreign = SCHEMA_1_FIXTURE.to_vec();
+        foreign[0] ^= 1;
+        let mut mislabeled = SCHEMA_2_FIXTURE.to_vec();
+        // schema_version follows bump; 692 encoded bytes, discriminator included
+        assert_eq!(mislabeled[691], DatasetV2::SCHEMA_VERSION);
+        mislabeled[691] = 3;
+        let mut stale = SCHEMA_3_FIXTURE.to_vec();
+        // 885 encoded bytes
+        assert_eq!(stale[884], DatasetV3::SCHEMA_VERSION);
+        stale[884] = DatasetV2::SCHEMA_VERSION;
+
+        for data in [&current[..], &foreign[..], &mislabeled[..], &stale[..], &SCHEMA_1_FIXTURE[..100], &[0u8; 16][..]] {
+            assert_eq!(Dataset::from_legacy(data).err().unwrap(), ErrorCode::NotLegacyDataset.into());
+        }
+    }
+}
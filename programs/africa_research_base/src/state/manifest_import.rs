@@ -0,0 +1,152 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::constants::MAX_MANIFEST_DATASETS;
+use crate::error::ErrorCode;
+
+const MANIFEST_TAG: &[u8] = b"manifest";
+
+// An institution's oracle-attested catalog of off-chain datasets, claimable by content hash.
+// Its address is keyed by the manifest hash, so each manifest can be imported only once
+#[account]
+#[derive(InitSpace)]
+pub struct ManifestImport {
+    pub registry: Pubkey,
+    pub institution: Pubkey, // Claims every listed dataset through create_dataset and earns its upload credit
+    pub manifest_hash: [u8; 32],
+    #[max_len(MAX_MANIFEST_DATASETS)]
+    pub content_hashes: Vec<[u8; 32]>,
+    pub claimed: u16, // One bit per content_hashes entry, set once a dataset is created from it
+    pub imported_at: i64,
+    pub bump: u8
+}
+
+impl ManifestImport {
+    pub const SPACE: usize = Self::INIT_SPACE;
+
+    // What the oracle signs: binds the listed datasets to one registry and institution
+    pub fn manifest_hash(registry: &Pubkey, institution: &Pubkey, content_hashes: &[[u8; 32]]) -> [u8; 32] {
+        let mut parts: Vec<&[u8]> = vec![MANIFEST_TAG, registry.as_ref(), institution.as_ref()];
+        parts.extend(content_hashes.iter().map(|content_hash| content_hash.as_slice()));
+        hashv(&parts).to_bytes()
+    }
+
+    pub fn validate(
+        registry: &Pubkey,
+        institution: &Pubkey,
+        manifest_hash: &[u8; 32],
+        content_hashes: &[[u8; 32]]
+    ) -> Result<()> {
+        require!(
+            !content_hashes.is_empty() && content_hashes.len() <= MAX_MANIFEST_DATASETS,
+            ErrorCode::InvalidManifest
+        );
+        require!(
+            Self::manifest_hash(registry, institution, content_hashes) == *manifest_hash,
+            ErrorCode::ManifestHashMismatch
+        );
+        Ok(())
+    }
+
+    // Consumes entry `entry` for a dataset the institution is creating with `content_hash`
+    pub fn claim(&mut self, entry: u8, content_hash: &[u8; 32], contributor: &Pubkey) -> Result<()> {
+        require_keys_eq!(*contributor, self.institution, ErrorCode::UnauthorizedManifestClaim);
+        require!(
+            self.content_hashes.get(entry as usize) == Some(content_hash),
+            ErrorCode::InvalidManifestEntry
+        );
+        let bit = 1u16 << entry;
+        require!(self.claimed & bit == 0, ErrorCode::ManifestEntryClaimed);
+        self.claimed |= bit;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_hash_binds_registry_institution_and_entries() {
+        let (registry, institution) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let entries = [[1u8; 32], [2u8; 32]];
+        let manifest_hash = ManifestImport::manifest_hash(&registry, &institution, &entries);
+
+        ManifestImport::validate(&registry, &institution, &manifest_hash, &entries).unwrap();
+        for (registry, institution, entries) in [
+            (Pubkey::new_unique(), institution, &entries[..]),
+            (registry, Pubkey::new_unique(), &entries[..]),
+            (registry, institution, &entries[..1]),
+            (registry, institution, &[[2u8; 32], [1u8; 32]][..]),
+        ] {
+            assert_eq!(
+                ManifestImport::validate(&registry, &institution, &manifest_hash, entries).unwrap_err(),
+                ErrorCode::ManifestHashMismatch.into()
+            );
+        }
+    }
+
+    #[test]
+    fn manifest_size_is_bounded() {
+        let (registry, institution) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let too_many = vec![[0u8; 32]; MAX_MANIFEST_DATASETS + 1];
+        for entries in [&[][..], &too_many[..]] {
+            let manifest_hash = ManifestImport::manifest_hash(&registry, &institution, entries);
+            assert_eq!(
+                ManifestImport::validate(&registry, &institution, &manifest_hash, entries).unwrap_err(),
+                ErrorCode::InvalidManifest.into()
+            );
+        }
+
+        let full = ManifestImport {
+            registry,
+            institution,
+            manifest_hash: [0u8; 32],
+            content_hashes: vec![[0u8; 32]; MAX_MANIFEST_DATASETS],
+            claimed: u16::MAX,
+            imported_at: 0,
+            bump: 255,
+        };
+        let mut data = Vec::new();
+        full.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), 8 + ManifestImport::SPACE);
+        // claimed has a bit for every entry
+        assert!(MAX_MANIFEST_DATASETS <= u16::BITS as usize);
+    }
+
+    #[test]
+    fn each_entry_is_claimed_once_by_the_institution() {
+        let (registry, institution) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let content_hashes = vec![[1u8; 32], [2u8; 32]];
+        let mut manifest = ManifestImport {
+            registry,
+            institution,
+            manifest_hash: ManifestImport::manifest_hash(&registry, &institution, &content_hashes),
+            content_hashes,
+            claimed: 0,
+            imported_at: 0,
+            bump: 255,
+        };
+
+        assert_eq!(
+            manifest.claim(1, &[2u8; 32], &Pubkey::new_unique()).unwrap_err(),
+            ErrorCode::UnauthorizedManifestClaim.into()
+        );
+        for (entry, content_hash) in [(0, [2u8; 32]), (2, [2u8; 32])] {
+            assert_eq!(
+                manifest.claim(entry, &content_hash, &institution).unwrap_err(),
+                ErrorCode::InvalidManifestEntry.into()
+            );
+        }
+        assert_eq!(manifest.claimed, 0);
+
+        manifest.claim(1, &[2u8; 32], &institution).unwrap();
+        assert_eq!(manifest.claimed, 0b10);
+        assert_eq!(
+            manifest.claim(1, &[2u8; 32], &institution).unwrap_err(),
+            ErrorCode::ManifestEntryClaimed.into()
+        );
+        manifest.claim(0, &[1u8; 32], &institution).unwrap();
+        assert_eq!(manifest.claimed, 0b11);
+    }
+}
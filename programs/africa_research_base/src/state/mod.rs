@@ -1,9 +1,16 @@
+// Accounts are Borsh-encoded in field declaration order, so an upgrade may only append fields
+// (Dataset: before the trailing schema_version) and must never reorder, retype or remove one;
+// verify_layout checks a live account against the current layout after an upgrade. Any Dataset
+// layout change also bumps DATASET_SCHEMA_VERSION and freezes the old layout in legacy_dataset
 pub mod registry;
 pub use registry::*;
 
 pub mod dataset;
 pub use dataset::*;
 
+pub mod legacy_dataset;
+pub use legacy_dataset::*;
+
 pub mod attribution;
 pub use attribution::*;
 
@@ -12,3 +19,102 @@ pub use citation::*;
 
 pub mod reputation;
 pub use reputation::*;
+
+pub mod config_change;
+pub use config_change::*;
+
+pub mod receipt;
+pub use receipt::*;
+
+pub mod treasury;
+pub use treasury::*;
+
+pub mod fee_vault;
+pub use fee_vault::*;
+
+pub mod space;
+pub use space::*;
+
+pub mod citation_pair;
+pub use citation_pair::*;
+
+pub mod external_citation;
+pub use external_citation::*;
+
+pub mod registry_config;
+pub use registry_config::*;
+
+pub mod access_nonce;
+pub use access_nonce::*;
+
+pub mod leaderboard;
+pub use leaderboard::*;
+
+pub mod penalty_record;
+pub use penalty_record::*;
+
+pub mod availability_report;
+pub use availability_report::*;
+
+pub mod delegation;
+pub use delegation::*;
+
+pub mod rating;
+pub use rating::*;
+
+pub mod download_record;
+pub use download_record::*;
+
+pub mod token_index;
+pub use token_index::*;
+
+pub mod download_report;
+pub use download_report::*;
+
+pub mod download_claim;
+pub use download_claim::*;
+
+pub mod endorsement;
+pub use endorsement::*;
+
+pub mod download_reversal;
+pub use download_reversal::*;
+
+pub mod recent_datasets;
+pub use recent_datasets::*;
+
+pub mod data_request;
+pub use data_request::*;
+
+pub mod trending_datasets;
+pub use trending_datasets::*;
+
+pub mod hash_registry;
+pub use hash_registry::*;
+
+pub mod data_dictionary;
+pub use data_dictionary::*;
+
+pub mod taxonomy;
+pub use taxonomy::*;
+
+pub mod earnings;
+pub use earnings::*;
+
+pub mod access_request;
+pub use access_request::*;
+
+pub mod dataset_grant;
+pub use dataset_grant::*;
+
+pub mod manifest_import;
+pub use manifest_import::*;
+
+pub mod collection;
+pub use collection::*;
+
+pub mod dataset_replicas;
+pub use dataset_replicas::*;
+
+#[cfg(test)]
+mod fixtures;
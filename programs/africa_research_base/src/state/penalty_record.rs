@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+// Public record of a moderation penalty, one per frozen dataset
+#[account]
+#[derive(InitSpace)]
+pub struct PenaltyRecord {
+    pub dataset: Pubkey,
+    pub contributor: Pubkey,
+    pub admin: Pubkey,
+    pub amount: u32, // Points configured at the time; the score itself never drops below zero
+    pub reason_hash: [u8; 32], // Hash of the off-chain policy finding
+    pub applied_at: i64,
+    pub bump: u8
+}
+
+impl PenaltyRecord {
+    pub const SPACE: usize = Self::INIT_SPACE;
+}
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+// One rater's current rating of a dataset; rating again revises it rather than adding another
+#[account]
+#[derive(InitSpace)]
+pub struct Rating {
+    pub dataset: Pubkey,
+    pub rater: Pubkey,
+    pub rating: u8, // 1..=MAX_RATING
+    pub weight: u8, // RATING_TIER_WEIGHTS entry applied to `rating`, so a revision can take it back out
+    pub rated_at: i64,
+    pub bump: u8
+}
+
+impl Rating {
+    pub const SPACE: usize = Self::INIT_SPACE;
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+
+// Purchase proof for a paid download, keyed by the slot it was paid in
+#[account]
+#[derive(InitSpace)]
+pub struct Receipt {
+    pub dataset: Pubkey,
+    pub downloader: Pubkey,
+    pub mint: Pubkey,
+    pub amount_paid: u64,
+    pub slot: u64,
+    pub paid_at: i64,
+    pub bump: u8
+}
+
+impl Receipt {
+    // Fixed-size account, so the derived InitSpace is exact
+    pub const SPACE: usize = Self::INIT_SPACE;
+
+    // Several paid downloads landing in the same slot accumulate on one receipt
+    pub fn record_payment(&mut self, mint: Pubkey, amount: u64, paid_at: i64) -> Result<()> {
+        require_keys_eq!(self.mint, mint, ErrorCode::ReceiptMintMismatch);
+        self.amount_paid = self.amount_paid
+            .checked_add(amount)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        self.paid_at = paid_at;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::NATIVE_MINT;
+
+    fn receipt() -> Receipt {
+        Receipt {
+            dataset: Pubkey::new_unique(),
+            downloader: Pubkey::new_unique(),
+            mint: NATIVE_MINT,
+            amount_paid: 0,
+            slot: 42,
+            paid_at: 0,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn paid_download_records_amount_and_mint() {
+        let mut receipt = receipt();
+        receipt.record_payment(NATIVE_MINT, 5_000_000, 1_700_000_000).unwrap();
+
+        assert_eq!(receipt.amount_paid, 5_000_000);
+        assert_eq!(receipt.mint, NATIVE_MINT);
+        assert_eq!(receipt.paid_at, 1_700_000_000);
+    }
+
+    #[test]
+    fn same_slot_payments_accumulate() {
+        let mut receipt = receipt();
+        receipt.record_payment(NATIVE_MINT, 5_000_000, 1).unwrap();
+        receipt.record_payment(NATIVE_MINT, 5_000_000, 1).unwrap();
+
+        assert_eq!(receipt.amount_paid, 10_000_000);
+    }
+
+    #[test]
+    fn payment_in_other_mint_is_rejected() {
+        let mut receipt = receipt();
+
+        assert_eq!(
+            receipt.record_payment(Pubkey::new_unique(), 1, 1).unwrap_err(),
+            ErrorCode::ReceiptMintMismatch.into()
+        );
+        assert_eq!(receipt.amount_paid, 0);
+    }
+}
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::RECENT_DATASETS_LEN;
+
+// The registry's newest datasets as a fixed ring, so a "latest uploads" view is one account read.
+// Entries are never removed, so a listed dataset may since have been closed.
+#[account]
+#[derive(InitSpace)]
+pub struct RecentDatasets {
+    pub registry: Pubkey,
+    pub datasets: [Pubkey; RECENT_DATASETS_LEN],
+    pub head: u8, // Slot the next dataset is written to, which holds the oldest once the ring is full
+    pub len: u8,
+    pub bump: u8
+}
+
+impl RecentDatasets {
+    pub const SPACE: usize = Self::INIT_SPACE;
+
+    // Overwrites the oldest entry once all RECENT_DATASETS_LEN slots are used
+    pub fn push(&mut self, dataset: Pubkey) {
+        self.datasets[self.head as usize] = dataset;
+        self.head = ((self.head as usize + 1) % RECENT_DATASETS_LEN) as u8;
+        if (self.len as usize) < RECENT_DATASETS_LEN {
+            self.len += 1;
+        }
+    }
+
+    // Newest first
+    pub fn latest(&self) -> Vec<Pubkey> {
+        (1..=self.len as usize)
+            .map(|age| self.datasets[(self.head as usize + RECENT_DATASETS_LEN - age) % RECENT_DATASETS_LEN])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ring() -> RecentDatasets {
+        RecentDatasets {
+            registry: Pubkey::new_unique(),
+            datasets: [Pubkey::default(); RECENT_DATASETS_LEN],
+            head: 0,
+            len: 0,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn partial_ring_lists_newest_first() {
+        let mut recent = ring();
+        let keys: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        keys.iter().for_each(|key| recent.push(*key));
+
+        assert_eq!(recent.latest(), keys.into_iter().rev().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn full_ring_keeps_the_most_recent_in_order() {
+        let mut recent = ring();
+        let keys: Vec<Pubkey> = (0..=RECENT_DATASETS_LEN).map(|_| Pubkey::new_unique()).collect();
+        keys.iter().for_each(|key| recent.push(*key));
+
+        let expected: Vec<Pubkey> = keys[1..].iter().rev().copied().collect();
+        assert_eq!(recent.latest(), expected);
+        assert_eq!(recent.len as usize, RECENT_DATASETS_LEN);
+        assert!(!recent.datasets.contains(&keys[0]));
+    }
+}
@@ -1,10 +1,329 @@
 use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::state::ConfigChange;
 
 #[account]
 #[derive(InitSpace)]
 pub struct Registry {
     pub admin: Pubkey,
+    pub creator: Pubkey, // Key the registry PDA is derived from; unlike `admin` it never changes
+    pub name: [u8; 32], // Namespace, so one creator can run several independent registries
     pub total_datasets: u64,
     pub total_downloads: u64,
+    pub platform_fee_bps: u16,
+    pub min_quality_score: u8,
+    pub reputation_grace_period: i64, // Seconds a dataset must survive before its upload counts
+    pub min_file_size: u64,
+    pub archived_count: u64,
+    pub citation_pair_cap: u8, // Citations from one contributor to another that count toward reputation
+    pub featured_reputation_threshold: u32, // Reputation score a contributor must exceed to feature a dataset
+    pub featured_count: u64,
+    pub citation_oracle: Pubkey, // Attests off-chain citations; the default key disables reporting
+    pub curated: bool, // Only approved datasets may be downloaded
+    pub freeze_penalty: u32, // Reputation points deducted when one of a contributor's datasets is frozen
+    pub unavailable_report_threshold: u8, // Distinct reporters needed to take a dataset with a dead URI offline
+    pub download_oracle: Pubkey, // Signs Merkle roots of off-chain download deltas; the default key disables reports
+    pub quality_scale: u8, // Scale quality scores are submitted and shown in; stored scores are always 0..=100
+    pub min_rating_count: u32, // Ratings needed before a dataset's average is reported as meaningful
+    pub structured_ai_metadata: bool, // ai_metadata must be a Borsh-encoded AiMetadata rather than free-form bytes
+    pub quality_dispute_delta: u8, // Points a self-reported quality may exceed the community's before it is flagged
+    pub quality_bond: u64, // Lamports a contributor escrows in each new dataset, refunded on close unless it is frozen
+    pub taxonomy_enabled: bool, // Set by the first add_category; research_fields must then be active categories
+    pub review_sla: i64, // Seconds a submitted dataset waits for review before anyone may auto-approve it; 0 disables
+    pub institution_oracle: Pubkey, // Signs institution dataset manifests; the default key disables imports
+    pub spam_deposit: u64, // Lamports each new dataset holds until its first qualifying download (see Dataset::release_spam_deposit)
+    pub treasury_withdrawal_cap: u64, // Lamports the admin may withdraw from the treasury per TREASURY_WITHDRAWAL_PERIOD; changed only through the config timelock
     pub bump: u8
-}
\ No newline at end of file
+}
+
+impl Registry {
+    // Fixed-size account, so the derived InitSpace is exact
+    pub const SPACE: usize = Self::INIT_SPACE;
+
+    // Deserializes registry account data, reporting a PDA that was never initialized as such
+    pub fn try_from_initialized(owner: &Pubkey, data: &[u8]) -> Result<Registry> {
+        require!(
+            *owner == crate::ID && data.get(..8) == Some(Registry::DISCRIMINATOR),
+            ErrorCode::RegistryNotInitialized
+        );
+        let registry = Registry::try_deserialize(&mut &data[..])?;
+        require!(registry.admin != Pubkey::default(), ErrorCode::RegistryNotInitialized);
+        Ok(registry)
+    }
+
+    pub fn ensure_min_file_size(&self, file_size: u64) -> Result<()> {
+        require!(file_size >= self.min_file_size, ErrorCode::FileTooSmall);
+        Ok(())
+    }
+
+    // Returns the milestone in DATASET_MILESTONES this dataset reached, if any
+    pub fn record_dataset(&mut self, featured: bool) -> Result<Option<u64>> {
+        let before = self.total_datasets;
+        self.total_datasets = self.total_datasets
+            .checked_add(1)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        if featured {
+            self.featured_count = self.featured_count
+                .checked_add(1)
+                .ok_or(ErrorCode::NumericalOverflow)?;
+        }
+        Ok(Self::milestone_crossed(before, self.total_datasets))
+    }
+
+    pub fn release_featured(&mut self) {
+        self.featured_count = self.featured_count.saturating_sub(1);
+    }
+
+    // The milestone m with before < m <= after, so each one fires on exactly one increment
+    pub fn milestone_crossed(before: u64, after: u64) -> Option<u64> {
+        DATASET_MILESTONES
+            .iter()
+            .rev()
+            .find(|&&milestone| before < milestone && milestone <= after)
+            .copied()
+    }
+
+    pub fn set_quality_scale(&mut self, quality_scale: u8) -> Result<()> {
+        require!(
+            matches!(quality_scale, QUALITY_SCALE_PERCENT | QUALITY_SCALE_STARS),
+            ErrorCode::InvalidQualityScale
+        );
+        self.quality_scale = quality_scale;
+        Ok(())
+    }
+
+    // Converts a score submitted in the registry's scale to the 0..=100 used for reputation math
+    pub fn normalize_quality(&self, quality_score: u8) -> Result<u8> {
+        require!(quality_score <= self.quality_scale, ErrorCode::InvalidQualityScore);
+        Ok((quality_score as u16 * 100 / self.quality_scale as u16) as u8)
+    }
+
+    // A stored 0..=100 score in the registry's scale, rounded half up
+    pub fn display_quality(&self, quality_score: u8) -> u8 {
+        ((quality_score.min(100) as u16 * self.quality_scale as u16 + 50) / 100) as u8
+    }
+
+    pub fn ensure_can_feature(&self, reputation_score: u32) -> Result<()> {
+        require!(
+            reputation_score > self.featured_reputation_threshold,
+            ErrorCode::InsufficientReputation
+        );
+        Ok(())
+    }
+
+    // Splits a download fee into (platform share, contributor share)
+    pub fn split_fee(&self, amount: u64) -> Result<(u64, u64)> {
+        let platform_share = (amount as u128)
+            .checked_mul(self.platform_fee_bps as u128)
+            .ok_or(ErrorCode::NumericalOverflow)?
+            / BPS_DENOMINATOR as u128;
+        let platform_share = platform_share as u64;
+        Ok((platform_share, amount - platform_share))
+    }
+
+    // Applies a config change whose timelock has already elapsed
+    pub fn apply_config_change(&mut self, change: &ConfigChange) -> Result<()> {
+        change.validate()?;
+
+        match *change {
+            ConfigChange::SetPlatformFee { fee_bps } => self.platform_fee_bps = fee_bps,
+            ConfigChange::SetMinQuality { min_quality_score } => self.min_quality_score = min_quality_score,
+            ConfigChange::TransferAdmin { new_admin } => self.admin = new_admin,
+            ConfigChange::SetReputationGracePeriod { seconds } => self.reputation_grace_period = seconds,
+            ConfigChange::SetTreasuryWithdrawalCap { lamports } => self.treasury_withdrawal_cap = lamports,
+        }
+
+        Ok(())
+    }
+}
+
+impl ConfigChange {
+    pub fn validate(&self) -> Result<()> {
+        match *self {
+            ConfigChange::SetPlatformFee { fee_bps } => {
+                require!(fee_bps <= MAX_PLATFORM_FEE_BPS, ErrorCode::InvalidPlatformFee);
+            }
+            ConfigChange::SetMinQuality { min_quality_score } => {
+                require!(min_quality_score <= 100, ErrorCode::InvalidQualityScore);
+            }
+            ConfigChange::TransferAdmin { new_admin } => {
+                require!(new_admin != Pubkey::default(), ErrorCode::InvalidAdmin);
+            }
+            ConfigChange::SetReputationGracePeriod { seconds } => {
+                require!(
+                    (0..=MAX_REPUTATION_GRACE_PERIOD).contains(&seconds),
+                    ErrorCode::InvalidGracePeriod
+                );
+            }
+            // Any cap is valid; 0 stops withdrawals
+            ConfigChange::SetTreasuryWithdrawalCap { .. } => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+impl Registry {
+    pub(crate) fn sample() -> Self {
+        Registry {
+            admin: Pubkey::new_unique(),
+            creator: Pubkey::new_unique(),
+            name: [1u8; 32],
+            total_datasets: 0,
+            total_downloads: 0,
+            platform_fee_bps: 0,
+            min_quality_score: 0,
+            reputation_grace_period: DEFAULT_REPUTATION_GRACE_PERIOD,
+            min_file_size: 0,
+            archived_count: 0,
+            citation_pair_cap: DEFAULT_CITATION_PAIR_CAP,
+            featured_reputation_threshold: DEFAULT_FEATURED_REPUTATION_THRESHOLD,
+            featured_count: 0,
+            citation_oracle: Pubkey::default(),
+            curated: false,
+            freeze_penalty: DEFAULT_FREEZE_PENALTY,
+            unavailable_report_threshold: DEFAULT_UNAVAILABLE_REPORT_THRESHOLD,
+            download_oracle: Pubkey::default(),
+            quality_scale: QUALITY_SCALE_PERCENT,
+            min_rating_count: MIN_RATINGS_FOR_QUALITY,
+            structured_ai_metadata: false,
+            quality_dispute_delta: DEFAULT_QUALITY_DISPUTE_DELTA,
+            quality_bond: 0,
+            taxonomy_enabled: false,
+            review_sla: DEFAULT_REVIEW_SLA,
+            institution_oracle: Pubkey::default(),
+            spam_deposit: 0,
+            treasury_withdrawal_cap: 0,
+            bump: 255,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_fee_takes_platform_share_in_bps() {
+        let registry = Registry { platform_fee_bps: 250, ..Registry::sample() };
+
+        assert_eq!(registry.split_fee(1_000_000).unwrap(), (25_000, 975_000));
+        assert_eq!(registry.split_fee(39).unwrap(), (0, 39));
+
+        let (platform_share, contributor_share) = registry.split_fee(u64::MAX).unwrap();
+        assert_eq!(platform_share + contributor_share, u64::MAX);
+    }
+
+    #[test]
+    fn file_below_minimum_is_rejected() {
+        let registry = Registry { min_file_size: 1_024, ..Registry::sample() };
+
+        assert_eq!(
+            registry.ensure_min_file_size(1_023).unwrap_err(),
+            ErrorCode::FileTooSmall.into()
+        );
+    }
+
+    #[test]
+    fn file_at_minimum_is_accepted() {
+        let registry = Registry { min_file_size: 1_024, ..Registry::sample() };
+
+        registry.ensure_min_file_size(1_024).unwrap();
+        Registry::sample().ensure_min_file_size(1).unwrap();
+    }
+
+    #[test]
+    fn uninitialized_registry_is_reported_as_such() {
+        let mut data = Vec::new();
+        Registry::sample().try_serialize(&mut data).unwrap();
+        let not_initialized = ErrorCode::RegistryNotInitialized.into();
+
+        Registry::try_from_initialized(&crate::ID, &data).unwrap();
+        assert_eq!(Registry::try_from_initialized(&crate::ID, &[]).err().unwrap(), not_initialized);
+        assert_eq!(Registry::try_from_initialized(&Pubkey::default(), &data).err().unwrap(), not_initialized);
+        assert_eq!(Registry::try_from_initialized(&crate::ID, &vec![0u8; data.len()]).err().unwrap(), not_initialized);
+
+        let mut zeroed = Vec::new();
+        Registry { admin: Pubkey::default(), ..Registry::sample() }.try_serialize(&mut zeroed).unwrap();
+        assert_eq!(Registry::try_from_initialized(&crate::ID, &zeroed).err().unwrap(), not_initialized);
+    }
+
+    #[test]
+    fn featuring_requires_score_above_threshold() {
+        let registry = Registry { featured_reputation_threshold: 200, ..Registry::sample() };
+
+        assert_eq!(
+            registry.ensure_can_feature(200).unwrap_err(),
+            ErrorCode::InsufficientReputation.into()
+        );
+        registry.ensure_can_feature(201).unwrap();
+    }
+
+    #[test]
+    fn percent_scale_keeps_scores_as_submitted() {
+        let registry = Registry::sample();
+
+        assert_eq!(registry.normalize_quality(0).unwrap(), 0);
+        assert_eq!(registry.normalize_quality(100).unwrap(), 100);
+        assert_eq!(registry.normalize_quality(101).unwrap_err(), ErrorCode::InvalidQualityScore.into());
+        assert_eq!(registry.display_quality(0), 0);
+        assert_eq!(registry.display_quality(73), 73);
+        assert_eq!(registry.display_quality(100), 100);
+    }
+
+    #[test]
+    fn star_scale_normalizes_to_percent_and_displays_rounded() {
+        let mut registry = Registry::sample();
+        registry.set_quality_scale(QUALITY_SCALE_STARS).unwrap();
+
+        assert_eq!(registry.normalize_quality(0).unwrap(), 0);
+        assert_eq!(registry.normalize_quality(3).unwrap(), 60);
+        assert_eq!(registry.normalize_quality(5).unwrap(), 100);
+        assert_eq!(registry.normalize_quality(6).unwrap_err(), ErrorCode::InvalidQualityScore.into());
+        assert_eq!(registry.display_quality(0), 0);
+        assert_eq!(registry.display_quality(9), 0);
+        assert_eq!(registry.display_quality(10), 1);
+        assert_eq!(registry.display_quality(60), 3);
+        assert_eq!(registry.display_quality(100), 5);
+    }
+
+    #[test]
+    fn only_known_quality_scales_are_accepted() {
+        let mut registry = Registry::sample();
+
+        for scale in [0, 4, 10, 101] {
+            assert_eq!(registry.set_quality_scale(scale).unwrap_err(), ErrorCode::InvalidQualityScale.into());
+        }
+        assert_eq!(registry.quality_scale, QUALITY_SCALE_PERCENT);
+    }
+
+    #[test]
+    fn dataset_counter_overflow_is_reported() {
+        let mut registry = Registry { total_datasets: u64::MAX, ..Registry::sample() };
+
+        assert_eq!(registry.record_dataset(false).unwrap_err(), ErrorCode::NumericalOverflow.into());
+
+        let mut registry = Registry::sample();
+        registry.record_dataset(true).unwrap();
+        assert_eq!((registry.total_datasets, registry.featured_count), (1, 1));
+        registry.release_featured();
+        registry.release_featured();
+        assert_eq!((registry.total_datasets, registry.featured_count), (1, 0));
+    }
+
+    #[test]
+    fn milestone_fires_only_on_the_crossing_dataset() {
+        let milestone_for = |total_datasets| {
+            Registry { total_datasets, ..Registry::sample() }.record_dataset(false).unwrap()
+        };
+
+        assert_eq!(milestone_for(98), None); // 99th dataset
+        assert_eq!(milestone_for(99), Some(100)); // 100th
+        assert_eq!(milestone_for(100), None); // 101st
+        assert_eq!(milestone_for(999), Some(1_000));
+        assert_eq!(Registry::milestone_crossed(50, 20_000), Some(10_000));
+    }
+}
@@ -0,0 +1,196 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+use crate::constants::{MAX_ALLOWED_HOSTS, MAX_FIELD_WEIGHT, MAX_FIELD_WEIGHTS};
+use crate::error::ErrorCode;
+use crate::uri::{gateway_host, is_native_uri};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct FieldWeight {
+    pub field: u8,
+    pub weight: u8, // 2..=MAX_FIELD_WEIGHT; fields at the default weight of 1 have no entry
+}
+
+// Registry settings too large to keep on `Registry` itself
+#[account]
+#[derive(InitSpace)]
+pub struct RegistryConfig {
+    pub registry: Pubkey,
+    // SHA-256 of each permitted lowercase gateway host; empty leaves gateway hosts unrestricted
+    #[max_len(MAX_ALLOWED_HOSTS)]
+    pub allowed_hosts: Vec<[u8; 32]>,
+    // Multipliers on the per-field upload credit of datasets tagged with each field
+    #[max_len(MAX_FIELD_WEIGHTS)]
+    pub field_weights: Vec<FieldWeight>,
+    pub bump: u8
+}
+
+impl RegistryConfig {
+    pub const SPACE: usize = Self::INIT_SPACE;
+
+    pub fn host_hash(host: &[u8]) -> [u8; 32] {
+        hash(&host.to_ascii_lowercase()).to_bytes()
+    }
+
+    pub fn allow_host(&mut self, host_hash: [u8; 32]) -> Result<()> {
+        if self.allowed_hosts.contains(&host_hash) {
+            return Ok(());
+        }
+        require!(self.allowed_hosts.len() < MAX_ALLOWED_HOSTS, ErrorCode::AllowListFull);
+        self.allowed_hosts.push(host_hash);
+        Ok(())
+    }
+
+    pub fn disallow_host(&mut self, host_hash: [u8; 32]) {
+        self.allowed_hosts.retain(|allowed| *allowed != host_hash);
+    }
+
+    // A weight of 1 drops the field back to the default
+    pub fn set_field_weight(&mut self, field: u8, weight: u8) -> Result<()> {
+        require!((1..=MAX_FIELD_WEIGHT).contains(&weight), ErrorCode::InvalidFieldWeight);
+        let existing = self.field_weights.iter().position(|w| w.field == field);
+        match existing {
+            _ if weight == 1 => self.field_weights.retain(|w| w.field != field),
+            Some(index) => self.field_weights[index].weight = weight,
+            None => {
+                require!(self.field_weights.len() < MAX_FIELD_WEIGHTS, ErrorCode::FieldWeightsFull);
+                self.field_weights.push(FieldWeight { field, weight });
+            }
+        }
+        Ok(())
+    }
+
+    pub fn field_weight(&self, field: u8) -> u32 {
+        self.field_weights
+            .iter()
+            .find(|w| w.field == field)
+            .map_or(1, |w| w.weight as u32)
+    }
+
+    pub fn weighted_points(&self, field: u8, points: u32) -> u32 {
+        points.saturating_mul(self.field_weight(field))
+    }
+
+    // Native IPFS/Arweave URIs always pass; gateway URIs need an allow-listed host
+    pub fn ensure_host_allowed(&self, data_uri: &[u8]) -> Result<()> {
+        if self.allowed_hosts.is_empty() || is_native_uri(data_uri) {
+            return Ok(());
+        }
+        let host = gateway_host(data_uri).ok_or(ErrorCode::DisallowedHost)?;
+        require!(
+            self.allowed_hosts.contains(&Self::host_hash(host)),
+            ErrorCode::DisallowedHost
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::upload_points;
+    use crate::state::Reputation;
+
+    fn uri(value: &str) -> [u8; 256] {
+        let mut buffer = [0u8; 256];
+        buffer[..value.len()].copy_from_slice(value.as_bytes());
+        buffer
+    }
+
+    fn config_allowing(host: &str) -> RegistryConfig {
+        let mut config = RegistryConfig {
+            registry: Pubkey::new_unique(),
+            allowed_hosts: Vec::new(),
+            field_weights: Vec::new(),
+            bump: 255,
+        };
+        config.allow_host(RegistryConfig::host_hash(host.as_bytes())).unwrap();
+        config
+    }
+
+    #[test]
+    fn allowed_host_passes() {
+        let config = config_allowing("gateway.pinata.cloud");
+
+        config.ensure_host_allowed(&uri("https://gateway.pinata.cloud/ipfs/bafy")).unwrap();
+        config.ensure_host_allowed(&uri("https://Gateway.Pinata.Cloud:443/ipfs/bafy")).unwrap();
+    }
+
+    #[test]
+    fn disallowed_host_is_rejected() {
+        let config = config_allowing("gateway.pinata.cloud");
+
+        assert_eq!(
+            config.ensure_host_allowed(&uri("https://drive.google.com/file/d/1/view")).unwrap_err(),
+            ErrorCode::DisallowedHost.into()
+        );
+        assert_eq!(
+            config.ensure_host_allowed(&uri("ftp://gateway.pinata.cloud/ipfs/bafy")).unwrap_err(),
+            ErrorCode::DisallowedHost.into()
+        );
+    }
+
+    #[test]
+    fn native_uris_bypass_host_check() {
+        let config = config_allowing("gateway.pinata.cloud");
+
+        config.ensure_host_allowed(&uri("ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi")).unwrap();
+        config.ensure_host_allowed(&uri("ar://bNbA3TEQVL60xlgCcqdz4ZPHFZ711cZ3hmkpGttDt_U")).unwrap();
+    }
+
+    #[test]
+    fn allow_list_is_bounded_and_deduplicated() {
+        let mut config = config_allowing("a.example");
+        config.allow_host(RegistryConfig::host_hash(b"a.example")).unwrap();
+        assert_eq!(config.allowed_hosts.len(), 1);
+
+        for i in 1..MAX_ALLOWED_HOSTS {
+            config.allow_host([i as u8; 32]).unwrap();
+        }
+        assert_eq!(config.allow_host([0xff; 32]).unwrap_err(), ErrorCode::AllowListFull.into());
+
+        config.disallow_host(RegistryConfig::host_hash(b"a.example"));
+        assert_eq!(config.allowed_hosts.len(), MAX_ALLOWED_HOSTS - 1);
+    }
+
+    #[test]
+    fn upload_in_a_weighted_field_earns_proportionally_more() {
+        const HEALTH: u8 = 3;
+        const ECONOMICS: u8 = 7;
+        let mut config = config_allowing("a.example");
+        config.set_field_weight(HEALTH, 3).unwrap();
+        let mut reputation = Reputation::sample();
+
+        let credited = reputation.credit_fields_by(&[HEALTH, ECONOMICS], |field| config.weighted_points(field, upload_points(80)));
+
+        assert_eq!(reputation.score_for_field(ECONOMICS), upload_points(80));
+        assert_eq!(reputation.score_for_field(HEALTH), 3 * reputation.score_for_field(ECONOMICS));
+
+        // Re-weighting later doesn't change what the recorded credit takes back
+        config.set_field_weight(HEALTH, 1).unwrap();
+        reputation.debit_upload_credit(80, &credited);
+        assert_eq!((reputation.score_for_field(HEALTH), reputation.score_for_field(ECONOMICS)), (0, 0));
+    }
+
+    #[test]
+    fn field_weights_default_to_one_and_are_bounded() {
+        let mut config = config_allowing("a.example");
+        assert_eq!(config.field_weight(1), 1);
+
+        assert_eq!(config.set_field_weight(1, 0).unwrap_err(), ErrorCode::InvalidFieldWeight.into());
+        assert_eq!(config.set_field_weight(1, MAX_FIELD_WEIGHT + 1).unwrap_err(), ErrorCode::InvalidFieldWeight.into());
+        config.set_field_weight(1, 4).unwrap();
+        config.set_field_weight(1, 2).unwrap();
+        assert_eq!((config.field_weight(1), config.field_weights.len()), (2, 1));
+        config.set_field_weight(1, 1).unwrap();
+        assert!(config.field_weights.is_empty());
+
+        for field in 0..MAX_FIELD_WEIGHTS as u8 {
+            config.set_field_weight(field, MAX_FIELD_WEIGHT).unwrap();
+        }
+        assert_eq!(config.set_field_weight(200, 2).unwrap_err(), ErrorCode::FieldWeightsFull.into());
+        let mut data = Vec::new();
+        config.try_serialize(&mut data).unwrap();
+        assert!(data.len() <= 8 + RegistryConfig::SPACE);
+    }
+}
@@ -1,15 +1,694 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::{
+    DECAY_BPS, DECAY_PERIOD, MAX_DECAY_PERIODS, MAX_FIELD_SCORES, MAX_SEEDED_CITATIONS, MAX_SEEDED_DOWNLOADS,
+    MAX_SEEDED_UPLOADS, MAX_VACATION_DURATION, RATING_TIER_WEIGHTS, REPUTATION_TIER_THRESHOLDS,
+};
+use crate::anonymity::published_key;
+use crate::error::ErrorCode;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct FieldScore {
+    pub field: u8,
+    pub score: u32,
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct Reputation {
     pub contributor: Pubkey,
     pub total_uploads: u32,
-    pub dataset_count: u32, // Track number of datasets created by this contributor
+    pub dataset_count: u32, // Index of the contributor's next dataset PDA; counts closed datasets too and never feeds the score
     pub download_time: i64,
     pub total_quality_score: u64,
     pub total_downloads: u64,
     pub total_citations: u32,
+    pub total_forks: u32,
+    pub total_penalties: u32, // Moderation deductions, subtracted after the other components are summed
+    pub total_decay: u32, // Inactivity decay, subtracted alongside total_penalties
+    pub last_decay_at: i64, // Start of the next decay period; 0 until decay_reputation first runs
+    pub decay_paused_until: i64, // Vacation: decay_reputation skips this contributor until then
+    pub delegated_to: Pubkey, // Institution this contributor's score counts toward, default for none
+    pub delegate_count: u32,
+    pub delegated_score: u32, // Sum of delegates' scores, snapshotted when each delegated
     pub reputation_score: u32,
+    // Points earned on datasets tagged with each research field; fields beyond the bound go untracked
+    #[max_len(MAX_FIELD_SCORES)]
+    pub field_scores: Vec<FieldScore>,
+    pub anonymous: bool, // Opted in via set_anonymous: events and return data carry pseudonym(contributor)
+    // Key this reputation moved to in rotate_contributor_key, default while current. The rotated-away
+    // account stays behind as an empty tombstone so the PDA can't be re-initialized, and so datasets
+    // still naming this key can be re-pointed to its successor
+    pub rotated_to: Pubkey,
     pub bump: u8
-}
\ No newline at end of file
+}
+
+impl Reputation {
+    // InitSpace reserves room for MAX_FIELD_SCORES entries
+    pub const SPACE: usize = Self::INIT_SPACE;
+
+    pub fn new(contributor: Pubkey, bump: u8) -> Self {
+        Reputation {
+            contributor,
+            total_uploads: 0,
+            dataset_count: 0,
+            download_time: 0,
+            total_quality_score: 0,
+            total_downloads: 0,
+            total_citations: 0,
+            total_forks: 0,
+            total_penalties: 0,
+            total_decay: 0,
+            last_decay_at: 0,
+            decay_paused_until: 0,
+            delegated_to: Pubkey::default(),
+            delegate_count: 0,
+            delegated_score: 0,
+            reputation_score: 0,
+            field_scores: Vec::new(),
+            anonymous: false,
+            rotated_to: Pubkey::default(),
+            bump
+        }
+    }
+
+    // False for the tombstone of a rotated-away key, which no instruction may credit or use
+    pub fn is_current(&self) -> bool {
+        self.rotated_to == Pubkey::default()
+    }
+
+    pub fn published_key(&self) -> Pubkey {
+        published_key(&self.contributor, self.anonymous)
+    }
+
+    // Nothing has been recorded since `new`: no datasets, credits, deductions or delegations
+    pub fn is_fresh(&self) -> bool {
+        self.total_uploads == 0
+            && self.dataset_count == 0
+            && self.total_quality_score == 0
+            && self.total_downloads == 0
+            && self.total_citations == 0
+            && self.total_forks == 0
+            && self.total_penalties == 0
+            && self.total_decay == 0
+            && self.last_decay_at == 0
+            && self.delegated_to == Pubkey::default()
+            && self.delegate_count == 0
+            && self.reputation_score == 0
+            && self.field_scores.is_empty()
+    }
+
+    // Imports history from an off-chain system; the caller recomputes the score from it. dataset_count
+    // stays 0, since no on-chain dataset PDAs exist yet.
+    pub fn seed(&mut self, total_uploads: u32, total_quality_score: u64, total_downloads: u64, total_citations: u32) -> Result<()> {
+        require!(self.is_fresh(), ErrorCode::ReputationAlreadyActive);
+        require!(
+            total_uploads <= MAX_SEEDED_UPLOADS
+                && total_quality_score <= total_uploads as u64 * 100
+                && total_downloads <= MAX_SEEDED_DOWNLOADS
+                && total_citations <= MAX_SEEDED_CITATIONS,
+            ErrorCode::InvalidSeedValues
+        );
+
+        self.total_uploads = total_uploads;
+        self.total_quality_score = total_quality_score;
+        self.total_downloads = total_downloads;
+        self.total_citations = total_citations;
+        Ok(())
+    }
+
+    // Advances the index the contributor's next dataset PDA is derived from
+    pub fn record_dataset(&mut self) -> Result<()> {
+        self.dataset_count = self.dataset_count
+            .checked_add(1)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        Ok(())
+    }
+
+    pub fn credit_upload(&mut self, quality_score: u8) -> Result<()> {
+        self.total_uploads = self.total_uploads
+            .checked_add(1)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        self.total_quality_score = self.total_quality_score
+            .checked_add(quality_score as u64)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        Ok(())
+    }
+
+    // Reverses `credit_upload` given the quality it credited, floored at zero
+    pub fn debit_upload(&mut self, quality_score: u8) {
+        self.total_uploads = self.total_uploads.saturating_sub(1);
+        self.total_quality_score = self.total_quality_score.saturating_sub(quality_score as u64);
+    }
+
+    // Takes back disputed downloads, floored at zero
+    pub fn revert_downloads(&mut self, count: u32) {
+        self.total_downloads = self.total_downloads.saturating_sub(count as u64);
+    }
+
+    pub fn apply_penalty(&mut self, amount: u32) -> Result<()> {
+        self.total_penalties = self.total_penalties
+            .checked_add(amount)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        Ok(())
+    }
+
+    // Adds decay for each full period since the last one and returns the points removed; the caller
+    // recomputes the score. The first call only starts the clock, and time on vacation never counts.
+    pub fn decay(&mut self, now: i64) -> Result<u32> {
+        if self.last_decay_at == 0 {
+            self.last_decay_at = now;
+            return Ok(0);
+        }
+        if now < self.decay_paused_until {
+            return Ok(0);
+        }
+
+        let start = self.last_decay_at.max(self.decay_paused_until);
+        let periods = (now - start) / DECAY_PERIOD;
+        if periods <= 0 {
+            return Ok(0);
+        }
+
+        let mut score = self.reputation_score as u64;
+        for _ in 0..periods.min(MAX_DECAY_PERIODS) {
+            score -= score * DECAY_BPS / 10_000;
+        }
+        let removed = self.reputation_score - score as u32;
+        self.total_decay = self.total_decay
+            .checked_add(removed)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        self.last_decay_at = start + periods * DECAY_PERIOD;
+        Ok(removed)
+    }
+
+    // Pauses decay until `until`, at most MAX_VACATION_DURATION ahead; 0 ends a vacation early.
+    // An active vacation cannot be extended, only ended.
+    pub fn set_vacation(&mut self, until: i64, now: i64) -> Result<()> {
+        let on_vacation = now < self.decay_paused_until;
+        if until == 0 {
+            if on_vacation {
+                // The part of the vacation already taken stays exempt
+                self.last_decay_at = self.last_decay_at.max(now);
+            }
+        } else {
+            require!(
+                !on_vacation && until > now && until - now <= MAX_VACATION_DURATION,
+                ErrorCode::InvalidVacation
+            );
+        }
+        self.decay_paused_until = until;
+        Ok(())
+    }
+
+    // Own score plus the delegated scores of contributors affiliated with this institution
+    pub fn aggregate_score(&self) -> u32 {
+        self.reputation_score.saturating_add(self.delegated_score)
+    }
+
+    // Adds this contributor's current score to `institution` and returns the amount added. Chains are
+    // limited to one level, which rules out cycles: institutions cannot delegate, delegates cannot
+    // accept delegations.
+    pub fn delegate(&mut self, institution: &mut Reputation) -> Result<u32> {
+        require!(self.contributor != institution.contributor, ErrorCode::SelfDelegation);
+        require!(self.delegated_to == Pubkey::default(), ErrorCode::AlreadyDelegated);
+        require!(
+            self.delegate_count == 0 && institution.delegated_to == Pubkey::default(),
+            ErrorCode::DelegationCycle
+        );
+
+        institution.delegated_score = institution.delegated_score
+            .checked_add(self.reputation_score)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        institution.delegate_count = institution.delegate_count
+            .checked_add(1)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        self.delegated_to = institution.contributor;
+        Ok(self.reputation_score)
+    }
+
+    // Removes exactly the `score` recorded when the delegation was made
+    pub fn revoke_delegation(&mut self, institution: &mut Reputation, score: u32) {
+        institution.delegated_score = institution.delegated_score.saturating_sub(score);
+        institution.delegate_count = institution.delegate_count.saturating_sub(1);
+        self.delegated_to = Pubkey::default();
+    }
+
+    // Moves this reputation to a new key and returns the copy to store there, leaving this account an
+    // empty tombstone pointing at it. Delegations are keyed by contributor, so they must be revoked, in
+    // both directions, before the key can move.
+    pub fn rotate(&mut self, new_contributor: Pubkey, bump: u8) -> Result<Reputation> {
+        require!(self.is_current(), ErrorCode::ContributorKeyRotated);
+        require!(new_contributor != self.contributor, ErrorCode::SameContributorKey);
+        require!(
+            self.delegated_to == Pubkey::default() && self.delegate_count == 0,
+            ErrorCode::RotationWhileDelegated
+        );
+        let rotated = Reputation { contributor: new_contributor, bump, ..self.clone() };
+        *self = Reputation {
+            anonymous: self.anonymous,
+            rotated_to: new_contributor,
+            ..Reputation::new(self.contributor, self.bump)
+        };
+        Ok(rotated)
+    }
+
+    // 0 for newcomers, rising by one for each threshold in REPUTATION_TIER_THRESHOLDS the score reaches
+    pub fn tier(&self) -> u32 {
+        REPUTATION_TIER_THRESHOLDS
+            .iter()
+            .filter(|&&threshold| self.reputation_score >= threshold)
+            .count() as u32
+    }
+
+    // How much this contributor's ratings count toward a dataset's average
+    pub fn rating_weight(&self) -> u8 {
+        RATING_TIER_WEIGHTS[self.tier() as usize]
+    }
+
+    pub fn score_for_field(&self, field: u8) -> u32 {
+        self.field_scores
+            .iter()
+            .find(|entry| entry.field == field)
+            .map_or(0, |entry| entry.score)
+    }
+
+    // Each distinct field is credited once, even if a pre-validation dataset lists it repeatedly
+    pub fn credit_fields(&mut self, fields: &[u8], points: u32) {
+        self.credit_fields_by(fields, |_| points);
+    }
+
+    // `credit_fields` with the points chosen per field, e.g. by a registry's field weights. Returns the
+    // points actually added to each field, so the credit can later be reversed exactly
+    pub fn credit_fields_by(&mut self, fields: &[u8], points: impl Fn(u8) -> u32) -> Vec<FieldScore> {
+        let mut credited = Vec::new();
+        for (i, &field) in fields.iter().enumerate() {
+            if fields[..i].contains(&field) {
+                continue;
+            }
+            let points = points(field);
+            let added = match self.field_scores.iter().position(|entry| entry.field == field) {
+                Some(index) => {
+                    let entry = &mut self.field_scores[index];
+                    let before = entry.score;
+                    entry.score = entry.score.saturating_add(points);
+                    entry.score - before
+                }
+                None if self.field_scores.len() < MAX_FIELD_SCORES => {
+                    self.field_scores.push(FieldScore { field, score: points });
+                    points
+                }
+                None => 0,
+            };
+            if added > 0 {
+                credited.push(FieldScore { field, score: added });
+            }
+        }
+        credited
+    }
+
+    // Membership test, so repeated fields are debited once, matching `credit_fields`
+    pub fn debit_fields(&mut self, fields: &[u8], points: u32) {
+        for entry in self.field_scores.iter_mut().filter(|entry| fields.contains(&entry.field)) {
+            entry.score = entry.score.saturating_sub(points);
+        }
+    }
+
+    // Reverses an upload credit from what was recorded when it was applied, so re-rating the dataset
+    // or re-weighting its fields afterwards can't make the debit differ from the credit
+    pub fn debit_upload_credit(&mut self, quality_score: u8, field_points: &[FieldScore]) {
+        self.debit_upload(quality_score);
+        for credited in field_points {
+            if let Some(entry) = self.field_scores.iter_mut().find(|entry| entry.field == credited.field) {
+                entry.score = entry.score.saturating_sub(credited.score);
+            }
+        }
+    }
+
+    // Mean quality per upload, clamped to 0-100; `None` until the first upload
+    pub fn average_quality(&self) -> Option<u8> {
+        if self.total_uploads == 0 {
+            return None;
+        }
+        let average = self.total_quality_score / self.total_uploads as u64;
+        Some(average.min(100) as u8)
+    }
+}
+
+#[cfg(test)]
+impl Reputation {
+    pub(crate) fn sample() -> Self {
+        Reputation::new(Pubkey::new_unique(), 255)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::DEFAULT_FREEZE_PENALTY;
+    use crate::instructions::calculate_reputation_score;
+
+    fn reputation(total_uploads: u32, total_quality_score: u64) -> Reputation {
+        Reputation { total_uploads, total_quality_score, ..Reputation::sample() }
+    }
+
+    #[test]
+    fn average_quality_is_none_without_uploads() {
+        assert_eq!(reputation(0, 0).average_quality(), None);
+        assert_eq!(reputation(0, 500).average_quality(), None);
+    }
+
+    #[test]
+    fn average_quality_of_single_upload() {
+        assert_eq!(reputation(1, 85).average_quality(), Some(85));
+    }
+
+    #[test]
+    fn average_quality_of_mixed_scores() {
+        // 90 + 60 + 75 = 225, floored to 75
+        assert_eq!(reputation(3, 225).average_quality(), Some(75));
+        // 100 + 51 = 151, floored to 75
+        assert_eq!(reputation(2, 151).average_quality(), Some(75));
+    }
+
+    #[test]
+    fn average_quality_is_clamped() {
+        assert_eq!(reputation(1, u64::MAX).average_quality(), Some(100));
+    }
+
+    #[test]
+    fn uploads_in_different_fields_score_separately() {
+        const HEALTH: u8 = 1;
+        const ECONOMICS: u8 = 2;
+        let mut reputation = Reputation::sample();
+
+        reputation.credit_fields(&[HEALTH], 90);
+        reputation.credit_fields(&[HEALTH], 80);
+        reputation.credit_fields(&[ECONOMICS], 20);
+
+        assert_eq!(reputation.score_for_field(HEALTH), 170);
+        assert_eq!(reputation.score_for_field(ECONOMICS), 20);
+        assert_eq!(reputation.score_for_field(3), 0);
+
+        reputation.debit_fields(&[ECONOMICS], 50);
+        assert_eq!(reputation.score_for_field(ECONOMICS), 0);
+    }
+
+    #[test]
+    fn repeated_fields_are_credited_once() {
+        const HEALTH: u8 = 1;
+        let mut reputation = Reputation::sample();
+
+        reputation.credit_fields(&[HEALTH, HEALTH, HEALTH], 90);
+
+        assert_eq!(reputation.field_scores, vec![FieldScore { field: HEALTH, score: 90 }]);
+        reputation.debit_fields(&[HEALTH, HEALTH], 40);
+        assert_eq!(reputation.score_for_field(HEALTH), 50);
+    }
+
+    #[test]
+    fn field_scores_stay_bounded() {
+        let mut reputation = Reputation::sample();
+        let fields: Vec<u8> = (0..=MAX_FIELD_SCORES as u8).collect();
+
+        reputation.credit_fields(&fields, 1);
+
+        assert_eq!(reputation.field_scores.len(), MAX_FIELD_SCORES);
+        assert_eq!(reputation.score_for_field(MAX_FIELD_SCORES as u8), 0);
+    }
+
+    #[test]
+    fn tier_rises_at_each_threshold() {
+        let tier_at = |reputation_score| Reputation { reputation_score, ..Reputation::sample() }.tier();
+
+        assert_eq!(tier_at(0), 0);
+        assert_eq!(tier_at(99), 0);
+        assert_eq!(tier_at(100), 1);
+        assert_eq!(tier_at(500), 2);
+        assert_eq!(tier_at(u32::MAX), 3);
+    }
+
+    #[test]
+    fn rating_weight_follows_tier() {
+        let weight_at = |reputation_score| Reputation { reputation_score, ..Reputation::sample() }.rating_weight();
+
+        assert_eq!(weight_at(0), 1);
+        assert_eq!(weight_at(100), 2);
+        assert_eq!(weight_at(500), 3);
+        assert_eq!(weight_at(2_000), 4);
+    }
+
+    #[test]
+    fn dataset_index_overflow_is_reported() {
+        let mut reputation = Reputation { dataset_count: u32::MAX, ..Reputation::sample() };
+
+        assert_eq!(reputation.record_dataset().unwrap_err(), ErrorCode::NumericalOverflow.into());
+        assert_eq!(reputation.dataset_count, u32::MAX);
+    }
+
+    #[test]
+    fn penalty_reduces_score_and_floors_at_zero() {
+        let mut reputation = Reputation { total_uploads: 3, total_quality_score: 240, ..Reputation::sample() };
+        calculate_reputation_score(&mut reputation).unwrap();
+        let unpenalized = reputation.reputation_score;
+
+        reputation.apply_penalty(DEFAULT_FREEZE_PENALTY).unwrap();
+        calculate_reputation_score(&mut reputation).unwrap();
+        assert_eq!(reputation.reputation_score, unpenalized - DEFAULT_FREEZE_PENALTY);
+
+        reputation.apply_penalty(u32::MAX - DEFAULT_FREEZE_PENALTY).unwrap();
+        calculate_reputation_score(&mut reputation).unwrap();
+        assert_eq!(reputation.reputation_score, 0);
+    }
+
+    #[test]
+    fn delegation_adds_and_revoking_removes_the_snapshot() {
+        let mut researcher = Reputation { reputation_score: 120, ..Reputation::sample() };
+        let mut institution = Reputation { reputation_score: 30, ..Reputation::sample() };
+
+        let score = researcher.delegate(&mut institution).unwrap();
+        assert_eq!(score, 120);
+        assert_eq!(institution.aggregate_score(), 150);
+        assert_eq!(researcher.delegated_to, institution.contributor);
+
+        // Later changes to the researcher's score leave the snapshot untouched
+        researcher.reputation_score = 500;
+        researcher.revoke_delegation(&mut institution, score);
+        assert_eq!(institution.aggregate_score(), 30);
+        assert_eq!(institution.delegate_count, 0);
+        assert_eq!(researcher.delegated_to, Pubkey::default());
+    }
+
+    #[test]
+    fn delegation_cannot_form_chains_or_cycles() {
+        let mut a = Reputation::sample();
+        let mut b = Reputation::sample();
+        let mut c = Reputation::sample();
+        a.delegate(&mut b).unwrap();
+
+        // b already has a delegate, so b cannot delegate onward (or back to a)
+        assert_eq!(b.delegate(&mut a).unwrap_err(), ErrorCode::DelegationCycle.into());
+        // a is a delegate, so c cannot delegate to it
+        assert_eq!(c.delegate(&mut a).unwrap_err(), ErrorCode::DelegationCycle.into());
+        assert_eq!(a.delegate(&mut c).unwrap_err(), ErrorCode::AlreadyDelegated.into());
+
+        let mut self_view = a.clone();
+        assert_eq!(a.delegate(&mut self_view).unwrap_err(), ErrorCode::SelfDelegation.into());
+    }
+
+    #[test]
+    fn reverted_downloads_lower_the_score_and_floor_at_zero() {
+        let mut reputation = Reputation { total_downloads: 30, ..Reputation::sample() };
+        calculate_reputation_score(&mut reputation).unwrap();
+        let before = reputation.reputation_score;
+
+        reputation.revert_downloads(20);
+        calculate_reputation_score(&mut reputation).unwrap();
+        assert_eq!(reputation.total_downloads, 10);
+        assert!(reputation.reputation_score < before);
+
+        reputation.revert_downloads(u32::MAX);
+        assert_eq!(reputation.total_downloads, 0);
+    }
+
+    #[test]
+    fn debit_upload_reverses_credit_and_floors_at_zero() {
+        let mut reputation = Reputation::sample();
+        reputation.credit_upload(80).unwrap();
+        calculate_reputation_score(&mut reputation).unwrap();
+        assert!(reputation.reputation_score > 0);
+
+        reputation.debit_upload(80);
+        calculate_reputation_score(&mut reputation).unwrap();
+        assert_eq!(reputation.total_uploads, 0);
+        assert_eq!(reputation.total_quality_score, 0);
+        assert_eq!(reputation.reputation_score, 0);
+
+        // Debiting more than was credited floors at zero rather than underflowing
+        reputation.credit_upload(40).unwrap();
+        reputation.debit_upload(90);
+        assert_eq!(reputation.total_uploads, 0);
+        assert_eq!(reputation.total_quality_score, 0);
+        reputation.debit_upload(0);
+        assert_eq!(reputation.total_uploads, 0);
+    }
+
+    fn decaying(reputation_score: u32, since: i64) -> Reputation {
+        Reputation { reputation_score, last_decay_at: since, ..Reputation::sample() }
+    }
+
+    #[test]
+    fn first_decay_only_starts_the_clock() {
+        let mut reputation = Reputation { reputation_score: 1_000, ..Reputation::sample() };
+        assert_eq!(reputation.decay(1_700_000_000).unwrap(), 0);
+        assert_eq!(reputation.last_decay_at, 1_700_000_000);
+        assert_eq!(reputation.decay(1_700_000_000 + DECAY_PERIOD - 1).unwrap(), 0);
+    }
+
+    #[test]
+    fn decay_compounds_per_full_period() {
+        let start = 1_700_000_000;
+        let mut reputation = decaying(1_000, start);
+        // 1000 - 50 = 950, then 950 - 47 = 903
+        assert_eq!(reputation.decay(start + 2 * DECAY_PERIOD + 5).unwrap(), 97);
+        assert_eq!(reputation.total_decay, 97);
+        assert_eq!(reputation.last_decay_at, start + 2 * DECAY_PERIOD);
+    }
+
+    #[test]
+    fn decay_is_skipped_during_vacation_and_resumes_after() {
+        let start = 1_700_000_000;
+        let mut reputation = decaying(1_000, start);
+        reputation.set_vacation(start + MAX_VACATION_DURATION, start).unwrap();
+
+        assert_eq!(reputation.decay(start + 3 * DECAY_PERIOD).unwrap(), 0);
+        assert_eq!(reputation.total_decay, 0);
+
+        // Only full periods after the vacation ends count
+        let back = start + MAX_VACATION_DURATION;
+        assert_eq!(reputation.decay(back + DECAY_PERIOD - 1).unwrap(), 0);
+        assert_eq!(reputation.decay(back + DECAY_PERIOD).unwrap(), 50);
+    }
+
+    #[test]
+    fn vacation_is_capped_and_cannot_be_extended() {
+        let now = 1_700_000_000;
+        let mut reputation = Reputation::sample();
+        assert_eq!(
+            reputation.set_vacation(now + MAX_VACATION_DURATION + 1, now).unwrap_err(),
+            ErrorCode::InvalidVacation.into()
+        );
+        assert_eq!(reputation.set_vacation(now, now).unwrap_err(), ErrorCode::InvalidVacation.into());
+
+        reputation.set_vacation(now + DECAY_PERIOD, now).unwrap();
+        assert_eq!(
+            reputation.set_vacation(now + 2 * DECAY_PERIOD, now + 1).unwrap_err(),
+            ErrorCode::InvalidVacation.into()
+        );
+
+        // Ending early keeps the time already taken exempt
+        reputation.last_decay_at = now;
+        reputation.set_vacation(0, now + 10).unwrap();
+        assert_eq!(reputation.decay_paused_until, 0);
+        assert_eq!(reputation.last_decay_at, now + 10);
+    }
+
+    #[test]
+    fn decay_lowers_the_computed_score() {
+        let mut reputation = Reputation::sample();
+        reputation.credit_upload(80).unwrap();
+        calculate_reputation_score(&mut reputation).unwrap();
+        let before = reputation.reputation_score;
+
+        reputation.last_decay_at = 1_700_000_000;
+        let removed = reputation.decay(1_700_000_000 + DECAY_PERIOD).unwrap();
+        calculate_reputation_score(&mut reputation).unwrap();
+        assert_eq!(reputation.reputation_score, before - removed);
+    }
+
+    #[test]
+    fn rotation_moves_the_whole_reputation_to_the_new_key() {
+        let mut reputation = Reputation::sample();
+        reputation.record_dataset().unwrap();
+        reputation.credit_upload(80).unwrap();
+        reputation.credit_fields(&[1], 80);
+        reputation.reputation_score = 42;
+        let new_contributor = Pubkey::new_unique();
+
+        let old_contributor = reputation.contributor;
+
+        let rotated = reputation.rotate(new_contributor, 7).unwrap();
+
+        assert_eq!(rotated.contributor, new_contributor);
+        assert_eq!(rotated.bump, 7);
+        assert_eq!(rotated.dataset_count, 1);
+        assert_eq!(rotated.total_quality_score, 80);
+        assert_eq!(rotated.score_for_field(1), 80);
+        assert_eq!(rotated.reputation_score, 42);
+        assert!(rotated.is_current());
+        // Nothing stays behind to be double counted, and the tombstone can't rotate again
+        assert_eq!(reputation.contributor, old_contributor);
+        assert_eq!(reputation.rotated_to, new_contributor);
+        assert_eq!((reputation.dataset_count, reputation.total_uploads, reputation.reputation_score), (0, 0, 0));
+        assert_eq!(
+            reputation.rotate(Pubkey::new_unique(), 1).err().unwrap(),
+            ErrorCode::ContributorKeyRotated.into()
+        );
+    }
+
+    #[test]
+    fn rotation_is_rejected_while_delegated_or_to_the_same_key() {
+        let mut delegator = Reputation::sample();
+        let mut institution = Reputation::sample();
+        delegator.delegate(&mut institution).unwrap();
+
+        assert_eq!(
+            delegator.rotate(Pubkey::new_unique(), 1).err().unwrap(),
+            ErrorCode::RotationWhileDelegated.into()
+        );
+        assert_eq!(
+            institution.rotate(Pubkey::new_unique(), 1).err().unwrap(),
+            ErrorCode::RotationWhileDelegated.into()
+        );
+        let mut reputation = Reputation::sample();
+        let contributor = reputation.contributor;
+        assert_eq!(
+            reputation.rotate(contributor, 1).err().unwrap(),
+            ErrorCode::SameContributorKey.into()
+        );
+    }
+
+    #[test]
+    fn seeding_populates_a_fresh_reputation_once() {
+        let mut reputation = Reputation::sample();
+
+        reputation.seed(40, 3_000, 500, 12).unwrap();
+        calculate_reputation_score(&mut reputation).unwrap();
+
+        assert_eq!(reputation.average_quality(), Some(75));
+        assert_eq!(reputation.reputation_score, 40 * 10 + 75 + 500 * 2 + 12 * 15);
+        assert_eq!(reputation.dataset_count, 0);
+        assert_eq!(reputation.seed(1, 50, 0, 0).err().unwrap(), ErrorCode::ReputationAlreadyActive.into());
+    }
+
+    #[test]
+    fn seeding_rejects_active_accounts_and_values_beyond_the_caps() {
+        let mut active = Reputation::sample();
+        active.record_dataset().unwrap();
+        assert_eq!(active.seed(1, 50, 0, 0).err().unwrap(), ErrorCode::ReputationAlreadyActive.into());
+
+        for (uploads, quality, downloads, citations) in [
+            (MAX_SEEDED_UPLOADS + 1, 0, 0, 0),
+            (2, 201, 0, 0),
+            (0, 0, MAX_SEEDED_DOWNLOADS + 1, 0),
+            (0, 0, 0, MAX_SEEDED_CITATIONS + 1),
+        ] {
+            let mut reputation = Reputation::sample();
+            assert_eq!(
+                reputation.seed(uploads, quality, downloads, citations).err().unwrap(),
+                ErrorCode::InvalidSeedValues.into()
+            );
+            assert!(reputation.is_fresh());
+        }
+    }
+}
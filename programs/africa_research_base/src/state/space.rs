@@ -0,0 +1,96 @@
+use anchor_lang::Space;
+
+// Bytes allocated for an account: Anchor discriminator plus the `InitSpace` bound of its fields
+pub const fn max_serialized_len<T: Space>() -> usize {
+    8 + T::INIT_SPACE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::prelude::*;
+    use crate::constants::*;
+    use crate::state::{Citation, CoContributorCredit, Dataset, FieldScore, Registry, Reputation};
+
+    fn assert_fits<T: AnchorSerialize>(account: &T, allocation: usize) {
+        let serialized = 8 + account.try_to_vec().unwrap().len();
+        assert!(
+            serialized <= allocation,
+            "serialized account is {serialized} bytes but only {allocation} are allocated"
+        );
+    }
+
+    fn max_dataset() -> Dataset {
+        Dataset {
+            co_contributors: vec![Pubkey::new_unique(); MAX_CO_CONTRIBUTORS],
+            editors: vec![Pubkey::new_unique(); MAX_EDITORS],
+            ai_metadata: vec![u8::MAX; MAX_AI_METADATA_LEN],
+            file_name: vec![b'a'; MAX_FILE_NAME_LEN],
+            research_fields: vec![u8::MAX; MAX_RESEARCH_FIELDS],
+            data_uri: vec![u8::MAX; DATA_URI_LEN],
+            title: vec![b'a'; MAX_TITLE_LEN],
+            last_updated: Some(i64::MAX),
+            expires_at: Some(i64::MAX),
+            license_expires_at: Some(i64::MAX),
+            supersedes: Some(Pubkey::new_unique()),
+            superseded_by: Some(Pubkey::new_unique()),
+            thumbnail_uri: Some([u8::MAX; THUMBNAIL_URI_LEN]),
+            language: Some(*b"sw"),
+            credited_field_points: vec![FieldScore { field: u8::MAX, score: u32::MAX }; MAX_RESEARCH_FIELDS],
+            parent: Some(Pubkey::new_unique()),
+            co_credits: vec![
+                CoContributorCredit {
+                    contributor: Pubkey::new_unique(),
+                    quality: u8::MAX,
+                    field_points: vec![FieldScore { field: u8::MAX, score: u32::MAX }; MAX_RESEARCH_FIELDS],
+                };
+                MAX_CO_CONTRIBUTORS
+            ],
+            ..Dataset::sample()
+        }
+    }
+
+    #[test]
+    fn dataset_fits_allocation() {
+        assert_fits(&max_dataset(), max_serialized_len::<Dataset>());
+    }
+
+    #[test]
+    fn dataset_space_matches_max_instance() {
+        assert_eq!(max_dataset().try_to_vec().unwrap().len(), Dataset::SPACE);
+    }
+
+    #[test]
+    fn citation_space_matches_max_instance() {
+        let citation = Citation {
+            dataset_id: Pubkey::new_unique(),
+            citer: Pubkey::new_unique(),
+            contributor: Pubkey::new_unique(),
+            published_information: vec![b'x'; MAX_PUBLISHED_INFORMATION_LEN],
+            citing_time: i64::MAX,
+            bump: 255,
+        };
+
+        assert_eq!(citation.try_to_vec().unwrap().len(), Citation::SPACE);
+    }
+
+    #[test]
+    fn reputation_fits_allocation() {
+        let mut reputation = Reputation::sample();
+        let fields: Vec<u8> = (0..MAX_FIELD_SCORES as u8).collect();
+        reputation.credit_fields(&fields, u32::MAX);
+
+        assert_fits(&reputation, max_serialized_len::<Reputation>());
+    }
+
+    #[test]
+    fn registry_fits_allocation() {
+        assert_fits(&Registry::sample(), max_serialized_len::<Registry>());
+    }
+
+    #[test]
+    #[should_panic(expected = "only")]
+    fn undersized_allocation_is_caught() {
+        assert_fits(&max_dataset(), max_serialized_len::<Dataset>() - 1);
+    }
+}
@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_CATEGORIES;
+use crate::error::ErrorCode;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct Category {
+    pub code: u8, // The value datasets list in research_fields
+    pub name: [u8; 32], // Zero-padded
+    pub retired: bool, // Kept so existing datasets still resolve it, but no longer accepted on upload
+}
+
+// A registry's controlled vocabulary of research field codes
+#[account]
+#[derive(InitSpace)]
+pub struct Taxonomy {
+    pub registry: Pubkey,
+    #[max_len(MAX_CATEGORIES)]
+    pub categories: Vec<Category>,
+    pub bump: u8
+}
+
+impl Taxonomy {
+    pub const SPACE: usize = Self::INIT_SPACE;
+
+    // Adding a retired code reinstates it under the new name
+    pub fn add(&mut self, code: u8, name: [u8; 32]) -> Result<()> {
+        if let Some(category) = self.categories.iter_mut().find(|c| c.code == code) {
+            require!(category.retired, ErrorCode::DuplicateCategory);
+            *category = Category { code, name, retired: false };
+            return Ok(());
+        }
+        require!(self.categories.len() < MAX_CATEGORIES, ErrorCode::TaxonomyFull);
+        self.categories.push(Category { code, name, retired: false });
+        Ok(())
+    }
+
+    pub fn retire(&mut self, code: u8) -> Result<()> {
+        let category = self.categories
+            .iter_mut()
+            .find(|c| c.code == code && !c.retired)
+            .ok_or(ErrorCode::UnknownCategory)?;
+        category.retired = true;
+        Ok(())
+    }
+
+    pub fn is_active(&self, code: u8) -> bool {
+        self.categories.iter().any(|c| c.code == code && !c.retired)
+    }
+
+    pub fn validate_research_fields(&self, research_fields: &[u8]) -> Result<()> {
+        require!(research_fields.iter().all(|field| self.is_active(*field)), ErrorCode::UnknownCategory);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn name(label: &[u8]) -> [u8; 32] {
+        let mut name = [0u8; 32];
+        name[..label.len()].copy_from_slice(label);
+        name
+    }
+
+    fn taxonomy() -> Taxonomy {
+        let mut taxonomy = Taxonomy { registry: Pubkey::new_unique(), categories: Vec::new(), bump: 255 };
+        taxonomy.add(1, name(b"climate")).unwrap();
+        taxonomy.add(2, name(b"public health")).unwrap();
+        taxonomy
+    }
+
+    #[test]
+    fn known_categories_pass_and_unknown_ones_are_rejected() {
+        let taxonomy = taxonomy();
+
+        taxonomy.validate_research_fields(&[2, 1]).unwrap();
+        taxonomy.validate_research_fields(&[]).unwrap();
+        assert_eq!(taxonomy.validate_research_fields(&[1, 9]).unwrap_err(), ErrorCode::UnknownCategory.into());
+    }
+
+    #[test]
+    fn retired_categories_are_rejected_until_reinstated() {
+        let mut taxonomy = taxonomy();
+        taxonomy.retire(1).unwrap();
+
+        assert_eq!(taxonomy.validate_research_fields(&[1]).unwrap_err(), ErrorCode::UnknownCategory.into());
+        assert_eq!(taxonomy.retire(1).unwrap_err(), ErrorCode::UnknownCategory.into());
+
+        taxonomy.add(1, name(b"climate science")).unwrap();
+        taxonomy.validate_research_fields(&[1]).unwrap();
+        assert_eq!(taxonomy.categories.len(), 2);
+        assert_eq!(taxonomy.categories[0].name, name(b"climate science"));
+    }
+
+    #[test]
+    fn active_codes_cannot_be_redefined() {
+        let mut taxonomy = taxonomy();
+
+        assert_eq!(taxonomy.add(2, name(b"epidemiology")).unwrap_err(), ErrorCode::DuplicateCategory.into());
+        assert_eq!(taxonomy.categories[1].name, name(b"public health"));
+    }
+
+    #[test]
+    fn taxonomy_is_bounded() {
+        let mut taxonomy = Taxonomy { registry: Pubkey::new_unique(), categories: Vec::new(), bump: 255 };
+        for code in 0..MAX_CATEGORIES as u8 {
+            taxonomy.add(code, name(b"field")).unwrap();
+        }
+
+        assert_eq!(taxonomy.add(MAX_CATEGORIES as u8, name(b"field")).unwrap_err(), ErrorCode::TaxonomyFull.into());
+        let mut data = Vec::new();
+        taxonomy.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), 8 + Taxonomy::SPACE);
+    }
+}
@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+use crate::constants::{MAX_TITLE_TOKENS, MAX_TOKEN_INDEX_ENTRIES, MIN_TOKEN_LEN};
+
+// Datasets in a registry whose file name contains one word, keyed by the word's hash
+#[account]
+#[derive(InitSpace)]
+pub struct TokenIndex {
+    pub registry: Pubkey,
+    pub token_hash: [u8; 8], // First 8 bytes of SHA-256 of the lowercase word
+    // Oldest first; the oldest entry is evicted once full
+    #[max_len(MAX_TOKEN_INDEX_ENTRIES)]
+    pub datasets: Vec<Pubkey>,
+    pub bump: u8
+}
+
+impl TokenIndex {
+    pub const SPACE: usize = Self::INIT_SPACE;
+
+    // Splits on anything but ASCII letters and digits, lowercases, drops short words and
+    // duplicates, and keeps the first MAX_TITLE_TOKENS; returns each word's hash
+    pub fn tokenize(file_name: &[u8]) -> Vec<[u8; 8]> {
+        let mut tokens: Vec<[u8; 8]> = Vec::with_capacity(MAX_TITLE_TOKENS);
+        for word in file_name.split(|b| !b.is_ascii_alphanumeric()) {
+            if word.len() < MIN_TOKEN_LEN {
+                continue;
+            }
+            let token = Self::token_hash(&word.to_ascii_lowercase());
+            if !tokens.contains(&token) {
+                tokens.push(token);
+                if tokens.len() == MAX_TITLE_TOKENS {
+                    break;
+                }
+            }
+        }
+        tokens
+    }
+
+    pub fn token_hash(word: &[u8]) -> [u8; 8] {
+        let mut token = [0u8; 8];
+        token.copy_from_slice(&hash(word).to_bytes()[..8]);
+        token
+    }
+
+    pub fn insert(&mut self, dataset: Pubkey) {
+        if self.datasets.contains(&dataset) {
+            return;
+        }
+        if self.datasets.len() == MAX_TOKEN_INDEX_ENTRIES {
+            self.datasets.remove(0);
+        }
+        self.datasets.push(dataset);
+    }
+
+    pub fn remove(&mut self, dataset: &Pubkey) {
+        self.datasets.retain(|entry| entry != dataset);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index() -> TokenIndex {
+        TokenIndex {
+            registry: Pubkey::new_unique(),
+            token_hash: [0u8; 8],
+            datasets: Vec::new(),
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn tokenize_lowercases_dedupes_and_caps() {
+        let tokens = TokenIndex::tokenize(b"Malaria_cases-2021 MALARIA a.csv extra words");
+
+        assert_eq!(
+            tokens,
+            [b"malaria".as_slice(), b"cases", b"2021", b"csv"].map(TokenIndex::token_hash)
+        );
+    }
+
+    #[test]
+    fn datasets_sharing_a_word_share_an_index() {
+        let first = TokenIndex::tokenize(b"rainfall_kenya.csv");
+        let second = TokenIndex::tokenize(b"Rainfall-Ghana.json");
+        assert_eq!(first[0], second[0]);
+
+        let mut index = index();
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        index.insert(a);
+        index.insert(b);
+        index.insert(a);
+        assert_eq!(index.datasets, vec![a, b]);
+
+        index.remove(&a);
+        assert_eq!(index.datasets, vec![b]);
+    }
+
+    #[test]
+    fn full_index_evicts_oldest() {
+        let mut index = index();
+        let keys: Vec<Pubkey> = (0..=MAX_TOKEN_INDEX_ENTRIES).map(|_| Pubkey::new_unique()).collect();
+        for key in &keys {
+            index.insert(*key);
+        }
+
+        assert_eq!(index.datasets.len(), MAX_TOKEN_INDEX_ENTRIES);
+        assert!(!index.datasets.contains(&keys[0]));
+        assert_eq!(index.datasets.last(), keys.last());
+    }
+}
@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::TREASURY_WITHDRAWAL_PERIOD;
+use crate::error::ErrorCode;
+
+// Collects the platform's share of download fees for a registry
+#[account]
+#[derive(InitSpace)]
+pub struct Treasury {
+    pub registry: Pubkey,
+    pub total_collected: u64,
+    pub total_withdrawn: u64,
+    pub period_start: i64, // Start of the current withdrawal period; 0 before the first withdrawal
+    pub period_withdrawn: u64, // Withdrawn since period_start, held to the registry's treasury_withdrawal_cap
+    pub bump: u8
+}
+
+impl Treasury {
+    // Fixed-size account, so the derived InitSpace is exact
+    pub const SPACE: usize = Self::INIT_SPACE;
+
+    // Counts `lamports` against the per-period cap, starting a fresh period once the last one has run out
+    pub fn record_withdrawal(&mut self, lamports: u64, cap: u64, now: i64) -> Result<()> {
+        require!(lamports > 0, ErrorCode::InvalidWithdrawalAmount);
+        let (period_start, already_withdrawn) = if now >= self.period_start.saturating_add(TREASURY_WITHDRAWAL_PERIOD) {
+            (now, 0)
+        } else {
+            (self.period_start, self.period_withdrawn)
+        };
+        let period_withdrawn = already_withdrawn
+            .checked_add(lamports)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        require!(period_withdrawn <= cap, ErrorCode::WithdrawalCapExceeded);
+
+        self.period_start = period_start;
+        self.period_withdrawn = period_withdrawn;
+        self.total_withdrawn = self.total_withdrawn
+            .checked_add(lamports)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CAP: u64 = 1_000_000_000;
+
+    fn treasury() -> Treasury {
+        Treasury {
+            registry: Pubkey::new_unique(),
+            total_collected: 0,
+            total_withdrawn: 0,
+            period_start: 0,
+            period_withdrawn: 0,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn withdrawals_within_the_cap_accumulate_over_the_period() {
+        let mut treasury = treasury();
+        treasury.record_withdrawal(600_000_000, CAP, 1_700_000_000).unwrap();
+        treasury.record_withdrawal(400_000_000, CAP, 1_700_000_100).unwrap();
+
+        assert_eq!((treasury.period_start, treasury.period_withdrawn), (1_700_000_000, CAP));
+        assert_eq!(treasury.total_withdrawn, CAP);
+    }
+
+    #[test]
+    fn withdrawals_over_the_cap_are_rejected_until_the_next_period() {
+        let mut treasury = treasury();
+        let start = 1_700_000_000;
+        treasury.record_withdrawal(600_000_000, CAP, start).unwrap();
+
+        assert_eq!(
+            treasury.record_withdrawal(400_000_001, CAP, start + 1).unwrap_err(),
+            ErrorCode::WithdrawalCapExceeded.into()
+        );
+        assert_eq!(
+            treasury.record_withdrawal(1, 0, start + TREASURY_WITHDRAWAL_PERIOD).unwrap_err(),
+            ErrorCode::WithdrawalCapExceeded.into()
+        );
+        assert_eq!(treasury.period_withdrawn, 600_000_000);
+
+        treasury.record_withdrawal(CAP, CAP, start + TREASURY_WITHDRAWAL_PERIOD).unwrap();
+        assert_eq!(treasury.period_start, start + TREASURY_WITHDRAWAL_PERIOD);
+        assert_eq!(treasury.total_withdrawn, 600_000_000 + CAP);
+        assert_eq!(treasury.record_withdrawal(0, CAP, start).unwrap_err(), ErrorCode::InvalidWithdrawalAmount.into());
+    }
+}
@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use core::cmp::Ordering;
+
+use crate::constants::TRENDING_LEN;
+use crate::state::Dataset;
+
+// Enough to recompute a dataset's trending score at any time without loading it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct TrendingEntry {
+    pub dataset: Pubkey,
+    pub download_count: u32, // As of the dataset's last download
+    pub upload_timestamp: i64,
+}
+
+impl TrendingEntry {
+    pub fn from_dataset(dataset: &Dataset) -> Self {
+        TrendingEntry {
+            dataset: dataset.id,
+            download_count: dataset.download_count,
+            upload_timestamp: dataset.upload_timestamp,
+        }
+    }
+
+    pub fn score(&self, now: i64) -> u64 {
+        Dataset::trending_score_of(self.download_count, self.upload_timestamp, now)
+    }
+}
+
+// A registry's top datasets by trending score, refreshed by record_download. Entries are ranked as of
+// the latest update; since scores decay at different rates, clients re-rank with `ranked` at read time.
+#[account]
+#[derive(InitSpace)]
+pub struct TrendingDatasets {
+    pub registry: Pubkey,
+    #[max_len(TRENDING_LEN)]
+    pub entries: Vec<TrendingEntry>,
+    pub bump: u8
+}
+
+impl TrendingDatasets {
+    pub const SPACE: usize = Self::INIT_SPACE;
+
+    // Higher score first, then the lower dataset key, so ties rank the same on every update
+    fn rank_cmp(a: &TrendingEntry, b: &TrendingEntry, now: i64) -> Ordering {
+        b.score(now).cmp(&a.score(now)).then(a.dataset.cmp(&b.dataset))
+    }
+
+    // Refreshes the dataset's entry, re-ranks at `now` and drops whatever falls past TRENDING_LEN
+    pub fn upsert(&mut self, entry: TrendingEntry, now: i64) {
+        self.entries.retain(|existing| existing.dataset != entry.dataset);
+        self.entries.push(entry);
+        self.entries.sort_by(|a, b| Self::rank_cmp(a, b, now));
+        self.entries.truncate(TRENDING_LEN);
+    }
+
+    pub fn ranked(&self, now: i64) -> Vec<TrendingEntry> {
+        let mut entries = self.entries.clone();
+        entries.sort_by(|a, b| Self::rank_cmp(a, b, now));
+        entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: i64 = 3_600;
+
+    fn entry(key: u8, download_count: u32, upload_timestamp: i64) -> TrendingEntry {
+        TrendingEntry { dataset: Pubkey::new_from_array([key; 32]), download_count, upload_timestamp }
+    }
+
+    fn trending() -> TrendingDatasets {
+        TrendingDatasets { registry: Pubkey::new_unique(), entries: Vec::new(), bump: 255 }
+    }
+
+    #[test]
+    fn recent_dataset_outranks_an_older_one_with_equal_downloads() {
+        let now = 100 * HOUR;
+        let mut trending = trending();
+
+        trending.upsert(entry(1, 25, now - 72 * HOUR), now);
+        trending.upsert(entry(2, 25, now - 2 * HOUR), now);
+
+        let order: Vec<u8> = trending.entries.iter().map(|e| e.dataset.to_bytes()[0]).collect();
+        assert_eq!(order, vec![2, 1]);
+    }
+
+    #[test]
+    fn upsert_refreshes_an_entry_and_stays_bounded() {
+        let now = 10 * HOUR;
+        let mut trending = trending();
+        for key in 0..TRENDING_LEN as u8 + 3 {
+            trending.upsert(entry(key, 1 + key as u32, now), now);
+        }
+        assert_eq!(trending.entries.len(), TRENDING_LEN);
+        assert!(trending.entries.iter().all(|e| e.download_count > 3));
+
+        // The weakest entry jumps to the top, without appearing twice
+        trending.upsert(entry(3, 100, now), now);
+        assert_eq!(trending.entries[0], entry(3, 100, now));
+        assert_eq!(trending.entries.iter().filter(|e| e.dataset == entry(3, 0, 0).dataset).count(), 1);
+        assert_eq!(trending.entries.len(), TRENDING_LEN);
+    }
+
+    #[test]
+    fn ranked_reorders_as_scores_decay() {
+        let now = 10 * HOUR;
+        let mut trending = trending();
+        // A burst of downloads on a brand-new dataset beats a steadier older one at first...
+        trending.upsert(entry(1, 30, now - 8 * HOUR), now);
+        trending.upsert(entry(2, 3, now), now);
+        assert_eq!(trending.entries[0].dataset, entry(2, 0, 0).dataset);
+
+        // ...but its score falls faster while it is young
+        let later = now + 24 * HOUR;
+        assert_eq!(trending.ranked(later)[0].dataset, entry(1, 0, 0).dataset);
+    }
+}
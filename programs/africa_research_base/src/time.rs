@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+// Handlers read the cluster clock only through these, so unit tests can pin it with `with_clock`.
+// Every recorded timestamp (upload_timestamp, last_updated, *_at) comes from here; no instruction
+// takes one as an argument. Caller-supplied times are only future deadlines such as expires_at,
+// and are checked against this clock.
+pub fn current_time() -> Result<i64> {
+    Ok(Clock::get()?.unix_timestamp)
+}
+
+pub fn current_slot() -> Result<u64> {
+    Ok(Clock::get()?.slot)
+}
+
+// The stubs are process-wide, so tests that swap them take turns
+#[cfg(test)]
+pub(crate) static SYSCALL_STUBS_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+// Off-chain, `Clock::get` goes through the global syscall stubs; swapping in a fixed clock
+// makes time-based rules deterministic under `cargo test`
+#[cfg(test)]
+pub(crate) fn with_clock<R>(unix_timestamp: i64, slot: u64, f: impl FnOnce() -> R) -> R {
+    use anchor_lang::solana_program::entrypoint::SUCCESS;
+    use anchor_lang::solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+
+    struct FixedClock(Clock);
+
+    impl SyscallStubs for FixedClock {
+        fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+            // The caller hands us an aligned, writable slot for one Clock
+            unsafe { std::ptr::write(var_addr as *mut Clock, self.0.clone()) };
+            SUCCESS
+        }
+    }
+
+    let _guard = SYSCALL_STUBS_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    let clock = Clock { slot, unix_timestamp, ..Clock::default() };
+    let previous = set_syscall_stubs(Box::new(FixedClock(clock)));
+    let result = f();
+    set_syscall_stubs(previous);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::Dataset;
+
+    #[test]
+    fn pinned_clock_drives_expiry_boundary() {
+        let dataset = Dataset { expires_at: Some(1_700_000_500), ..Dataset::sample() };
+
+        let at_boundary = with_clock(1_700_000_500, 10, || dataset.is_expired(current_time().unwrap()));
+        let just_after = with_clock(1_700_000_501, 11, || dataset.is_expired(current_time().unwrap()));
+
+        assert!(!at_boundary);
+        assert!(just_after);
+    }
+
+    #[test]
+    fn pinned_clock_reports_slot() {
+        assert_eq!(with_clock(0, 42, || current_slot().unwrap()), 42);
+    }
+}
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{ALLOWED_URI_SCHEMES, NATIVE_URI_SCHEMES};
+use crate::error::ErrorCode;
+
+// URIs may arrive in fixed, zero-padded buffers; the meaningful bytes end at the first zero
+
+pub fn uri_bytes(buffer: &[u8]) -> &[u8] {
+    let end = buffer.iter().position(|&b| b == 0).unwrap_or(buffer.len());
+    &buffer[..end]
+}
+
+pub fn has_allowed_scheme(buffer: &[u8]) -> bool {
+    let uri = uri_bytes(buffer);
+    ALLOWED_URI_SCHEMES
+        .iter()
+        .any(|scheme| uri.len() > scheme.len() && uri.starts_with(scheme))
+}
+
+// Content-addressed URIs, resolvable without trusting any particular host
+pub fn is_native_uri(buffer: &[u8]) -> bool {
+    let uri = uri_bytes(buffer);
+    NATIVE_URI_SCHEMES.iter().any(|scheme| uri.starts_with(scheme))
+}
+
+// Host of a gateway URI such as `https://host:port/path`; `None` for any other scheme
+pub fn gateway_host(buffer: &[u8]) -> Option<&[u8]> {
+    let rest = uri_bytes(buffer).strip_prefix(b"https://")?;
+    let end = rest
+        .iter()
+        .position(|b| matches!(b, b'/' | b':' | b'?' | b'#'))
+        .unwrap_or(rest.len());
+    Some(&rest[..end])
+}
+
+pub fn validate_uri_scheme(buffer: &[u8]) -> Result<()> {
+    require!(has_allowed_scheme(buffer), ErrorCode::InvalidUriScheme);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::THUMBNAIL_URI_LEN;
+
+    fn padded(uri: &str) -> [u8; THUMBNAIL_URI_LEN] {
+        let mut buffer = [0u8; THUMBNAIL_URI_LEN];
+        buffer[..uri.len()].copy_from_slice(uri.as_bytes());
+        buffer
+    }
+
+    #[test]
+    fn known_schemes_are_accepted() {
+        validate_uri_scheme(&padded("ipfs://bafy-thumbnail")).unwrap();
+        validate_uri_scheme(&padded("ar://thumbnail-tx")).unwrap();
+        validate_uri_scheme(&padded("https://gateway.example/thumb.png")).unwrap();
+    }
+
+    #[test]
+    fn gateway_host_stops_at_port_and_path() {
+        assert_eq!(gateway_host(&padded("https://dweb.link:8080/ipfs/bafy")), Some(&b"dweb.link"[..]));
+        assert_eq!(gateway_host(&padded("https://dweb.link")), Some(&b"dweb.link"[..]));
+        assert_eq!(gateway_host(&padded("ipfs://bafy")), None);
+        assert!(is_native_uri(&padded("ipfs://bafy")));
+        assert!(!is_native_uri(&padded("https://dweb.link/ipfs/bafy")));
+    }
+
+    #[test]
+    fn unknown_or_bare_schemes_are_rejected() {
+        for uri in ["ftp://host/thumb.png", "http://insecure/thumb.png", "ipfs://", ""] {
+            assert_eq!(
+                validate_uri_scheme(&padded(uri)).unwrap_err(),
+                ErrorCode::InvalidUriScheme.into()
+            );
+        }
+    }
+}
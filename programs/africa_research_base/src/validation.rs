@@ -0,0 +1,146 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+
+// Input rules checked by `create_dataset`, in the order they were historically enforced; a rule's
+// bit in `ValidationFlags` is its index here, so the first set bit is the first failure
+const RULES: [ErrorCode; 26] = [
+    ErrorCode::FileNameTooLong,
+    ErrorCode::MetadataTooLong,
+    ErrorCode::InvalidQualityScore,
+    ErrorCode::QualityBelowMinimum,
+    ErrorCode::FileTooLarge,
+    ErrorCode::InvalidFileSize,
+    ErrorCode::FileTooSmall,
+    ErrorCode::PreviewHashRequired,
+    ErrorCode::TooManyColumns,
+    ErrorCode::DisallowedHost,
+    ErrorCode::InvalidExpiry,
+    ErrorCode::InvalidUriScheme,
+    ErrorCode::TooManyCoContributors,
+    ErrorCode::DuplicateCoContributor,
+    ErrorCode::TooManyResearchFields,
+    ErrorCode::DuplicateResearchField,
+    ErrorCode::InvalidFileName,
+    ErrorCode::DataUriTooLong,
+    ErrorCode::TitleTooLong,
+    ErrorCode::InvalidTitle,
+    ErrorCode::InvalidLicenseExpiry,
+    ErrorCode::MalformedAiMetadata,
+    ErrorCode::UnknownCategory,
+    ErrorCode::ZeroQualityScore,
+    ErrorCode::InvalidFeaturedUntil,
+    ErrorCode::InvalidLanguageCode,
+];
+
+// Failed rules collected in one pass over a submission, so every problem can be reported at once
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ValidationFlags(u32);
+
+impl ValidationFlags {
+    fn bit(code: ErrorCode) -> Option<u32> {
+        RULES
+            .iter()
+            .position(|rule| *rule as u32 == code as u32)
+            .map(|index| 1 << index)
+    }
+
+    pub fn require(&mut self, condition: bool, code: ErrorCode) {
+        if !condition {
+            self.0 |= Self::bit(code).expect("rule is listed in RULES");
+        }
+    }
+
+    // Records a helper's failure as its rule's flag; errors outside RULES are returned unchanged
+    pub fn check(&mut self, result: Result<()>) -> Result<()> {
+        let Err(error) = result else {
+            return Ok(());
+        };
+        let flag = RULES
+            .iter()
+            .find(|rule| error == Error::from(**rule))
+            .and_then(|rule| Self::bit(*rule));
+        match flag {
+            Some(flag) => {
+                self.0 |= flag;
+                Ok(())
+            }
+            None => Err(error),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn failed(&self) -> impl Iterator<Item = ErrorCode> + '_ {
+        RULES
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| self.0 & (1 << index) != 0)
+            .map(|(_, rule)| *rule)
+    }
+
+    // With `report_all`, several failures are logged together under MultipleValidationErrors;
+    // otherwise the first failed rule is returned on its own, as before
+    pub fn into_result(self, report_all: bool) -> Result<()> {
+        let mut failed = self.failed();
+        let Some(first) = failed.next() else {
+            return Ok(());
+        };
+        if !report_all || self.0.count_ones() == 1 {
+            return Err(first.into());
+        }
+
+        let names: Vec<String> = self.failed().map(|rule| rule.name()).collect();
+        msg!("Validation failed: {}", names.join(", "));
+        Err(ErrorCode::MultipleValidationErrors.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn two_failures() -> ValidationFlags {
+        let mut flags = ValidationFlags::default();
+        flags.require(true, ErrorCode::FileNameTooLong);
+        flags.require(false, ErrorCode::InvalidQualityScore);
+        flags.check(Err(ErrorCode::PreviewHashRequired.into())).unwrap();
+        flags
+    }
+
+    #[test]
+    fn both_violations_are_reported() {
+        let flags = two_failures();
+        let names: Vec<String> = flags.failed().map(|rule| rule.name()).collect();
+
+        assert_eq!(names, ["InvalidQualityScore", "PreviewHashRequired"]);
+        assert_eq!(flags.into_result(true).unwrap_err(), ErrorCode::MultipleValidationErrors.into());
+    }
+
+    #[test]
+    fn compatibility_mode_returns_first_failure() {
+        assert_eq!(two_failures().into_result(false).unwrap_err(), ErrorCode::InvalidQualityScore.into());
+    }
+
+    #[test]
+    fn single_failure_keeps_its_own_error() {
+        let mut flags = ValidationFlags::default();
+        flags.require(false, ErrorCode::TooManyColumns);
+
+        assert_eq!(flags.into_result(true).unwrap_err(), ErrorCode::TooManyColumns.into());
+        ValidationFlags::default().into_result(true).unwrap();
+    }
+
+    #[test]
+    fn unrelated_errors_are_not_absorbed() {
+        let mut flags = ValidationFlags::default();
+
+        assert_eq!(
+            flags.check(Err(ErrorCode::DatasetInactive.into())).unwrap_err(),
+            ErrorCode::DatasetInactive.into()
+        );
+        assert!(flags.is_empty());
+    }
+}
@@ -0,0 +1,206 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { AfricaResearchBase } from '../target/types/africa_research_base';
+import {
+  deriveDatasetPDA,
+  deriveRecentDatasetsPDA,
+  deriveRegistryConfigPDA,
+  deriveRegistryPDA,
+  deriveReputationPDA,
+  deriveTreasuryPDA,
+  registryName,
+  tokenIndexAccounts,
+} from './utils';
+
+export interface RegistryFixture {
+  registry: PublicKey;
+  treasury: PublicKey;
+  registryConfig: PublicKey;
+  name: number[];
+}
+
+export interface DatasetOverrides {
+  contentHash?: number[];
+  fileName?: string;
+  fileSize?: number;
+  dataUri?: string;
+  columnCount?: number;
+  rowCount?: number;
+  qualityScore?: number;
+  previewHash?: number[];
+  aiMetadata?: Buffer;
+  options?: Partial<DatasetOptions>;
+  taxonomy?: PublicKey; // Needed once the registry has a taxonomy and the dataset lists research fields
+  parent?: PublicKey; // Earlier version by the same contributor
+  manifest?: PublicKey; // Imported manifest the upload claims an entry of; set options.manifestEntry with it
+}
+
+export interface DatasetOptions {
+  expiresAt: anchor.BN | null;
+  thumbnailUri: number[] | null;
+  featured: boolean;
+  coContributors: PublicKey[];
+  researchFields: number[];
+  reportAllErrors: boolean;
+  title: Buffer | null; // Defaults to the file name
+  licenseExpiresAt: anchor.BN | null; // Null for a perpetual license
+  allowZeroQuality: boolean; // A qualityScore of 0 is rejected otherwise
+  featuredUntil: anchor.BN | null; // Null keeps a featured dataset featured
+  language: number[] | null; // Two ISO 639-1 letters as bytes
+  manifestEntry: number | null; // Entry of the passed manifest this upload claims
+}
+
+export const DEFAULT_OPTIONS: DatasetOptions = {
+  expiresAt: null,
+  thumbnailUri: null,
+  featured: false,
+  coContributors: [],
+  researchFields: [],
+  reportAllErrors: false,
+  title: null,
+  licenseExpiresAt: null,
+  allowZeroQuality: false,
+  featuredUntil: null,
+  language: null,
+  manifestEntry: null,
+};
+
+export const fundedKeypair = async (provider: anchor.AnchorProvider): Promise<Keypair> => {
+  const keypair = Keypair.generate();
+  const signature = await provider.connection.requestAirdrop(
+    keypair.publicKey,
+    2 * anchor.web3.LAMPORTS_PER_SOL
+  );
+  await provider.connection.confirmTransaction(signature);
+  return keypair;
+};
+
+export const createRegistry = async (
+  program: Program<AfricaResearchBase>,
+  admin: PublicKey,
+  label: string
+): Promise<RegistryFixture> => {
+  const name = registryName(label);
+  const [registry] = await deriveRegistryPDA(admin, name, program.programId);
+  const [treasury] = await deriveTreasuryPDA(registry, program.programId);
+  const [registryConfig] = await deriveRegistryConfigPDA(registry, program.programId);
+
+  await program.methods
+    .initializeRegistry(name)
+    .accounts({
+      admin: admin,
+      user: admin,
+      contributor: admin,
+      registry: registry,
+      treasury: treasury,
+      registryConfig: registryConfig,
+      systemProgram: SystemProgram.programId,
+    })
+    .rpc();
+
+  return { registry, treasury, registryConfig, name };
+};
+
+export const createReputation = async (
+  program: Program<AfricaResearchBase>,
+  admin: PublicKey,
+  contributor: Keypair
+): Promise<PublicKey> => {
+  const [reputation] = await deriveReputationPDA(contributor.publicKey, program.programId);
+
+  await program.methods
+    .initializeReputation()
+    .accounts({
+      admin: admin,
+      user: contributor.publicKey,
+      contributor: contributor.publicKey,
+      reputation: reputation,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([contributor])
+    .rpc();
+
+  return reputation;
+};
+
+export const createDataset = async (
+  program: Program<AfricaResearchBase>,
+  admin: PublicKey,
+  registry: PublicKey,
+  contributor: Keypair,
+  overrides: DatasetOverrides = {}
+): Promise<PublicKey> => {
+  const [reputation] = await deriveReputationPDA(contributor.publicKey, program.programId);
+  const { datasetCount } = await program.account.reputation.fetch(reputation);
+  const [dataset] = await deriveDatasetPDA(contributor.publicKey, datasetCount, program.programId);
+  const [registryConfig] = await deriveRegistryConfigPDA(registry, program.programId);
+  const [recentDatasets] = await deriveRecentDatasetsPDA(registry, program.programId);
+
+  const dataUri = Buffer.from(overrides.dataUri ?? "ipfs://bafy-test-dataset");
+  const fileName = overrides.fileName ?? "fixture.csv";
+  const options = { ...DEFAULT_OPTIONS, ...overrides.options };
+
+  await program.methods
+    .createDataset(
+      overrides.contentHash ?? Array.from(Keypair.generate().publicKey.toBytes()),
+      overrides.aiMetadata ?? Buffer.from('{"source": "fixture"}'),
+      Buffer.from(fileName),
+      new anchor.BN(overrides.fileSize ?? 4096),
+      dataUri,
+      new anchor.BN(overrides.columnCount ?? 5),
+      new anchor.BN(overrides.rowCount ?? 100),
+      overrides.qualityScore ?? 80,
+      overrides.previewHash ?? Array.from(Buffer.alloc(32)),
+      options
+    )
+    .accounts({
+      admin: admin,
+      contributor: contributor.publicKey,
+      dataset: dataset,
+      registry: registry,
+      registryConfig: registryConfig,
+      reputation: reputation,
+      recentDatasets: recentDatasets,
+      taxonomy: overrides.taxonomy ?? null,
+      parent: overrides.parent ?? null,
+      manifest: overrides.manifest ?? null,
+      systemProgram: SystemProgram.programId,
+    })
+    .remainingAccounts(await tokenIndexAccounts(registry, options.title ?? fileName, program.programId))
+    .signers([contributor])
+    .rpc();
+
+  return dataset;
+};
+
+export const forkDataset = async (
+  program: Program<AfricaResearchBase>,
+  admin: PublicKey,
+  registry: PublicKey,
+  source: PublicKey,
+  forker: Keypair
+): Promise<PublicKey> => {
+  const { contributor } = await program.account.dataset.fetch(source);
+  const [forkerReputation] = await deriveReputationPDA(forker.publicKey, program.programId);
+  const [sourceReputation] = await deriveReputationPDA(contributor, program.programId);
+  const { datasetCount } = await program.account.reputation.fetch(forkerReputation);
+  const [fork] = await deriveDatasetPDA(forker.publicKey, datasetCount, program.programId);
+
+  await program.methods
+    .forkDataset()
+    .accounts({
+      admin: admin,
+      forker: forker.publicKey,
+      registry: registry,
+      source: source,
+      fork: fork,
+      forkerReputation: forkerReputation,
+      sourceReputation: sourceReputation,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([forker])
+    .rpc();
+
+  return fork;
+};
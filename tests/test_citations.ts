@@ -0,0 +1,170 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { expect } from 'chai';
+import { AfricaResearchBase } from '../target/types/africa_research_base';
+import { createDataset, createRegistry, createReputation, fundedKeypair, RegistryFixture } from './fixtures';
+import { deriveCitationPairPDA, deriveCitationPDA, deriveExternalCitationPDA, deriveReputationPDA } from './utils';
+
+describe("Citations", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.AfricaResearchBase as Program<AfricaResearchBase>;
+  const admin = provider.wallet.publicKey;
+
+  let fixture: RegistryFixture;
+  let author: Keypair;
+  let citer: Keypair;
+
+  const cite = async (dataset: PublicKey) => {
+    const [citation] = await deriveCitationPDA(dataset, citer.publicKey, program.programId);
+    const [citationPair] = await deriveCitationPairPDA(citer.publicKey, author.publicKey, program.programId);
+    const [reputation] = await deriveReputationPDA(author.publicKey, program.programId);
+
+    await program.methods
+      .recordCitation(Buffer.from("doi:10.1000/arb-test"))
+      .accounts({
+        citer: citer.publicKey,
+        registry: fixture.registry,
+        dataset: dataset,
+        citation: citation,
+        citationPair: citationPair,
+        reputation: reputation,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([citer])
+      .rpc();
+
+    return citation;
+  };
+
+  before(async () => {
+    fixture = await createRegistry(program, admin, "citations-" + Date.now());
+    author = await fundedKeypair(provider);
+    citer = await fundedKeypair(provider);
+    await createReputation(program, admin, author);
+  });
+
+  it("Should record a 4th cross-citation without raising reputation", async () => {
+    const [reputation] = await deriveReputationPDA(author.publicKey, program.programId);
+
+    for (let i = 0; i < 3; i++) {
+      await cite(await createDataset(program, admin, fixture.registry, author));
+    }
+    expect((await program.account.reputation.fetch(reputation)).totalCitations).to.equal(3);
+
+    const fourth = await createDataset(program, admin, fixture.registry, author);
+    const citation = await cite(fourth);
+
+    expect((await program.account.citation.fetch(citation)).citer.toString()).to.equal(citer.publicKey.toString());
+    expect((await program.account.dataset.fetch(fourth)).citationCount).to.equal(1);
+    expect((await program.account.reputation.fetch(reputation)).totalCitations).to.equal(3);
+
+    const [citationPair] = await deriveCitationPairPDA(citer.publicKey, author.publicKey, program.programId);
+    expect((await program.account.citationPair.fetch(citationPair)).count).to.equal(4);
+  });
+
+  describe("report_external_citation", () => {
+    let oracle: Keypair;
+
+    const report = async (signer: Keypair, dataset: PublicKey, publicationHash: number[]) => {
+      const [externalCitation] = await deriveExternalCitationPDA(dataset, publicationHash, program.programId);
+      const [reputation] = await deriveReputationPDA(author.publicKey, program.programId);
+
+      await program.methods
+        .reportExternalCitation(publicationHash)
+        .accounts({
+          oracle: signer.publicKey,
+          registry: fixture.registry,
+          dataset: dataset,
+          externalCitation: externalCitation,
+          reputation: reputation,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([signer])
+        .rpc();
+
+      return externalCitation;
+    };
+
+    before(async () => {
+      oracle = await fundedKeypair(provider);
+      await program.methods
+        .setCitationOracle(oracle.publicKey)
+        .accounts({ admin: admin, registry: fixture.registry })
+        .rpc();
+    });
+
+    it("Should credit a citation attested by the oracle", async () => {
+      const [reputation] = await deriveReputationPDA(author.publicKey, program.programId);
+      const dataset = await createDataset(program, admin, fixture.registry, author);
+      const before = (await program.account.reputation.fetch(reputation)).totalCitations;
+      const publicationHash = Array.from(Keypair.generate().publicKey.toBytes());
+
+      const externalCitation = await report(oracle, dataset, publicationHash);
+
+      const record = await program.account.externalCitation.fetch(externalCitation);
+      expect(record.publicationHash).to.deep.equal(publicationHash);
+      expect(record.oracle.toString()).to.equal(oracle.publicKey.toString());
+      expect((await program.account.dataset.fetch(dataset)).citationCount).to.equal(1);
+      expect((await program.account.reputation.fetch(reputation)).totalCitations).to.equal(before + 1);
+    });
+
+    it("Should reject reports from anyone but the oracle", async () => {
+      const dataset = await createDataset(program, admin, fixture.registry, author);
+
+      try {
+        await report(citer, dataset, Array.from(Keypair.generate().publicKey.toBytes()));
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("UnauthorizedOracle");
+      }
+    });
+  });
+
+  describe("revoke_citation", () => {
+    const revoke = async (signer: Keypair, dataset: PublicKey, citation: PublicKey) => {
+      const [citationPair] = await deriveCitationPairPDA(citer.publicKey, author.publicKey, program.programId);
+      const [reputation] = await deriveReputationPDA(author.publicKey, program.programId);
+
+      await program.methods
+        .revokeCitation()
+        .accounts({
+          authority: signer.publicKey,
+          registry: fixture.registry,
+          dataset: dataset,
+          citation: citation,
+          citationPair: citationPair,
+          reputation: reputation,
+          citer: citer.publicKey,
+        })
+        .signers([signer])
+        .rpc();
+    };
+
+    it("Should let the citer revoke a citation", async () => {
+      const dataset = await createDataset(program, admin, fixture.registry, author);
+      const citation = await cite(dataset);
+      expect((await program.account.dataset.fetch(dataset)).citationCount).to.equal(1);
+
+      await revoke(citer, dataset, citation);
+
+      expect((await program.account.dataset.fetch(dataset)).citationCount).to.equal(0);
+      expect(await program.account.citation.fetchNullable(citation)).to.equal(null);
+    });
+
+    it("Should reject revocation by an unrelated signer", async () => {
+      const stranger = await fundedKeypair(provider);
+      const dataset = await createDataset(program, admin, fixture.registry, author);
+      const citation = await cite(dataset);
+
+      try {
+        await revoke(stranger, dataset, citation);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("UnauthorizedRevocation");
+      }
+    });
+  });
+});
@@ -0,0 +1,360 @@
+import * as anchor from '@coral-xyz/anchor';
+import { BorshCoder, EventParser, Program } from '@coral-xyz/anchor';
+import { BPF_LOADER_UPGRADEABLE_PROGRAM_ID, Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { expect } from 'chai';
+import { sha256 } from 'js-sha256';
+import { AfricaResearchBase } from '../target/types/africa_research_base';
+import { createDataset, createRegistry, createReputation, forkDataset, fundedKeypair, RegistryFixture } from './fixtures';
+import { deriveEarningsPDA, deriveReputationPDA, deriveVaultPDA, tokenIndexAccounts } from './utils';
+
+describe("Contributors", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.AfricaResearchBase as Program<AfricaResearchBase>;
+  const admin = provider.wallet.publicKey;
+
+  let fixture: RegistryFixture;
+
+  const rotate = async (oldKey: Keypair, newKey: PublicKey, signers: Keypair[], datasets: PublicKey[] = []) => {
+    const [oldReputation] = await deriveReputationPDA(oldKey.publicKey, program.programId);
+    const [newReputation] = await deriveReputationPDA(newKey, program.programId);
+    await program.methods
+      .rotateContributorKey()
+      .accounts({
+        oldContributor: oldKey.publicKey,
+        newContributor: newKey,
+        oldReputation: oldReputation,
+        newReputation: newReputation,
+        oldEarnings: null,
+        newEarnings: null,
+      })
+      .remainingAccounts(datasets.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true })))
+      .signers(signers)
+      .rpc();
+    return { oldReputation, newReputation };
+  };
+
+  const seed = async (contributor: PublicKey, uploads: number, qualityTotal: number) => {
+    const [programData] = PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      BPF_LOADER_UPGRADEABLE_PROGRAM_ID
+    );
+    await program.methods
+      .seedReputation(uploads, new anchor.BN(qualityTotal), new anchor.BN(0), 0)
+      .accounts({ authority: admin, programData: programData, contributor: contributor })
+      .rpc();
+    const [reputation] = await deriveReputationPDA(contributor, program.programId);
+    return reputation;
+  };
+
+  before(async () => {
+    fixture = await createRegistry(program, admin, "contributors-" + Date.now());
+  });
+
+  describe("rotate_contributor_key", () => {
+    it("Should move the reputation and the passed datasets to the new key", async () => {
+      const oldKey = await fundedKeypair(provider);
+      const newKey = await fundedKeypair(provider);
+      await createReputation(program, admin, oldKey);
+      const dataset = await createDataset(program, admin, fixture.registry, oldKey);
+
+      const { oldReputation, newReputation } = await rotate(oldKey, newKey.publicKey, [oldKey, newKey], [dataset]);
+
+      const rotated = await program.account.reputation.fetch(newReputation);
+      expect(rotated.contributor.toBase58()).to.equal(newKey.publicKey.toBase58());
+      expect(rotated.datasetCount).to.equal(1);
+      // The old PDA stays occupied by an empty tombstone, so it can't be re-initialized
+      const tombstone = await program.account.reputation.fetch(oldReputation);
+      expect(tombstone.rotatedTo.toBase58()).to.equal(newKey.publicKey.toBase58());
+      expect(tombstone.datasetCount).to.equal(0);
+      const moved = await program.account.dataset.fetch(dataset);
+      expect(moved.contributor.toBase58()).to.equal(newKey.publicKey.toBase58());
+      expect(moved.creator.toBase58()).to.equal(oldKey.publicKey.toBase58());
+    });
+
+    it("Should keep downloads and close working after rotation, for the new key only", async () => {
+      const oldKey = await fundedKeypair(provider);
+      const newKey = await fundedKeypair(provider);
+      const downloader = await fundedKeypair(provider);
+      await createReputation(program, admin, oldKey);
+      const fileName = "rotated.csv";
+      const dataset = await createDataset(program, admin, fixture.registry, oldKey, { fileName });
+      const { newReputation } = await rotate(oldKey, newKey.publicKey, [oldKey, newKey], [dataset]);
+
+      await program.methods
+        .recordDownload(new anchor.BN(0))
+        .accounts({
+          downloader: downloader.publicKey,
+          registry: fixture.registry,
+          dataset: dataset,
+          reputation: newReputation,
+          treasury: fixture.treasury,
+          feeVault: null,
+          receipt: null,
+          downloadRecord: null,
+          earnings: null,
+          contributor: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([downloader])
+        .rpc();
+      expect((await program.account.reputation.fetch(newReputation)).totalDownloads.toNumber()).to.equal(1);
+
+      const close = async (signer: Keypair) =>
+        program.methods
+          .closeDataset()
+          .accounts({
+            contributor: signer.publicKey,
+            registry: fixture.registry,
+            dataset: dataset,
+          })
+          .remainingAccounts(await tokenIndexAccounts(fixture.registry, fileName, program.programId))
+          .signers([signer])
+          .rpc();
+
+      try {
+        await close(oldKey);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("UnauthorizedUpdate");
+      }
+      await close(newKey);
+      expect(await program.account.dataset.fetchNullable(dataset)).to.be.null;
+    });
+
+    it("Should let anyone re-point a dataset the rotation didn't carry", async () => {
+      const oldKey = await fundedKeypair(provider);
+      const newKey = await fundedKeypair(provider);
+      await createReputation(program, admin, oldKey);
+      const dataset = await createDataset(program, admin, fixture.registry, oldKey);
+      const { oldReputation } = await rotate(oldKey, newKey.publicKey, [oldKey, newKey]);
+
+      await program.methods
+        .repointDatasets()
+        .accounts({ rotatedReputation: oldReputation })
+        .remainingAccounts([{ pubkey: dataset, isSigner: false, isWritable: true }])
+        .rpc();
+
+      expect((await program.account.dataset.fetch(dataset)).contributor.toBase58())
+        .to.equal(newKey.publicKey.toBase58());
+    });
+
+    it("Should re-point a dataset's fee vault, so recovery pays the new key", async () => {
+      const oldKey = await fundedKeypair(provider);
+      const newKey = await fundedKeypair(provider);
+      await createReputation(program, admin, oldKey);
+      const fileName = "rotated-vault.csv";
+      const dataset = await createDataset(program, admin, fixture.registry, oldKey, { fileName });
+      const [feeVault] = await deriveVaultPDA(dataset, program.programId);
+      await program.methods
+        .setDownloadFee(new anchor.BN(1_000_000))
+        .accounts({
+          contributor: oldKey.publicKey,
+          dataset: dataset,
+          feeVault: feeVault,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([oldKey])
+        .rpc();
+
+      const [oldReputation] = await deriveReputationPDA(oldKey.publicKey, program.programId);
+      const [newReputation] = await deriveReputationPDA(newKey.publicKey, program.programId);
+      const [newEarnings] = await deriveEarningsPDA(newKey.publicKey, program.programId);
+      await program.methods
+        .rotateContributorKey()
+        .accounts({
+          oldContributor: oldKey.publicKey,
+          newContributor: newKey.publicKey,
+          oldReputation: oldReputation,
+          newReputation: newReputation,
+          oldEarnings: (await deriveEarningsPDA(oldKey.publicKey, program.programId))[0],
+          newEarnings: newEarnings,
+        })
+        .remainingAccounts([{ pubkey: dataset, isSigner: false, isWritable: true }])
+        .signers([oldKey, newKey])
+        .rpc();
+      expect((await program.account.feeVault.fetch(feeVault)).contributor.toBase58())
+        .to.equal(oldKey.publicKey.toBase58());
+
+      await program.methods
+        .repointDatasets()
+        .accounts({ rotatedReputation: oldReputation })
+        .remainingAccounts([{ pubkey: feeVault, isSigner: false, isWritable: true }])
+        .rpc();
+      expect((await program.account.feeVault.fetch(feeVault)).contributor.toBase58())
+        .to.equal(newKey.publicKey.toBase58());
+
+      await program.methods
+        .closeDataset()
+        .accounts({
+          contributor: newKey.publicKey,
+          registry: fixture.registry,
+          dataset: dataset,
+          reputation: newReputation,
+        })
+        .remainingAccounts(await tokenIndexAccounts(fixture.registry, fileName, program.programId))
+        .signers([newKey])
+        .rpc();
+      await program.methods
+        .recoverStuckVault()
+        .accounts({
+          admin: admin,
+          registry: fixture.registry,
+          treasury: fixture.treasury,
+          feeVault: feeVault,
+          contributor: newKey.publicKey,
+          earnings: newEarnings,
+          dataset: dataset,
+        })
+        .rpc();
+      expect(await provider.connection.getAccountInfo(feeVault)).to.equal(null);
+    });
+
+    it("Should reject a rotation the new key doesn't co-sign", async () => {
+      const oldKey = await fundedKeypair(provider);
+      const newKey = Keypair.generate();
+      const oldReputation = await createReputation(program, admin, oldKey);
+
+      try {
+        await rotate(oldKey, newKey.publicKey, [oldKey]);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("Signature verification failed");
+      }
+      expect((await program.account.reputation.fetch(oldReputation)).contributor.toBase58())
+        .to.equal(oldKey.publicKey.toBase58());
+    });
+  });
+
+  describe("audit_reputation", () => {
+    it("Should break the score into components that sum to the stored score", async () => {
+      const contributor = await fundedKeypair(provider);
+      const forker = await fundedKeypair(provider);
+      const reputation = await createReputation(program, admin, contributor);
+      await createReputation(program, admin, forker);
+      const dataset = await createDataset(program, admin, fixture.registry, contributor);
+      await forkDataset(program, admin, fixture.registry, dataset, forker);
+
+      const signature = await program.methods
+        .auditReputation()
+        .accounts({ reputation: reputation })
+        .rpc({ commitment: "confirmed" });
+      const transaction = await provider.connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const [encoded] = transaction.meta.returnData.data;
+      const returned = Buffer.from(encoded, "base64");
+      expect(returned[0]).to.equal(2);
+      // upload, quality, download, citation and fork credits, then penalties, decay and normalization
+      const parts = Array.from({ length: 8 }, (_, i) => returned.readUInt32LE(1 + 4 * i));
+      const score = parts.slice(0, 5).reduce((sum, part) => sum + part, 0) - parts[5] - parts[6] - parts[7];
+
+      const { reputationScore } = await program.account.reputation.fetch(reputation);
+      expect(parts[4]).to.be.greaterThan(0);
+      expect(score).to.equal(reputationScore);
+    });
+  });
+
+  describe("get_contributor_overview", () => {
+    it("Should summarise the contributor's reputation and datasets in one call", async () => {
+      const contributor = await fundedKeypair(provider);
+      const reputation = await createReputation(program, admin, contributor);
+      const datasets = [
+        await createDataset(program, admin, fixture.registry, contributor),
+        await createDataset(program, admin, fixture.registry, contributor),
+      ];
+
+      const signature = await program.methods
+        .getContributorOverview()
+        .accounts({ reputation: reputation, earnings: null })
+        .remainingAccounts(datasets.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false })))
+        .rpc({ commitment: "confirmed" });
+      const transaction = await provider.connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const [encoded] = transaction.meta.returnData.data;
+      const returned = Buffer.from(encoded, "base64");
+
+      const account = await program.account.reputation.fetch(reputation);
+      expect(returned[0]).to.equal(1);
+      // total_uploads, active_datasets, reputation_score and tier, then lifetime_earnings
+      const [totalUploads, activeDatasets, reputationScore] = [1, 5, 9].map((offset) => returned.readUInt32LE(offset));
+      expect(totalUploads).to.equal(account.totalUploads);
+      expect(activeDatasets).to.equal(datasets.length);
+      expect(reputationScore).to.equal(account.reputationScore);
+      expect(Number(returned.readBigUInt64LE(17))).to.equal(0);
+    });
+  });
+
+  describe("seed_reputation", () => {
+    it("Should populate a new contributor's reputation from imported history", async () => {
+      const contributor = Keypair.generate();
+
+      const reputation = await seed(contributor.publicKey, 20, 1500);
+
+      const seeded = await program.account.reputation.fetch(reputation);
+      expect(seeded.contributor.toBase58()).to.equal(contributor.publicKey.toBase58());
+      expect(seeded.totalUploads).to.equal(20);
+      expect(seeded.totalQualityScore.toNumber()).to.equal(1500);
+      expect(seeded.datasetCount).to.equal(0);
+      expect(seeded.reputationScore).to.be.greaterThan(0);
+    });
+
+    it("Should reject re-seeding a reputation that already has activity", async () => {
+      const contributor = Keypair.generate();
+      await seed(contributor.publicKey, 5, 400);
+
+      try {
+        await seed(contributor.publicKey, 50, 4000);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("ReputationAlreadyActive");
+      }
+    });
+  });
+
+  describe("anonymous mode", () => {
+    const eventsOf = async (address: PublicKey) => {
+      const [{ signature }] = await provider.connection.getSignaturesForAddress(address, {}, "confirmed");
+      const transaction = await provider.connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const parser = new EventParser(program.programId, new BorshCoder(program.idl));
+      return Array.from(parser.parseLogs(transaction.meta.logMessages));
+    };
+
+    it("Should publish a pseudonym in the creation event while authorizing the real key", async () => {
+      const contributor = await fundedKeypair(provider);
+      const reputation = await createReputation(program, admin, contributor);
+      await program.methods
+        .setAnonymous(true)
+        .accounts({ contributor: contributor.publicKey, reputation: reputation })
+        .signers([contributor])
+        .rpc();
+
+      const dataset = await createDataset(program, admin, fixture.registry, contributor);
+
+      const created = (await eventsOf(dataset)).find((event) => event.name === "datasetCreated");
+      const pseudonym = new PublicKey(
+        Buffer.from(sha256.array(Buffer.concat([Buffer.from("anonymous"), contributor.publicKey.toBuffer()])))
+      );
+      expect(created.data.contributor.toBase58()).to.equal(pseudonym.toBase58());
+      expect(created.data.contributor.toBase58()).to.not.equal(contributor.publicKey.toBase58());
+
+      // Ownership is still checked against the real key
+      const renewedUntil = Math.floor(Date.now() / 1000) + 365 * 24 * 60 * 60;
+      await program.methods
+        .renewLicense(new anchor.BN(renewedUntil))
+        .accounts({ contributor: contributor.publicKey, dataset: dataset })
+        .signers([contributor])
+        .rpc();
+      const account = await program.account.dataset.fetch(dataset);
+      expect(account.licenseExpiresAt.toNumber()).to.equal(renewedUntil);
+      expect((await program.account.reputation.fetch(reputation)).datasetCount).to.equal(1);
+    });
+  });
+});
@@ -0,0 +1,1960 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { expect } from 'chai';
+import { sha256 } from 'js-sha256';
+import { AfricaResearchBase } from '../target/types/africa_research_base';
+import {
+  createDataset,
+  createRegistry,
+  createReputation,
+  DEFAULT_OPTIONS,
+  forkDataset,
+  fundedKeypair,
+  RegistryFixture,
+} from './fixtures';
+import {
+  collectionRoot,
+  deriveAvailabilityReportPDA,
+  deriveCollectionPDA,
+  deriveDataDictionaryPDA,
+  deriveDataRequestPDA,
+  deriveDatasetPDA,
+  deriveDatasetReplicasPDA,
+  deriveEndorsementPDA,
+  deriveHashRegistryPDA,
+  derivePenaltyRecordPDA,
+  deriveRatingPDA,
+  deriveRecentDatasetsPDA,
+  deriveRegistryPDA,
+  deriveReputationPDA,
+  deriveTaxonomyPDA,
+  deriveTokenIndexPDA,
+  registryName,
+  tokenIndexAccounts,
+  tokenize,
+} from './utils';
+
+describe("Dataset lifecycle", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.AfricaResearchBase as Program<AfricaResearchBase>;
+  const admin = provider.wallet.publicKey;
+
+  let fixture: RegistryFixture;
+  let alice: Keypair;
+  let bob: Keypair;
+
+  before(async () => {
+    fixture = await createRegistry(program, admin, "lifecycle-" + Date.now());
+    alice = await fundedKeypair(provider);
+    bob = await fundedKeypair(provider);
+    await createReputation(program, admin, alice);
+    await createReputation(program, admin, bob);
+  });
+
+  describe("merge_datasets", () => {
+    it("Should sum counters into the canonical dataset and close the duplicate", async () => {
+      const contentHash = Array.from(Keypair.generate().publicKey.toBytes());
+      const canonical = await createDataset(program, admin, fixture.registry, alice, { contentHash });
+      const duplicate = await createDataset(program, admin, fixture.registry, bob, { contentHash });
+      const [bobReputation] = await deriveReputationPDA(bob.publicKey, program.programId);
+
+      await program.methods
+        .recordDownload(new anchor.BN(0))
+        .accounts({
+          downloader: alice.publicKey,
+          registry: fixture.registry,
+          dataset: duplicate,
+          reputation: bobReputation,
+          treasury: fixture.treasury,
+          feeVault: null,
+          receipt: null,
+          downloadRecord: null,
+          earnings: null,
+          contributor: null,
+        })
+        .signers([alice])
+        .rpc();
+      const bobBefore = await provider.connection.getBalance(bob.publicKey);
+
+      await program.methods
+        .mergeDatasets()
+        .accounts({
+          admin: admin,
+          registry: fixture.registry,
+          canonical: canonical,
+          duplicate: duplicate,
+          duplicateContributor: bob.publicKey,
+          duplicateReputation: bobReputation,
+        })
+        .remainingAccounts(await tokenIndexAccounts(fixture.registry, "fixture.csv", program.programId))
+        .rpc();
+
+      const canonicalAccount = await program.account.dataset.fetch(canonical);
+      expect(canonicalAccount.downloadCount).to.equal(1);
+      expect(canonicalAccount.lastUpdated).to.not.equal(null);
+      expect(await program.account.dataset.fetchNullable(duplicate)).to.equal(null);
+      expect(await provider.connection.getBalance(bob.publicKey)).to.be.greaterThan(bobBefore);
+
+      const [index] = await deriveTokenIndexPDA(fixture.registry, tokenize("fixture")[0], program.programId);
+      const indexed = (await program.account.tokenIndex.fetch(index)).datasets.map((key) => key.toString());
+      expect(indexed).to.include(canonical.toString());
+      expect(indexed).to.not.include(duplicate.toString());
+    });
+
+    it("Should reject merging datasets with different content", async () => {
+      const canonical = await createDataset(program, admin, fixture.registry, alice);
+      const other = await createDataset(program, admin, fixture.registry, bob);
+
+      try {
+        await program.methods
+          .mergeDatasets()
+          .accounts({
+            admin: admin,
+            registry: fixture.registry,
+            canonical: canonical,
+            duplicate: other,
+            duplicateContributor: bob.publicKey,
+          })
+          .remainingAccounts(await tokenIndexAccounts(fixture.registry, "fixture.csv", program.programId))
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("HashMismatch");
+      }
+    });
+  });
+
+  describe("preview_hash", () => {
+    it("Should require a preview hash for datasets larger than the preview window", async () => {
+      try {
+        await createDataset(program, admin, fixture.registry, alice, { fileSize: 5 * 1024 * 1024 });
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("PreviewHashRequired");
+      }
+
+      const previewHash = Array.from(Buffer.alloc(32, 9));
+      const dataset = await createDataset(program, admin, fixture.registry, alice, {
+        fileSize: 5 * 1024 * 1024,
+        previewHash,
+      });
+      expect((await program.account.dataset.fetch(dataset)).previewHash).to.deep.equal(previewHash);
+    });
+  });
+
+  describe("thumbnail_uri", () => {
+    const thumbnail = (uri: string): number[] => {
+      const buffer = Buffer.alloc(128);
+      Buffer.from(uri).copy(buffer);
+      return Array.from(buffer);
+    };
+
+    it("Should store and read back a thumbnail URI", async () => {
+      const thumbnailUri = thumbnail("ipfs://bafy-thumbnail-preview");
+      const dataset = await createDataset(program, admin, fixture.registry, alice, {
+        options: { thumbnailUri },
+      });
+
+      expect((await program.account.dataset.fetch(dataset)).thumbnailUri).to.deep.equal(thumbnailUri);
+    });
+
+    it("Should leave the thumbnail empty when omitted", async () => {
+      const dataset = await createDataset(program, admin, fixture.registry, alice);
+      expect((await program.account.dataset.fetch(dataset)).thumbnailUri).to.equal(null);
+    });
+
+    it("Should reject a thumbnail URI with an unknown scheme", async () => {
+      try {
+        await createDataset(program, admin, fixture.registry, alice, {
+          options: { thumbnailUri: thumbnail("ftp://example.org/thumb.png") },
+        });
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidUriScheme");
+      }
+    });
+  });
+
+  describe("featured datasets", () => {
+    it("Should reject a featured dataset from a contributor at or below the threshold", async () => {
+      await program.methods
+        .setFeaturedThreshold(1)
+        .accounts({ admin: admin, registry: fixture.registry })
+        .rpc();
+
+      const contributor = await fundedKeypair(provider);
+      await createReputation(program, admin, contributor);
+
+      try {
+        await createDataset(program, admin, fixture.registry, contributor, { options: { featured: true } });
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InsufficientReputation");
+      }
+    });
+
+    it("Should let a contributor above the threshold create a featured dataset", async () => {
+      const contributor = await fundedKeypair(provider);
+      await createReputation(program, admin, contributor);
+      const [reputation] = await deriveReputationPDA(contributor.publicKey, program.programId);
+
+      // One download is worth DOWNLOAD_WEIGHT points, lifting the score above the threshold of 1
+      const first = await createDataset(program, admin, fixture.registry, contributor);
+      await program.methods
+        .recordDownload(new anchor.BN(0))
+        .accounts({
+          downloader: alice.publicKey,
+          registry: fixture.registry,
+          dataset: first,
+          reputation: reputation,
+          treasury: fixture.treasury,
+          feeVault: null,
+          receipt: null,
+          downloadRecord: null,
+          earnings: null,
+          contributor: null,
+        })
+        .signers([alice])
+        .rpc();
+
+      const featuredBefore = (await program.account.registry.fetch(fixture.registry)).featuredCount;
+      const dataset = await createDataset(program, admin, fixture.registry, contributor, {
+        options: { featured: true },
+      });
+
+      expect((await program.account.dataset.fetch(dataset)).featured).to.equal(true);
+      const registryAccount = await program.account.registry.fetch(fixture.registry);
+      expect(registryAccount.featuredCount.toNumber()).to.equal(featuredBefore.toNumber() + 1);
+    });
+
+    it("Should unfeature a dataset once its promotion window has passed", async () => {
+      const contributor = await fundedKeypair(provider);
+      await createReputation(program, admin, contributor);
+      const [reputation] = await deriveReputationPDA(contributor.publicKey, program.programId);
+      const first = await createDataset(program, admin, fixture.registry, contributor);
+      await program.methods
+        .recordDownload(new anchor.BN(0))
+        .accounts({
+          downloader: alice.publicKey,
+          registry: fixture.registry,
+          dataset: first,
+          reputation: reputation,
+          treasury: fixture.treasury,
+          feeVault: null,
+          receipt: null,
+          downloadRecord: null,
+          earnings: null,
+          contributor: null,
+        })
+        .signers([alice])
+        .rpc();
+
+      const featuredUntil = Math.floor(Date.now() / 1000) + 3;
+      const dataset = await createDataset(program, admin, fixture.registry, contributor, {
+        options: { featured: true, featuredUntil: new anchor.BN(featuredUntil) },
+      });
+      const featuredBefore = (await program.account.registry.fetch(fixture.registry)).featuredCount;
+
+      try {
+        await program.methods
+          .unfeatureExpired()
+          .accounts({ registry: fixture.registry, dataset: dataset })
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("FeatureNotExpired");
+      }
+
+      await new Promise((resolve) => setTimeout(resolve, 5000));
+      await program.methods
+        .unfeatureExpired()
+        .accounts({ registry: fixture.registry, dataset: dataset })
+        .rpc();
+
+      const account = await program.account.dataset.fetch(dataset);
+      expect(account.featured).to.equal(false);
+      expect(account.featuredUntil.toNumber()).to.equal(0);
+      const registryAccount = await program.account.registry.fetch(fixture.registry);
+      expect(registryAccount.featuredCount.toNumber()).to.equal(featuredBefore.toNumber() - 1);
+    });
+
+    it("Should give back the featured slot when a featured dataset is closed or merged", async () => {
+      const contributor = await fundedKeypair(provider);
+      await createReputation(program, admin, contributor);
+      const [reputation] = await deriveReputationPDA(contributor.publicKey, program.programId);
+      const first = await createDataset(program, admin, fixture.registry, contributor);
+      await program.methods
+        .recordDownload(new anchor.BN(0))
+        .accounts({
+          downloader: alice.publicKey,
+          registry: fixture.registry,
+          dataset: first,
+          reputation: reputation,
+          treasury: fixture.treasury,
+          feeVault: null,
+          receipt: null,
+          downloadRecord: null,
+          earnings: null,
+          contributor: null,
+        })
+        .signers([alice])
+        .rpc();
+
+      const featuredBefore = (await program.account.registry.fetch(fixture.registry)).featuredCount;
+      const closed = await createDataset(program, admin, fixture.registry, contributor, {
+        options: { featured: true },
+      });
+      await program.methods
+        .closeDataset()
+        .accounts({
+          contributor: contributor.publicKey,
+          registry: fixture.registry,
+          dataset: closed,
+          reputation: reputation,
+        })
+        .remainingAccounts(await tokenIndexAccounts(fixture.registry, "fixture.csv", program.programId))
+        .signers([contributor])
+        .rpc();
+      let registryAccount = await program.account.registry.fetch(fixture.registry);
+      expect(registryAccount.featuredCount.toNumber()).to.equal(featuredBefore.toNumber());
+
+      const contentHash = Array.from(Keypair.generate().publicKey.toBytes());
+      const canonical = await createDataset(program, admin, fixture.registry, alice, { contentHash });
+      const duplicate = await createDataset(program, admin, fixture.registry, contributor, {
+        contentHash,
+        options: { featured: true },
+      });
+      await program.methods
+        .mergeDatasets()
+        .accounts({
+          admin: admin,
+          registry: fixture.registry,
+          canonical: canonical,
+          duplicate: duplicate,
+          duplicateContributor: contributor.publicKey,
+          duplicateReputation: reputation,
+        })
+        .remainingAccounts(await tokenIndexAccounts(fixture.registry, "fixture.csv", program.programId))
+        .rpc();
+      registryAccount = await program.account.registry.fetch(fixture.registry);
+      expect(registryAccount.featuredCount.toNumber()).to.equal(featuredBefore.toNumber());
+    });
+  });
+
+  describe("quality scale", () => {
+    let stars: RegistryFixture;
+
+    before(async () => {
+      stars = await createRegistry(program, admin, "stars-" + Date.now());
+      await program.methods
+        .setQualityScale(5)
+        .accounts({ admin: admin, registry: stars.registry })
+        .rpc();
+    });
+
+    it("Should store star ratings normalized to 0-100", async () => {
+      const top = await createDataset(program, admin, stars.registry, alice, { qualityScore: 5 });
+      const middle = await createDataset(program, admin, stars.registry, alice, { qualityScore: 3 });
+
+      expect((await program.account.dataset.fetch(top)).qualityScore).to.equal(100);
+      expect((await program.account.dataset.fetch(middle)).qualityScore).to.equal(60);
+    });
+
+    it("Should reject a score above the registry's scale", async () => {
+      try {
+        await createDataset(program, admin, stars.registry, alice, { qualityScore: 6 });
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidQualityScore");
+      }
+    });
+
+    it("Should reject an unknown scale", async () => {
+      try {
+        await program.methods
+          .setQualityScale(10)
+          .accounts({ admin: admin, registry: stars.registry })
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidQualityScale");
+      }
+      expect((await program.account.registry.fetch(stars.registry)).qualityScale).to.equal(5);
+    });
+  });
+
+  describe("zero quality score", () => {
+    it("Should reject an implicit zero", async () => {
+      try {
+        await createDataset(program, admin, fixture.registry, alice, { qualityScore: 0 });
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("ZeroQualityScore");
+      }
+    });
+
+    it("Should accept a zero that is explicitly allowed", async () => {
+      const dataset = await createDataset(program, admin, fixture.registry, alice, {
+        qualityScore: 0,
+        options: { allowZeroQuality: true },
+      });
+
+      expect((await program.account.dataset.fetch(dataset)).qualityScore).to.equal(0);
+    });
+  });
+
+  describe("collections", () => {
+    it("Should update the collection root deterministically as members change", async () => {
+      const name = registryName("collection-" + Date.now());
+      const [collection] = await deriveCollectionPDA(fixture.registry, alice.publicKey, name, program.programId);
+      await program.methods
+        .createCollection(name)
+        .accounts({
+          curator: alice.publicKey,
+          registry: fixture.registry,
+          collection: collection,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([alice])
+        .rpc();
+
+      const hashes = [0, 1].map(() => Array.from(Keypair.generate().publicKey.toBytes()));
+      const members = [];
+      for (const contentHash of hashes) {
+        members.push(await createDataset(program, admin, fixture.registry, alice, { contentHash }));
+      }
+      const rootOf = async () => Buffer.from((await program.account.collection.fetch(collection)).collectionRoot);
+      const emptyRoot = await rootOf();
+      expect(emptyRoot).to.deep.equal(collectionRoot([]));
+
+      for (const dataset of members) {
+        await program.methods
+          .addToCollection()
+          .accounts({ curator: alice.publicKey, collection: collection, dataset: dataset })
+          .signers([alice])
+          .rpc();
+      }
+      expect(await rootOf()).to.deep.equal(collectionRoot(hashes));
+
+      await program.methods
+        .removeFromCollection(members[0])
+        .accounts({ curator: alice.publicKey, collection: collection })
+        .signers([alice])
+        .rpc();
+      expect(await rootOf()).to.deep.equal(collectionRoot([hashes[1]]));
+
+      try {
+        await program.methods
+          .addToCollection()
+          .accounts({ curator: bob.publicKey, collection: collection, dataset: members[0] })
+          .signers([bob])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("UnauthorizedCurator");
+      }
+    });
+  });
+
+  describe("data_uri host allow-list", () => {
+    let hosts: RegistryFixture;
+
+    before(async () => {
+      hosts = await createRegistry(program, admin, "hosts-" + Date.now());
+      const hostHash = Array.from(Buffer.from(sha256("gateway.pinata.cloud"), "hex"));
+      await program.methods
+        .allowHost(hostHash)
+        .accounts({ admin: admin, registry: hosts.registry, registryConfig: hosts.registryConfig })
+        .rpc();
+    });
+
+    it("Should accept a data URI on an allowed gateway host", async () => {
+      const dataset = await createDataset(program, admin, hosts.registry, alice, {
+        dataUri: "https://gateway.pinata.cloud/ipfs/bafy-allowed",
+      });
+      expect((await program.account.dataset.fetch(dataset)).isActive).to.equal(true);
+    });
+
+    it("Should reject a data URI on a host missing from the allow-list", async () => {
+      try {
+        await createDataset(program, admin, hosts.registry, alice, {
+          dataUri: "https://drive.google.com/file/d/disallowed/view",
+        });
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("DisallowedHost");
+      }
+    });
+
+    it("Should let native IPFS URIs bypass the host check", async () => {
+      const dataset = await createDataset(program, admin, hosts.registry, alice, {
+        dataUri: "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+      });
+      expect((await program.account.dataset.fetch(dataset)).isActive).to.equal(true);
+    });
+  });
+
+  describe("co-contributors", () => {
+    it("Should store co-contributors set at creation", async () => {
+      const dataset = await createDataset(program, admin, fixture.registry, alice, {
+        options: { coContributors: [bob.publicKey] },
+      });
+
+      const account = await program.account.dataset.fetch(dataset);
+      expect(account.coContributors.map((key) => key.toString())).to.deep.equal([bob.publicKey.toString()]);
+    });
+
+    it("Should reject duplicate co-contributors", async () => {
+      try {
+        await createDataset(program, admin, fixture.registry, alice, {
+          options: { coContributors: [bob.publicKey, bob.publicKey] },
+        });
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("DuplicateCoContributor");
+      }
+    });
+  });
+
+  describe("validation reporting", () => {
+    it("Should report every failed rule when asked to", async () => {
+      try {
+        await createDataset(program, admin, fixture.registry, alice, {
+          qualityScore: 101,
+          columnCount: 150,
+          options: { reportAllErrors: true },
+        });
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("MultipleValidationErrors");
+        const logs: string[] = error.logs ?? [];
+        const report = logs.find((line) => line.includes("Validation failed"));
+        expect(report).to.include("InvalidQualityScore");
+        expect(report).to.include("TooManyColumns");
+      }
+    });
+
+    it("Should keep returning only the first failure by default", async () => {
+      try {
+        await createDataset(program, admin, fixture.registry, alice, { qualityScore: 101, columnCount: 150 });
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidQualityScore");
+      }
+    });
+  });
+
+  describe("research fields", () => {
+    it("Should reject a field listed more than once", async () => {
+      try {
+        await createDataset(program, admin, fixture.registry, alice, { options: { researchFields: [1, 1, 1] } });
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("DuplicateResearchField");
+      }
+    });
+
+    it("Should store distinct fields as given", async () => {
+      const dataset = await createDataset(program, admin, fixture.registry, alice, { options: { researchFields: [1, 4] } });
+
+      expect(Array.from((await program.account.dataset.fetch(dataset)).researchFields)).to.deep.equal([1, 4]);
+    });
+
+    describe("bulk_tag", () => {
+      const bulkTag = (researchFields: number[], datasets: PublicKey[]) =>
+        program.methods
+          .bulkTag(Buffer.from(researchFields))
+          .accounts({ admin: admin, registry: fixture.registry })
+          .remainingAccounts(datasets.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true })))
+          .rpc();
+
+      it("Should tag three datasets with the same field in one call", async () => {
+        const datasets = [
+          await createDataset(program, admin, fixture.registry, alice),
+          await createDataset(program, admin, fixture.registry, bob, { options: { researchFields: [3] } }),
+          await createDataset(program, admin, fixture.registry, alice, { options: { researchFields: [9, 3] } }),
+        ];
+
+        await bulkTag([9], datasets);
+
+        const fields = await Promise.all(
+          datasets.map(async (dataset) => Array.from((await program.account.dataset.fetch(dataset)).researchFields))
+        );
+        expect(fields).to.deep.equal([[9], [3, 9], [9, 3]]);
+      });
+
+      it("Should leave every dataset untouched when one would exceed the field cap", async () => {
+        const roomy = await createDataset(program, admin, fixture.registry, alice);
+        const full = await createDataset(program, admin, fixture.registry, bob, { options: { researchFields: [1, 2, 3, 4] } });
+
+        try {
+          await bulkTag([5], [roomy, full]);
+          expect.fail("Should have thrown an error");
+        } catch (error) {
+          expect(error.toString()).to.include("TooManyResearchFields");
+        }
+        expect(Array.from((await program.account.dataset.fetch(roomy)).researchFields)).to.deep.equal([]);
+      });
+    });
+  });
+
+  describe("category taxonomy", () => {
+    let curated: RegistryFixture;
+    let taxonomy: PublicKey;
+
+    const categoryName = (name: string) => {
+      const padded = Buffer.alloc(32);
+      padded.write(name);
+      return Array.from(padded);
+    };
+
+    before(async () => {
+      curated = await createRegistry(program, admin, "taxonomy-" + Date.now());
+      [taxonomy] = await deriveTaxonomyPDA(curated.registry, program.programId);
+      for (const [code, name] of [[1, "climate"], [2, "public health"]] as const) {
+        await program.methods
+          .addCategory(code, categoryName(name))
+          .accounts({ admin: admin, registry: curated.registry })
+          .rpc();
+      }
+    });
+
+    it("Should accept research fields that are active categories", async () => {
+      const dataset = await createDataset(program, admin, curated.registry, alice, {
+        options: { researchFields: [2, 1] },
+        taxonomy: taxonomy,
+      });
+
+      expect(Array.from((await program.account.dataset.fetch(dataset)).researchFields)).to.deep.equal([2, 1]);
+    });
+
+    it("Should reject a research field outside the taxonomy", async () => {
+      try {
+        await createDataset(program, admin, curated.registry, alice, {
+          options: { researchFields: [1, 7] },
+          taxonomy: taxonomy,
+        });
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("UnknownCategory");
+      }
+    });
+
+    it("Should reject a retired category and research fields without the taxonomy", async () => {
+      await program.methods
+        .retireCategory(2)
+        .accounts({ admin: admin, registry: curated.registry })
+        .rpc();
+
+      for (const overrides of [{ taxonomy: taxonomy, researchFields: [2] }, { taxonomy: undefined, researchFields: [1] }]) {
+        try {
+          await createDataset(program, admin, curated.registry, alice, {
+            options: { researchFields: overrides.researchFields },
+            taxonomy: overrides.taxonomy,
+          });
+          expect.fail("Should have thrown an error");
+        } catch (error) {
+          expect(error.toString()).to.include("UnknownCategory");
+        }
+      }
+    });
+  });
+
+  describe("lock_data_uri", () => {
+    const newUri = (uri: string): Buffer => Buffer.from(uri);
+
+    const updateUri = async (dataset: PublicKey, uri: string) => {
+      await program.methods
+        .updateDataUri(newUri(uri))
+        .accounts({
+          authority: alice.publicKey,
+          registry: fixture.registry,
+          registryConfig: fixture.registryConfig,
+          dataset: dataset,
+        })
+        .signers([alice])
+        .rpc();
+    };
+
+    const lock = async (dataset: PublicKey) => {
+      await program.methods
+        .lockDataUri()
+        .accounts({ contributor: alice.publicKey, dataset: dataset })
+        .signers([alice])
+        .rpc();
+    };
+
+    it("Should reject URI updates once the URI is locked", async () => {
+      const dataset = await createDataset(program, admin, fixture.registry, alice);
+      await updateUri(dataset, "ipfs://bafy-before-lock");
+      await lock(dataset);
+
+      try {
+        await updateUri(dataset, "ipfs://bafy-after-lock");
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("UriLocked");
+      }
+      expect(Buffer.from((await program.account.dataset.fetch(dataset)).dataUri).toString()).to.equal("ipfs://bafy-before-lock");
+    });
+
+    it("Should keep the lock irreversible", async () => {
+      const dataset = await createDataset(program, admin, fixture.registry, alice);
+      await lock(dataset);
+
+      try {
+        await lock(dataset);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("UriLocked");
+      }
+      expect((await program.account.dataset.fetch(dataset)).uriLocked).to.equal(true);
+    });
+  });
+
+  describe("fork_dataset", () => {
+    it("Should link the fork to its source and bump the source's fork count", async () => {
+      const source = await createDataset(program, admin, fixture.registry, alice, { fileName: "survey.csv" });
+      const [aliceReputation] = await deriveReputationPDA(alice.publicKey, program.programId);
+      const scoreBefore = (await program.account.reputation.fetch(aliceReputation)).reputationScore;
+
+      const forked = await forkDataset(program, admin, fixture.registry, source, bob);
+
+      const sourceAccount = await program.account.dataset.fetch(source);
+      const forkAccount = await program.account.dataset.fetch(forked);
+      expect(forkAccount.forkedFrom.toBase58()).to.equal(source.toBase58());
+      expect(forkAccount.contributor.toBase58()).to.equal(bob.publicKey.toBase58());
+      expect(Buffer.from(forkAccount.fileName).toString()).to.equal("survey.csv");
+      expect(forkAccount.contentHash).to.deep.equal(sourceAccount.contentHash);
+      expect(sourceAccount.forkCount).to.equal(1);
+
+      const aliceAfter = await program.account.reputation.fetch(aliceReputation);
+      expect(aliceAfter.totalForks).to.equal(1);
+      expect(aliceAfter.reputationScore).to.be.greaterThan(scoreBefore);
+    });
+
+    it("Should reject forking one's own dataset", async () => {
+      const source = await createDataset(program, admin, fixture.registry, alice);
+
+      try {
+        await forkDataset(program, admin, fixture.registry, source, alice);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("SelfFork");
+      }
+      expect((await program.account.dataset.fetch(source)).forkCount).to.equal(0);
+    });
+  });
+
+  describe("compare_datasets", () => {
+    const compare = async (dataset: PublicKey, other: PublicKey): Promise<number> => {
+      const signature = await program.methods
+        .compareDatasets()
+        .accounts({ dataset: dataset, other: other })
+        .rpc({ commitment: "confirmed" });
+      const transaction = await provider.connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const [encoded] = transaction.meta.returnData.data;
+      return Buffer.from(encoded, "base64")[0];
+    };
+
+    it("Should report only the changed row count between versions", async () => {
+      const contentHash = Array.from(Keypair.generate().publicKey.toBytes());
+      const first = await createDataset(program, admin, fixture.registry, alice, { contentHash, fileName: "census.csv" });
+      const second = await createDataset(program, admin, fixture.registry, alice, {
+        contentHash,
+        fileName: "census.csv",
+        rowCount: 101,
+        parent: first,
+      });
+
+      expect((await program.account.dataset.fetch(second)).parent.toBase58()).to.equal(first.toBase58());
+      expect(await compare(second, first)).to.equal(1 << 1); // DIFF_ROW_COUNT
+      expect(await compare(first, second)).to.equal(1 << 1);
+    });
+
+    it("Should reject a fork, which is not a version", async () => {
+      const source = await createDataset(program, admin, fixture.registry, alice, { fileName: "census.csv" });
+      const forked = await forkDataset(program, admin, fixture.registry, source, bob);
+
+      try {
+        await compare(forked, source);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("NotInLineage");
+      }
+    });
+
+    it("Should reject another contributor's dataset as a parent version", async () => {
+      const first = await createDataset(program, admin, fixture.registry, alice);
+
+      try {
+        await createDataset(program, admin, fixture.registry, bob, { parent: first });
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidParentVersion");
+      }
+    });
+
+    it("Should reject datasets outside one lineage", async () => {
+      const first = await createDataset(program, admin, fixture.registry, alice);
+      const second = await createDataset(program, admin, fixture.registry, bob);
+
+      try {
+        await compare(first, second);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("NotInLineage");
+      }
+    });
+  });
+
+  describe("endorse_dataset", () => {
+    const endorse = async (dataset: PublicKey, endorser: Keypair): Promise<PublicKey> => {
+      const [endorsement] = await deriveEndorsementPDA(dataset, endorser.publicKey, program.programId);
+      await program.methods
+        .endorseDataset()
+        .accounts({
+          endorser: endorser.publicKey,
+          dataset: dataset,
+          endorsement: endorsement,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([endorser])
+        .rpc();
+      return endorsement;
+    };
+
+    it("Should record the endorsement and bump the dataset's count", async () => {
+      const dataset = await createDataset(program, admin, fixture.registry, alice);
+
+      const endorsement = await endorse(dataset, bob);
+
+      const record = await program.account.endorsement.fetch(endorsement);
+      expect(record.endorser.toBase58()).to.equal(bob.publicKey.toBase58());
+      expect(record.dataset.toBase58()).to.equal(dataset.toBase58());
+      const account = await program.account.dataset.fetch(dataset);
+      expect(account.endorsementCount).to.equal(1);
+      expect(account.ratingCount).to.equal(0);
+    });
+
+    it("Should reject a second endorsement from the same account", async () => {
+      const dataset = await createDataset(program, admin, fixture.registry, alice);
+      await endorse(dataset, bob);
+
+      try {
+        await endorse(dataset, bob);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("already in use");
+      }
+      expect((await program.account.dataset.fetch(dataset)).endorsementCount).to.equal(1);
+    });
+
+    it("Should reject endorsing one's own dataset", async () => {
+      const dataset = await createDataset(program, admin, fixture.registry, alice);
+
+      try {
+        await endorse(dataset, alice);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("SelfEndorsement");
+      }
+    });
+  });
+
+  describe("mark_superseded", () => {
+    const supersede = (oldDataset: PublicKey, newDataset: PublicKey, signers: Keypair[]) =>
+      program.methods
+        .markSuperseded()
+        .accounts({
+          newContributor: bob.publicKey,
+          oldContributor: alice.publicKey,
+          registry: fixture.registry,
+          newDataset: newDataset,
+          oldDataset: oldDataset,
+        })
+        .signers(signers)
+        .rpc();
+
+    it("Should link the replacement and retire the old dataset", async () => {
+      const oldDataset = await createDataset(program, admin, fixture.registry, alice);
+      const newDataset = await createDataset(program, admin, fixture.registry, bob);
+
+      await supersede(oldDataset, newDataset, [alice, bob]);
+
+      const replaced = await program.account.dataset.fetch(oldDataset);
+      expect(replaced.isActive).to.equal(false);
+      expect(replaced.supersededBy.toBase58()).to.equal(newDataset.toBase58());
+      expect((await program.account.dataset.fetch(newDataset)).supersedes.toBase58()).to.equal(oldDataset.toBase58());
+    });
+
+    it("Should reject a supersession the old dataset's contributor doesn't sign", async () => {
+      const oldDataset = await createDataset(program, admin, fixture.registry, alice);
+      const newDataset = await createDataset(program, admin, fixture.registry, bob);
+
+      try {
+        await supersede(oldDataset, newDataset, [bob]);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("Signature verification failed");
+      }
+      expect((await program.account.dataset.fetch(oldDataset)).isActive).to.equal(true);
+    });
+  });
+
+  describe("freeze_dataset", () => {
+    const reasonHash = Array.from(Buffer.from(sha256.arrayBuffer("policy-violation")));
+
+    const freeze = async (dataset: PublicKey, contributor: PublicKey): Promise<PublicKey> => {
+      const [penaltyRecord] = await derivePenaltyRecordPDA(dataset, program.programId);
+      const [reputation] = await deriveReputationPDA(contributor, program.programId);
+
+      await program.methods
+        .freezeDataset(reasonHash)
+        .accounts({
+          admin: admin,
+          registry: fixture.registry,
+          dataset: dataset,
+          penaltyRecord: penaltyRecord,
+          reputation: reputation,
+          treasury: fixture.treasury,
+        })
+        .rpc();
+
+      return penaltyRecord;
+    };
+
+    it("Should deduct the configured penalty and record it", async () => {
+      const carol = await fundedKeypair(provider);
+      const carolReputation = await createReputation(program, admin, carol);
+      const source = await createDataset(program, admin, fixture.registry, carol);
+      // A fork gives carol a small score to penalize
+      await forkDataset(program, admin, fixture.registry, source, bob);
+      const scoreBefore = (await program.account.reputation.fetch(carolReputation)).reputationScore;
+
+      await program.methods
+        .setFreezePenalty(3)
+        .accounts({ admin: admin, registry: fixture.registry })
+        .rpc();
+      const penaltyRecord = await freeze(source, carol.publicKey);
+
+      const reputation = await program.account.reputation.fetch(carolReputation);
+      expect(reputation.reputationScore).to.equal(scoreBefore - 3);
+      expect(reputation.totalPenalties).to.equal(3);
+
+      const record = await program.account.penaltyRecord.fetch(penaltyRecord);
+      expect(record.dataset.toBase58()).to.equal(source.toBase58());
+      expect(record.contributor.toBase58()).to.equal(carol.publicKey.toBase58());
+      expect(record.amount).to.equal(3);
+      expect(record.reasonHash).to.deep.equal(reasonHash);
+
+      const dataset = await program.account.dataset.fetch(source);
+      expect(dataset.frozen).to.equal(true);
+      expect(dataset.isActive).to.equal(false);
+    });
+
+    it("Should block updates to a finalized dataset but still allow a freeze", async () => {
+      const erin = await fundedKeypair(provider);
+      await createReputation(program, admin, erin);
+      const dataset = await createDataset(program, admin, fixture.registry, erin);
+
+      await program.methods
+        .finalizeDataset()
+        .accounts({ contributor: erin.publicKey, dataset: dataset })
+        .signers([erin])
+        .rpc();
+      expect((await program.account.dataset.fetch(dataset)).finalized).to.equal(true);
+
+      for (const update of [
+        () =>
+          program.methods
+            .updateDataUri(Buffer.from("ipfs://QmFinalizedElsewhere"))
+            .accounts({
+              authority: erin.publicKey,
+              registry: fixture.registry,
+              registryConfig: fixture.registryConfig,
+              dataset: dataset,
+            })
+            .signers([erin])
+            .rpc(),
+        () =>
+          program.methods
+            .addEditor(bob.publicKey)
+            .accounts({ contributor: erin.publicKey, dataset: dataset })
+            .signers([erin])
+            .rpc(),
+        () =>
+          program.methods
+            .finalizeDataset()
+            .accounts({ contributor: erin.publicKey, dataset: dataset })
+            .signers([erin])
+            .rpc(),
+      ]) {
+        try {
+          await update();
+          expect.fail("Should have thrown an error");
+        } catch (error) {
+          expect(error.toString()).to.include("DatasetFinalized");
+        }
+      }
+
+      await freeze(dataset, erin.publicKey);
+      expect((await program.account.dataset.fetch(dataset)).frozen).to.equal(true);
+    });
+
+    it("Should floor the score at zero", async () => {
+      const dave = await fundedKeypair(provider);
+      const daveReputation = await createReputation(program, admin, dave);
+      const dataset = await createDataset(program, admin, fixture.registry, dave);
+
+      await program.methods
+        .setFreezePenalty(1_000)
+        .accounts({ admin: admin, registry: fixture.registry })
+        .rpc();
+      await freeze(dataset, dave.publicKey);
+
+      expect((await program.account.reputation.fetch(daveReputation)).reputationScore).to.equal(0);
+    });
+  });
+
+  describe("quality bond", () => {
+    const bond = 100_000_000;
+    let bonded: RegistryFixture;
+
+    before(async () => {
+      bonded = await createRegistry(program, admin, "bonded-" + Date.now());
+      await program.methods
+        .setQualityBond(new anchor.BN(bond))
+        .accounts({ admin: admin, registry: bonded.registry })
+        .rpc();
+    });
+
+    const rentOf = async (account: PublicKey) =>
+      provider.connection.getMinimumBalanceForRentExemption((await provider.connection.getAccountInfo(account)).data.length);
+
+    it("Should refund the bond when a clean dataset is closed", async () => {
+      const dataset = await createDataset(program, admin, bonded.registry, alice);
+      expect((await program.account.dataset.fetch(dataset)).bond.toNumber()).to.equal(bond);
+      expect(await provider.connection.getBalance(dataset)).to.equal((await rentOf(dataset)) + bond);
+
+      const held = await provider.connection.getBalance(dataset);
+      const before = await provider.connection.getBalance(alice.publicKey);
+      await program.methods
+        .closeDataset()
+        .accounts({
+          contributor: alice.publicKey,
+          registry: bonded.registry,
+          dataset: dataset,
+          reputation: (await deriveReputationPDA(alice.publicKey, program.programId))[0],
+        })
+        .remainingAccounts(await tokenIndexAccounts(bonded.registry, "fixture.csv", program.programId))
+        .signers([alice])
+        .rpc();
+
+      expect(await provider.connection.getBalance(alice.publicKey)).to.equal(before + held);
+    });
+
+    it("Should forfeit the bond to the treasury when the dataset is frozen", async () => {
+      const dataset = await createDataset(program, admin, bonded.registry, bob);
+      const rent = await rentOf(dataset);
+      const treasuryBefore = await provider.connection.getBalance(bonded.treasury);
+      const [penaltyRecord] = await derivePenaltyRecordPDA(dataset, program.programId);
+
+      await program.methods
+        .freezeDataset(Array.from(Buffer.from(sha256.arrayBuffer("fabricated-data"))))
+        .accounts({
+          admin: admin,
+          registry: bonded.registry,
+          dataset: dataset,
+          penaltyRecord: penaltyRecord,
+          reputation: (await deriveReputationPDA(bob.publicKey, program.programId))[0],
+          treasury: bonded.treasury,
+        })
+        .rpc();
+
+      expect(await provider.connection.getBalance(bonded.treasury)).to.equal(treasuryBefore + bond);
+      expect(await provider.connection.getBalance(dataset)).to.equal(rent);
+      expect((await program.account.dataset.fetch(dataset)).bond.toNumber()).to.equal(0);
+    });
+  });
+
+  describe("report_unavailable", () => {
+    const report = async (dataset: PublicKey, reporter: Keypair) => {
+      const [availabilityReport] = await deriveAvailabilityReportPDA(dataset, program.programId);
+      await program.methods
+        .reportUnavailable()
+        .accounts({
+          reporter: reporter.publicKey,
+          registry: fixture.registry,
+          dataset: dataset,
+          availabilityReport: availabilityReport,
+        })
+        .signers([reporter])
+        .rpc();
+    };
+
+    before(async () => {
+      await program.methods
+        .setUnavailableReportThreshold(2)
+        .accounts({ admin: admin, registry: fixture.registry })
+        .rpc();
+    });
+
+    it("Should deactivate the dataset once enough distinct reporters agree", async () => {
+      const dataset = await createDataset(program, admin, fixture.registry, alice);
+
+      await report(dataset, bob);
+      expect((await program.account.dataset.fetch(dataset)).isActive).to.equal(true);
+
+      try {
+        await report(dataset, bob);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("DuplicateUnavailableReport");
+      }
+
+      await report(dataset, await fundedKeypair(provider));
+      const account = await program.account.dataset.fetch(dataset);
+      expect(account.unavailable).to.equal(true);
+      expect(account.isActive).to.equal(false);
+    });
+
+    it("Should clear the flag when the contributor moves the data URI", async () => {
+      const dataset = await createDataset(program, admin, fixture.registry, alice);
+      await report(dataset, bob);
+      await report(dataset, await fundedKeypair(provider));
+
+      const dataUri = Buffer.from("ipfs://bafy-repinned");
+      await program.methods
+        .updateDataUri(dataUri)
+        .accounts({
+          authority: alice.publicKey,
+          registry: fixture.registry,
+          registryConfig: fixture.registryConfig,
+          dataset: dataset,
+        })
+        .signers([alice])
+        .rpc();
+
+      const account = await program.account.dataset.fetch(dataset);
+      expect(account.unavailable).to.equal(false);
+      expect(account.isActive).to.equal(true);
+
+      // Reports against the old URI no longer count
+      await report(dataset, bob);
+      expect((await program.account.dataset.fetch(dataset)).isActive).to.equal(true);
+    });
+  });
+
+  describe("file_name validation", () => {
+    it("Should reject a file name containing a control byte", async () => {
+      try {
+        await createDataset(program, admin, fixture.registry, alice, { fileName: "data\u0000.csv" });
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidFileName");
+      }
+    });
+
+    it("Should accept a clean name and trim trailing whitespace", async () => {
+      const dataset = await createDataset(program, admin, fixture.registry, alice, { fileName: "clinic_visits.csv  " });
+
+      const { fileName } = await program.account.dataset.fetch(dataset);
+      expect(Buffer.from(fileName).toString()).to.equal("clinic_visits.csv");
+    });
+  });
+
+  describe("rating-derived quality", () => {
+    // Passing the rater's reputation weights the rating by tier; without one it counts once
+    const rate = async (dataset: PublicKey, rater: Keypair, rating: number, raterReputation: PublicKey | null = null) => {
+      const [ratingAccount] = await deriveRatingPDA(dataset, rater.publicKey, program.programId);
+      await program.methods
+        .rateDataset(rating)
+        .accounts({ rater: rater.publicKey, dataset: dataset, rating: ratingAccount, raterReputation: raterReputation })
+        .signers([rater])
+        .rpc();
+    };
+
+    // The registry floor can only be raised through the two-day config timelock, so hiding
+    // below the floor is covered by the Rust unit tests
+    it("Should replace the self-reported quality once enough ratings are in", async () => {
+      const dataset = await createDataset(program, admin, fixture.registry, alice, { qualityScore: 90 });
+      const raters = [bob, await fundedKeypair(provider), await fundedKeypair(provider)];
+      for (const rater of raters) {
+        await rate(dataset, rater, 2);
+      }
+      // Revising a rating replaces it rather than counting twice
+      await rate(dataset, bob, 1);
+
+      await program.methods
+        .setQualityFromRatings()
+        .accounts({ caller: admin, registry: fixture.registry, dataset: dataset })
+        .rpc();
+
+      const account = await program.account.dataset.fetch(dataset);
+      expect(account.ratingCount).to.equal(3);
+      expect(account.ratingWeight.toNumber()).to.equal(3);
+      expect(account.qualityScore).to.equal(33);
+      expect(account.isActive).to.equal(true);
+      // 90 self-reported against 33 from the community is beyond the default 25-point delta
+      expect(account.qualityDisputed).to.equal(true);
+    });
+
+    it("Should not flag a self-report close to the community's quality", async () => {
+      const dataset = await createDataset(program, admin, fixture.registry, alice, { qualityScore: 50 });
+      for (const rater of [bob, await fundedKeypair(provider), await fundedKeypair(provider)]) {
+        await rate(dataset, rater, 2);
+      }
+
+      await program.methods
+        .setQualityFromRatings()
+        .accounts({ caller: admin, registry: fixture.registry, dataset: dataset })
+        .rpc();
+
+      const account = await program.account.dataset.fetch(dataset);
+      expect(account.qualityScore).to.equal(40);
+      expect(account.qualityDisputed).to.equal(false);
+    });
+
+    it("Should weight a newcomer's rating the same with or without their reputation", async () => {
+      const dataset = await createDataset(program, admin, fixture.registry, alice);
+      const [bobReputation] = await deriveReputationPDA(bob.publicKey, program.programId);
+      expect((await program.account.reputation.fetch(bobReputation)).reputationScore).to.be.lessThan(100);
+
+      await rate(dataset, bob, 4, bobReputation);
+
+      const account = await program.account.dataset.fetch(dataset);
+      expect(account.ratingSum.toNumber()).to.equal(4);
+      expect(account.ratingWeight.toNumber()).to.equal(1);
+      const [ratingAccount] = await deriveRatingPDA(dataset, bob.publicKey, program.programId);
+      expect((await program.account.rating.fetch(ratingAccount)).weight).to.equal(1);
+    });
+
+    it("Should flag the rating summary provisional until the registry minimum is met", async () => {
+      const summarize = async (dataset: PublicKey) => {
+        const signature = await program.methods
+          .viewRatingSummary()
+          .accounts({ registry: fixture.registry, dataset: dataset })
+          .rpc({ commitment: "confirmed" });
+        const transaction = await provider.connection.getTransaction(signature, {
+          commitment: "confirmed",
+          maxSupportedTransactionVersion: 0,
+        });
+        const [encoded] = transaction.meta.returnData.data;
+        return Buffer.from(encoded, "base64");
+      };
+      await program.methods
+        .setMinRatingCount(2)
+        .accounts({ admin: admin, registry: fixture.registry })
+        .rpc();
+      const dataset = await createDataset(program, admin, fixture.registry, alice);
+
+      await rate(dataset, bob, 5);
+      // version, None, rating_count = 1, provisional
+      const provisional = await summarize(dataset);
+      expect(provisional[1]).to.equal(0);
+      expect(provisional.readUInt32LE(2)).to.equal(1);
+      expect(provisional[6]).to.equal(1);
+
+      await rate(dataset, await fundedKeypair(provider), 4);
+      // version, Some(450), rating_count = 2, not provisional
+      const meaningful = await summarize(dataset);
+      expect(meaningful[1]).to.equal(1);
+      expect(meaningful.readUInt16LE(2)).to.equal(450);
+      expect(meaningful.readUInt32LE(4)).to.equal(2);
+      expect(meaningful[8]).to.equal(0);
+    });
+
+    it("Should reject a contributor rating their own dataset", async () => {
+      const dataset = await createDataset(program, admin, fixture.registry, alice);
+
+      try {
+        await rate(dataset, alice, 5);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("SelfRating");
+      }
+    });
+  });
+
+  describe("recent datasets", () => {
+    const RECENT_DATASETS_LEN = 16;
+
+    it("Should keep the most recent datasets in order once the ring wraps", async () => {
+      const registry = await createRegistry(program, admin, "recent-" + Date.now());
+      const created: PublicKey[] = [];
+      for (let i = 0; i <= RECENT_DATASETS_LEN; i++) {
+        created.push(await createDataset(program, admin, registry.registry, alice));
+      }
+
+      const [address] = await deriveRecentDatasetsPDA(registry.registry, program.programId);
+      const recent = await program.account.recentDatasets.fetch(address);
+      expect(recent.len).to.equal(RECENT_DATASETS_LEN);
+      // Newest first, walking back from the slot before head
+      const latest = Array.from({ length: recent.len }, (_, age) =>
+        recent.datasets[(recent.head + RECENT_DATASETS_LEN - 1 - age) % RECENT_DATASETS_LEN].toBase58()
+      );
+      expect(latest).to.deep.equal(created.slice(1).reverse().map((key) => key.toBase58()));
+    });
+  });
+
+  describe("structured ai_metadata", () => {
+    // Borsh layout of AiMetadata: model_id, confidence, then a u32 count of 16-byte tags
+    const encodeAiMetadata = (confidence: number, tags: string[]): Buffer => {
+      const count = Buffer.alloc(4);
+      count.writeUInt32LE(tags.length);
+      const tagBytes = tags.map((tag) => Buffer.concat([Buffer.from(tag), Buffer.alloc(16 - tag.length)]));
+      return Buffer.concat([Buffer.alloc(32, 7), Buffer.from([confidence]), count, ...tagBytes]);
+    };
+
+    let structured: RegistryFixture;
+
+    before(async () => {
+      structured = await createRegistry(program, admin, "structured-" + Date.now());
+      await program.methods
+        .setStructuredAiMetadata(true)
+        .accounts({ admin: admin, registry: structured.registry })
+        .rpc();
+    });
+
+    it("Should accept well-formed metadata", async () => {
+      const aiMetadata = encodeAiMetadata(92, ["health", "survey"]);
+      const dataset = await createDataset(program, admin, structured.registry, alice, { aiMetadata });
+
+      expect(Buffer.from((await program.account.dataset.fetch(dataset)).aiMetadata)).to.deep.equal(aiMetadata);
+    });
+
+    it("Should reject a malformed metadata blob", async () => {
+      for (const aiMetadata of [Buffer.from('{"source": "fixture"}'), encodeAiMetadata(101, [])]) {
+        try {
+          await createDataset(program, admin, structured.registry, alice, { aiMetadata });
+          expect.fail("Should have thrown an error");
+        } catch (error) {
+          expect(error.toString()).to.include("MalformedAiMetadata");
+        }
+      }
+    });
+
+    it("Should flag a declared language the metadata's detected language contradicts", async () => {
+      const swahili = Array.from(Buffer.from("sw"));
+      const detectedAs = async (code: string) => {
+        const aiMetadata = Buffer.concat([encodeAiMetadata(92, ["health"]), Buffer.from(code)]);
+        const dataset = await createDataset(program, admin, structured.registry, alice, {
+          aiMetadata,
+          options: { language: swahili },
+        });
+        return program.account.dataset.fetch(dataset);
+      };
+
+      const mismatched = await detectedAs("fr");
+      expect(mismatched.languageMismatch).to.equal(true);
+      expect(mismatched.isActive).to.equal(true);
+
+      const matching = await detectedAs("SW");
+      expect(matching.languageMismatch).to.equal(false);
+      expect(matching.language).to.deep.equal(swahili);
+    });
+  });
+
+  describe("expand_metadata", () => {
+    // MAX_AI_METADATA_LEN bytes are covered by the initial allocation; MAX_AI_METADATA_GROWTH per call
+    const MAX_AI_METADATA_LEN = 1500;
+    const MAX_AI_METADATA_GROWTH = 512;
+
+    const expand = async (dataset: PublicKey, baseSpace: number, extra: Buffer) => {
+      const info = await provider.connection.getAccountInfo(dataset);
+      const { aiMetadata } = await program.account.dataset.fetch(dataset);
+      const overflow = Math.max(0, aiMetadata.length + extra.length - MAX_AI_METADATA_LEN);
+      const space = Math.max(info.data.length, baseSpace + overflow);
+      const rent = await provider.connection.getMinimumBalanceForRentExemption(space);
+      const deposit = Math.max(0, rent - info.lamports);
+      await program.methods
+        .expandMetadata(extra, new anchor.BN(deposit))
+        .accounts({ contributor: alice.publicKey, registry: fixture.registry, dataset: dataset })
+        .signers([alice])
+        .rpc();
+      return deposit;
+    };
+
+    it("Should grow ai_metadata past the initial allocation when the exact rent is prepaid", async () => {
+      const dataset = await createDataset(program, admin, fixture.registry, alice);
+      const baseSpace = (await provider.connection.getAccountInfo(dataset)).data.length;
+      const initial = (await program.account.dataset.fetch(dataset)).aiMetadata.length;
+
+      let deposits = 0;
+      for (let i = 0; i < 4; i++) {
+        deposits += await expand(dataset, baseSpace, Buffer.alloc(MAX_AI_METADATA_GROWTH, i));
+      }
+
+      const info = await provider.connection.getAccountInfo(dataset);
+      const expanded = await program.account.dataset.fetch(dataset);
+      expect(expanded.aiMetadata.length).to.equal(initial + 4 * MAX_AI_METADATA_GROWTH);
+      expect(info.data.length).to.equal(baseSpace + expanded.aiMetadata.length - MAX_AI_METADATA_LEN);
+      expect(deposits).to.be.greaterThan(0);
+      expect(info.lamports).to.equal(await provider.connection.getMinimumBalanceForRentExemption(info.data.length));
+    });
+
+    it("Should reject growth beyond the per-call cap", async () => {
+      const dataset = await createDataset(program, admin, fixture.registry, alice);
+      const baseSpace = (await provider.connection.getAccountInfo(dataset)).data.length;
+
+      try {
+        await expand(dataset, baseSpace, Buffer.alloc(MAX_AI_METADATA_GROWTH + 1));
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("GrowthExceeded");
+      }
+    });
+
+    it("Should reject a deposit that overpays the incremental rent", async () => {
+      const dataset = await createDataset(program, admin, fixture.registry, alice);
+
+      try {
+        await program.methods
+          .expandMetadata(Buffer.alloc(8), new anchor.BN(1))
+          .accounts({ contributor: alice.publicKey, registry: fixture.registry, dataset: dataset })
+          .signers([alice])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("IncorrectRentDeposit");
+      }
+    });
+  });
+
+  describe("data requests", () => {
+    it("Should escrow a bounty and pay it to the contributor of the fulfilling dataset", async () => {
+      const bounty = 50_000_000;
+      const topic = Buffer.from("Maize yields by district, " + Date.now());
+      const topicHash = Array.from(Buffer.from(sha256.arrayBuffer(topic)));
+      const [request] = await deriveDataRequestPDA(bob.publicKey, topicHash, program.programId);
+
+      await program.methods
+        .requestDataset(topicHash, topic, Array.from(Buffer.alloc(32, 3)), new anchor.BN(bounty))
+        .accounts({ requester: bob.publicKey, registry: fixture.registry, dataRequest: request })
+        .signers([bob])
+        .rpc();
+      const rent = await provider.connection.getMinimumBalanceForRentExemption(
+        (await provider.connection.getAccountInfo(request)).data.length
+      );
+      expect(await provider.connection.getBalance(request)).to.equal(rent + bounty);
+
+      const dataset = await createDataset(program, admin, fixture.registry, alice);
+      const contributorBefore = await provider.connection.getBalance(alice.publicKey);
+      await program.methods
+        .fulfillRequest()
+        .accounts({ requester: bob.publicKey, dataRequest: request, dataset: dataset, contributor: alice.publicKey })
+        .signers([bob])
+        .rpc();
+
+      expect(await provider.connection.getBalance(alice.publicKey)).to.equal(contributorBefore + bounty);
+      expect(await provider.connection.getBalance(request)).to.equal(rent);
+      const fulfilled = await program.account.dataRequest.fetch(request);
+      expect(fulfilled.fulfilledBy.toBase58()).to.equal(dataset.toBase58());
+      expect(Buffer.from(fulfilled.topic).toString()).to.equal(topic.toString());
+    });
+  });
+
+  describe("token index", () => {
+    it("Should list two datasets sharing a file name word under that token", async () => {
+      const first = await createDataset(program, admin, fixture.registry, alice, { fileName: "Rainfall_Kenya_2021.csv" });
+      const second = await createDataset(program, admin, fixture.registry, bob, { fileName: "rainfall-nigeria.csv" });
+
+      const [index] = await deriveTokenIndexPDA(fixture.registry, tokenize("rainfall")[0], program.programId);
+      const account = await program.account.tokenIndex.fetch(index);
+      const keys = account.datasets.map((key) => key.toString());
+      expect(keys).to.include(first.toString());
+      expect(keys).to.include(second.toString());
+    });
+
+    it("Should drop a closed dataset from its token indexes", async () => {
+      const fileName = "cholera_cases.csv";
+      const dataset = await createDataset(program, admin, fixture.registry, alice, { fileName });
+
+      await program.methods
+        .closeDataset()
+        .accounts({
+          contributor: alice.publicKey,
+          registry: fixture.registry,
+          dataset: dataset,
+          reputation: (await deriveReputationPDA(alice.publicKey, program.programId))[0],
+        })
+        .remainingAccounts(await tokenIndexAccounts(fixture.registry, fileName, program.programId))
+        .signers([alice])
+        .rpc();
+
+      const [index] = await deriveTokenIndexPDA(fixture.registry, tokenize("cholera")[0], program.programId);
+      const account = await program.account.tokenIndex.fetch(index);
+      expect(account.datasets.map((key) => key.toString())).to.not.include(dataset.toString());
+    });
+  });
+
+  describe("title", () => {
+    it("Should store a title distinct from the file name and index the title", async () => {
+      const title = Buffer.from("Malaria incidence, Lagos");
+      const dataset = await createDataset(program, admin, fixture.registry, alice, {
+        fileName: "export_20240101_a9f3.csv",
+        options: { title },
+      });
+
+      const account = await program.account.dataset.fetch(dataset);
+      expect(Buffer.from(account.title).toString()).to.equal(title.toString());
+      expect(Buffer.from(account.fileName).toString()).to.equal("export_20240101_a9f3.csv");
+
+      const [titleIndex] = await deriveTokenIndexPDA(fixture.registry, tokenize("malaria")[0], program.programId);
+      const indexed = await program.account.tokenIndex.fetch(titleIndex);
+      expect(indexed.datasets.map((key) => key.toString())).to.include(dataset.toString());
+      const [fileIndex] = await deriveTokenIndexPDA(fixture.registry, tokenize("a9f3")[0], program.programId);
+      expect(await provider.connection.getAccountInfo(fileIndex)).to.equal(null);
+    });
+
+    it("Should default the title to the file name", async () => {
+      const dataset = await createDataset(program, admin, fixture.registry, alice, { fileName: "birth_weights.csv" });
+      const account = await program.account.dataset.fetch(dataset);
+      expect(Buffer.from(account.title).toString()).to.equal("birth_weights.csv");
+    });
+
+    it("Should move the dataset between token indexes when the title changes", async () => {
+      const oldTitle = "Rainfall Accra";
+      const newTitle = "Precipitation Accra";
+      const dataset = await createDataset(program, admin, fixture.registry, alice, {
+        options: { title: Buffer.from(oldTitle) },
+      });
+
+      await program.methods
+        .updateTitle(Buffer.from(newTitle))
+        .accounts({
+          authority: alice.publicKey,
+          registry: fixture.registry,
+          dataset: dataset,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts([
+          ...(await tokenIndexAccounts(fixture.registry, oldTitle, program.programId)),
+          ...(await tokenIndexAccounts(fixture.registry, newTitle, program.programId)),
+        ])
+        .signers([alice])
+        .rpc();
+
+      const account = await program.account.dataset.fetch(dataset);
+      expect(Buffer.from(account.title).toString()).to.equal(newTitle);
+      const listed = async (word: string) => {
+        const [index] = await deriveTokenIndexPDA(fixture.registry, tokenize(word)[0], program.programId);
+        const { datasets } = await program.account.tokenIndex.fetch(index);
+        return datasets.map((key) => key.toString()).includes(dataset.toString());
+      };
+      expect(await listed("rainfall")).to.equal(false);
+      expect(await listed("precipitation")).to.equal(true);
+      expect(await listed("accra")).to.equal(true);
+    });
+  });
+
+  describe("close_dataset", () => {
+    it("Should leave upload totals and score where they were before the dataset", async () => {
+      const contributor = await fundedKeypair(provider);
+      await createReputation(program, admin, contributor);
+      const [reputation] = await deriveReputationPDA(contributor.publicKey, program.programId);
+
+      const fileName = "closing.csv";
+      const dataset = await createDataset(program, admin, fixture.registry, contributor, { fileName });
+      await program.methods
+        .closeDataset()
+        .accounts({ contributor: contributor.publicKey, registry: fixture.registry, dataset: dataset, reputation })
+        .remainingAccounts(await tokenIndexAccounts(fixture.registry, fileName, program.programId))
+        .signers([contributor])
+        .rpc();
+
+      const account = await program.account.reputation.fetch(reputation);
+      expect(account.totalUploads).to.equal(0);
+      expect(account.totalQualityScore.toNumber()).to.equal(0);
+      expect(account.reputationScore).to.equal(0);
+      expect(account.datasetCount).to.equal(1); // The PDA index still advances
+      expect(await provider.connection.getAccountInfo(dataset)).to.equal(null);
+
+      try {
+        await program.methods
+          .finalizeReputation()
+          .accounts({
+            payer: contributor.publicKey,
+            registry: fixture.registry,
+            registryConfig: fixture.registryConfig,
+            dataset: dataset,
+            reputation: reputation,
+          })
+          .signers([contributor])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("AccountNotInitialized");
+      }
+      expect((await program.account.reputation.fetch(reputation)).totalUploads).to.equal(0);
+    });
+  });
+
+  describe("editors", () => {
+    const newUri = (uri: string): Buffer => Buffer.from(uri);
+
+    const updateUri = async (dataset: PublicKey, authority: Keypair, uri: string) => {
+      await program.methods
+        .updateDataUri(newUri(uri))
+        .accounts({
+          authority: authority.publicKey,
+          registry: fixture.registry,
+          registryConfig: fixture.registryConfig,
+          dataset: dataset,
+        })
+        .signers([authority])
+        .rpc();
+    };
+
+    const addEditor = async (dataset: PublicKey, editor: PublicKey) => {
+      await program.methods
+        .addEditor(editor)
+        .accounts({ contributor: alice.publicKey, dataset: dataset })
+        .signers([alice])
+        .rpc();
+    };
+
+    it("Should let an editor update the dataset until removed", async () => {
+      const dataset = await createDataset(program, admin, fixture.registry, alice);
+      await addEditor(dataset, bob.publicKey);
+
+      await updateUri(dataset, bob, "ipfs://edited-by-bob");
+      const account = await program.account.dataset.fetch(dataset);
+      expect(Buffer.from(account.dataUri).toString()).to.equal("ipfs://edited-by-bob");
+
+      await program.methods
+        .removeEditor(bob.publicKey)
+        .accounts({ contributor: alice.publicKey, dataset: dataset })
+        .signers([alice])
+        .rpc();
+
+      try {
+        await updateUri(dataset, bob, "ipfs://after-removal");
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("UnauthorizedUpdate");
+      }
+    });
+
+    it("Should cap the number of editors", async () => {
+      const dataset = await createDataset(program, admin, fixture.registry, alice);
+      for (let i = 0; i < 5; i++) {
+        await addEditor(dataset, Keypair.generate().publicKey);
+      }
+
+      try {
+        await addEditor(dataset, Keypair.generate().publicKey);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("TooManyEditors");
+      }
+    });
+  });
+
+  describe("create_dataset return data", () => {
+    it("Should return the new dataset's address and index", async () => {
+      const contributor = await fundedKeypair(provider);
+      await createReputation(program, admin, contributor);
+      const [reputation] = await deriveReputationPDA(contributor.publicKey, program.programId);
+      const [dataset] = await deriveDatasetPDA(contributor.publicKey, 0, program.programId);
+
+      const fileName = "returned.csv";
+      const signature = await program.methods
+        .createDataset(
+          Array.from(Keypair.generate().publicKey.toBytes()),
+          Buffer.from('{"source": "return-data"}'),
+          Buffer.from(fileName),
+          new anchor.BN(4096),
+          Buffer.from("ipfs://bafy-return-data"),
+          new anchor.BN(5),
+          new anchor.BN(100),
+          80,
+          Array.from(Buffer.alloc(32)),
+          DEFAULT_OPTIONS
+        )
+        .accounts({
+          admin: admin,
+          contributor: contributor.publicKey,
+          dataset: dataset,
+          registry: fixture.registry,
+          registryConfig: fixture.registryConfig,
+          reputation: reputation,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(await tokenIndexAccounts(fixture.registry, fileName, program.programId))
+        .signers([contributor])
+        .rpc({ commitment: "confirmed" });
+
+      const transaction = await provider.connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const [encoded] = transaction.meta.returnData.data;
+      const returned = Buffer.from(encoded, "base64");
+      expect(returned.length).to.equal(37);
+      expect(returned[0]).to.equal(1);
+      expect(new PublicKey(returned.subarray(1, 33)).toString()).to.equal(dataset.toString());
+      expect(returned.readUInt32LE(33)).to.equal(0);
+    });
+  });
+
+  describe("resolve_by_hash", () => {
+    const resolve = async (contentHash: number[]) => {
+      const [hashRegistry] = await deriveHashRegistryPDA(contentHash, program.programId);
+      // A claimed hash resolves only alongside the dataset it names
+      const entry = await program.account.hashRegistry.fetchNullable(hashRegistry);
+      const claimed = entry !== null && !entry.dataset.equals(PublicKey.default);
+      const signature = await program.methods
+        .resolveByHash(contentHash)
+        .accounts({ hashRegistry: hashRegistry, dataset: claimed ? entry.dataset : null })
+        .rpc({ commitment: "confirmed" });
+      const transaction = await provider.connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const [encoded] = transaction.meta.returnData.data;
+      const returned = Buffer.from(encoded, "base64");
+      // version, found flag, dataset key, contributor key
+      return {
+        version: returned[0],
+        found: returned[1] === 1,
+        dataset: new PublicKey(returned.subarray(2, 34)),
+        contributor: new PublicKey(returned.subarray(34, 66)),
+      };
+    };
+
+    it("Should resolve a registered hash to its first dataset", async () => {
+      const contentHash = Array.from(Keypair.generate().publicKey.toBytes());
+      const first = await createDataset(program, admin, fixture.registry, alice, { contentHash });
+      await createDataset(program, admin, fixture.registry, bob, { contentHash });
+
+      const resolution = await resolve(contentHash);
+      expect(resolution.version).to.equal(1);
+      expect(resolution.found).to.equal(true);
+      expect(resolution.dataset.toBase58()).to.equal(first.toBase58());
+      expect(resolution.contributor.toBase58()).to.equal(alice.publicKey.toBase58());
+    });
+
+    it("Should report a hash as not found once its dataset is closed, and let it be claimed again", async () => {
+      const contentHash = Array.from(Keypair.generate().publicKey.toBytes());
+      const fileName = "resolved-then-closed.csv";
+      const first = await createDataset(program, admin, fixture.registry, alice, { contentHash, fileName });
+      expect((await resolve(contentHash)).dataset.toBase58()).to.equal(first.toBase58());
+
+      const [reputation] = await deriveReputationPDA(alice.publicKey, program.programId);
+      const [hashRegistry] = await deriveHashRegistryPDA(contentHash, program.programId);
+      await program.methods
+        .closeDataset()
+        .accounts({ contributor: alice.publicKey, registry: fixture.registry, dataset: first, reputation, hashRegistry })
+        .remainingAccounts(await tokenIndexAccounts(fixture.registry, fileName, program.programId))
+        .signers([alice])
+        .rpc();
+
+      const closed = await resolve(contentHash);
+      expect(closed.found).to.equal(false);
+      expect(closed.dataset.toBase58()).to.equal(PublicKey.default.toBase58());
+
+      const next = await createDataset(program, admin, fixture.registry, bob, { contentHash });
+      expect((await resolve(contentHash)).dataset.toBase58()).to.equal(next.toBase58());
+    });
+
+    it("Should report an unknown hash as not found", async () => {
+      const resolution = await resolve(Array.from(Keypair.generate().publicKey.toBytes()));
+
+      expect(resolution.found).to.equal(false);
+      expect(resolution.dataset.toBase58()).to.equal(PublicKey.default.toBase58());
+    });
+  });
+
+  describe("create_dataset signers", () => {
+    it("Should require only the registry admin and the contributor to sign", async () => {
+      const ix = program.idl.instructions.find((instruction) => instruction.name === "createDataset");
+      const signers = ix.accounts.filter((account) => "signer" in account && account.signer).map((account) => account.name);
+      expect(signers).to.deep.equal(["admin", "contributor"]);
+
+      // The provider wallet signs as admin and fee payer; the contributor's is the only extra signature
+      const contributor = await fundedKeypair(provider);
+      await createReputation(program, admin, contributor);
+      const dataset = await createDataset(program, admin, fixture.registry, contributor);
+      expect((await program.account.dataset.fetch(dataset)).contributor.toBase58()).to.equal(contributor.publicKey.toBase58());
+    });
+
+    it("Should reject a dataset the contributor doesn't sign for", async () => {
+      const contributor = await fundedKeypair(provider);
+      await createReputation(program, admin, contributor);
+      const [reputation] = await deriveReputationPDA(contributor.publicKey, program.programId);
+      const [dataset] = await deriveDatasetPDA(contributor.publicKey, 0, program.programId);
+      const fileName = "unsigned.csv";
+
+      try {
+        await program.methods
+          .createDataset(
+            Array.from(Keypair.generate().publicKey.toBytes()),
+            Buffer.from('{"source": "unsigned"}'),
+            Buffer.from(fileName),
+            new anchor.BN(4096),
+            Buffer.from("ipfs://bafy-unsigned"),
+            new anchor.BN(5),
+            new anchor.BN(100),
+            80,
+            Array.from(Buffer.alloc(32)),
+            DEFAULT_OPTIONS
+          )
+          .accounts({
+            admin: admin,
+            contributor: contributor.publicKey,
+            dataset: dataset,
+            registry: fixture.registry,
+            registryConfig: fixture.registryConfig,
+            reputation: reputation,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(await tokenIndexAccounts(fixture.registry, fileName, program.programId))
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("Signature verification failed");
+      }
+    });
+  });
+
+  describe("migrate_data_uri", () => {
+    it("Should refuse to migrate a dataset already in the current layout", async () => {
+      const dataset = await createDataset(program, admin, fixture.registry, alice, { dataUri: "ipfs://bafy-current" });
+      const account = await program.account.dataset.fetch(dataset);
+      expect(account.schemaVersion).to.equal(4);
+      expect(Buffer.from(account.dataUri).toString()).to.equal("ipfs://bafy-current");
+
+      try {
+        await program.methods
+          .migrateDataUri()
+          .accounts({ payer: admin, dataset: dataset, systemProgram: SystemProgram.programId })
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("NotLegacyDataset");
+      }
+    });
+
+    it("Should reject a data_uri longer than 256 bytes", async () => {
+      try {
+        await createDataset(program, admin, fixture.registry, alice, { dataUri: "ipfs://" + "a".repeat(250) });
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("DataUriTooLong");
+      }
+    });
+  });
+
+  describe("upload_timestamp", () => {
+    it("Should come from the cluster clock, never from instruction arguments", async () => {
+      const createDatasetIx = program.idl.instructions.find((ix) => ix.name === "createDataset" || ix.name === "create_dataset");
+      expect(createDatasetIx.args.map((arg) => arg.name).filter((name) => /timestamp/i.test(name))).to.be.empty;
+
+      const dataset = await createDataset(program, admin, fixture.registry, alice);
+      const account = await program.account.dataset.fetch(dataset);
+      const [signature] = await provider.connection.getSignaturesForAddress(dataset, {}, "confirmed");
+      expect(account.uploadTimestamp.toNumber()).to.equal(signature.blockTime);
+    });
+  });
+
+  describe("data dictionary", () => {
+    const column = (name: string, dtype: number) => {
+      const padded = Buffer.alloc(32);
+      padded.write(name);
+      return { name: Array.from(padded), dtype };
+    };
+
+    const setDictionary = (dataset: PublicKey, columns: { name: number[]; dtype: number }[]) =>
+      program.methods
+        .setDataDictionary(columns)
+        .accounts({ contributor: alice.publicKey, dataset: dataset })
+        .signers([alice])
+        .rpc();
+
+    it("Should store one column definition per dataset column", async () => {
+      const dataset = await createDataset(program, admin, fixture.registry, alice, { columnCount: 2 });
+
+      await setDictionary(dataset, [column("region", 1), column("rainfall_mm", 2)]);
+
+      const [dictionary] = await deriveDataDictionaryPDA(dataset, program.programId);
+      const account = await program.account.dataDictionary.fetch(dictionary);
+      expect(account.dataset.toBase58()).to.equal(dataset.toBase58());
+      expect(account.columns.map((c) => c.dtype)).to.deep.equal([1, 2]);
+      expect(Buffer.from(account.columns[1].name).toString().replace(/\0+$/, "")).to.equal("rainfall_mm");
+    });
+
+    it("Should reject a dictionary whose entry count differs from column_count", async () => {
+      const dataset = await createDataset(program, admin, fixture.registry, alice, { columnCount: 3 });
+
+      try {
+        await setDictionary(dataset, [column("region", 1), column("year", 3)]);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("ColumnCountMismatch");
+      }
+    });
+  });
+
+  describe("replicas", () => {
+    const BACKEND_IPFS = 0;
+    const BACKEND_ARWEAVE = 1;
+    const cid = (value: string) => {
+      const padded = Buffer.alloc(64);
+      padded.write(value);
+      return Array.from(padded);
+    };
+
+    const addReplica = (dataset: PublicKey, backend: number, value: string) =>
+      program.methods
+        .addReplica(backend, cid(value))
+        .accounts({ contributor: alice.publicKey, dataset: dataset })
+        .signers([alice])
+        .rpc();
+
+    it("Should record a replica on each of two backends", async () => {
+      const dataset = await createDataset(program, admin, fixture.registry, alice);
+
+      await addReplica(dataset, BACKEND_IPFS, "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi");
+      await addReplica(dataset, BACKEND_ARWEAVE, "bNbA3TEQVL60xlgCcqdz4ZPHFZ711cZ3hmkpGttDt_U");
+
+      const [replicas] = await deriveDatasetReplicasPDA(dataset, program.programId);
+      const account = await program.account.datasetReplicas.fetch(replicas);
+      expect(account.dataset.toBase58()).to.equal(dataset.toBase58());
+      expect(account.replicas.map((r) => r.backend)).to.deep.equal([BACKEND_IPFS, BACKEND_ARWEAVE]);
+    });
+
+    it("Should reject a replica beyond MAX_REPLICAS", async () => {
+      const dataset = await createDataset(program, admin, fixture.registry, alice);
+      for (let i = 0; i < 4; i++) {
+        await addReplica(dataset, BACKEND_IPFS, "bafy-copy-" + i);
+      }
+
+      try {
+        await addReplica(dataset, BACKEND_ARWEAVE, "one-too-many");
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("ReplicasFull");
+      }
+    });
+  });
+
+    it("Should reject create_dataset before initialize_registry with RegistryNotInitialized", async () => {
+      const [registry] = await deriveRegistryPDA(admin, registryName("never-" + Date.now()), program.programId);
+
+      try {
+        await createDataset(program, admin, registry, alice);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("RegistryNotInitialized");
+      }
+    });
+  });
+});
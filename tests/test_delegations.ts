@@ -0,0 +1,145 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import { expect } from 'chai';
+import { AfricaResearchBase } from '../target/types/africa_research_base';
+import { createDataset, createRegistry, createReputation, forkDataset, fundedKeypair, RegistryFixture } from './fixtures';
+import { deriveDelegationPDA, deriveReputationPDA } from './utils';
+
+describe("Institutional reputation delegation", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.AfricaResearchBase as Program<AfricaResearchBase>;
+  const admin = provider.wallet.publicKey;
+
+  let fixture: RegistryFixture;
+  let researcher: Keypair;
+  let institution: Keypair;
+  let researcherReputation: PublicKey;
+  let institutionReputation: PublicKey;
+
+  const delegate = async (delegator: Keypair, to: PublicKey) => {
+    const [delegatorReputation] = await deriveReputationPDA(delegator.publicKey, program.programId);
+    const [delegation] = await deriveDelegationPDA(delegator.publicKey, program.programId);
+    await program.methods
+      .delegateReputation()
+      .accounts({
+        delegator: delegator.publicKey,
+        delegatorReputation: delegatorReputation,
+        institutionReputation: to,
+        delegation: delegation,
+      })
+      .signers([delegator])
+      .rpc();
+  };
+
+  before(async () => {
+    fixture = await createRegistry(program, admin, "delegation-" + Date.now());
+    researcher = await fundedKeypair(provider);
+    institution = await fundedKeypair(provider);
+    researcherReputation = await createReputation(program, admin, researcher);
+    institutionReputation = await createReputation(program, admin, institution);
+
+    // A fork of the researcher's dataset gives them a score to delegate
+    const forker = await fundedKeypair(provider);
+    await createReputation(program, admin, forker);
+    const dataset = await createDataset(program, admin, fixture.registry, researcher);
+    await forkDataset(program, admin, fixture.registry, dataset, forker);
+  });
+
+  it("Should add the researcher's score to the institution and remove it on revoke", async () => {
+    const { reputationScore } = await program.account.reputation.fetch(researcherReputation);
+    expect(reputationScore).to.be.greaterThan(0);
+
+    await delegate(researcher, institutionReputation);
+
+    let aggregate = await program.account.reputation.fetch(institutionReputation);
+    expect(aggregate.delegatedScore).to.equal(reputationScore);
+    expect(aggregate.delegateCount).to.equal(1);
+    expect((await program.account.reputation.fetch(researcherReputation)).delegatedTo.toBase58())
+      .to.equal(institution.publicKey.toBase58());
+
+    const [delegation] = await deriveDelegationPDA(researcher.publicKey, program.programId);
+    await program.methods
+      .revokeDelegation()
+      .accounts({
+        delegator: researcher.publicKey,
+        delegatorReputation: researcherReputation,
+        institutionReputation: institutionReputation,
+        delegation: delegation,
+      })
+      .signers([researcher])
+      .rpc();
+
+    aggregate = await program.account.reputation.fetch(institutionReputation);
+    expect(aggregate.delegatedScore).to.equal(0);
+    expect(aggregate.delegateCount).to.equal(0);
+    expect(await program.account.delegation.fetchNullable(delegation)).to.be.null;
+  });
+
+  it("Should reject delegating back to a delegate", async () => {
+    await delegate(researcher, institutionReputation);
+
+    try {
+      await delegate(institution, researcherReputation);
+      expect.fail("Should have thrown an error");
+    } catch (error) {
+      expect(error.toString()).to.include("DelegationCycle");
+    }
+  });
+
+  describe("vacation", () => {
+    const now = () => Math.floor(Date.now() / 1000);
+
+    it("Should pause decay for a capped duration set by the contributor", async () => {
+      const until = now() + 30 * 24 * 60 * 60;
+      await program.methods
+        .setVacation(new anchor.BN(until))
+        .accounts({ contributor: researcher.publicKey, reputation: researcherReputation })
+        .signers([researcher])
+        .rpc();
+      expect((await program.account.reputation.fetch(researcherReputation)).decayPausedUntil.toNumber()).to.equal(until);
+
+      // Decay during the vacation leaves the score untouched
+      const { reputationScore } = await program.account.reputation.fetch(researcherReputation);
+      await program.methods
+        .decayReputation()
+        .accounts({ caller: admin, reputation: researcherReputation })
+        .rpc();
+      expect((await program.account.reputation.fetch(researcherReputation)).reputationScore).to.equal(reputationScore);
+
+      try {
+        await program.methods
+          .setVacation(new anchor.BN(now() + 365 * 24 * 60 * 60))
+          .accounts({ contributor: researcher.publicKey, reputation: researcherReputation })
+          .signers([researcher])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidVacation");
+      }
+    });
+
+    it("Should let an admin set a vacation for institutions only", async () => {
+      const until = now() + 7 * 24 * 60 * 60;
+      await program.methods
+        .setInstitutionVacation(new anchor.BN(until))
+        .accounts({ admin: admin, registry: fixture.registry, reputation: institutionReputation })
+        .rpc();
+      expect((await program.account.reputation.fetch(institutionReputation)).decayPausedUntil.toNumber()).to.equal(until);
+
+      const individual = await fundedKeypair(provider);
+      const individualReputation = await createReputation(program, admin, individual);
+      try {
+        await program.methods
+          .setInstitutionVacation(new anchor.BN(until))
+          .accounts({ admin: admin, registry: fixture.registry, reputation: individualReputation })
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("NotAnInstitution");
+      }
+    });
+  });
+});
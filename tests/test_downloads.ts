@@ -0,0 +1,979 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { expect } from 'chai';
+import { AfricaResearchBase } from '../target/types/africa_research_base';
+import { createDataset, createRegistry, createReputation, fundedKeypair, RegistryFixture } from './fixtures';
+import {
+  deriveAccessNoncePDA,
+  deriveAccessRequestPDA,
+  deriveDatasetGrantPDA,
+  deriveDownloadClaimPDA,
+  deriveDownloadRecordPDA,
+  deriveDownloadReportPDA,
+  deriveDownloadReversalPDA,
+  deriveEarningsPDA,
+  deriveReceiptPDA,
+  deriveReputationPDA,
+  deriveTrendingPDA,
+  deriveVaultPDA,
+  downloadLeaf,
+  merkleNode,
+  tokenIndexAccounts,
+} from './utils';
+
+const NATIVE_MINT = new PublicKey("So11111111111111111111111111111111111111112");
+
+describe("Downloads", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.AfricaResearchBase as Program<AfricaResearchBase>;
+  const admin = provider.wallet.publicKey;
+
+  let fixture: RegistryFixture;
+  let contributor: Keypair;
+  let downloader: Keypair;
+
+  before(async () => {
+    fixture = await createRegistry(program, admin, "downloads-" + Date.now());
+    contributor = await fundedKeypair(provider);
+    downloader = await fundedKeypair(provider);
+    await createReputation(program, admin, contributor);
+  });
+
+  it("Should issue a receipt for a paid download", async () => {
+    const fee = 5_000_000;
+    const dataset = await createDataset(program, admin, fixture.registry, contributor);
+    const [feeVault] = await deriveVaultPDA(dataset, program.programId);
+    const [reputation] = await deriveReputationPDA(contributor.publicKey, program.programId);
+    const [earnings] = await deriveEarningsPDA(contributor.publicKey, program.programId);
+
+    await program.methods
+      .setDownloadFee(new anchor.BN(fee))
+      .accounts({
+        contributor: contributor.publicKey,
+        dataset: dataset,
+        feeVault: feeVault,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([contributor])
+      .rpc();
+
+    const slot = await provider.connection.getSlot();
+    const [receipt] = await deriveReceiptPDA(dataset, downloader.publicKey, slot, program.programId);
+    const vaultBefore = await provider.connection.getBalance(feeVault);
+
+    await program.methods
+      .recordDownload(new anchor.BN(slot))
+      .accounts({
+        downloader: downloader.publicKey,
+        registry: fixture.registry,
+        dataset: dataset,
+        reputation: reputation,
+        treasury: fixture.treasury,
+        feeVault: feeVault,
+        receipt: receipt,
+        downloadRecord: null,
+        earnings: earnings,
+        contributor: null,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([downloader])
+      .rpc();
+
+    const receiptAccount = await program.account.receipt.fetch(receipt);
+    expect(receiptAccount.amountPaid.toNumber()).to.equal(fee);
+    expect(receiptAccount.mint.toString()).to.equal(NATIVE_MINT.toString());
+    expect(receiptAccount.slot.toNumber()).to.equal(slot);
+    expect(receiptAccount.downloader.toString()).to.equal(downloader.publicKey.toString());
+
+    // Platform fee defaults to zero, so the whole fee lands in the vault
+    expect(await provider.connection.getBalance(feeVault)).to.equal(vaultBefore + fee);
+    expect((await program.account.dataset.fetch(dataset)).downloadCount).to.equal(1);
+  });
+
+  it("Should record a free download without a receipt", async () => {
+    const dataset = await createDataset(program, admin, fixture.registry, contributor);
+    const [reputation] = await deriveReputationPDA(contributor.publicKey, program.programId);
+
+    await program.methods
+      .recordDownload(new anchor.BN(0))
+      .accounts({
+        downloader: downloader.publicKey,
+        registry: fixture.registry,
+        dataset: dataset,
+        reputation: reputation,
+        treasury: fixture.treasury,
+        feeVault: null,
+        receipt: null,
+        downloadRecord: null,
+        earnings: null,
+        contributor: null,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([downloader])
+      .rpc();
+
+    expect((await program.account.dataset.fetch(dataset)).downloadCount).to.equal(1);
+  });
+
+  describe("spam deposit", () => {
+    const deposit = 10_000_000;
+    let guarded: RegistryFixture;
+
+    before(async () => {
+      guarded = await createRegistry(program, admin, "spam-deposit-" + Date.now());
+      await program.methods
+        .setSpamDeposit(new anchor.BN(deposit))
+        .accounts({ admin: admin, registry: guarded.registry })
+        .rpc();
+    });
+
+    const download = async (dataset: PublicKey, by: Keypair, refundTo: PublicKey | null, paid = false) => {
+      const slot = paid ? await provider.connection.getSlot() : 0;
+      return program.methods
+        .recordDownload(new anchor.BN(slot))
+        .accounts({
+          downloader: by.publicKey,
+          registry: guarded.registry,
+          dataset: dataset,
+          reputation: (await deriveReputationPDA(contributor.publicKey, program.programId))[0],
+          treasury: guarded.treasury,
+          feeVault: paid ? (await deriveVaultPDA(dataset, program.programId))[0] : null,
+          receipt: paid ? (await deriveReceiptPDA(dataset, by.publicKey, slot, program.programId))[0] : null,
+          downloadRecord: null,
+          earnings: paid ? (await deriveEarningsPDA(contributor.publicKey, program.programId))[0] : null,
+          contributor: refundTo,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([by])
+        .rpc();
+    };
+
+    it("Should hold the deposit through the hold period, even for a paid outside download", async () => {
+      const dataset = await createDataset(program, admin, guarded.registry, contributor);
+      const rent = await provider.connection.getMinimumBalanceForRentExemption(
+        (await provider.connection.getAccountInfo(dataset)).data.length
+      );
+      expect((await program.account.dataset.fetch(dataset)).spamDeposit.toNumber()).to.equal(deposit);
+      expect(await provider.connection.getBalance(dataset)).to.equal(rent + deposit);
+
+      // Neither the contributor's own download nor a free one from a fresh key counts
+      await download(dataset, contributor, null);
+      await download(dataset, await fundedKeypair(provider), null);
+      expect((await program.account.dataset.fetch(dataset)).spamDeposit.toNumber()).to.equal(deposit);
+
+      // Nor does a 1-lamport fee the contributor sets and pays back into their own vault
+      await program.methods
+        .setDownloadFee(new anchor.BN(1))
+        .accounts({
+          contributor: contributor.publicKey,
+          dataset: dataset,
+          feeVault: (await deriveVaultPDA(dataset, program.programId))[0],
+          earnings: (await deriveEarningsPDA(contributor.publicKey, program.programId))[0],
+        })
+        .signers([contributor])
+        .rpc();
+
+      const before = await provider.connection.getBalance(contributor.publicKey);
+      await download(dataset, await fundedKeypair(provider), contributor.publicKey, true);
+
+      const account = await program.account.dataset.fetch(dataset);
+      expect(account.spamDeposit.toNumber()).to.equal(deposit);
+      expect(account.downloadCount).to.equal(3);
+      expect(await provider.connection.getBalance(dataset)).to.equal(rent + deposit);
+      expect(await provider.connection.getBalance(contributor.publicKey)).to.equal(before);
+    });
+
+    it("Should forfeit the deposit to the treasury when a never-downloaded dataset is closed", async () => {
+      const fileName = "never-downloaded.csv";
+      const dataset = await createDataset(program, admin, guarded.registry, contributor, { fileName });
+      await download(dataset, await fundedKeypair(provider), null);
+      const held = await provider.connection.getBalance(dataset);
+      const treasuryBefore = await provider.connection.getBalance(guarded.treasury);
+      const contributorBefore = await provider.connection.getBalance(contributor.publicKey);
+
+      await program.methods
+        .closeDataset()
+        .accounts({
+          contributor: contributor.publicKey,
+          registry: guarded.registry,
+          dataset: dataset,
+          reputation: (await deriveReputationPDA(contributor.publicKey, program.programId))[0],
+          treasury: guarded.treasury,
+        })
+        .remainingAccounts(await tokenIndexAccounts(guarded.registry, fileName, program.programId))
+        .signers([contributor])
+        .rpc();
+
+      expect(await provider.connection.getBalance(guarded.treasury)).to.equal(treasuryBefore + deposit);
+      expect(await provider.connection.getBalance(contributor.publicKey)).to.equal(contributorBefore + held - deposit);
+    });
+  });
+
+  describe("earnings", () => {
+    it("Should track lifetime and available earnings across downloads and a withdrawal", async () => {
+      const fee = 2_000_000;
+      const earner = await fundedKeypair(provider);
+      await createReputation(program, admin, earner);
+      const dataset = await createDataset(program, admin, fixture.registry, earner);
+      const [feeVault] = await deriveVaultPDA(dataset, program.programId);
+      const [reputation] = await deriveReputationPDA(earner.publicKey, program.programId);
+      const [earnings] = await deriveEarningsPDA(earner.publicKey, program.programId);
+
+      await program.methods
+        .setDownloadFee(new anchor.BN(fee))
+        .accounts({ contributor: earner.publicKey, dataset: dataset, feeVault: feeVault, earnings: earnings })
+        .signers([earner])
+        .rpc();
+
+      const vaultBefore = await provider.connection.getBalance(feeVault);
+      for (const buyer of [downloader, await fundedKeypair(provider)]) {
+        const slot = await provider.connection.getSlot();
+        const [receipt] = await deriveReceiptPDA(dataset, buyer.publicKey, slot, program.programId);
+        await program.methods
+          .recordDownload(new anchor.BN(slot))
+          .accounts({
+            downloader: buyer.publicKey,
+            registry: fixture.registry,
+            dataset: dataset,
+            reputation: reputation,
+            treasury: fixture.treasury,
+            feeVault: feeVault,
+            earnings: earnings,
+            contributor: null,
+            receipt: receipt,
+            downloadRecord: null,
+          })
+          .signers([buyer])
+          .rpc();
+      }
+      const earned = (await provider.connection.getBalance(feeVault)) - vaultBefore;
+      expect(earned).to.be.greaterThan(0);
+
+      const withdrawal = Math.floor(earned / 3);
+      await program.methods
+        .withdrawEarnings(new anchor.BN(withdrawal))
+        .accounts({ contributor: earner.publicKey, dataset: dataset, feeVault: feeVault, earnings: earnings })
+        .signers([earner])
+        .rpc();
+
+      const account = await program.account.earnings.fetch(earnings);
+      expect(account.lifetimeLamports.toNumber()).to.equal(earned);
+      expect(account.lifetimeLamports.sub(account.withdrawnLamports).toNumber()).to.equal(earned - withdrawal);
+      expect(account.lastWithdrawal.toNumber()).to.be.greaterThan(0);
+
+      try {
+        await program.methods
+          .withdrawEarnings(new anchor.BN(earned - withdrawal + 1))
+          .accounts({ contributor: earner.publicKey, dataset: dataset, feeVault: feeVault, earnings: earnings })
+          .signers([earner])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InsufficientEarnings");
+      }
+    });
+  });
+
+  describe("trending", () => {
+    it("Should rank the registry's datasets by trending score as downloads are recorded", async () => {
+      const trendingFixture = await createRegistry(program, admin, "trending-" + Date.now());
+      const [reputation] = await deriveReputationPDA(contributor.publicKey, program.programId);
+      const [trending] = await deriveTrendingPDA(trendingFixture.registry, program.programId);
+      const popular = await createDataset(program, admin, trendingFixture.registry, contributor);
+      const quiet = await createDataset(program, admin, trendingFixture.registry, contributor);
+
+      for (const dataset of [quiet, popular, popular]) {
+        await program.methods
+          .recordDownload(new anchor.BN(0))
+          .accounts({
+            downloader: downloader.publicKey,
+            registry: trendingFixture.registry,
+            dataset: dataset,
+            reputation: reputation,
+            treasury: trendingFixture.treasury,
+            feeVault: null,
+            receipt: null,
+            downloadRecord: null,
+            earnings: null,
+            contributor: null,
+            trending: trending,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([downloader])
+          .rpc();
+      }
+
+      const { entries } = await program.account.trendingDatasets.fetch(trending);
+      expect(entries.map((entry) => entry.dataset.toBase58())).to.deep.equal([popular.toBase58(), quiet.toBase58()]);
+      expect(entries.map((entry) => entry.downloadCount)).to.deep.equal([2, 1]);
+    });
+  });
+
+  describe("Expiry", () => {
+    const freeDownload = (dataset: PublicKey, reputation: PublicKey) =>
+      program.methods
+        .recordDownload(new anchor.BN(0))
+        .accounts({
+          downloader: downloader.publicKey,
+          registry: fixture.registry,
+          dataset: dataset,
+          reputation: reputation,
+          treasury: fixture.treasury,
+          feeVault: null,
+          receipt: null,
+          downloadRecord: null,
+          earnings: null,
+          contributor: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([downloader])
+        .rpc();
+
+    it("Should allow downloads before expiry, then block and archive after", async () => {
+      const expiresAt = Math.floor(Date.now() / 1000) + 4;
+      const dataset = await createDataset(program, admin, fixture.registry, contributor, {
+        options: { expiresAt: new anchor.BN(expiresAt) },
+      });
+      const [reputation] = await deriveReputationPDA(contributor.publicKey, program.programId);
+
+      await freeDownload(dataset, reputation);
+      expect((await program.account.dataset.fetch(dataset)).downloadCount).to.equal(1);
+
+      await new Promise((resolve) => setTimeout(resolve, 6000));
+
+      try {
+        await freeDownload(dataset, reputation);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("DatasetExpired");
+      }
+
+      const archivedBefore = (await program.account.registry.fetch(fixture.registry)).archivedCount.toNumber();
+      await program.methods
+        .archiveExpired()
+        .accounts({
+          caller: downloader.publicKey,
+          registry: fixture.registry,
+          dataset: dataset,
+        })
+        .signers([downloader])
+        .rpc();
+
+      expect((await program.account.dataset.fetch(dataset)).isActive).to.equal(false);
+      expect((await program.account.registry.fetch(fixture.registry)).archivedCount.toNumber()).to.equal(archivedBefore + 1);
+    });
+
+    it("Should block downloads past license expiry and allow them after renewal", async () => {
+      const licenseExpiresAt = Math.floor(Date.now() / 1000) + 4;
+      const dataset = await createDataset(program, admin, fixture.registry, contributor, {
+        options: { licenseExpiresAt: new anchor.BN(licenseExpiresAt) },
+      });
+      const [reputation] = await deriveReputationPDA(contributor.publicKey, program.programId);
+
+      await new Promise((resolve) => setTimeout(resolve, 6000));
+
+      try {
+        await freeDownload(dataset, reputation);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("LicenseExpired");
+      }
+
+      const renewedUntil = Math.floor(Date.now() / 1000) + 365 * 24 * 60 * 60;
+      await program.methods
+        .renewLicense(new anchor.BN(renewedUntil))
+        .accounts({ contributor: contributor.publicKey, dataset: dataset })
+        .signers([contributor])
+        .rpc();
+      await freeDownload(dataset, reputation);
+
+      const account = await program.account.dataset.fetch(dataset);
+      expect(account.licenseExpiresAt.toNumber()).to.equal(renewedUntil);
+      expect(account.downloadCount).to.equal(1);
+      expect(account.isActive).to.equal(true);
+    });
+  });
+
+  describe("access nonces", () => {
+    const paidDownload = async (): Promise<{ dataset: PublicKey; receipt: PublicKey }> => {
+      const dataset = await createDataset(program, admin, fixture.registry, contributor);
+      const [feeVault] = await deriveVaultPDA(dataset, program.programId);
+      const [reputation] = await deriveReputationPDA(contributor.publicKey, program.programId);
+      const [earnings] = await deriveEarningsPDA(contributor.publicKey, program.programId);
+
+      await program.methods
+        .setDownloadFee(new anchor.BN(1_000_000))
+        .accounts({
+          contributor: contributor.publicKey,
+          dataset: dataset,
+          feeVault: feeVault,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([contributor])
+        .rpc();
+
+      const slot = await provider.connection.getSlot();
+      const [receipt] = await deriveReceiptPDA(dataset, downloader.publicKey, slot, program.programId);
+      await program.methods
+        .recordDownload(new anchor.BN(slot))
+        .accounts({
+          downloader: downloader.publicKey,
+          registry: fixture.registry,
+          dataset: dataset,
+          reputation: reputation,
+          treasury: fixture.treasury,
+          feeVault: feeVault,
+          receipt: receipt,
+          downloadRecord: null,
+          earnings: earnings,
+          contributor: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([downloader])
+        .rpc();
+
+      return { dataset, receipt };
+    };
+
+    const consume = async (dataset: PublicKey, accessNonce: PublicKey, nonce: number[]) => {
+      await program.methods
+        .consumeNonce(nonce)
+        .accounts({ contributor: contributor.publicKey, dataset: dataset, accessNonce: accessNonce })
+        .signers([contributor])
+        .rpc();
+    };
+
+    it("Should issue a nonce to a paying downloader and consume it once", async () => {
+      const { dataset, receipt } = await paidDownload();
+      const [accessNonce] = await deriveAccessNoncePDA(receipt, program.programId);
+
+      await program.methods
+        .requestAccessNonce()
+        .accounts({
+          grantee: downloader.publicKey,
+          dataset: dataset,
+          receipt: receipt,
+          downloadRecord: null,
+          earnings: null,
+          accessNonce: accessNonce,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([downloader])
+        .rpc();
+
+      const issued = await program.account.accessNonce.fetch(accessNonce);
+      expect(issued.grantee.toString()).to.equal(downloader.publicKey.toString());
+      expect(issued.expiresAt.toNumber()).to.be.greaterThan(Math.floor(Date.now() / 1000) - 60);
+
+      await consume(dataset, accessNonce, issued.nonce);
+      expect((await program.account.accessNonce.fetch(accessNonce)).consumed).to.equal(true);
+
+      try {
+        await consume(dataset, accessNonce, issued.nonce);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("NonceConsumed");
+      }
+    });
+  });
+
+  describe("access requests", () => {
+    const JUSTIFICATION = Array(32).fill(4);
+
+    const requestAccess = async (dataset: PublicKey, requester: Keypair): Promise<PublicKey> => {
+      const [accessRequest] = await deriveAccessRequestPDA(dataset, requester.publicKey, program.programId);
+      await program.methods
+        .requestAccess(JUSTIFICATION)
+        .accounts({
+          requester: requester.publicKey,
+          dataset: dataset,
+          accessRequest: accessRequest,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([requester])
+        .rpc();
+      return accessRequest;
+    };
+
+    it("Should queue a pending access request", async () => {
+      const dataset = await createDataset(program, admin, fixture.registry, contributor);
+      const accessRequest = await requestAccess(dataset, downloader);
+
+      const account = await program.account.accessRequest.fetch(accessRequest);
+      expect(account.dataset.toString()).to.equal(dataset.toString());
+      expect(account.requester.toString()).to.equal(downloader.publicKey.toString());
+      expect(account.justificationHash).to.deep.equal(JUSTIFICATION);
+      expect(account.status).to.equal(0);
+      expect(account.respondedAt).to.equal(null);
+    });
+
+    it("Should create a grant when the contributor approves", async () => {
+      const dataset = await createDataset(program, admin, fixture.registry, contributor);
+      const accessRequest = await requestAccess(dataset, downloader);
+      const [grant] = await deriveDatasetGrantPDA(dataset, downloader.publicKey, program.programId);
+
+      await program.methods
+        .respondToAccessRequest(true)
+        .accounts({
+          contributor: contributor.publicKey,
+          dataset: dataset,
+          accessRequest: accessRequest,
+          grant: grant,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([contributor])
+        .rpc();
+
+      expect((await program.account.accessRequest.fetch(accessRequest)).status).to.equal(1);
+      const account = await program.account.datasetGrant.fetch(grant);
+      expect(account.grantee.toString()).to.equal(downloader.publicKey.toString());
+      expect(account.grantedBy.toString()).to.equal(contributor.publicKey.toString());
+      expect(account.accessRequest.toString()).to.equal(accessRequest.toString());
+    });
+
+    it("Should deny without creating a grant", async () => {
+      const dataset = await createDataset(program, admin, fixture.registry, contributor);
+      const accessRequest = await requestAccess(dataset, downloader);
+      const [grant] = await deriveDatasetGrantPDA(dataset, downloader.publicKey, program.programId);
+
+      await program.methods
+        .respondToAccessRequest(false)
+        .accounts({
+          contributor: contributor.publicKey,
+          dataset: dataset,
+          accessRequest: accessRequest,
+          grant: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([contributor])
+        .rpc();
+
+      expect((await program.account.accessRequest.fetch(accessRequest)).status).to.equal(2);
+      expect(await provider.connection.getAccountInfo(grant)).to.equal(null);
+
+      try {
+        await program.methods
+          .respondToAccessRequest(true)
+          .accounts({
+            contributor: contributor.publicKey,
+            dataset: dataset,
+            accessRequest: accessRequest,
+            grant: grant,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([contributor])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("AccessRequestResolved");
+      }
+    });
+
+    it("Should refuse a denial that passes the grant account", async () => {
+      const dataset = await createDataset(program, admin, fixture.registry, contributor);
+      const accessRequest = await requestAccess(dataset, downloader);
+      const [grant] = await deriveDatasetGrantPDA(dataset, downloader.publicKey, program.programId);
+
+      try {
+        await program.methods
+          .respondToAccessRequest(false)
+          .accounts({
+            contributor: contributor.publicKey,
+            dataset: dataset,
+            accessRequest: accessRequest,
+            grant: grant,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([contributor])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("GrantNotAllowed");
+      }
+
+      expect(await provider.connection.getAccountInfo(grant)).to.equal(null);
+      expect((await program.account.accessRequest.fetch(accessRequest)).status).to.equal(0);
+    });
+  });
+
+  it("Should credit downloads to the dataset's research fields", async () => {
+    const HEALTH = 1;
+    const ECONOMICS = 2;
+    const author = await fundedKeypair(provider);
+    const [reputation] = await deriveReputationPDA(author.publicKey, program.programId);
+    await createReputation(program, admin, author);
+
+    const health = await createDataset(program, admin, fixture.registry, author, { options: { researchFields: [HEALTH] } });
+    const economics = await createDataset(program, admin, fixture.registry, author, { options: { researchFields: [ECONOMICS] } });
+
+    for (const dataset of [health, health, economics]) {
+      await program.methods
+        .recordDownload(new anchor.BN(0))
+        .accounts({
+          downloader: downloader.publicKey,
+          registry: fixture.registry,
+          dataset: dataset,
+          reputation: reputation,
+          treasury: fixture.treasury,
+          feeVault: null,
+          receipt: null,
+          downloadRecord: null,
+          earnings: null,
+          contributor: null,
+        })
+        .signers([downloader])
+        .rpc();
+    }
+
+    const { fieldScores } = await program.account.reputation.fetch(reputation);
+    const scoreFor = (field: number) => fieldScores.find((entry) => entry.field === field)?.score ?? 0;
+    expect(scoreFor(HEALTH)).to.equal(2 * scoreFor(ECONOMICS));
+    expect(scoreFor(ECONOMICS)).to.be.greaterThan(0);
+  });
+
+  describe("Per-downloader cap", () => {
+    const cappedDataset = async (cap: number, windowSeconds: number): Promise<PublicKey> => {
+      const dataset = await createDataset(program, admin, fixture.registry, contributor);
+      await program.methods
+        .setDownloadCap(cap, new anchor.BN(windowSeconds))
+        .accounts({ contributor: contributor.publicKey, dataset: dataset })
+        .signers([contributor])
+        .rpc();
+      return dataset;
+    };
+
+    const cappedDownload = async (dataset: PublicKey) => {
+      const [reputation] = await deriveReputationPDA(contributor.publicKey, program.programId);
+      const [downloadRecord] = await deriveDownloadRecordPDA(dataset, downloader.publicKey, program.programId);
+      await program.methods
+        .recordDownload(new anchor.BN(0))
+        .accounts({
+          downloader: downloader.publicKey,
+          registry: fixture.registry,
+          dataset: dataset,
+          reputation: reputation,
+          treasury: fixture.treasury,
+          feeVault: null,
+          receipt: null,
+          downloadRecord: downloadRecord,
+          earnings: null,
+          contributor: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([downloader])
+        .rpc();
+    };
+
+    it("Should rate-limit a downloader who reaches the cap", async () => {
+      const dataset = await cappedDataset(2, 3600);
+      await cappedDownload(dataset);
+      await cappedDownload(dataset);
+
+      try {
+        await cappedDownload(dataset);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("DownloadRateLimited");
+      }
+      expect((await program.account.dataset.fetch(dataset)).downloadCount).to.equal(2);
+    });
+
+    it("Should allow downloads again once the window has passed", async () => {
+      const dataset = await cappedDataset(1, 2);
+      await cappedDownload(dataset);
+
+      await new Promise((resolve) => setTimeout(resolve, 4000));
+      await cappedDownload(dataset);
+
+      const [downloadRecord] = await deriveDownloadRecordPDA(dataset, downloader.publicKey, program.programId);
+      expect((await program.account.downloadRecord.fetch(downloadRecord)).count).to.equal(1);
+      expect((await program.account.dataset.fetch(dataset)).downloadCount).to.equal(2);
+    });
+  });
+
+  describe("Pin funding", () => {
+    const PIN_FEE_PER_DAY = 100_000;
+    const SECONDS_PER_DAY = 24 * 60 * 60;
+
+    it("Should extend pinned_until and pay into the fee vault", async () => {
+      const dataset = await createDataset(program, admin, fixture.registry, contributor);
+      const [feeVault] = await deriveVaultPDA(dataset, program.programId);
+      const extend = (days: number) =>
+        program.methods
+          .extendPin(days)
+          .accounts({ funder: downloader.publicKey, dataset: dataset, feeVault: feeVault })
+          .signers([downloader])
+          .rpc();
+
+      await extend(30);
+      const first = await program.account.dataset.fetch(dataset);
+      const vaultAfterFirst = await provider.connection.getBalance(feeVault);
+      expect(first.pinnedUntil.toNumber()).to.be.greaterThan(Math.floor(Date.now() / 1000));
+
+      await extend(10);
+      const second = await program.account.dataset.fetch(dataset);
+      expect(second.pinnedUntil.toNumber()).to.equal(first.pinnedUntil.toNumber() + 10 * SECONDS_PER_DAY);
+      expect(second.pinSecondsFunded.toNumber()).to.equal(40 * SECONDS_PER_DAY);
+      expect(await provider.connection.getBalance(feeVault)).to.equal(vaultAfterFirst + 10 * PIN_FEE_PER_DAY);
+    });
+  });
+
+  describe("fee currency", () => {
+    it("Should quote new vaults in SOL and reject a fee mint that is not a token mint", async () => {
+      const dataset = await createDataset(program, admin, fixture.registry, contributor);
+      const [feeVault] = await deriveVaultPDA(dataset, program.programId);
+
+      await program.methods
+        .setDownloadFee(new anchor.BN(1_000_000))
+        .accounts({
+          contributor: contributor.publicKey,
+          dataset: dataset,
+          feeVault: feeVault,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([contributor])
+        .rpc();
+
+      const vault = await program.account.feeVault.fetch(feeVault);
+      expect(vault.feeDecimals).to.equal(9);
+      expect(Buffer.from(vault.feeSymbol).toString().replace(/\0+$/, "")).to.equal("SOL");
+
+      try {
+        await program.methods
+          .setFeeCurrency(Array.from(Buffer.from("USDC\0\0\0\0")))
+          .accounts({
+            contributor: contributor.publicKey,
+            dataset: dataset,
+            feeVault: feeVault,
+            feeMint: downloader.publicKey,
+          })
+          .signers([contributor])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidFeeMint");
+      }
+    });
+  });
+
+  describe("off-chain download reports", () => {
+    it("Should credit a proven delta once and reject a replayed claim", async () => {
+      const oracle = await fundedKeypair(provider);
+      await program.methods
+        .setDownloadOracle(oracle.publicKey)
+        .accounts({ admin: admin, registry: fixture.registry })
+        .rpc();
+
+      const dataset = await createDataset(program, admin, fixture.registry, contributor);
+      const other = await createDataset(program, admin, fixture.registry, contributor);
+      const leaf = downloadLeaf(dataset, 5);
+      const sibling = downloadLeaf(other, 3);
+
+      const reportId = Date.now();
+      const [report] = await deriveDownloadReportPDA(fixture.registry, reportId, program.programId);
+      await program.methods
+        .submitDownloadReport(new anchor.BN(reportId), Array.from(merkleNode(leaf, sibling)))
+        .accounts({
+          oracle: oracle.publicKey,
+          registry: fixture.registry,
+          downloadReport: report,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([oracle])
+        .rpc();
+
+      const [reputation] = await deriveReputationPDA(contributor.publicKey, program.programId);
+      const [claim] = await deriveDownloadClaimPDA(report, dataset, program.programId);
+      const before = (await program.account.reputation.fetch(reputation)).totalDownloads.toNumber();
+      const claimDelta = () =>
+        program.methods
+          .claimDownloadDelta(5, [Array.from(sibling)])
+          .accounts({
+            contributor: contributor.publicKey,
+            registry: fixture.registry,
+            downloadReport: report,
+            dataset: dataset,
+            downloadClaim: claim,
+            reputation: reputation,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([contributor])
+          .rpc();
+
+      await claimDelta();
+      expect((await program.account.dataset.fetch(dataset)).downloadCount).to.equal(5);
+      expect((await program.account.reputation.fetch(reputation)).totalDownloads.toNumber()).to.equal(before + 5);
+      expect((await program.account.downloadReport.fetch(report)).claimedDownloads.toNumber()).to.equal(5);
+
+      try {
+        await claimDelta();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("already in use");
+      }
+      expect((await program.account.dataset.fetch(dataset)).downloadCount).to.equal(5);
+    });
+  });
+
+  describe("recover_stuck_vault", () => {
+    const openVault = async (fileName: string): Promise<[PublicKey, PublicKey]> => {
+      const dataset = await createDataset(program, admin, fixture.registry, contributor, { fileName });
+      const [feeVault] = await deriveVaultPDA(dataset, program.programId);
+      await program.methods
+        .setDownloadFee(new anchor.BN(1_000_000))
+        .accounts({
+          contributor: contributor.publicKey,
+          dataset: dataset,
+          feeVault: feeVault,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([contributor])
+        .rpc();
+      return [dataset, feeVault];
+    };
+
+    const recover = async (dataset: PublicKey, feeVault: PublicKey, registry = fixture) =>
+      program.methods
+        .recoverStuckVault()
+        .accounts({
+          admin: admin,
+          registry: registry.registry,
+          treasury: registry.treasury,
+          feeVault: feeVault,
+          contributor: contributor.publicKey,
+          earnings: (await deriveEarningsPDA(contributor.publicKey, program.programId))[0],
+          dataset: dataset,
+        })
+        .rpc();
+
+    const closeDataset = async (dataset: PublicKey, fileName: string) =>
+      program.methods
+        .closeDataset()
+        .accounts({
+          contributor: contributor.publicKey,
+          registry: fixture.registry,
+          dataset: dataset,
+          reputation: (await deriveReputationPDA(contributor.publicKey, program.programId))[0],
+        })
+        .remainingAccounts(await tokenIndexAccounts(fixture.registry, fileName, program.programId))
+        .signers([contributor])
+        .rpc();
+
+    it("Should refund unwithdrawn income to the contributor and sweep only the rent reserve", async () => {
+      const fileName = "orphaned.csv";
+      const [dataset, feeVault] = await openVault(fileName);
+      const slot = await provider.connection.getSlot();
+      await program.methods
+        .recordDownload(new anchor.BN(slot))
+        .accounts({
+          downloader: downloader.publicKey,
+          registry: fixture.registry,
+          dataset: dataset,
+          reputation: (await deriveReputationPDA(contributor.publicKey, program.programId))[0],
+          treasury: fixture.treasury,
+          feeVault: feeVault,
+          receipt: (await deriveReceiptPDA(dataset, downloader.publicKey, slot, program.programId))[0],
+          downloadRecord: null,
+          earnings: (await deriveEarningsPDA(contributor.publicKey, program.programId))[0],
+          contributor: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([downloader])
+        .rpc();
+      await closeDataset(dataset, fileName);
+
+      const vaultInfo = await provider.connection.getAccountInfo(feeVault);
+      const reserve = await provider.connection.getMinimumBalanceForRentExemption(vaultInfo.data.length);
+      const income = vaultInfo.lamports - reserve;
+      expect(income).to.be.greaterThan(0);
+      const treasuryBefore = await provider.connection.getBalance(fixture.treasury);
+      const contributorBefore = await provider.connection.getBalance(contributor.publicKey);
+
+      await recover(dataset, feeVault);
+
+      expect(await provider.connection.getAccountInfo(feeVault)).to.equal(null);
+      expect(await provider.connection.getBalance(contributor.publicKey)).to.equal(contributorBefore + income);
+      expect(await provider.connection.getBalance(fixture.treasury)).to.equal(treasuryBefore + reserve);
+    });
+
+    it("Should refuse an admin of a different registry", async () => {
+      const fileName = "foreign-admin.csv";
+      const [dataset, feeVault] = await openVault(fileName);
+      await closeDataset(dataset, fileName);
+      const stranger = await createRegistry(program, admin, "stranger-" + Date.now());
+
+      try {
+        await recover(dataset, feeVault, stranger);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("UnauthorizedAdmin");
+      }
+      expect(await provider.connection.getAccountInfo(feeVault)).to.not.equal(null);
+    });
+
+    it("Should refuse while the dataset still exists", async () => {
+      const [dataset, feeVault] = await openVault("still-here.csv");
+
+      try {
+        await recover(dataset, feeVault);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("DatasetStillExists");
+      }
+      expect(await provider.connection.getAccountInfo(feeVault)).to.not.equal(null);
+    });
+  });
+
+  describe("revert_downloads", () => {
+    it("Should take disputed downloads off the dataset and reputation and record the reversal", async () => {
+      const dataset = await createDataset(program, admin, fixture.registry, contributor);
+      const [reputation] = await deriveReputationPDA(contributor.publicKey, program.programId);
+      for (let i = 0; i < 3; i++) {
+        await program.methods
+          .recordDownload(new anchor.BN(0))
+          .accounts({
+            downloader: downloader.publicKey,
+            registry: fixture.registry,
+            dataset: dataset,
+            reputation: reputation,
+            treasury: fixture.treasury,
+            feeVault: null,
+            receipt: null,
+            downloadRecord: null,
+            earnings: null,
+            contributor: null,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([downloader])
+          .rpc();
+      }
+      const before = await program.account.reputation.fetch(reputation);
+      const reasonHash = Array.from(Buffer.alloc(32, 7));
+      const [reversal] = await deriveDownloadReversalPDA(dataset, 1, program.programId);
+
+      await program.methods
+        .revertDownloads(new anchor.BN(1), 2, reasonHash)
+        .accounts({
+          admin: admin,
+          registry: fixture.registry,
+          dataset: dataset,
+          reputation: reputation,
+          reversal: reversal,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      expect((await program.account.dataset.fetch(dataset)).downloadCount).to.equal(1);
+      const after = await program.account.reputation.fetch(reputation);
+      expect(after.totalDownloads.toNumber()).to.equal(before.totalDownloads.toNumber() - 2);
+      const record = await program.account.downloadReversal.fetch(reversal);
+      expect(record.admin.toString()).to.equal(admin.toString());
+      expect(record.requested).to.equal(2);
+      expect(record.removed).to.equal(2);
+      expect(record.reasonHash).to.deep.equal(reasonHash);
+    });
+  });
+});
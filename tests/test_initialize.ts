@@ -4,6 +4,7 @@ import { PublicKey, Keypair, SystemProgram } from '@solana/web3.js';
 import { expect } from 'chai';
 import { sha256 } from 'js-sha256';
 import { AfricaResearchBase } from '../target/types/africa_research_base';
+import { tokenIndexAccounts } from './utils';
 
 describe("Africa Research Base (ARB)", () => {
   const provider = anchor.AnchorProvider.env();
@@ -12,17 +13,22 @@ describe("Africa Research Base (ARB)", () => {
   const program = anchor.workspace.AfricaResearchBase as Program<AfricaResearchBase>;
   
   let datasetRegistry: PublicKey;
+  let treasury: PublicKey;
+  let registryConfig: PublicKey;
   const admin = provider.wallet.publicKey;
   
   const researcher1 = Keypair.generate();
   const researcher2 = Keypair.generate();
   const researcher3 = Keypair.generate();
   
+  const REGISTRY_NAME = Array.from(Buffer.concat([Buffer.from("arb-main"), Buffer.alloc(24)]));
   const TEST_FILE_NAME = "climate_survey_uganda_2024.csv";
   const TEST_FILE_SIZE = 1024 * 1024; // 1MB
   const TEST_COLUMN_COUNT = 25;
   const TEST_ROW_COUNT = 1500;
   const TEST_QUALITY_SCORE = 85;
+  const TEST_PREVIEW_HASH = Array.from(Buffer.alloc(32, 1));
+  const TEST_OPTIONS = { expiresAt: null, thumbnailUri: null, featured: false, coContributors: [], researchFields: [], reportAllErrors: false, title: null, licenseExpiresAt: null, allowZeroQuality: false, featuredUntil: null, language: null, manifestEntry: null };
   const TEST_AI_METADATA = Buffer.from(JSON.stringify({
     fields: ["temperature", "rainfall", "location", "date"],
     dataTypes: ["numeric", "numeric", "categorical", "date"],
@@ -39,12 +45,7 @@ describe("Africa Research Base (ARB)", () => {
     return Array.from(Buffer.from(hash, 'hex')).slice(0, 32);
   };
 
-  const createDataUri = (uri: string): number[] => {
-    const buffer = Buffer.from(uri, 'utf-8');
-    const paddedBuffer = Buffer.alloc(256);
-    buffer.copy(paddedBuffer);
-    return Array.from(paddedBuffer);
-  };
+  const createDataUri = (uri: string): Buffer => Buffer.from(uri, 'utf-8');
 
   before(async () => {
     // Airdrop SOL to test accounts
@@ -60,7 +61,15 @@ describe("Africa Research Base (ARB)", () => {
 
     // Find registry PDA
     [datasetRegistry] = PublicKey.findProgramAddressSync(
-      [Buffer.from("registry"), admin.toBuffer()],
+      [Buffer.from("registry"), admin.toBuffer(), Buffer.from(REGISTRY_NAME)],
+      program.programId
+    );
+    [treasury] = PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury"), datasetRegistry.toBuffer()],
+      program.programId
+    );
+    [registryConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("registry_config"), datasetRegistry.toBuffer()],
       program.programId
     );
   });
@@ -69,12 +78,14 @@ describe("Africa Research Base (ARB)", () => {
     it("Should initialize the dataset registry successfully", async () => {
       try {
         await program.methods
-          .initializeRegistry()
+          .initializeRegistry(REGISTRY_NAME)
           .accounts({
             admin: admin,
             user: admin,
             contributor: admin,
             registry: datasetRegistry,
+            treasury: treasury,
+            registryConfig: registryConfig,
             systemProgram: SystemProgram.programId,
           })
           .rpc();
@@ -88,23 +99,26 @@ describe("Africa Research Base (ARB)", () => {
       }
     });
 
-    it("Should fail to initialize registry twice", async () => {
+    it("Should reject initializing a registry twice with AlreadyInitialized", async () => {
       try {
         await program.methods
-          .initializeRegistry()
+          .initializeRegistry(REGISTRY_NAME)
           .accounts({
             admin: admin,
             user: admin,
             contributor: admin,
             registry: datasetRegistry,
+            treasury: treasury,
+            registryConfig: registryConfig,
             systemProgram: SystemProgram.programId,
           })
           .rpc();
         expect.fail("Should have thrown an error");
       } catch (error) {
-        const errorMessage = error.error?.errorMessage || error.message;
-        expect(errorMessage).to.include("already in use");
+        expect(error.toString()).to.include("AlreadyInitialized");
       }
+      const registryAccount = await program.account.registry.fetch(datasetRegistry);
+      expect(registryAccount.admin.toString()).to.equal(admin.toString());
     });
   });
 
@@ -161,17 +175,20 @@ describe("Africa Research Base (ARB)", () => {
             dataUri,
             new anchor.BN(TEST_COLUMN_COUNT),
             new anchor.BN(TEST_ROW_COUNT),
-            TEST_QUALITY_SCORE
+            TEST_QUALITY_SCORE,
+            TEST_PREVIEW_HASH,
+            TEST_OPTIONS
           )
           .accounts({
             admin: admin,
-            user: researcher1.publicKey,
             contributor: researcher1.publicKey,
             dataset: datasetPda,
             registry: datasetRegistry,
+            registryConfig: registryConfig,
             reputation: repPda,
             systemProgram: SystemProgram.programId,
           })
+          .remainingAccounts(await tokenIndexAccounts(datasetRegistry, fileNameBuffer, program.programId))
           .signers([researcher1])
           .rpc();
 
@@ -188,9 +205,10 @@ describe("Africa Research Base (ARB)", () => {
         const registryAccount = await program.account.registry.fetch(datasetRegistry);
         expect(registryAccount.totalDatasets.toNumber()).to.be.greaterThan(0);
 
+        // Upload credit is staged until finalizeReputation runs after the grace period
         const reputationAccount = await program.account.reputation.fetch(repPda);
-        expect(reputationAccount.totalUploads).to.be.greaterThan(0);
-        expect(reputationAccount.totalQualityScore.toNumber()).to.be.greaterThan(0);
+        expect(reputationAccount.datasetCount).to.be.greaterThan(0);
+        expect(datasetAccount.reputationFinalized).to.equal(false);
       });
 
       it("Should create dataset with minimum file size (1 byte)", async () => {
@@ -217,17 +235,20 @@ describe("Africa Research Base (ARB)", () => {
             dataUri,
             new anchor.BN(1),
             new anchor.BN(1),
-            50
+            50,
+            TEST_PREVIEW_HASH,
+            TEST_OPTIONS
           )
           .accounts({
             admin: admin,
-            user: researcher2.publicKey,
             contributor: researcher2.publicKey,
             dataset: datasetPda,
             registry: datasetRegistry,
+            registryConfig: registryConfig,
             reputation: repPda,
             systemProgram: SystemProgram.programId,
           })
+          .remainingAccounts(await tokenIndexAccounts(datasetRegistry, fileNameBuffer, program.programId))
           .signers([researcher2])
           .rpc();
 
@@ -260,17 +281,20 @@ describe("Africa Research Base (ARB)", () => {
             dataUri,
             new anchor.BN(100),
             new anchor.BN(10000),
-            100
+            100,
+            TEST_PREVIEW_HASH,
+            TEST_OPTIONS
           )
           .accounts({
             admin: admin,
-            user: researcher3.publicKey,
             contributor: researcher3.publicKey,
             dataset: datasetPda,
             registry: datasetRegistry,
+            registryConfig: registryConfig,
             reputation: repPda,
             systemProgram: SystemProgram.programId,
           })
+          .remainingAccounts(await tokenIndexAccounts(datasetRegistry, fileNameBuffer, program.programId))
           .signers([researcher3])
           .rpc();
 
@@ -307,17 +331,20 @@ describe("Africa Research Base (ARB)", () => {
               dataUri,
               new anchor.BN(TEST_COLUMN_COUNT),
               new anchor.BN(TEST_ROW_COUNT),
-              TEST_QUALITY_SCORE
+              TEST_QUALITY_SCORE,
+              TEST_PREVIEW_HASH,
+              TEST_OPTIONS
             )
             .accounts({
               admin: admin,
-              user: researcher1.publicKey,
               contributor: researcher1.publicKey,
               dataset: datasetPda,
               registry: datasetRegistry,
+              registryConfig: registryConfig,
               reputation: repPda,
               systemProgram: SystemProgram.programId,
             })
+            .remainingAccounts(await tokenIndexAccounts(datasetRegistry, fileNameBuffer, program.programId))
             .signers([researcher1])
             .rpc();
           expect.fail("Should have thrown an error");
@@ -356,17 +383,20 @@ describe("Africa Research Base (ARB)", () => {
               dataUri,
               new anchor.BN(TEST_COLUMN_COUNT),
               new anchor.BN(TEST_ROW_COUNT),
-              150 // Invalid quality score
+              150, // Invalid quality score
+              TEST_PREVIEW_HASH,
+              TEST_OPTIONS
             )
             .accounts({
               admin: admin,
-              user: researcher2.publicKey,
               contributor: researcher2.publicKey,
               dataset: datasetPda,
               registry: datasetRegistry,
+              registryConfig: registryConfig,
               reputation: repPda,
               systemProgram: SystemProgram.programId,
             })
+            .remainingAccounts(await tokenIndexAccounts(datasetRegistry, fileNameBuffer, program.programId))
             .signers([researcher2])
             .rpc();
           expect.fail("Should have thrown an error");
@@ -404,17 +434,20 @@ describe("Africa Research Base (ARB)", () => {
               dataUri,
               new anchor.BN(TEST_COLUMN_COUNT),
               new anchor.BN(TEST_ROW_COUNT),
-              TEST_QUALITY_SCORE
+              TEST_QUALITY_SCORE,
+              TEST_PREVIEW_HASH,
+              TEST_OPTIONS
             )
             .accounts({
               admin: admin,
-              user: researcher3.publicKey,
               contributor: researcher3.publicKey,
               dataset: datasetPda,
               registry: datasetRegistry,
+              registryConfig: registryConfig,
               reputation: repPda,
               systemProgram: SystemProgram.programId,
             })
+            .remainingAccounts(await tokenIndexAccounts(datasetRegistry, fileNameBuffer, program.programId))
             .signers([researcher3])
             .rpc();
           expect.fail("Should have thrown an error");
@@ -456,17 +489,20 @@ describe("Africa Research Base (ARB)", () => {
             dataUri,
             new anchor.BN(TEST_COLUMN_COUNT),
             new anchor.BN(TEST_ROW_COUNT),
-            TEST_QUALITY_SCORE
+            TEST_QUALITY_SCORE,
+            TEST_PREVIEW_HASH,
+            TEST_OPTIONS
           )
           .accounts({
             admin: admin,
-            user: researcher1.publicKey,
             contributor: researcher1.publicKey,
             dataset: datasetPda,
             registry: datasetRegistry,
+            registryConfig: registryConfig,
             reputation: repPda,
             systemProgram: SystemProgram.programId,
           })
+          .remainingAccounts(await tokenIndexAccounts(datasetRegistry, fileNameBuffer, program.programId))
           .signers([researcher1])
           .rpc();
 
@@ -481,17 +517,20 @@ describe("Africa Research Base (ARB)", () => {
               createDataUri("https://drive.google.com/file/d/duplicate/view"),
               new anchor.BN(TEST_COLUMN_COUNT),
               new anchor.BN(TEST_ROW_COUNT),
-              TEST_QUALITY_SCORE
+              TEST_QUALITY_SCORE,
+              TEST_PREVIEW_HASH,
+              TEST_OPTIONS
             )
             .accounts({
               admin: admin,
-              user: researcher1.publicKey,
               contributor: researcher1.publicKey,
               dataset: datasetPda,
               registry: datasetRegistry,
+              registryConfig: registryConfig,
               reputation: repPda,
               systemProgram: SystemProgram.programId,
             })
+            .remainingAccounts(await tokenIndexAccounts(datasetRegistry, fileNameBuffer, program.programId))
             .signers([researcher1])
             .rpc();
           expect.fail("Should have thrown an error");
@@ -526,17 +565,20 @@ describe("Africa Research Base (ARB)", () => {
               dataUri,
               new anchor.BN(150), // Too many columns
               new anchor.BN(TEST_ROW_COUNT),
-              TEST_QUALITY_SCORE
+              TEST_QUALITY_SCORE,
+              TEST_PREVIEW_HASH,
+              TEST_OPTIONS
             )
             .accounts({
               admin: admin,
-              user: researcher1.publicKey,
               contributor: researcher1.publicKey,
               dataset: datasetPda,
               registry: datasetRegistry,
+              registryConfig: registryConfig,
               reputation: repPda,
               systemProgram: SystemProgram.programId,
             })
+            .remainingAccounts(await tokenIndexAccounts(datasetRegistry, fileNameBuffer, program.programId))
             .signers([researcher1])
             .rpc();
           expect.fail("Should have thrown an error");
@@ -553,7 +595,7 @@ describe("Africa Research Base (ARB)", () => {
 
   describe("Reputation System", () => {
     describe("Upload Reputation Updates", () => {
-      it("Should update reputation after successful dataset upload", async () => {
+      it("Should credit an upload only through finalizeReputation after the grace period", async () => {
         const contentHash = generateContentHash("reputation_upload_test_" + Date.now());
         const dataUri = createDataUri("https://drive.google.com/file/d/reputation/view");
         const fileNameBuffer = Buffer.from("reputation_test.csv", 'utf-8');
@@ -577,85 +619,48 @@ describe("Africa Research Base (ARB)", () => {
             dataUri,
             new anchor.BN(TEST_COLUMN_COUNT),
             new anchor.BN(TEST_ROW_COUNT),
-            90
+            90,
+            TEST_PREVIEW_HASH,
+            TEST_OPTIONS
           )
           .accounts({
             admin: admin,
-            user: researcher1.publicKey,
             contributor: researcher1.publicKey,
             dataset: datasetPda,
             registry: datasetRegistry,
+            registryConfig: registryConfig,
             reputation: repPda,
             systemProgram: SystemProgram.programId,
           })
+          .remainingAccounts(await tokenIndexAccounts(datasetRegistry, fileNameBuffer, program.programId))
           .signers([researcher1])
           .rpc();
 
         const initialRep = await program.account.reputation.fetch(repPda);
-        const initialScore = initialRep.reputationScore;
+        const initialUploads = initialRep.totalUploads;
 
-        await program.methods
-          .updateReputationUpload(90)
-          .accounts({
-            admin: admin,
-            contributor: researcher1.publicKey,
-            reputation: repPda,
-            systemProgram: SystemProgram.programId,
-          })
-          .signers([researcher1])
-          .rpc();
+        // The direct-credit instruction is gone, and finalizing inside the grace period is refused
+        expect((program.methods as any).updateReputationUpload).to.equal(undefined);
+        try {
+          await program.methods
+            .finalizeReputation()
+            .accounts({
+              payer: researcher1.publicKey,
+              registry: datasetRegistry,
+              registryConfig: registryConfig,
+              dataset: datasetPda,
+              reputation: repPda,
+            })
+            .signers([researcher1])
+            .rpc();
+          expect.fail("Should have thrown an error");
+        } catch (error) {
+          expect(error.toString()).to.include("GracePeriodActive");
+        }
 
         const updatedRep = await program.account.reputation.fetch(repPda);
-        expect(updatedRep.reputationScore).to.be.greaterThan(initialScore);
-        expect(updatedRep.totalUploads).to.be.greaterThan(0);
-      });
-
-      it("Should give higher reputation for better quality scores", async () => {
-        const [repPda1] = PublicKey.findProgramAddressSync(
-          [Buffer.from("reputation"), researcher2.publicKey.toBuffer()],
-          program.programId
-        );
-
-        const [repPda2] = PublicKey.findProgramAddressSync(
-          [Buffer.from("reputation"), researcher3.publicKey.toBuffer()],
-          program.programId
-        );
-
-        const initialRep1 = await program.account.reputation.fetch(repPda1);
-        const initialScore1 = initialRep1.reputationScore;
-
-        const initialRep2 = await program.account.reputation.fetch(repPda2);
-        const initialScore2 = initialRep2.reputationScore;
-
-        await program.methods
-          .updateReputationUpload(95)
-          .accounts({
-            admin: admin,
-            contributor: researcher2.publicKey,
-            reputation: repPda1,
-            systemProgram: SystemProgram.programId,
-          })
-          .signers([researcher2])
-          .rpc();
-
-        await program.methods
-          .updateReputationUpload(60)
-          .accounts({
-            admin: admin,
-            contributor: researcher3.publicKey,
-            reputation: repPda2,
-            systemProgram: SystemProgram.programId,
-          })
-          .signers([researcher3])
-          .rpc();
-
-        const highQualityRep = await program.account.reputation.fetch(repPda1);
-        const lowQualityRep = await program.account.reputation.fetch(repPda2);
-
-        const highQualityIncrease = highQualityRep.reputationScore - initialScore1;
-        const lowQualityIncrease = lowQualityRep.reputationScore - initialScore2;
-
-        expect(highQualityIncrease).to.be.greaterThan(lowQualityIncrease);
+        expect(updatedRep.totalUploads).to.equal(initialUploads);
+        expect((await program.account.dataset.fetch(datasetPda)).reputationFinalized).to.equal(false);
       });
     });
 
@@ -684,17 +689,20 @@ describe("Africa Research Base (ARB)", () => {
             dataUri,
             new anchor.BN(TEST_COLUMN_COUNT),
             new anchor.BN(TEST_ROW_COUNT),
-            TEST_QUALITY_SCORE
+            TEST_QUALITY_SCORE,
+            TEST_PREVIEW_HASH,
+            TEST_OPTIONS
           )
           .accounts({
             admin: admin,
-            user: researcher1.publicKey,
             contributor: researcher1.publicKey,
             dataset: datasetPda,
             registry: datasetRegistry,
+            registryConfig: registryConfig,
             reputation: repPda,
             systemProgram: SystemProgram.programId,
           })
+          .remainingAccounts(await tokenIndexAccounts(datasetRegistry, fileNameBuffer, program.programId))
           .signers([researcher1])
           .rpc();
 
@@ -709,6 +717,7 @@ describe("Africa Research Base (ARB)", () => {
             contributor: researcher1.publicKey,
             reputation: repPda,
             dataset: datasetPda,
+            registry: datasetRegistry,
             systemProgram: SystemProgram.programId,
           })
           .signers([researcher1])
@@ -743,17 +752,20 @@ describe("Africa Research Base (ARB)", () => {
             dataUri,
             new anchor.BN(TEST_COLUMN_COUNT),
             new anchor.BN(TEST_ROW_COUNT),
-            TEST_QUALITY_SCORE
+            TEST_QUALITY_SCORE,
+            TEST_PREVIEW_HASH,
+            TEST_OPTIONS
           )
           .accounts({
             admin: admin,
-            user: researcher2.publicKey,
             contributor: researcher2.publicKey,
             dataset: datasetPda,
             registry: datasetRegistry,
+            registryConfig: registryConfig,
             reputation: repPda,
             systemProgram: SystemProgram.programId,
           })
+          .remainingAccounts(await tokenIndexAccounts(datasetRegistry, fileNameBuffer, program.programId))
           .signers([researcher2])
           .rpc();
 
@@ -805,17 +817,20 @@ describe("Africa Research Base (ARB)", () => {
             dataUri,
             new anchor.BN(TEST_COLUMN_COUNT),
             new anchor.BN(TEST_ROW_COUNT),
-            TEST_QUALITY_SCORE
+            TEST_QUALITY_SCORE,
+            TEST_PREVIEW_HASH,
+            TEST_OPTIONS
           )
           .accounts({
             admin: admin,
-            user: researcher3.publicKey,
             contributor: researcher3.publicKey,
             dataset: datasetPda,
             registry: datasetRegistry,
+            registryConfig: registryConfig,
             reputation: repPda,
             systemProgram: SystemProgram.programId,
           })
+          .remainingAccounts(await tokenIndexAccounts(datasetRegistry, fileNameBuffer, program.programId))
           .signers([researcher3])
           .rpc();
 
@@ -829,6 +844,7 @@ describe("Africa Research Base (ARB)", () => {
             contributor: researcher3.publicKey,
             reputation: repPda,
             dataset: datasetPda,
+            registry: datasetRegistry,
             systemProgram: SystemProgram.programId,
           });
       });
@@ -0,0 +1,70 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { PublicKey } from '@solana/web3.js';
+import { expect } from 'chai';
+import * as fs from 'fs';
+import * as path from 'path';
+import { AfricaResearchBase } from '../target/types/africa_research_base';
+
+// `cargo test` writes the fixture accounts built in src/state/fixtures.rs; decoding them with the IDL
+// coder checks the client's view of each layout against the program's
+describe("Account layouts", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.AfricaResearchBase as Program<AfricaResearchBase>;
+  const fixturesDir = process.env.ACCOUNT_FIXTURES_DIR ?? path.join(__dirname, '..', 'target', 'account-fixtures');
+  const key = (byte: number) => new PublicKey(Buffer.alloc(32, byte));
+
+  const decode = (name: string, file: string) => {
+    const bytes = fs.readFileSync(path.join(fixturesDir, file));
+    return program.coder.accounts.decode(name, bytes);
+  };
+
+  before(function () {
+    if (!fs.existsSync(path.join(fixturesDir, 'dataset.bin'))) {
+      this.skip(); // Run `cargo test` first
+    }
+  });
+
+  it("Should decode the Dataset fixture", () => {
+    const dataset = decode("dataset", "dataset.bin");
+
+    expect(dataset.id.toBase58()).to.equal(key(1).toBase58());
+    expect(dataset.coContributors.map((k: PublicKey) => k.toBase58())).to.deep.equal([key(4).toBase58(), key(5).toBase58()]);
+    expect(Buffer.from(dataset.fileName).toString()).to.equal("rainfall_2020.csv");
+    expect(Buffer.from(dataset.dataUri).toString()).to.equal("ipfs://QmFixture");
+    expect(dataset.qualityScore).to.equal(87);
+    expect(dataset.lastUpdated.toNumber()).to.equal(1_700_000_100);
+    expect(Buffer.from(dataset.title).toString()).to.equal("Rainfall Accra 2020");
+    expect(dataset.supersedes.toBase58()).to.equal(key(13).toBase58());
+    expect(dataset.supersededBy).to.equal(null);
+    expect(dataset.featuredUntil.toNumber()).to.equal(1_702_000_000);
+    expect(dataset.spamDeposit.toNumber()).to.equal(2_000_000);
+    expect([Buffer.from(dataset.language).toString(), dataset.languageMismatch]).to.deep.equal(["sw", true]);
+    expect(dataset.creator.toBase58()).to.equal(key(3).toBase58());
+    expect(dataset.coCredits.map((c: any) => [c.contributor.toBase58(), c.quality])).to.deep.equal([[key(4).toBase58(), 29]]);
+    expect([dataset.finalized, dataset.bump, dataset.schemaVersion]).to.deep.equal([true, 254, 4]);
+  });
+
+  it("Should decode the Reputation fixture", () => {
+    const reputation = decode("reputation", "reputation.bin");
+
+    expect(reputation.contributor.toBase58()).to.equal(key(3).toBase58());
+    expect(reputation.totalDownloads.toNumber()).to.equal(320);
+    expect(reputation.fieldScores).to.deep.equal([{ field: 1, score: 410 }, { field: 3, score: 96 }]);
+    expect(reputation.rotatedTo.toBase58()).to.equal(PublicKey.default.toBase58());
+    expect([reputation.reputationScore, reputation.anonymous, reputation.bump]).to.deep.equal([1_293, true, 253]);
+  });
+
+  it("Should decode the Registry fixture", () => {
+    const registry = decode("registry", "registry.bin");
+
+    expect(registry.admin.toBase58()).to.equal(key(20).toBase58());
+    expect(registry.platformFeeBps).to.equal(250);
+    expect(registry.qualityScale).to.equal(5);
+    expect(registry.institutionOracle.toBase58()).to.equal(key(24).toBase58());
+    expect(registry.spamDeposit.toNumber()).to.equal(1_000_000);
+    expect(registry.bump).to.equal(252);
+  });
+});
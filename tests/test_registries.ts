@@ -0,0 +1,288 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { Ed25519Program, Keypair, SystemProgram, SYSVAR_INSTRUCTIONS_PUBKEY } from '@solana/web3.js';
+import { expect } from 'chai';
+import { AfricaResearchBase } from '../target/types/africa_research_base';
+import { createDataset, createRegistry, createReputation, fundedKeypair } from './fixtures';
+import {
+  deriveDatasetPDA,
+  deriveManifestImportPDA,
+  deriveRegistryConfigPDA,
+  deriveRegistryPDA,
+  deriveReputationPDA,
+  deriveTreasuryPDA,
+  manifestHash,
+  registryName,
+  tokenIndexAccounts,
+} from './utils';
+
+describe("Multiple registries", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.AfricaResearchBase as Program<AfricaResearchBase>;
+  const admin = provider.wallet.publicKey;
+  const researcher = Keypair.generate();
+
+  const healthName = registryName("health-" + Date.now());
+  const climateName = registryName("climate-" + Date.now());
+
+  before(async () => {
+    const signature = await provider.connection.requestAirdrop(
+      researcher.publicKey,
+      2 * anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(signature);
+
+    const [repPda] = await deriveReputationPDA(researcher.publicKey, program.programId);
+    await program.methods
+      .initializeReputation()
+      .accounts({
+        admin: admin,
+        user: researcher.publicKey,
+        contributor: researcher.publicKey,
+        reputation: repPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([researcher])
+      .rpc();
+  });
+
+  it("Should create two independent registries for the same admin", async () => {
+    for (const name of [healthName, climateName]) {
+      const [registryPda] = await deriveRegistryPDA(admin, name, program.programId);
+      const [treasury] = await deriveTreasuryPDA(registryPda, program.programId);
+      const [registryConfig] = await deriveRegistryConfigPDA(registryPda, program.programId);
+      await program.methods
+        .initializeRegistry(name)
+        .accounts({
+          admin: admin,
+          user: admin,
+          contributor: admin,
+          registry: registryPda,
+          treasury: treasury,
+          registryConfig: registryConfig,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const registryAccount = await program.account.registry.fetch(registryPda);
+      expect(registryAccount.name).to.deep.equal(name);
+      expect(registryAccount.totalDatasets.toNumber()).to.equal(0);
+    }
+  });
+
+  it("Should attach a dataset to exactly one registry", async () => {
+    const [healthRegistry] = await deriveRegistryPDA(admin, healthName, program.programId);
+    const [climateRegistry] = await deriveRegistryPDA(admin, climateName, program.programId);
+    const [healthConfig] = await deriveRegistryConfigPDA(healthRegistry, program.programId);
+    const [repPda] = await deriveReputationPDA(researcher.publicKey, program.programId);
+    const [datasetPda] = await deriveDatasetPDA(researcher.publicKey, 0, program.programId);
+
+    const dataUri = Buffer.from("ipfs://registry-test");
+
+    await program.methods
+      .createDataset(
+        Array.from(Buffer.alloc(32, 7)),
+        Buffer.from('{"registry": "health"}'),
+        Buffer.from("malaria_cases.csv"),
+        new anchor.BN(2048),
+        dataUri,
+        new anchor.BN(4),
+        new anchor.BN(10),
+        70,
+        Array.from(Buffer.alloc(32)),
+        { expiresAt: null, thumbnailUri: null, featured: false, coContributors: [], researchFields: [], reportAllErrors: false, title: null, licenseExpiresAt: null, allowZeroQuality: false, featuredUntil: null, language: null, manifestEntry: null }
+      )
+      .accounts({
+        admin: admin,
+        contributor: researcher.publicKey,
+        dataset: datasetPda,
+        registry: healthRegistry,
+        registryConfig: healthConfig,
+        reputation: repPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(await tokenIndexAccounts(healthRegistry, "malaria_cases.csv", program.programId))
+      .signers([researcher])
+      .rpc();
+
+    const datasetAccount = await program.account.dataset.fetch(datasetPda);
+    expect(datasetAccount.registry.toString()).to.equal(healthRegistry.toString());
+    expect((await program.account.registry.fetch(healthRegistry)).totalDatasets.toNumber()).to.equal(1);
+    expect((await program.account.registry.fetch(climateRegistry)).totalDatasets.toNumber()).to.equal(0);
+
+    try {
+      await program.methods
+        .updateReputationDownload()
+        .accounts({
+          admin: admin,
+          contributor: researcher.publicKey,
+          reputation: repPda,
+          dataset: datasetPda,
+          registry: climateRegistry,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([researcher])
+        .rpc();
+      expect.fail("Should have rejected a dataset from another registry");
+    } catch (error) {
+      expect(error.toString()).to.include("ConstraintHasOne");
+    }
+  });
+
+  it("Should pass verify_layout for freshly initialized accounts", async () => {
+    const [healthRegistry] = await deriveRegistryPDA(admin, healthName, program.programId);
+    const [repPda] = await deriveReputationPDA(researcher.publicKey, program.programId);
+    const [datasetPda] = await deriveDatasetPDA(researcher.publicKey, 0, program.programId);
+
+    for (const account of [healthRegistry, repPda, datasetPda]) {
+      await program.methods.verifyLayout().accounts({ account: account }).rpc();
+    }
+
+    const [registryConfig] = await deriveRegistryConfigPDA(healthRegistry, program.programId);
+    try {
+      await program.methods.verifyLayout().accounts({ account: registryConfig }).rpc();
+      expect.fail("Should have thrown an error");
+    } catch (error) {
+      expect(error.toString()).to.include("LayoutMismatch");
+    }
+  });
+
+  describe("field weights", () => {
+    it("Should store a registry's research field weights and drop ones reset to the default", async () => {
+      const fixture = await createRegistry(program, admin, "weights-" + Date.now());
+      const setWeight = (field: number, weight: number) =>
+        program.methods
+          .setFieldWeight(field, weight)
+          .accounts({ admin: admin, registry: fixture.registry, registryConfig: fixture.registryConfig })
+          .rpc();
+
+      await setWeight(3, 4);
+      await setWeight(7, 2);
+      await setWeight(7, 1);
+
+      const config = await program.account.registryConfig.fetch(fixture.registryConfig);
+      expect(config.fieldWeights).to.deep.equal([{ field: 3, weight: 4 }]);
+
+      try {
+        await setWeight(3, 0);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidFieldWeight");
+      }
+    });
+  });
+
+  describe("treasury_withdraw", () => {
+    it("Should hold the admin to the withdrawal cap, which starts at zero", async () => {
+      const fixture = await createRegistry(program, admin, "treasury-" + Date.now());
+      const destination = Keypair.generate().publicKey;
+      await provider.sendAndConfirm(
+        new anchor.web3.Transaction().add(
+          SystemProgram.transfer({ fromPubkey: admin, toPubkey: fixture.treasury, lamports: 1_000_000 })
+        )
+      );
+
+      try {
+        await program.methods
+          .treasuryWithdraw(new anchor.BN(500_000))
+          .accounts({ admin: admin, registry: fixture.registry, treasury: fixture.treasury, destination: destination })
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("WithdrawalCapExceeded");
+      }
+      expect(await provider.connection.getBalance(destination)).to.equal(0);
+    });
+  });
+
+    it("Should import a manifest signed by the institution oracle once", async () => {
+      const fixture = await createRegistry(program, admin, "institution-" + Date.now());
+      const oracle = Keypair.generate();
+      const institution = await fundedKeypair(provider);
+      await program.methods
+        .setInstitutionOracle(oracle.publicKey)
+        .accounts({ admin: admin, registry: fixture.registry })
+        .rpc();
+
+      const contentHashes = [Keypair.generate(), Keypair.generate()].map((k) => Array.from(k.publicKey.toBytes()));
+      const hash = manifestHash(fixture.registry, institution.publicKey, contentHashes);
+      const [manifest] = await deriveManifestImportPDA(fixture.registry, hash, program.programId);
+
+      const importManifest = () =>
+        program.methods
+          .importDatasetFromSignedManifest(Array.from(hash), contentHashes)
+          .accounts({
+            institution: institution.publicKey,
+            registry: fixture.registry,
+            manifest: manifest,
+            instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+            systemProgram: SystemProgram.programId,
+          })
+          .preInstructions([Ed25519Program.createInstructionWithPrivateKey({ privateKey: oracle.secretKey, message: hash })])
+          .signers([institution])
+          .rpc();
+
+      await importManifest();
+
+      const account = await program.account.manifestImport.fetch(manifest);
+      expect(account.institution.toString()).to.equal(institution.publicKey.toString());
+      expect(account.contentHashes).to.deep.equal(contentHashes);
+
+      try {
+        await importManifest();
+        expect.fail("Should have rejected a replayed manifest");
+      } catch (error) {
+        expect(error.toString()).to.include("already in use");
+      }
+    });
+
+    it("Should let the institution claim each manifest entry once as its own dataset", async () => {
+      const fixture = await createRegistry(program, admin, "claims-" + Date.now());
+      const oracle = Keypair.generate();
+      const institution = await fundedKeypair(provider);
+      await createReputation(program, admin, institution);
+      await program.methods
+        .setInstitutionOracle(oracle.publicKey)
+        .accounts({ admin: admin, registry: fixture.registry })
+        .rpc();
+
+      const contentHashes = [Keypair.generate(), Keypair.generate()].map((k) => Array.from(k.publicKey.toBytes()));
+      const hash = manifestHash(fixture.registry, institution.publicKey, contentHashes);
+      const [manifest] = await deriveManifestImportPDA(fixture.registry, hash, program.programId);
+      await program.methods
+        .importDatasetFromSignedManifest(Array.from(hash), contentHashes)
+        .accounts({
+          institution: institution.publicKey,
+          registry: fixture.registry,
+          manifest: manifest,
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+          systemProgram: SystemProgram.programId,
+        })
+        .preInstructions([Ed25519Program.createInstructionWithPrivateKey({ privateKey: oracle.secretKey, message: hash })])
+        .signers([institution])
+        .rpc();
+
+      const claim = (entry: number, fileName: string) =>
+        createDataset(program, admin, fixture.registry, institution, {
+          contentHash: contentHashes[entry],
+          fileName,
+          manifest,
+          options: { manifestEntry: entry },
+        });
+
+      const dataset = await claim(1, "claimed.csv");
+      expect((await program.account.dataset.fetch(dataset)).contributor.toString()).to.equal(institution.publicKey.toString());
+      expect((await program.account.manifestImport.fetch(manifest)).claimed).to.equal(0b10);
+
+      try {
+        await claim(1, "claimed-again.csv");
+        expect.fail("Should have rejected a second claim of the entry");
+      } catch (error) {
+        expect(error.toString()).to.include("ManifestEntryClaimed");
+      }
+      expect((await program.account.manifestImport.fetch(manifest)).claimed).to.equal(0b10);
+    });
+  });
+});
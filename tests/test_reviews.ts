@@ -0,0 +1,159 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import { expect } from 'chai';
+import { AfricaResearchBase } from '../target/types/africa_research_base';
+import { createDataset, createRegistry, createReputation, fundedKeypair, RegistryFixture } from './fixtures';
+import { deriveReputationPDA } from './utils';
+
+describe("Curated review workflow", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.AfricaResearchBase as Program<AfricaResearchBase>;
+  const admin = provider.wallet.publicKey;
+
+  let fixture: RegistryFixture;
+  let contributor: Keypair;
+  let reviewer: Keypair;
+  let downloader: Keypair;
+
+  const download = async (dataset: PublicKey) => {
+    const [reputation] = await deriveReputationPDA(contributor.publicKey, program.programId);
+    await program.methods
+      .recordDownload(new anchor.BN(0))
+      .accounts({
+        downloader: downloader.publicKey,
+        registry: fixture.registry,
+        dataset: dataset,
+        reputation: reputation,
+        treasury: fixture.treasury,
+        feeVault: null,
+        receipt: null,
+        downloadRecord: null,
+        earnings: null,
+        contributor: null,
+      })
+      .signers([downloader])
+      .rpc();
+  };
+
+  const assign = async (dataset: PublicKey) => {
+    await program.methods
+      .assignReviewer(reviewer.publicKey)
+      .accounts({ admin: admin, registry: fixture.registry, dataset: dataset })
+      .rpc();
+  };
+
+  before(async () => {
+    fixture = await createRegistry(program, admin, "curated-" + Date.now());
+    contributor = await fundedKeypair(provider);
+    reviewer = await fundedKeypair(provider);
+    downloader = await fundedKeypair(provider);
+    await createReputation(program, admin, contributor);
+
+    await program.methods
+      .setCurated(true)
+      .accounts({ admin: admin, registry: fixture.registry })
+      .rpc();
+  });
+
+  it("Should only serve a dataset after it is assigned, reviewed and approved", async () => {
+    const dataset = await createDataset(program, admin, fixture.registry, contributor);
+
+    try {
+      await download(dataset);
+      expect.fail("Should have thrown an error");
+    } catch (error) {
+      expect(error.toString()).to.include("DatasetNotApproved");
+    }
+
+    await assign(dataset);
+    const noteHash = Array.from(Buffer.alloc(32, 5));
+    await program.methods
+      .submitReview(true, noteHash)
+      .accounts({ reviewer: reviewer.publicKey, dataset: dataset })
+      .signers([reviewer])
+      .rpc();
+
+    const reviewed = await program.account.dataset.fetch(dataset);
+    expect(reviewed.reviewStatus).to.equal(2);
+    expect(reviewed.reviewNoteHash).to.deep.equal(noteHash);
+
+    await download(dataset);
+    expect((await program.account.dataset.fetch(dataset)).downloadCount).to.equal(1);
+  });
+
+  it("Should reject a review from anyone but the assigned reviewer", async () => {
+    const dataset = await createDataset(program, admin, fixture.registry, contributor);
+    await assign(dataset);
+
+    try {
+      await program.methods
+        .submitReview(true, Array.from(Buffer.alloc(32)))
+        .accounts({ reviewer: contributor.publicKey, dataset: dataset })
+        .signers([contributor])
+        .rpc();
+      expect.fail("Should have thrown an error");
+    } catch (error) {
+      expect(error.toString()).to.include("UnauthorizedReviewer");
+    }
+  });
+
+  describe("review SLA", () => {
+    let sla: RegistryFixture;
+
+    const setReviewSla = (seconds: number) =>
+      program.methods
+        .setReviewSla(new anchor.BN(seconds))
+        .accounts({ admin: admin, registry: sla.registry })
+        .rpc();
+
+    const autoApprove = (dataset: PublicKey) =>
+      program.methods
+        .autoApproveExpired()
+        .accounts({ registry: sla.registry, dataset: dataset })
+        .rpc();
+
+    before(async () => {
+      sla = await createRegistry(program, admin, "review-sla-" + Date.now());
+      await program.methods
+        .setCurated(true)
+        .accounts({ admin: admin, registry: sla.registry })
+        .rpc();
+      await setReviewSla(2);
+    });
+
+    it("Should auto-approve an unreviewed dataset once its deadline passes", async () => {
+      const dataset = await createDataset(program, admin, sla.registry, contributor);
+      const { reviewDeadline, uploadTimestamp } = await program.account.dataset.fetch(dataset);
+      expect(reviewDeadline.toNumber()).to.equal(uploadTimestamp.toNumber() + 2);
+
+      try {
+        await autoApprove(dataset);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("ReviewDeadlineNotReached");
+      }
+
+      await new Promise((resolve) => setTimeout(resolve, 4000));
+      await autoApprove(dataset);
+
+      expect((await program.account.dataset.fetch(dataset)).reviewStatus).to.equal(2);
+    });
+
+    it("Should refuse to auto-approve once the admin disables it", async () => {
+      const dataset = await createDataset(program, admin, sla.registry, contributor);
+      await new Promise((resolve) => setTimeout(resolve, 4000));
+      await setReviewSla(0);
+
+      try {
+        await autoApprove(dataset);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("AutoApproveDisabled");
+      }
+      expect((await program.account.dataset.fetch(dataset)).reviewStatus).to.equal(0);
+    });
+  });
+});
@@ -0,0 +1,62 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import { expect } from 'chai';
+import { AfricaResearchBase } from '../target/types/africa_research_base';
+import { createDataset, createRegistry, createReputation, fundedKeypair } from './fixtures';
+import { deriveDatasetPDA, deriveRegistryPDA, deriveReputationPDA, registryName } from './utils';
+
+// The derive helpers in utils.ts mirror src/seeds.rs; these check them against the addresses
+// Anchor resolves from the IDL seeds and the ones the on-chain constraints accept
+describe("PDA seeds", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.AfricaResearchBase as Program<AfricaResearchBase>;
+  const admin = provider.wallet.publicKey;
+
+  it("Should match the reference vectors in seeds.rs", async () => {
+    const key = new PublicKey(Buffer.alloc(32, 1));
+
+    const [registry, registryBump] = await deriveRegistryPDA(key, registryName("main"), program.programId);
+    const [dataset, datasetBump] = await deriveDatasetPDA(key, 7, program.programId);
+    const [reputation, reputationBump] = await deriveReputationPDA(key, program.programId);
+
+    expect([registry.toBase58(), registryBump]).to.deep.equal(["82T2a4nSRoBSBqMmztSvcTL8JP7RadrmMYHRLmoK7NfH", 254]);
+    expect([dataset.toBase58(), datasetBump]).to.deep.equal(["FM1T8wNbe26ScjZpPQQCWjKRpmhAaFrnPdgnftRnXeHK", 255]);
+    expect([reputation.toBase58(), reputationBump]).to.deep.equal(["5awzLym73BScFunwaWrpf2kp2HFsVhgwxtYmKKntr6Qk", 255]);
+  });
+
+  it("Should match the registry and reputation addresses Anchor resolves", async () => {
+    const contributor = Keypair.generate().publicKey;
+    const name = registryName("seeds-" + Date.now());
+
+    const registryAccounts = await program.methods
+      .initializeRegistry(name)
+      .accounts({ admin: admin, user: admin, contributor: admin })
+      .pubkeys();
+    const reputationAccounts = await program.methods
+      .initializeReputation()
+      .accounts({ admin: admin, user: contributor, contributor: contributor })
+      .pubkeys();
+
+    const [registry] = await deriveRegistryPDA(admin, name, program.programId);
+    const [reputation] = await deriveReputationPDA(contributor, program.programId);
+    expect(registryAccounts.registry.toBase58()).to.equal(registry.toBase58());
+    expect(reputationAccounts.reputation.toBase58()).to.equal(reputation.toBase58());
+  });
+
+  it("Should derive dataset addresses the create_dataset constraints accept", async () => {
+    const fixture = await createRegistry(program, admin, "seeds-datasets-" + Date.now());
+    const contributor = await fundedKeypair(provider);
+    const reputation = await createReputation(program, admin, contributor);
+
+    // createDataset passes deriveDatasetPDA's address explicitly, so a mismatch fails ConstraintSeeds
+    for (let index = 0; index < 2; index++) {
+      const dataset = await createDataset(program, admin, fixture.registry, contributor);
+      const [expected] = await deriveDatasetPDA(contributor.publicKey, index, program.programId);
+      expect(dataset.toBase58()).to.equal(expected.toBase58());
+    }
+    expect((await program.account.reputation.fetch(reputation)).datasetCount).to.equal(2);
+  });
+});
@@ -1,10 +1,14 @@
 import * as anchor from '@coral-xyz/anchor';
 import { BN } from '@coral-xyz/anchor';
+import { sha256 } from 'js-sha256';
 
 export const PROGRAM_SEED = 'africa_research_base';
 export const REGISTRY_SEED = 'registry';
 export const DATASET_SEED = 'dataset';
 export const REPUTATION_SEED = 'reputation';
+export const TREASURY_SEED = 'treasury';
+export const VAULT_SEED = 'vault';
+export const RECEIPT_SEED = 'receipt';
 
 export const findProgramAddress = async (
   seeds: Array<Buffer | Uint8Array>,
@@ -13,26 +17,35 @@ export const findProgramAddress = async (
   return await anchor.web3.PublicKey.findProgramAddress(seeds, programId);
 };
 
+export const registryName = (label: string): number[] => {
+  const name = Buffer.alloc(32);
+  Buffer.from(label, 'utf-8').copy(name);
+  return Array.from(name);
+};
+
 export const deriveRegistryPDA = async (
   admin: anchor.web3.PublicKey,
+  name: number[],
   programId: anchor.web3.PublicKey
 ): Promise<[anchor.web3.PublicKey, number]> => {
   return await findProgramAddress(
-    [Buffer.from(REGISTRY_SEED), admin.toBuffer()],
+    [Buffer.from(REGISTRY_SEED), admin.toBuffer(), Buffer.from(name)],
     programId
   );
 };
 
 export const deriveDatasetPDA = async (
   contributor: anchor.web3.PublicKey,
-  //contentHash: number[],
+  datasetIndex: number,
   programId: anchor.web3.PublicKey
 ): Promise<[anchor.web3.PublicKey, number]> => {
+  const index = Buffer.alloc(4);
+  index.writeUInt32LE(datasetIndex);
   return await findProgramAddress(
     [
       Buffer.from(DATASET_SEED),
       contributor.toBuffer(),
-      //Buffer.from(contentHash)
+      index
     ],
     programId
   );
@@ -48,6 +61,381 @@ export const deriveReputationPDA = async (
   );
 };
 
+export const deriveTreasuryPDA = async (
+  registry: anchor.web3.PublicKey,
+  programId: anchor.web3.PublicKey
+): Promise<[anchor.web3.PublicKey, number]> => {
+  return await findProgramAddress(
+    [Buffer.from(TREASURY_SEED), registry.toBuffer()],
+    programId
+  );
+};
+
+export const deriveVaultPDA = async (
+  dataset: anchor.web3.PublicKey,
+  programId: anchor.web3.PublicKey
+): Promise<[anchor.web3.PublicKey, number]> => {
+  return await findProgramAddress(
+    [Buffer.from(VAULT_SEED), dataset.toBuffer()],
+    programId
+  );
+};
+
+export const deriveReceiptPDA = async (
+  dataset: anchor.web3.PublicKey,
+  downloader: anchor.web3.PublicKey,
+  slot: number,
+  programId: anchor.web3.PublicKey
+): Promise<[anchor.web3.PublicKey, number]> => {
+  const slotBytes = Buffer.alloc(8);
+  slotBytes.writeBigUInt64LE(BigInt(slot));
+  return await findProgramAddress(
+    [Buffer.from(RECEIPT_SEED), dataset.toBuffer(), downloader.toBuffer(), slotBytes],
+    programId
+  );
+};
+
+export const deriveCitationPDA = async (
+  dataset: anchor.web3.PublicKey,
+  citer: anchor.web3.PublicKey,
+  programId: anchor.web3.PublicKey
+): Promise<[anchor.web3.PublicKey, number]> => {
+  return await findProgramAddress(
+    [Buffer.from('citation'), dataset.toBuffer(), citer.toBuffer()],
+    programId
+  );
+};
+
+export const deriveCitationPairPDA = async (
+  citer: anchor.web3.PublicKey,
+  cited: anchor.web3.PublicKey,
+  programId: anchor.web3.PublicKey
+): Promise<[anchor.web3.PublicKey, number]> => {
+  return await findProgramAddress(
+    [Buffer.from('citation_pair'), citer.toBuffer(), cited.toBuffer()],
+    programId
+  );
+};
+
+export const deriveExternalCitationPDA = async (
+  dataset: anchor.web3.PublicKey,
+  publicationHash: number[],
+  programId: anchor.web3.PublicKey
+): Promise<[anchor.web3.PublicKey, number]> => {
+  return await findProgramAddress(
+    [Buffer.from('external_citation'), dataset.toBuffer(), Buffer.from(publicationHash)],
+    programId
+  );
+};
+
+export const deriveRegistryConfigPDA = async (
+  registry: anchor.web3.PublicKey,
+  programId: anchor.web3.PublicKey
+): Promise<[anchor.web3.PublicKey, number]> => {
+  return await findProgramAddress(
+    [Buffer.from('registry_config'), registry.toBuffer()],
+    programId
+  );
+};
+
+export const deriveAccessNoncePDA = async (
+  receipt: anchor.web3.PublicKey,
+  programId: anchor.web3.PublicKey
+): Promise<[anchor.web3.PublicKey, number]> => {
+  return await findProgramAddress(
+    [Buffer.from('access_nonce'), receipt.toBuffer()],
+    programId
+  );
+};
+
+export const derivePenaltyRecordPDA = async (
+  dataset: anchor.web3.PublicKey,
+  programId: anchor.web3.PublicKey
+): Promise<[anchor.web3.PublicKey, number]> => {
+  return await findProgramAddress(
+    [Buffer.from('penalty'), dataset.toBuffer()],
+    programId
+  );
+};
+
+export const deriveAvailabilityReportPDA = async (
+  dataset: anchor.web3.PublicKey,
+  programId: anchor.web3.PublicKey
+): Promise<[anchor.web3.PublicKey, number]> => {
+  return await findProgramAddress(
+    [Buffer.from('availability'), dataset.toBuffer()],
+    programId
+  );
+};
+
+export const deriveDelegationPDA = async (
+  delegator: anchor.web3.PublicKey,
+  programId: anchor.web3.PublicKey
+): Promise<[anchor.web3.PublicKey, number]> => {
+  return await findProgramAddress(
+    [Buffer.from('delegation'), delegator.toBuffer()],
+    programId
+  );
+};
+
+export const deriveRatingPDA = async (
+  dataset: anchor.web3.PublicKey,
+  rater: anchor.web3.PublicKey,
+  programId: anchor.web3.PublicKey
+): Promise<[anchor.web3.PublicKey, number]> => {
+  return await findProgramAddress(
+    [Buffer.from('rating'), dataset.toBuffer(), rater.toBuffer()],
+    programId
+  );
+};
+
+export const deriveDownloadRecordPDA = async (
+  dataset: anchor.web3.PublicKey,
+  downloader: anchor.web3.PublicKey,
+  programId: anchor.web3.PublicKey
+): Promise<[anchor.web3.PublicKey, number]> => {
+  return await findProgramAddress(
+    [Buffer.from('download_record'), dataset.toBuffer(), downloader.toBuffer()],
+    programId
+  );
+};
+
+// Mirrors TokenIndex::tokenize: lowercase ASCII alphanumeric words of 2+ chars, first 4 distinct
+export const tokenize = (title: Buffer | string): Buffer[] => {
+  const tokens: Buffer[] = [];
+  for (const word of Buffer.from(title).toString('latin1').split(/[^A-Za-z0-9]+/)) {
+    if (word.length < 2) continue;
+    const token = Buffer.from(sha256.arrayBuffer(word.toLowerCase())).subarray(0, 8);
+    if (tokens.some((existing) => existing.equals(token))) continue;
+    tokens.push(token);
+    if (tokens.length === 4) break;
+  }
+  return tokens;
+};
+
+export const deriveTokenIndexPDA = async (
+  registry: anchor.web3.PublicKey,
+  token: Buffer,
+  programId: anchor.web3.PublicKey
+): Promise<[anchor.web3.PublicKey, number]> => {
+  return await findProgramAddress(
+    [Buffer.from('token_index'), registry.toBuffer(), token],
+    programId
+  );
+};
+
+// Remaining accounts expected by create_dataset / close_dataset
+export const tokenIndexAccounts = async (
+  registry: anchor.web3.PublicKey,
+  title: Buffer | string,
+  programId: anchor.web3.PublicKey
+): Promise<anchor.web3.AccountMeta[]> => {
+  const metas: anchor.web3.AccountMeta[] = [];
+  for (const token of tokenize(title)) {
+    const [pubkey] = await deriveTokenIndexPDA(registry, token, programId);
+    metas.push({ pubkey, isSigner: false, isWritable: true });
+  }
+  return metas;
+};
+
+export const deriveDownloadReportPDA = async (
+  registry: anchor.web3.PublicKey,
+  reportId: number,
+  programId: anchor.web3.PublicKey
+): Promise<[anchor.web3.PublicKey, number]> => {
+  return await findProgramAddress(
+    [Buffer.from('download_report'), registry.toBuffer(), new BN(reportId).toArrayLike(Buffer, 'le', 8)],
+    programId
+  );
+};
+
+export const deriveDownloadClaimPDA = async (
+  report: anchor.web3.PublicKey,
+  dataset: anchor.web3.PublicKey,
+  programId: anchor.web3.PublicKey
+): Promise<[anchor.web3.PublicKey, number]> => {
+  return await findProgramAddress(
+    [Buffer.from('download_claim'), report.toBuffer(), dataset.toBuffer()],
+    programId
+  );
+};
+
+// Mirror DownloadReport::leaf / DownloadReport::node
+export const downloadLeaf = (dataset: anchor.web3.PublicKey, delta: number): Buffer => {
+  const deltaBytes = Buffer.alloc(4);
+  deltaBytes.writeUInt32LE(delta);
+  return Buffer.from(sha256.arrayBuffer(Buffer.concat([Buffer.from([0]), dataset.toBuffer(), deltaBytes])));
+};
+
+export const merkleNode = (a: Buffer, b: Buffer): Buffer => {
+  const [low, high] = Buffer.compare(a, b) <= 0 ? [a, b] : [b, a];
+  return Buffer.from(sha256.arrayBuffer(Buffer.concat([Buffer.from([1]), low, high])));
+};
+
+export const deriveEndorsementPDA = async (
+  dataset: anchor.web3.PublicKey,
+  endorser: anchor.web3.PublicKey,
+  programId: anchor.web3.PublicKey
+): Promise<[anchor.web3.PublicKey, number]> => {
+  return await findProgramAddress(
+    [Buffer.from('endorse'), dataset.toBuffer(), endorser.toBuffer()],
+    programId
+  );
+};
+
+export const deriveDownloadReversalPDA = async (
+  dataset: anchor.web3.PublicKey,
+  reversalId: number,
+  programId: anchor.web3.PublicKey
+): Promise<[anchor.web3.PublicKey, number]> => {
+  return await findProgramAddress(
+    [Buffer.from('download_reversal'), dataset.toBuffer(), new BN(reversalId).toArrayLike(Buffer, 'le', 8)],
+    programId
+  );
+};
+
+export const deriveRecentDatasetsPDA = async (
+  registry: anchor.web3.PublicKey,
+  programId: anchor.web3.PublicKey
+): Promise<[anchor.web3.PublicKey, number]> => {
+  return await findProgramAddress(
+    [Buffer.from('recent_datasets'), registry.toBuffer()],
+    programId
+  );
+};
+
+export const deriveTrendingPDA = async (
+  registry: anchor.web3.PublicKey,
+  programId: anchor.web3.PublicKey
+): Promise<[anchor.web3.PublicKey, number]> => {
+  return await findProgramAddress(
+    [Buffer.from('trending'), registry.toBuffer()],
+    programId
+  );
+};
+
+export const deriveHashRegistryPDA = async (
+  contentHash: number[],
+  programId: anchor.web3.PublicKey
+): Promise<[anchor.web3.PublicKey, number]> => {
+  return await findProgramAddress(
+    [Buffer.from('content_hash'), Buffer.from(contentHash)],
+    programId
+  );
+};
+
+export const deriveDataDictionaryPDA = async (
+  dataset: anchor.web3.PublicKey,
+  programId: anchor.web3.PublicKey
+): Promise<[anchor.web3.PublicKey, number]> => {
+  return await findProgramAddress(
+    [Buffer.from('dict'), dataset.toBuffer()],
+    programId
+  );
+};
+
+export const deriveTaxonomyPDA = async (
+  registry: anchor.web3.PublicKey,
+  programId: anchor.web3.PublicKey
+): Promise<[anchor.web3.PublicKey, number]> => {
+  return await findProgramAddress(
+    [Buffer.from('taxonomy'), registry.toBuffer()],
+    programId
+  );
+};
+
+export const deriveEarningsPDA = async (
+  contributor: anchor.web3.PublicKey,
+  programId: anchor.web3.PublicKey
+): Promise<[anchor.web3.PublicKey, number]> => {
+  return await findProgramAddress(
+    [Buffer.from('earnings'), contributor.toBuffer()],
+    programId
+  );
+};
+
+export const deriveAccessRequestPDA = async (
+  dataset: anchor.web3.PublicKey,
+  requester: anchor.web3.PublicKey,
+  programId: anchor.web3.PublicKey
+): Promise<[anchor.web3.PublicKey, number]> => {
+  return await findProgramAddress(
+    [Buffer.from('access_req'), dataset.toBuffer(), requester.toBuffer()],
+    programId
+  );
+};
+
+export const deriveDatasetGrantPDA = async (
+  dataset: anchor.web3.PublicKey,
+  grantee: anchor.web3.PublicKey,
+  programId: anchor.web3.PublicKey
+): Promise<[anchor.web3.PublicKey, number]> => {
+  return await findProgramAddress(
+    [Buffer.from('grant'), dataset.toBuffer(), grantee.toBuffer()],
+    programId
+  );
+};
+
+export const deriveManifestImportPDA = async (
+  registry: anchor.web3.PublicKey,
+  manifestHash: Buffer,
+  programId: anchor.web3.PublicKey
+): Promise<[anchor.web3.PublicKey, number]> => {
+  return await findProgramAddress(
+    [Buffer.from('manifest'), registry.toBuffer(), manifestHash],
+    programId
+  );
+};
+
+// Mirrors ManifestImport::manifest_hash
+export const manifestHash = (
+  registry: anchor.web3.PublicKey,
+  institution: anchor.web3.PublicKey,
+  contentHashes: number[][]
+): Buffer => {
+  const parts = [Buffer.from('manifest'), registry.toBuffer(), institution.toBuffer(), ...contentHashes.map((h) => Buffer.from(h))];
+  return Buffer.from(sha256.arrayBuffer(Buffer.concat(parts)));
+};
+
+export const deriveCollectionPDA = async (
+  registry: anchor.web3.PublicKey,
+  curator: anchor.web3.PublicKey,
+  name: number[],
+  programId: anchor.web3.PublicKey
+): Promise<[anchor.web3.PublicKey, number]> => {
+  return await findProgramAddress(
+    [Buffer.from('collection'), registry.toBuffer(), curator.toBuffer(), Buffer.from(name)],
+    programId
+  );
+};
+
+// Mirrors Collection::root: member content hashes in ascending order
+export const collectionRoot = (contentHashes: number[][]): Buffer => {
+  const sorted = contentHashes.map((h) => Buffer.from(h)).sort(Buffer.compare);
+  return Buffer.from(sha256.arrayBuffer(Buffer.concat([Buffer.from('collection'), ...sorted])));
+};
+
+export const deriveDatasetReplicasPDA = async (
+  dataset: anchor.web3.PublicKey,
+  programId: anchor.web3.PublicKey
+): Promise<[anchor.web3.PublicKey, number]> => {
+  return await findProgramAddress(
+    [Buffer.from('replicas'), dataset.toBuffer()],
+    programId
+  );
+};
+
+export const deriveDataRequestPDA = async (
+  requester: anchor.web3.PublicKey,
+  topicHash: number[],
+  programId: anchor.web3.PublicKey
+): Promise<[anchor.web3.PublicKey, number]> => {
+  return await findProgramAddress(
+    [Buffer.from('request'), requester.toBuffer(), Buffer.from(topicHash)],
+    programId
+  );
+};
+
 export const createBN = (num: number): BN => {
   return new BN(num);
 };
\ No newline at end of file